@@ -151,11 +151,11 @@ trait Adaptor {
     const CAN_DROP: bool;
     const ANY_ITER: bool;
 
-    fn put<T: 'static>(&self, value: T) -> &mut T;
+    fn put<T: Send + 'static>(&self, value: T) -> &mut T;
     fn put_no_drop<T>(&self, value: T) -> &mut T;
     fn copy_slice<T: Copy>(&self, slice: &[T]) -> &mut [T];
     fn copy_str(&self, string: &str) -> &mut str;
-    fn from_iter<T: 'static>(&self, iter: impl Iterator<Item = T>) -> &mut [T];
+    fn from_iter<T: Send + 'static>(&self, iter: impl Iterator<Item = T>) -> &mut [T];
     fn from_iter_no_drop<T>(&self, iter: impl Iterator<Item = T>) -> &mut [T];
 
     #[inline(always)]
@@ -174,7 +174,7 @@ where
     const ANY_ITER: bool = true;
 
     #[inline(always)]
-    fn put<T: 'static>(&self, value: T) -> &mut T {
+    fn put<T: Send + 'static>(&self, value: T) -> &mut T {
         self.put(value)
     }
 
@@ -194,7 +194,7 @@ where
     }
 
     #[inline(always)]
-    fn from_iter<T: 'static>(&self, iter: impl Iterator<Item = T>) -> &mut [T] {
+    fn from_iter<T: Send + 'static>(&self, iter: impl Iterator<Item = T>) -> &mut [T] {
         self.emplace().from_iter(iter)
     }
 
@@ -214,7 +214,7 @@ impl Adaptor for bumpalo::Bump {
     const ANY_ITER: bool = false;
 
     #[inline(always)]
-    fn put<T: 'static>(&self, _value: T) -> &mut T {
+    fn put<T: Send + 'static>(&self, _value: T) -> &mut T {
         unimplemented!()
     }
 
@@ -234,7 +234,7 @@ impl Adaptor for bumpalo::Bump {
     }
 
     #[inline(always)]
-    fn from_iter<T: 'static>(&self, _iter: impl Iterator<Item = T>) -> &mut [T] {
+    fn from_iter<T: Send + 'static>(&self, _iter: impl Iterator<Item = T>) -> &mut [T] {
         unimplemented!()
     }
 
@@ -0,0 +1,108 @@
+//! This module provides type-erased handle to the value
+//! placed into blink allocator.
+
+use core::{
+    any::{Any, TypeId},
+    fmt,
+};
+
+/// Type-erased reference to the value placed into blink allocator.
+///
+/// Created with [`Blink::put_any`](crate::Blink::put_any).
+/// Value is registered for drop on reset, same as with
+/// [`Blink::put`](crate::Blink::put),
+/// and its type is recovered with downcasting.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "alloc")] fn main() {
+/// use blink_alloc::{Blink, BlinkAny};
+///
+/// let mut blink = Blink::new();
+/// let events: Vec<BlinkAny> = vec![
+///     blink.put_any(String::from("click")),
+///     blink.put_any(42u32),
+/// ];
+///
+/// for event in events {
+///     match event.downcast::<String>() {
+///         Ok(name) => assert_eq!(name, "click"),
+///         Err(event) => assert_eq!(event.downcast_ref::<u32>(), Some(&42)),
+///     }
+/// }
+/// blink.reset();
+/// # }
+/// # #[cfg(not(feature = "alloc"))] fn main() {}
+/// ```
+pub struct BlinkAny<'a> {
+    value: &'a mut dyn Any,
+    type_name: &'static str,
+}
+
+impl<'a> BlinkAny<'a> {
+    #[inline(always)]
+    pub(crate) fn new<T: Any>(value: &'a mut T) -> Self {
+        BlinkAny {
+            value,
+            type_name: core::any::type_name::<T>(),
+        }
+    }
+
+    /// Returns `true` if the value is of type `T`.
+    #[inline(always)]
+    pub fn is<T: Any>(&self) -> bool {
+        self.value.is::<T>()
+    }
+
+    /// Returns `TypeId` of the value.
+    #[inline(always)]
+    pub fn type_id(&self) -> TypeId {
+        Any::type_id(&*self.value)
+    }
+
+    /// Returns name of the value's type.
+    /// See [`core::any::type_name`].
+    #[inline(always)]
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Returns reference to the value if it is of type `T`.
+    #[inline(always)]
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.value.downcast_ref()
+    }
+
+    /// Returns mutable reference to the value if it is of type `T`.
+    #[inline(always)]
+    pub fn downcast_mut<T: Any>(&mut self) -> Option<&mut T> {
+        self.value.downcast_mut()
+    }
+
+    /// Converts handle into reference to the value if it is of type `T`.
+    /// Returns handle back otherwise.
+    #[inline]
+    pub fn downcast<T: Any>(self) -> Result<&'a mut T, Self> {
+        if self.is::<T>() {
+            Ok(self.value.downcast_mut().unwrap())
+        } else {
+            Err(self)
+        }
+    }
+
+    /// Returns type-erased reference to the value.
+    #[inline(always)]
+    pub fn into_inner(self) -> &'a mut dyn Any {
+        self.value
+    }
+}
+
+impl fmt::Debug for BlinkAny<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlinkAny")
+            .field("type_name", &self.type_name)
+            .finish_non_exhaustive()
+    }
+}
@@ -18,6 +18,94 @@ use allocator_api2::alloc::Allocator;
 /// * [`Allocator::shrink`] will always succeed and never move memory
 /// when `ptr` is already aligned to `new_layout.align()`.
 ///
+/// This is also the backend trait for [`Blink`](crate::Blink).
+/// Custom arena engines that implement [`Allocator`] and this trait
+/// can be used with [`Blink`](crate::Blink) to reuse its emplace
+/// and drop machinery.
+/// Implementing statistics methods is optional.
+///
+/// The trait is dyn-compatible, so APIs may accept `&dyn BlinkAllocator`
+/// instead of being generic over every arena flavor.
+/// `&dyn BlinkAllocator` is an allocator itself
+/// and can back collections and [`Blink`](crate::Blink).
+///
+/// ```
+/// # #![cfg_attr(feature = "nightly", feature(allocator_api))]
+/// # #[cfg(feature = "alloc")] fn main() {
+/// # #[cfg(feature = "nightly")]
+/// # use std::vec::Vec;
+/// # #[cfg(not(feature = "nightly"))]
+/// # use allocator_api2::vec::Vec;
+/// use blink_alloc::{Blink, BlinkAlloc, BlinkAllocator, StaticBuffer};
+///
+/// fn squares(arena: &dyn BlinkAllocator) -> Vec<u32, &dyn BlinkAllocator> {
+///     let mut vec = Vec::new_in(arena);
+///     vec.extend((0..10).map(|i| i * i));
+///     vec
+/// }
+///
+/// let blink = BlinkAlloc::new();
+/// assert_eq!(squares(&blink)[3], 9);
+///
+/// let mut buffer = [core::mem::MaybeUninit::uninit(); 1024];
+/// let fixed = BlinkAlloc::new_in(StaticBuffer::new(&mut buffer));
+/// assert_eq!(squares(&fixed)[4], 16);
+///
+/// let arena: &dyn BlinkAllocator = &blink;
+/// let blink = Blink::new_in(arena);
+/// assert_eq!(*blink.put(42), 42);
+/// # }
+/// # #[cfg(not(feature = "alloc"))] fn main() {}
+/// ```
+///
+/// # Example
+///
+/// ```
+/// # #![cfg_attr(feature = "nightly", feature(allocator_api))]
+/// # #[cfg(feature = "alloc")] fn main() {
+/// # use std::{alloc::Layout, ptr::NonNull};
+/// # #[cfg(feature = "nightly")]
+/// # use std::alloc::{AllocError, Allocator};
+/// # #[cfg(not(feature = "nightly"))]
+/// # use allocator_api2::alloc::{AllocError, Allocator};
+/// use blink_alloc::{Blink, BlinkAlloc, BlinkAllocator};
+///
+/// /// Custom arena that counts resets.
+/// struct Counting {
+///     blink: BlinkAlloc,
+///     resets: usize,
+/// }
+///
+/// unsafe impl Allocator for Counting {
+///     fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+///         self.blink.allocate(layout)
+///     }
+///
+///     unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+///         self.blink.deallocate(ptr, layout.size())
+///     }
+/// }
+///
+/// unsafe impl BlinkAllocator for Counting {
+///     fn reset(&mut self) {
+///         self.resets += 1;
+///         self.blink.reset();
+///     }
+///
+///     fn allocated_bytes(&self) -> Option<usize> {
+///         Some(self.blink.allocated_bytes())
+///     }
+/// }
+///
+/// let mut blink = Blink::new_in(Counting { blink: BlinkAlloc::new(), resets: 0 });
+/// blink.put(42);
+/// assert!(blink.allocated_bytes().unwrap() >= 4);
+/// blink.reset();
+/// assert_eq!(blink.allocator().resets, 1);
+/// # }
+/// # #[cfg(not(feature = "alloc"))] fn main() {}
+/// ```
+///
 /// # Safety
 ///
 /// Draws most requirements from [`Allocator`] super-trait.
@@ -45,23 +133,70 @@ pub unsafe trait BlinkAllocator: Allocator {
     ///
     /// [`Vec`]: alloc::vec::Vec
     fn reset(&mut self);
+
+    /// Resets allocator releasing all memory it holds
+    /// back to the underlying allocator.
+    /// Defaults to [`reset`][BlinkAllocator::reset].
+    ///
+    /// # Safety
+    ///
+    /// Same as for [`reset`][BlinkAllocator::reset].
+    #[inline]
+    fn reset_final(&mut self) {
+        self.reset();
+    }
+
+    /// Returns the number of bytes allocated from this instance
+    /// since last reset.
+    /// Returns `None` if allocator does not track it.
+    #[inline]
+    fn allocated_bytes(&self) -> Option<usize> {
+        None
+    }
+
+    /// Returns the total capacity of memory chunks held by this instance.
+    /// Returns `None` if allocator does not track it.
+    #[inline]
+    fn total_capacity(&self) -> Option<usize> {
+        None
+    }
 }
 
 unsafe impl<A> BlinkAllocator for &A
 where
-    A: BlinkAllocator,
+    A: BlinkAllocator + ?Sized,
 {
     #[inline]
     fn reset(&mut self) {}
+
+    #[inline]
+    fn allocated_bytes(&self) -> Option<usize> {
+        A::allocated_bytes(self)
+    }
+
+    #[inline]
+    fn total_capacity(&self) -> Option<usize> {
+        A::total_capacity(self)
+    }
 }
 
 unsafe impl<'a, A> BlinkAllocator for &'a mut A
 where
     &'a mut A: Allocator,
-    A: BlinkAllocator,
+    A: BlinkAllocator + ?Sized,
 {
     #[inline]
     fn reset(&mut self) {
         A::reset(self);
     }
+
+    #[inline]
+    fn allocated_bytes(&self) -> Option<usize> {
+        A::allocated_bytes(self)
+    }
+
+    #[inline]
+    fn total_capacity(&self) -> Option<usize> {
+        A::total_capacity(self)
+    }
 }
@@ -0,0 +1,200 @@
+//! This module provides reference-counted handle
+//! to the value placed into blink allocator.
+
+use core::{
+    fmt,
+    marker::PhantomData,
+    ops::Deref,
+    ptr::{self, NonNull},
+    sync::atomic::{fence, AtomicUsize, Ordering},
+};
+
+/// Control block and payload of [`ARc`].
+pub(crate) struct ArcInner<'a, T> {
+    count: AtomicUsize,
+
+    /// Counter of live payloads in the owning `Blink`.
+    #[cfg(debug_assertions)]
+    live: &'a AtomicUsize,
+
+    #[cfg(not(debug_assertions))]
+    live: PhantomData<&'a AtomicUsize>,
+
+    value: T,
+}
+
+impl<'a, T> ArcInner<'a, T> {
+    #[cfg(debug_assertions)]
+    #[inline(always)]
+    pub(crate) fn new(value: T, live: &'a AtomicUsize) -> Self {
+        live.fetch_add(1, Ordering::Relaxed);
+        ArcInner {
+            count: AtomicUsize::new(1),
+            live,
+            value,
+        }
+    }
+
+    #[cfg(not(debug_assertions))]
+    #[inline(always)]
+    pub(crate) fn new(value: T) -> Self {
+        ArcInner {
+            count: AtomicUsize::new(1),
+            live: PhantomData,
+            value,
+        }
+    }
+}
+
+/// Reference-counted shared handle to the value
+/// whose control block and payload live in blink allocator.
+///
+/// Created with [`Blink::arc`](crate::Blink::arc).
+/// Works like `Arc` but does not use global heap.
+/// Value is dropped when the last handle is dropped,
+/// while memory is reclaimed when allocator is reset.
+///
+/// Handles borrow the [`Blink`](crate::Blink),
+/// so all of them die before reset.
+/// In debug builds reset asserts that no payload is still alive,
+/// which catches handles leaked with [`core::mem::forget`].
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "std")] fn main() {
+/// use blink_alloc::Blink;
+///
+/// let mut blink = Blink::new();
+/// let config = blink.arc(vec![1, 2, 3]);
+///
+/// std::thread::scope(|scope| {
+///     for _ in 0..4 {
+///         let config = config.clone();
+///         scope.spawn(move || assert_eq!(config.len(), 3));
+///     }
+/// });
+///
+/// drop(config);
+/// blink.reset();
+/// # }
+/// # #[cfg(not(feature = "std"))] fn main() {}
+/// ```
+pub struct ARc<'a, T> {
+    inner: NonNull<ArcInner<'a, T>>,
+    marker: PhantomData<&'a ArcInner<'a, T>>,
+}
+
+// Safety: `ARc` shares `T` between threads
+// and drops it on the thread that drops the last handle.
+unsafe impl<T> Send for ARc<'_, T> where T: Send + Sync {}
+unsafe impl<T> Sync for ARc<'_, T> where T: Send + Sync {}
+
+impl<'a, T> ARc<'a, T> {
+    /// Creates handle from initialized control block.
+    ///
+    /// # Safety
+    ///
+    /// `inner` must be initialized with count of 1,
+    /// and memory must be valid for `'a`.
+    #[inline(always)]
+    pub(crate) unsafe fn from_inner(inner: NonNull<ArcInner<'a, T>>) -> Self {
+        ARc {
+            inner,
+            marker: PhantomData,
+        }
+    }
+
+    #[inline(always)]
+    fn inner(&self) -> &ArcInner<'a, T> {
+        // Safety: control block is alive while any handle exists.
+        unsafe { self.inner.as_ref() }
+    }
+
+    /// Returns number of handles to the value.
+    #[inline(always)]
+    pub fn strong_count(this: &Self) -> usize {
+        this.inner().count.load(Ordering::Acquire)
+    }
+
+    /// Returns `true` if two handles point to the same value.
+    #[inline(always)]
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        this.inner == other.inner
+    }
+}
+
+impl<T> Clone for ARc<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        let old = self.inner().count.fetch_add(1, Ordering::Relaxed);
+
+        // Same as `Arc` - guard against overflow from leaked clones.
+        if old > isize::MAX as usize {
+            panic!("Reference count overflow");
+        }
+
+        ARc {
+            inner: self.inner,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Drop for ARc<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        if self.inner().count.fetch_sub(1, Ordering::Release) != 1 {
+            return;
+        }
+        fence(Ordering::Acquire);
+
+        // Safety: this is the last handle.
+        // Memory itself is owned by the allocator.
+        unsafe {
+            #[cfg(debug_assertions)]
+            let live = self.inner().live;
+
+            ptr::drop_in_place(ptr::addr_of_mut!((*self.inner.as_ptr()).value));
+
+            #[cfg(debug_assertions)]
+            live.fetch_sub(1, Ordering::Release);
+        }
+    }
+}
+
+impl<T> Deref for ARc<'_, T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.inner().value
+    }
+}
+
+impl<T> AsRef<T> for ARc<'_, T> {
+    #[inline(always)]
+    fn as_ref(&self) -> &T {
+        self
+    }
+}
+
+impl<T> fmt::Debug for ARc<'_, T>
+where
+    T: fmt::Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        T::fmt(self, f)
+    }
+}
+
+impl<T> fmt::Display for ARc<'_, T>
+where
+    T: fmt::Display,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        T::fmt(self, f)
+    }
+}
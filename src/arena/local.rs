@@ -2,10 +2,185 @@ use super::*;
 
 with_cursor!(Cell<*mut u8>);
 
+/// Number of recent allocations tracked for LIFO reclamation.
+const LIFO_WINDOW: usize = 4;
+
+/// Allocation start, cursor position after it and cursor position before it.
+type LifoEntry = Cell<(*mut u8, *mut u8, *mut u8)>;
+
+#[allow(clippy::declare_interior_mutable_const)]
+const LIFO_EMPTY: LifoEntry = Cell::new((ptr::null_mut(), ptr::null_mut(), ptr::null_mut()));
+
+/// Allocations with smaller alignment are served from small blocks
+/// when size classes are enabled.
+const SMALL_ALIGN: usize = align_of::<usize>();
+
+/// Largest allocation served from small blocks.
+const SMALL_MAX_SIZE: usize = 64;
+
+/// Layout of small blocks carved from chunks.
+const SMALL_BLOCK: Layout = Layout::new::<[usize; 32]>();
+
+/// Start, cursor and end of the small block.
+type SmallBlock = (*mut u8, *mut u8, *mut u8);
+
+const SMALL_EMPTY: SmallBlock = (ptr::null_mut(), ptr::null_mut(), ptr::null_mut());
+
+/// Copies contents of moved allocation.
+#[inline(always)]
+unsafe fn copy_small(
+    old_ptr: NonNull<u8>,
+    new_ptr: NonNull<[u8]>,
+    old_layout: Layout,
+    new_layout: Layout,
+) {
+    let size = old_layout.size().min(new_layout.size());
+    // Safety: both allocations are at least `size` bytes
+    // and new allocation is not overlapping old one.
+    unsafe { ptr::copy_nonoverlapping(old_ptr.as_ptr(), new_ptr.as_ptr().cast::<u8>(), size) };
+}
+
+/// Zeroes part of allocated memory that is not known to be zeroed.
+/// Memory past `frontier` in the bump direction is known to be zeroed.
+#[inline(always)]
+unsafe fn zero_dirty(ptr: NonNull<[u8]>, frontier: Option<*mut u8>) {
+    let start = ptr.as_ptr().cast::<u8>();
+    let end = unsafe { start.add(ptr.len()) };
+
+    #[cfg(not(feature = "bump-down"))]
+    let (dirty_start, dirty_end) = match frontier {
+        None => (start, end),
+        Some(frontier) => (start, end.min(frontier)),
+    };
+
+    #[cfg(feature = "bump-down")]
+    let (dirty_start, dirty_end) = match frontier {
+        None => (start, end),
+        Some(frontier) => (start.max(frontier), end),
+    };
+
+    if dirty_start < dirty_end {
+        unsafe {
+            ptr::write_bytes(dirty_start, 0, dirty_end.offset_from(dirty_start) as usize);
+        }
+    }
+}
+
+/// Chunk source that provides zeroed chunks
+/// to arena that keeps free memory zeroed.
+struct ZeroFreeSource<'a, S> {
+    source: &'a S,
+    zeroed: bool,
+}
+
+unsafe impl<S> ChunkSource for ZeroFreeSource<'_, S>
+where
+    S: ChunkSource,
+{
+    #[inline(always)]
+    fn allocate_chunk(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        if self.zeroed {
+            self.source.allocate_chunk_zeroed(layout)
+        } else {
+            self.source.allocate_chunk(layout)
+        }
+    }
+
+    #[inline(always)]
+    fn allocate_chunk_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.source.allocate_chunk_zeroed(layout)
+    }
+
+    #[inline(always)]
+    unsafe fn grow_chunk_in_place(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<usize, AllocError> {
+        let len = unsafe { self.source.grow_chunk_in_place(ptr, old_layout, new_layout) }?;
+        if self.zeroed {
+            // Safety: chunk is extended to `len` bytes.
+            unsafe {
+                ptr::write_bytes(
+                    ptr.as_ptr().add(old_layout.size()),
+                    0,
+                    len - old_layout.size(),
+                )
+            };
+        }
+        Ok(len)
+    }
+
+    #[inline(always)]
+    unsafe fn release_chunk(&self, ptr: NonNull<u8>, layout: Layout) {
+        unsafe { self.source.release_chunk(ptr, layout) }
+    }
+}
+
+/// Zeroes memory of chunks in the list that is not used yet.
+fn zero_unused_chunks(mut next: Option<NonNull<ChunkHeader>>) {
+    while let Some(chunk) = next {
+        // Safety: all chunks in the list are valid.
+        let me = unsafe { chunk.as_ref() };
+        let cursor = me.cursor.get();
+
+        #[cfg(not(feature = "bump-down"))]
+        let (start, end) = (cursor, me.end());
+
+        #[cfg(feature = "bump-down")]
+        let (start, end) = (me.base().cast_mut(), cursor);
+
+        // Safety: memory between `start` and `end` belongs to the chunk
+        // and is not used by allocations.
+        unsafe { ptr::write_bytes(start, 0, end.offset_from(start) as usize) };
+        next = me.prev;
+    }
+}
+
 /// Thread-local arena allocator.
 pub struct ArenaLocal {
     root: Cell<Option<NonNull<ChunkHeader>>>,
     min_chunk_size: Cell<usize>,
+    max_chunk_size: usize,
+    retention: Cell<RetentionPolicy>,
+
+    /// Minimal alignment of new chunks.
+    chunk_align: usize,
+
+    /// Chunks kept by `reset_keep_all` to be reused.
+    spare: Cell<Option<NonNull<ChunkHeader>>>,
+
+    /// Chunk for which zeroed frontier is tracked.
+    zeroed_chunk: Cell<Option<NonNull<ChunkHeader>>>,
+
+    /// Memory of `zeroed_chunk` past this frontier
+    /// in the bump direction is known to be zeroed.
+    zeroed_frontier: Cell<*mut u8>,
+
+    /// Stack of recent allocations preceded by alignment padding.
+    /// Deallocating them in LIFO order reclaims the padding as well,
+    /// so that preceding allocation becomes the last one again.
+    /// When full, the oldest entry is dropped.
+    lifo: [LifoEntry; LIFO_WINDOW],
+    lifo_top: Cell<usize>,
+
+    /// Serve allocations with small alignment from separate blocks,
+    /// so that they don't misalign the cursor.
+    size_classes: bool,
+
+    /// Current block for allocations with small alignment.
+    small: Cell<SmallBlock>,
+
+    /// Never allocate new chunk while there is one.
+    fixed: bool,
+
+    /// Keep memory not used by live allocations zeroed.
+    zero_free: bool,
+
+    /// Sum of sizes of live allocations since last reset.
+    #[cfg(feature = "exact-stats")]
+    allocated: Cell<usize>,
 }
 
 /// It is safe to send `ArenaLocal` between threads.
@@ -15,7 +190,7 @@ impl Drop for ArenaLocal {
     #[inline(always)]
     fn drop(&mut self) {
         debug_assert!(
-            self.root.get().is_none(),
+            self.root.get().is_none() && self.spare.get().is_none(),
             "Owner must reset `ArenaLocal` with `keep_last` set to `false` before drop"
         );
     }
@@ -24,22 +199,66 @@ impl Drop for ArenaLocal {
 impl ArenaLocal {
     #[inline(always)]
     pub const fn new() -> Self {
-        ArenaLocal {
-            root: Cell::new(None),
-            min_chunk_size: Cell::new(CHUNK_START_SIZE),
-        }
+        ArenaLocal::with_chunk_size(CHUNK_START_SIZE)
     }
 
     #[inline(always)]
     pub const fn with_chunk_size(min_chunk_size: usize) -> Self {
+        ArenaLocal::with_chunk_size_range(min_chunk_size, usize::MAX)
+    }
+
+    #[inline(always)]
+    pub const fn with_chunk_size_range(min_chunk_size: usize, max_chunk_size: usize) -> Self {
         ArenaLocal {
             root: Cell::new(None),
             min_chunk_size: Cell::new(min_chunk_size),
+            max_chunk_size,
+            retention: Cell::new(RetentionPolicy::KeepMostRecentlyUsed),
+            chunk_align: 1,
+            spare: Cell::new(None),
+            zeroed_chunk: Cell::new(None),
+            zeroed_frontier: Cell::new(ptr::null_mut()),
+            lifo: [LIFO_EMPTY; LIFO_WINDOW],
+            lifo_top: Cell::new(0),
+            size_classes: false,
+            small: Cell::new(SMALL_EMPTY),
+            fixed: false,
+            zero_free: false,
+            #[cfg(feature = "exact-stats")]
+            allocated: Cell::new(0),
         }
     }
 
+    /// Returns arena that serves all allocations from single chunk
+    /// with at least `capacity` bytes of usable memory.
+    #[inline(always)]
+    pub fn with_capacity_exact(capacity: usize) -> Self {
+        let max_chunk_size = capacity
+            .checked_add(size_of::<ChunkHeader>())
+            .and_then(|size| align_up(size, align_of::<ChunkHeader>()))
+            .unwrap_or(usize::MAX);
+
+        let mut arena = ArenaLocal::with_chunk_size_range(capacity, max_chunk_size);
+        arena.fixed = true;
+        arena
+    }
+
+    /// Returns new empty arena with the same chunk sizes, alignment,
+    /// size classes, retention policy and fixed capacity mode.
+    #[inline(always)]
+    pub fn empty_like(&self) -> Self {
+        let mut arena =
+            ArenaLocal::with_chunk_size_range(self.min_chunk_size.get(), self.max_chunk_size);
+        arena.retention.set(self.retention.get());
+        arena.chunk_align = self.chunk_align;
+        arena.size_classes = self.size_classes;
+        arena.fixed = self.fixed;
+        arena.zero_free = self.zero_free;
+        arena
+    }
+
     #[inline(always)]
-    #[cfg(feature = "sync")]
+    #[cfg(feature = "sync-core")]
     pub fn last_chunk_size(&self) -> usize {
         match self.root.get() {
             None => 0,
@@ -52,19 +271,420 @@ impl ArenaLocal {
 
     #[inline(always)]
     pub unsafe fn alloc_fast(&self, layout: Layout) -> Option<NonNull<[u8]>> {
+        if self.is_small(layout) {
+            return unsafe { self.alloc_small_fast(layout) };
+        }
+
         if let Some(root) = self.root.get() {
-            return unsafe { ChunkHeader::alloc(root, layout) };
+            // Safety: `root` is a valid pointer to chunk allocation.
+            let cursor = unsafe { root.as_ref().cursor.get() };
+            let ptr = unsafe { ChunkHeader::alloc(root, layout) }?;
+            let end = unsafe { root.as_ref().cursor.get() };
+            self.push_lifo(ptr.as_ptr().cast(), layout.size(), cursor, end);
+            self.count(layout.size(), 0);
+            return Some(ptr);
         }
         None
     }
 
+    /// Checks if allocation is served from small blocks.
+    #[inline(always)]
+    fn is_small(&self, layout: Layout) -> bool {
+        self.size_classes && layout.align() < SMALL_ALIGN && layout.size() <= SMALL_MAX_SIZE
+    }
+
+    /// Checks if pointer belongs to current small block.
+    #[inline(always)]
+    fn in_small(&self, ptr: NonNull<u8>) -> bool {
+        let (start, _, end) = self.small.get();
+        start <= ptr.as_ptr() && ptr.as_ptr() < end
+    }
+
+    /// Allocates from current small block.
+    #[inline(always)]
+    fn alloc_small(&self, layout: Layout) -> Option<NonNull<[u8]>> {
+        let (start, cursor, end) = self.small.get();
+        if start.is_null() {
+            return None;
+        }
+
+        let offset = cursor.align_offset(layout.align());
+        let available = end as usize - cursor as usize;
+        if available < offset || available - offset < layout.size() {
+            return None;
+        }
+
+        // Safety: allocation fits into the block.
+        let ptr = unsafe { cursor.add(offset) };
+        self.small
+            .set((start, unsafe { ptr.add(layout.size()) }, end));
+
+        // Safety: block is never at null address.
+        let ptr = unsafe { NonNull::new_unchecked(ptr) };
+        Some(NonNull::slice_from_raw_parts(ptr, layout.size()))
+    }
+
+    /// Makes the block current small block.
+    #[inline(always)]
+    fn set_small(&self, block: NonNull<[u8]>) {
+        let start = block.as_ptr().cast::<u8>();
+        // Safety: block is allocated with this length.
+        let end = unsafe { start.add(block.len()) };
+        self.small.set((start, start, end));
+    }
+
+    /// Allocates from small block,
+    /// carving new one from current chunk if needed.
+    #[inline]
+    unsafe fn alloc_small_fast(&self, layout: Layout) -> Option<NonNull<[u8]>> {
+        let ptr = match self.alloc_small(layout) {
+            Some(ptr) => ptr,
+            None => {
+                let root = self.root.get()?;
+                // Safety: `root` is a valid pointer to chunk allocation.
+                let block = unsafe { ChunkHeader::alloc(root, SMALL_BLOCK) }?;
+                self.set_small(block);
+                self.alloc_small(layout)?
+            }
+        };
+        self.count(layout.size(), 0);
+        Some(ptr)
+    }
+
+    /// Resizes allocation from current small block.
+    #[inline]
+    unsafe fn resize_small_fast(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Option<NonNull<[u8]>> {
+        let (start, cursor, end) = self.small.get();
+        let old_end = ptr.as_ptr().wrapping_add(old_layout.size());
+
+        if ptr.as_ptr().align_offset(new_layout.align()) == 0 {
+            let fits = if old_end == cursor {
+                end as usize - ptr.as_ptr() as usize >= new_layout.size()
+            } else {
+                new_layout.size() <= old_layout.size()
+            };
+
+            if fits {
+                if old_end == cursor {
+                    self.small
+                        .set((start, ptr.as_ptr().wrapping_add(new_layout.size()), end));
+                }
+                self.count(new_layout.size(), old_layout.size());
+                return Some(NonNull::slice_from_raw_parts(ptr, new_layout.size()));
+            }
+        }
+
+        let new_ptr = unsafe { self.alloc_fast(new_layout) }?;
+        unsafe { copy_small(ptr, new_ptr, old_layout, new_layout) };
+        self.count(0, old_layout.size());
+        Some(new_ptr)
+    }
+
+    /// Handles deallocation from current small block.
+    /// Returns `false` if pointer doesn't belong to it.
+    #[inline(always)]
+    fn dealloc_small(&self, ptr: NonNull<u8>, size: usize) -> bool {
+        if !self.in_small(ptr) {
+            return false;
+        }
+
+        let (start, cursor, end) = self.small.get();
+        if ptr.as_ptr().wrapping_add(size) == cursor {
+            self.small.set((start, ptr.as_ptr(), end));
+        }
+        true
+    }
+
+    /// Remembers cursor position before allocation
+    /// if allocation is preceded by alignment padding.
+    #[inline(always)]
+    fn push_lifo(&self, ptr: *mut u8, size: usize, cursor: *mut u8, end: *mut u8) {
+        #[cfg(not(feature = "bump-down"))]
+        let padded = {
+            let _ = size;
+            cursor != ptr
+        };
+
+        #[cfg(feature = "bump-down")]
+        let padded = cursor != ptr.wrapping_add(size);
+
+        if padded {
+            let mut top = self.lifo_top.get();
+            if top == LIFO_WINDOW {
+                for idx in 1..LIFO_WINDOW {
+                    self.lifo[idx - 1].set(self.lifo[idx].get());
+                }
+                top -= 1;
+            }
+            self.lifo[top].set((ptr, end, cursor));
+            self.lifo_top.set(top + 1);
+        }
+    }
+
+    /// Updates exact allocated bytes counter.
+    #[inline(always)]
+    fn count(&self, added: usize, removed: usize) {
+        #[cfg(feature = "exact-stats")]
+        self.allocated
+            .set(self.allocated.get().saturating_sub(removed) + added);
+
+        #[cfg(not(feature = "exact-stats"))]
+        let _ = (added, removed);
+    }
+
+    /// Forgets tracked allocations and current small block.
+    /// Must be called when chunks are rewound or freed.
+    #[inline(always)]
+    fn clear_lifo(&self) {
+        for entry in &self.lifo[..self.lifo_top.get()] {
+            entry.set((ptr::null_mut(), ptr::null_mut(), ptr::null_mut()));
+        }
+        self.lifo_top.set(0);
+        self.small.set(SMALL_EMPTY);
+    }
+
+    /// Zeroes exact allocated bytes counter.
+    /// Must be called when all allocations are freed.
+    #[inline(always)]
+    fn clear_count(&self) {
+        #[cfg(feature = "exact-stats")]
+        self.allocated.set(0);
+    }
+
     #[inline(always)]
     pub unsafe fn alloc_slow(
         &self,
         layout: Layout,
-        allocator: impl Allocator,
+        allocator: &impl ChunkSource,
     ) -> Result<NonNull<[u8]>, AllocError> {
-        alloc_slow(&self.root, self.min_chunk_size.get(), layout, allocator)
+        let allocator = &self.source(allocator);
+        if self.is_small(layout) {
+            let block = unsafe { self.alloc_chunk_slow(SMALL_BLOCK, allocator) }?;
+            self.set_small(block);
+            let ptr = self.alloc_small(layout).ok_or(AllocError)?;
+            self.count(layout.size(), 0);
+            return Ok(ptr);
+        }
+
+        let ptr = unsafe { self.alloc_chunk_slow(layout, allocator) }?;
+        self.count(layout.size(), 0);
+        Ok(ptr)
+    }
+
+    /// Allocates from new chunk or grown current one.
+    #[inline(always)]
+    unsafe fn alloc_chunk_slow(
+        &self,
+        layout: Layout,
+        allocator: &impl ChunkSource,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        self.check_fixed()?;
+        self.clear_lifo();
+
+        #[cfg(all(not(feature = "bump-down"), not(feature = "header-end")))]
+        if let Some(root) = self.grow_root(layout, allocator) {
+            // Safety: `root` is a valid pointer to chunk allocation.
+            if let Some(ptr) = unsafe { ChunkHeader::alloc(root, layout) } {
+                return Ok(ptr);
+            }
+        }
+
+        unsafe {
+            alloc_slow(
+                &self.root,
+                &self.spare,
+                self.min_chunk_size.get(),
+                self.max_chunk_size,
+                self.chunk_align,
+                layout,
+                allocator,
+                false,
+            )
+        }
+    }
+
+    /// Allocates zeroed memory.
+    /// Skips zeroing memory of the chunk that was allocated zeroed
+    /// and not yet touched.
+    #[inline]
+    pub unsafe fn alloc_zeroed(
+        &self,
+        layout: Layout,
+        allocator: &impl ChunkSource,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        self.update_zeroed_frontier();
+
+        if let Some(root) = self.root.get() {
+            if let Some(ptr) = unsafe { ChunkHeader::alloc(root, layout) } {
+                let frontier = match self.zeroed_chunk.get() {
+                    Some(chunk) if chunk == root => Some(self.zeroed_frontier.get()),
+                    _ => None,
+                };
+                unsafe { zero_dirty(ptr, frontier) };
+                self.update_zeroed_frontier();
+                self.count(layout.size(), 0);
+                return Ok(ptr);
+            }
+        }
+
+        if let Some(ptr) = unsafe { alloc_spare(&self.root, &self.spare, layout) } {
+            // Spare chunks are not zeroed.
+            unsafe { zero_dirty(ptr, None) };
+            self.count(layout.size(), 0);
+            return Ok(ptr);
+        }
+
+        self.check_fixed()?;
+        let ptr = unsafe {
+            alloc_slow(
+                &self.root,
+                &self.spare,
+                self.min_chunk_size.get(),
+                self.max_chunk_size,
+                self.chunk_align,
+                layout,
+                allocator,
+                true,
+            )?
+        };
+        self.count(layout.size(), 0);
+
+        // Whole new chunk is zeroed.
+        self.zeroed_chunk.set(self.root.get());
+        let chunk = unsafe { self.root.get().unwrap_unchecked().as_ref() };
+        self.zeroed_frontier.set(chunk.cursor.get());
+        Ok(ptr)
+    }
+
+    /// Tries to extend current chunk in place so that `layout` fits into it.
+    /// Chunk grows by as much as a new chunk would take.
+    ///
+    /// Skipped while there are spare chunks, as their memory is already allocated.
+    /// Only possible when usable memory is bumped towards the end of the chunk allocation.
+    #[cfg(all(not(feature = "bump-down"), not(feature = "header-end")))]
+    #[cold]
+    unsafe fn grow_root(
+        &self,
+        layout: Layout,
+        allocator: &impl ChunkSource,
+    ) -> Option<NonNull<ChunkHeader>> {
+        let root = self.root.get()?;
+
+        if self.spare.get().is_some() {
+            return None;
+        }
+
+        // Safety: root is a valid chunk.
+        let me = unsafe { root.as_ref() };
+        let start = root.cast::<u8>();
+
+        // Safety: `end` belongs to the chunk allocation.
+        let old_size = unsafe { me.end.offset_from(start.as_ptr()) } as usize;
+
+        let mut extra = self
+            .min_chunk_size
+            .get()
+            .max(me.cumulative_size + me.cap())
+            .checked_add(layout.size().max(CHUNK_MIN_GROW_STEP))?;
+
+        if layout.align() > align_of::<ChunkHeader>() {
+            extra = extra.checked_add(layout.align())?;
+        }
+
+        let align = self.chunk_align.max(align_of::<ChunkHeader>());
+        let new_size = round_chunk_size(old_size.checked_add(extra)?);
+        let new_size = align_up(new_size, align)?;
+
+        if new_size > self.max_chunk_size {
+            return None;
+        }
+
+        // Safety: layout of the chunk allocation as it is released.
+        let old_layout = unsafe { Layout::from_size_align_unchecked(old_size, align) };
+        let new_layout = Layout::from_size_align(new_size, align).ok()?;
+
+        let len = unsafe { allocator.grow_chunk_in_place(start, old_layout, new_layout) }.ok()?;
+        debug_assert!(len >= new_size);
+
+        // Safety:
+        // Chunk is extended to `len` bytes without moving.
+        // Thread-local arena is the only one to access the header.
+        unsafe { (*root.as_ptr()).end = start.as_ptr().add(len) };
+
+        // Grown memory is not zeroed.
+        if self.zeroed_chunk.get() == Some(root) {
+            self.zeroed_chunk.set(None);
+        }
+
+        Some(root)
+    }
+
+    /// Fails if arena is in fixed capacity mode and already has a chunk.
+    #[inline(always)]
+    fn check_fixed(&self) -> Result<(), AllocError> {
+        if self.fixed && self.root.get().is_some() {
+            return Err(AllocError);
+        }
+        Ok(())
+    }
+
+    /// Allocates first chunk if arena has none.
+    #[inline]
+    pub unsafe fn reserve_chunk(&self, allocator: &impl ChunkSource) -> Result<(), AllocError> {
+        if self.root.get().is_some() {
+            return Ok(());
+        }
+
+        let allocator = &self.source(allocator);
+
+        unsafe {
+            alloc_slow(
+                &self.root,
+                &self.spare,
+                self.min_chunk_size.get(),
+                self.max_chunk_size,
+                self.chunk_align,
+                Layout::new::<()>(),
+                allocator,
+                false,
+            )
+        }?;
+        Ok(())
+    }
+
+    /// Moves zeroed frontier to the cursor of tracked chunk if needed.
+    /// Must be called before cursor is moved backward.
+    #[inline(always)]
+    fn update_zeroed_frontier(&self) {
+        if let Some(chunk) = self.zeroed_chunk.get() {
+            // Safety: tracked chunk is always in the chunk list.
+            let cursor = unsafe { chunk.as_ref().cursor.get() };
+            let frontier = self.zeroed_frontier.get();
+
+            #[cfg(not(feature = "bump-down"))]
+            let dirty = cursor > frontier;
+
+            #[cfg(feature = "bump-down")]
+            let dirty = cursor < frontier;
+
+            if dirty {
+                self.zeroed_frontier.set(cursor);
+            }
+        }
+    }
+
+    /// Stops tracking zeroed frontier if tracked chunk
+    /// is no longer in the chunk list.
+    #[inline(always)]
+    fn check_zeroed_chunk(&self) {
+        if self.zeroed_chunk.get() != self.root.get() {
+            self.zeroed_chunk.set(None);
+        }
     }
 
     #[inline(always)]
@@ -74,10 +694,18 @@ impl ArenaLocal {
         old_layout: Layout,
         new_layout: Layout,
     ) -> Option<NonNull<[u8]>> {
-        if let Some(root) = self.root.get() {
-            return unsafe { ChunkHeader::resize(root, ptr, old_layout, new_layout) };
-        }
-        None
+        let new_ptr = if self.in_small(ptr) {
+            unsafe { self.resize_small_fast(ptr, old_layout, new_layout) }?
+        } else {
+            self.update_zeroed_frontier();
+            let root = self.root.get()?;
+            let new_ptr = unsafe { ChunkHeader::resize(root, ptr, old_layout, new_layout) }?;
+            self.count(new_layout.size(), old_layout.size());
+            new_ptr
+        };
+
+        unsafe { self.zero_replaced(ptr, old_layout, new_ptr, new_layout) };
+        Some(new_ptr)
     }
 
     #[inline(always)]
@@ -86,37 +714,278 @@ impl ArenaLocal {
         ptr: NonNull<u8>,
         old_layout: Layout,
         new_layout: Layout,
-        allocator: impl Allocator,
+        allocator: &impl ChunkSource,
     ) -> Result<NonNull<[u8]>, AllocError> {
-        resize_slow(
+        let allocator = &self.source(allocator);
+        let new_ptr = unsafe { self.resize_chunk_slow(ptr, old_layout, new_layout, allocator) }?;
+        unsafe { self.zero_replaced(ptr, old_layout, new_ptr, new_layout) };
+        Ok(new_ptr)
+    }
+
+    /// Resizes allocation in new chunk or grown current one.
+    #[inline(always)]
+    unsafe fn resize_chunk_slow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+        allocator: &impl ChunkSource,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        if self.in_small(ptr) {
+            // Small block can't be grown, allocation is moved.
+            let new_ptr = unsafe { self.alloc_slow(new_layout, allocator) }?;
+            unsafe { copy_small(ptr, new_ptr, old_layout, new_layout) };
+            self.count(0, old_layout.size());
+            return Ok(new_ptr);
+        }
+
+        self.check_fixed()?;
+        self.clear_lifo();
+
+        #[cfg(all(not(feature = "bump-down"), not(feature = "header-end")))]
+        if let Some(root) = self.grow_root(new_layout, allocator) {
+            // Safety: `root` is a valid pointer to chunk allocation.
+            // Allocation is extended in place if it is the last one.
+            if let Some(ptr) = unsafe { ChunkHeader::resize(root, ptr, old_layout, new_layout) } {
+                self.count(new_layout.size(), old_layout.size());
+                return Ok(ptr);
+            }
+        }
+
+        let ptr = resize_slow(
             &self.root,
+            &self.spare,
             self.min_chunk_size.get(),
+            self.max_chunk_size,
+            self.chunk_align,
             ptr,
             old_layout,
             new_layout,
             allocator,
-        )
+        )?;
+        self.count(new_layout.size(), old_layout.size());
+        Ok(ptr)
     }
 
     #[inline(always)]
     pub unsafe fn dealloc(&self, ptr: NonNull<u8>, size: usize) {
+        self.update_zeroed_frontier();
+        self.count(0, size);
+
+        if self.zero_free {
+            // Safety: memory of deallocated allocation is not used anymore.
+            unsafe { ptr::write_bytes(ptr.as_ptr(), 0, size) };
+        }
+
+        if self.dealloc_small(ptr, size) {
+            return;
+        }
+
+        let top = self.lifo_top.get();
+        if top > 0 {
+            let (start, end, cursor) = self.lifo[top - 1].get();
+            if start == ptr.as_ptr() {
+                // Allocation is freed, entry is not needed anymore.
+                self.lifo[top - 1].set((ptr::null_mut(), ptr::null_mut(), ptr::null_mut()));
+                self.lifo_top.set(top - 1);
+
+                if let Some(root) = self.root.get() {
+                    // Trust the entry only if cursor is still where
+                    // it was left by the allocation.
+                    // Safety: `root` is a valid pointer to chunk allocation.
+                    if unsafe { root.as_ref().cursor.get() } == end {
+                        #[cfg(not(feature = "bump-down"))]
+                        let size = end as usize - start as usize;
+
+                        // Safety: entry was recorded when `ptr` was allocated
+                        // and chunks were not rewound since.
+                        if unsafe { ChunkHeader::dealloc_to(root, ptr, size, cursor) } {
+                            return;
+                        }
+                    }
+                }
+            }
+        }
+
         dealloc(self.root.get(), ptr, size)
     }
 
     #[inline(always)]
-    pub unsafe fn reset(&mut self, keep_last: bool, allocator: impl Allocator) {
-        unsafe { reset(&self.root, keep_last, allocator) }
+    pub fn set_size_classes(&mut self, enabled: bool) {
+        self.size_classes = enabled;
+    }
+
+    #[inline(always)]
+    pub fn set_chunk_align(&mut self, align: usize) {
+        debug_assert!(align.is_power_of_two());
+        self.chunk_align = align;
+    }
+
+    /// Makes arena keep memory not used by live allocations zeroed.
+    /// New chunks are allocated zeroed and memory is zeroed
+    /// when allocations are deallocated, shrunk or moved and on reset.
+    #[inline(always)]
+    pub fn set_zero_free(&mut self) {
+        if !self.zero_free {
+            self.zero_free = true;
+            zero_unused_chunks(self.root.get());
+            zero_unused_chunks(self.spare.get());
+        }
+    }
+
+    #[inline(always)]
+    pub fn zero_free(&self) -> bool {
+        self.zero_free
+    }
+
+    /// Wraps chunk source to allocate zeroed chunks if needed.
+    #[inline(always)]
+    fn source<'a, S>(&self, source: &'a S) -> ZeroFreeSource<'a, S> {
+        ZeroFreeSource {
+            source,
+            zeroed: self.zero_free,
+        }
+    }
+
+    /// Zeroes memory of the old allocation that is not covered by the new one.
+    #[inline(always)]
+    unsafe fn zero_replaced(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_ptr: NonNull<[u8]>,
+        new_layout: Layout,
+    ) {
+        if !self.zero_free {
+            return;
+        }
+
+        let start = ptr.as_ptr() as usize;
+        let end = start + old_layout.size();
+        let new_start = new_ptr.as_ptr().cast::<u8>() as usize;
+        let new_end = new_start + new_layout.size();
+
+        // Safety: both parts are within the old allocation
+        // that is not used anymore.
+        unsafe {
+            let before = end.min(new_start).saturating_sub(start);
+            ptr::write_bytes(ptr.as_ptr(), 0, before);
+
+            let after = new_end.max(start);
+            if after < end {
+                ptr::write_bytes(ptr.as_ptr().add(after - start), 0, end - after);
+            }
+        }
+    }
+
+    /// Zeroes used memory of all chunks before they are rewound.
+    #[inline(always)]
+    fn zero_used(&self) {
+        if self.zero_free {
+            for (start, len) in self.used_chunks() {
+                // Safety: memory is used by allocations that are being reset.
+                unsafe { ptr::write_bytes(start.as_ptr(), 0, len) };
+            }
+        }
+    }
+
+    #[inline(always)]
+    pub fn set_retention(&mut self, policy: RetentionPolicy) {
+        self.retention.set(policy);
+    }
+
+    #[inline(always)]
+    pub unsafe fn reset(&mut self, keep_last: bool, allocator: &impl ChunkSource) {
+        unsafe { self.reset_unchecked(keep_last, allocator) }
+    }
+
+    #[inline(always)]
+    pub unsafe fn reset_unchecked(&self, keep_last: bool, allocator: &impl ChunkSource) {
+        let allocator = &self.source(allocator);
+        self.update_zeroed_frontier();
+        self.clear_lifo();
+        self.clear_count();
+        self.zero_used();
+        unsafe { free_spare(&self.spare, self.chunk_align, allocator) };
+        if keep_last {
+            unsafe {
+                reset_retain(
+                    &self.root,
+                    self.retention.get(),
+                    self.max_chunk_size,
+                    self.chunk_align,
+                    allocator,
+                )
+            }
+        } else {
+            unsafe { reset(&self.root, false, self.chunk_align, allocator) }
+        }
+        self.check_zeroed_chunk();
     }
 
+    /// Resets with consolidation regardless of retention policy.
     #[inline(always)]
-    pub unsafe fn reset_unchecked(&self, keep_last: bool, allocator: impl Allocator) {
-        unsafe { reset(&self.root, keep_last, allocator) }
+    pub unsafe fn reset_compact(&mut self, allocator: &impl ChunkSource) {
+        let allocator = &self.source(allocator);
+        self.update_zeroed_frontier();
+        self.clear_lifo();
+        self.clear_count();
+        self.zero_used();
+        unsafe {
+            free_spare(&self.spare, self.chunk_align, allocator);
+            reset_retain(
+                &self.root,
+                RetentionPolicy::Consolidate,
+                self.max_chunk_size,
+                self.chunk_align,
+                allocator,
+            )
+        }
+        self.check_zeroed_chunk();
     }
 
-    #[cfg(feature = "sync")]
+    #[inline(always)]
+    pub fn reset_keep_all(&mut self) {
+        self.update_zeroed_frontier();
+        self.clear_lifo();
+        self.clear_count();
+        self.zero_used();
+        reset_keep_all(&self.root, &self.spare);
+        self.check_zeroed_chunk();
+    }
+
+    #[cfg(feature = "sync-core")]
     #[inline(always)]
     pub fn reset_leak(&mut self, keep_last: bool) {
-        reset_leak(&self.root, keep_last)
+        self.update_zeroed_frontier();
+        self.clear_lifo();
+        self.clear_count();
+        if keep_last && self.zero_free {
+            // Only the last chunk is reused, leaked ones are left intact.
+            if let Some((start, len)) = self.used_chunks().next() {
+                // Safety: memory of the last chunk is rewound.
+                unsafe { ptr::write_bytes(start.as_ptr(), 0, len) };
+            }
+        }
+        reset_leak(&self.root, keep_last);
+        self.check_zeroed_chunk();
+    }
+
+    #[inline(always)]
+    pub unsafe fn donate(&mut self, other: &mut Self, allocator: &impl ChunkSource) {
+        self.update_zeroed_frontier();
+        self.clear_lifo();
+        self.clear_count();
+        self.zero_used();
+        if self.chunk_align == other.chunk_align && (self.zero_free || !other.zero_free) {
+            unsafe { donate(&self.root, &other.root, self.chunk_align, allocator) }
+        } else {
+            // Chunks are released with arena's alignment,
+            // so they can't be moved to arena with different one.
+            // Arena that keeps free memory zeroed can't take dirty chunks.
+            unsafe { reset(&self.root, false, self.chunk_align, allocator) }
+        }
+        self.zeroed_chunk.set(None);
     }
 
     /// Returns the approximate number of bytes allocated from this arena.
@@ -125,23 +994,66 @@ impl ArenaLocal {
     /// (which are ~fully used, minus alignment padding) plus the cursor
     /// offset in the current chunk. After warm-up (when a single chunk
     /// serves all allocations), this is exact.
+    ///
+    /// With `exact-stats` feature returns sum of sizes of live allocations instead.
     pub fn allocated_bytes(&self) -> usize {
+        #[cfg(feature = "exact-stats")]
+        {
+            self.allocated.get()
+        }
+
+        #[cfg(not(feature = "exact-stats"))]
+        {
+            let Some(root) = self.root.get() else {
+                return 0;
+            };
+            let chunk = unsafe { root.as_ref() };
+            chunk.used() + chunk.cumulative_size
+        }
+    }
+
+    /// Returns the total capacity of all chunks in this arena.
+    pub fn total_capacity(&self) -> usize {
         let Some(root) = self.root.get() else {
             return 0;
         };
         let chunk = unsafe { root.as_ref() };
-        let cursor = chunk.cursor.load(Ordering::Relaxed) as usize;
-        let base = chunk.base() as usize;
-        let current_used = cursor - base;
-        current_used + chunk.cumulative_size
+        chunk.cap() + chunk.cumulative_size + spare_capacity(self.spare.get())
     }
 
-    /// Returns the total capacity of all chunks in this arena.
-    pub fn total_capacity(&self) -> usize {
+    /// Returns bytes consumed by chunk headers and unreachable chunk tails.
+    pub fn overhead_bytes(&self) -> usize {
+        overhead_bytes(self.root.get(), self.spare.get())
+    }
+
+    /// Returns number of chunks held by this arena, including spare ones.
+    pub fn chunk_count(&self) -> usize {
+        count_chunks(self.root.get()) + count_chunks(self.spare.get())
+    }
+
+    /// Checks if `ptr` points into memory of this arena's chunks.
+    pub fn contains(&self, ptr: NonNull<u8>) -> bool {
+        contains(self.root.get(), ptr)
+    }
+
+    /// Returns iterator over utilization of chunks.
+    pub fn utilization(&self) -> Chunks<'_> {
+        // Safety: chunks are deallocated only with mutable borrow.
+        unsafe { Chunks::new(self.root.get()) }
+    }
+
+    /// Returns iterator over used memory regions of chunks.
+    pub fn used_chunks(&self) -> UsedChunks<'_> {
+        // Safety: chunks are deallocated only with mutable borrow.
+        unsafe { UsedChunks::new(self.root.get()) }
+    }
+
+    /// Returns number of bytes left in the current chunk.
+    pub fn remaining_capacity(&self) -> usize {
         let Some(root) = self.root.get() else {
             return 0;
         };
         let chunk = unsafe { root.as_ref() };
-        chunk.cap() + chunk.cumulative_size
+        chunk.remaining()
     }
 }
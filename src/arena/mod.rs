@@ -6,20 +6,28 @@
 use core::{
     alloc::Layout,
     cell::Cell,
+    marker::PhantomData,
     mem::{align_of, size_of},
     ptr::{self, NonNull},
-    sync::atomic::{AtomicPtr, Ordering},
+    sync::atomic::Ordering,
 };
 
+#[cfg(not(loom))]
+use core::sync::atomic::AtomicPtr;
+
+// Model-checked under `loom`.
+#[cfg(loom)]
+use loom::sync::atomic::AtomicPtr;
+
 #[cfg(not(feature = "nightly"))]
-use allocator_api2::alloc::{AllocError, Allocator};
+use allocator_api2::alloc::AllocError;
 #[cfg(feature = "nightly")]
-use core::alloc::{AllocError, Allocator};
+use core::alloc::AllocError;
 
-use crate::cold;
+use crate::{cold, source::ChunkSource};
 
-#[cfg(feature = "sync")]
-use parking_lot::RwLock;
+#[cfg(feature = "sync-core")]
+use crate::lock::RwLock;
 
 #[inline(always)]
 fn is_aligned_to(value: usize, align: usize) -> bool {
@@ -42,7 +50,63 @@ fn align_down(value: usize, align: usize) -> usize {
     value & !mask
 }
 
+/// Rounds alignment and size of the layout up to `min_align`.
+/// Allocations made with resulting layouts never share
+/// `min_align`-aligned blocks of memory.
+///
+/// Size is always rounded up to the alignment,
+/// so allocation covers usable region up to the next alignment boundary.
+#[inline(always)]
+pub fn pad_layout(layout: Layout, min_align: usize) -> Result<Layout, AllocError> {
+    if min_align == 1 {
+        return Ok(layout.pad_to_align());
+    }
+    match layout.align_to(min_align) {
+        Ok(layout) => Ok(layout.pad_to_align()),
+        Err(_) => Err(AllocError),
+    }
+}
+
+/// Rounds size of deallocated memory block up to `min_align`
+/// to match size padded with [`pad_layout`].
+#[inline(always)]
+pub fn pad_size(size: usize, min_align: usize) -> usize {
+    // Padded size of the allocation is not less than this.
+    align_up(size, min_align).unwrap_or(size)
+}
+
+/// Hints CPU to fetch cache line at `ptr` where next allocation will be placed.
+/// So that the first write into it does not stall on cache miss.
+///
+/// Prefetch never faults, so `ptr` may point outside of the chunk.
+#[inline(always)]
+fn prefetch(ptr: *const u8) {
+    #[cfg(all(feature = "prefetch", target_arch = "x86_64"))]
+    // Safety: prefetch is a hint and never faults.
+    unsafe {
+        use core::arch::x86_64::{_mm_prefetch, _MM_HINT_T0};
+        _mm_prefetch::<_MM_HINT_T0>(ptr.cast());
+    }
+
+    #[cfg(all(feature = "prefetch", target_arch = "aarch64"))]
+    // Safety: prefetch is a hint and never faults.
+    unsafe {
+        core::arch::asm!(
+            "prfm pstl1keep, [{ptr}]",
+            ptr = in(reg) ptr,
+            options(nostack, readonly, preserves_flags),
+        );
+    }
+
+    #[cfg(not(all(
+        feature = "prefetch",
+        any(target_arch = "x86_64", target_arch = "aarch64")
+    )))]
+    let _ = ptr;
+}
+
 /// A sum of layout size and align mask.
+#[cfg(not(feature = "bump-down"))]
 #[inline(always)]
 fn layout_sum(layout: &Layout) -> usize {
     // Layout constrains guarantee that this won't overflow.
@@ -53,6 +117,7 @@ pub(crate) trait CasPtr {
     #[allow(dead_code)]
     fn new(value: *mut u8) -> Self;
     fn load(&self, order: Ordering) -> *mut u8;
+    #[allow(dead_code)]
     fn set(&mut self, value: *mut u8);
     fn compare_exchange(
         &self,
@@ -132,11 +197,18 @@ impl CasPtr for AtomicPtr<u8> {
         self.load(order)
     }
 
+    #[cfg(not(loom))]
     #[inline(always)]
     fn set(&mut self, value: *mut u8) {
         *self.get_mut() = value;
     }
 
+    #[cfg(loom)]
+    #[inline(always)]
+    fn set(&mut self, value: *mut u8) {
+        self.with_mut(|ptr| *ptr = value);
+    }
+
     #[inline(always)]
     fn compare_exchange(
         &self,
@@ -162,8 +234,109 @@ impl CasPtr for AtomicPtr<u8> {
     }
 }
 
+/// Policy that selects which chunk is retained on reset.
+///
+/// All other chunks are returned to the underlying allocator.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum RetentionPolicy {
+    /// Keep the chunk that served the most recent allocations.
+    /// Normally it is also the largest one.
+    #[default]
+    KeepMostRecentlyUsed,
+
+    /// Keep the chunk with largest capacity.
+    KeepLargest,
+
+    /// Keep the smallest chunk with capacity not less than specified number of bytes.
+    /// If there's no such chunk, keep the largest one.
+    KeepBestFit(usize),
+
+    /// Replace all chunks with a single chunk sized to fit
+    /// all allocations made since previous reset.
+    /// Steady state is reached after one cycle.
+    ///
+    /// Consolidated chunk size is bounded by maximum chunk size.
+    /// If the most recent chunk is large enough or
+    /// new chunk allocation fails, it is kept instead.
+    Consolidate,
+}
+
+/// Utilization of a single memory chunk.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ChunkUtilization {
+    /// Usable capacity of the chunk.
+    pub capacity: usize,
+
+    /// Bytes used by allocations, including alignment padding.
+    pub used: usize,
+
+    /// Bytes left unused when allocation switched to a newer chunk.
+    /// Always zero for the current chunk.
+    pub stranded: usize,
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for ChunkUtilization {
+    #[inline]
+    fn format(&self, f: defmt::Formatter<'_>) {
+        defmt::write!(
+            f,
+            "capacity: {=usize} B, used: {=usize} B, stranded: {=usize} B",
+            self.capacity,
+            self.used,
+            self.stranded,
+        )
+    }
+}
+
+enum UtilizationInner<'a> {
+    Local(local::Chunks<'a>),
+    #[cfg(feature = "sync-core")]
+    Sync(sync::Chunks<'a>),
+}
+
+/// Iterator over utilization of allocator's chunks,
+/// from the current chunk to the oldest one.
+///
+/// Spare chunks kept by `reset_keep_all` are not included.
+pub struct Utilization<'a> {
+    inner: UtilizationInner<'a>,
+}
+
+impl<'a> From<local::Chunks<'a>> for Utilization<'a> {
+    #[inline(always)]
+    fn from(chunks: local::Chunks<'a>) -> Self {
+        Utilization {
+            inner: UtilizationInner::Local(chunks),
+        }
+    }
+}
+
+#[cfg(feature = "sync-core")]
+impl<'a> From<sync::Chunks<'a>> for Utilization<'a> {
+    #[inline(always)]
+    fn from(chunks: sync::Chunks<'a>) -> Self {
+        Utilization {
+            inner: UtilizationInner::Sync(chunks),
+        }
+    }
+}
+
+impl Iterator for Utilization<'_> {
+    type Item = ChunkUtilization;
+
+    #[inline]
+    fn next(&mut self) -> Option<ChunkUtilization> {
+        match &mut self.inner {
+            UtilizationInner::Local(chunks) => chunks.next(),
+            #[cfg(feature = "sync-core")]
+            UtilizationInner::Sync(chunks) => chunks.next(),
+        }
+    }
+}
+
 /// 0.25 KB. Initial chunk size.
-const CHUNK_START_SIZE: usize = 256;
+pub(crate) const CHUNK_START_SIZE: usize = 256;
 
 /// 16 KB. After this size, new chunk size is not aligned to next power of two.
 const CHUNK_POWER_OF_TWO_THRESHOLD: usize = 1 << 14;
@@ -171,12 +344,27 @@ const CHUNK_POWER_OF_TWO_THRESHOLD: usize = 1 << 14;
 /// 1/16 KB. Minimum chunk size growth step.
 const CHUNK_MIN_GROW_STEP: usize = 64;
 
+/// Rounds chunk size up.
+/// Grows size exponentially until a threshold.
+#[inline(always)]
+fn round_chunk_size(chunk_size: usize) -> usize {
+    if chunk_size < CHUNK_POWER_OF_TWO_THRESHOLD {
+        chunk_size.next_power_of_two()
+    } else {
+        align_up(chunk_size, CHUNK_POWER_OF_TWO_THRESHOLD).unwrap_or(chunk_size)
+    }
+}
+
 macro_rules! with_cursor {
     ($cursor:ty) => {
         #[repr(C)]
         pub struct ChunkHeader {
             cursor: $cursor,
+            #[cfg(not(feature = "header-end"))]
             end: *mut u8,
+            // Usable memory ends where header starts.
+            #[cfg(feature = "header-end")]
+            base: *mut u8,
             prev: Option<NonNull<Self>>,
             cumulative_size: usize,
         }
@@ -185,36 +373,56 @@ macro_rules! with_cursor {
             #[inline]
             unsafe fn alloc_chunk(
                 size: usize,
-                allocator: impl Allocator,
+                align: usize,
+                allocator: &(impl ChunkSource + ?Sized),
                 prev: Option<NonNull<Self>>,
+                zeroed: bool,
             ) -> Result<NonNull<Self>, AllocError> {
-                let Some(size) = align_up(size, align_of::<Self>()) else {
+                debug_assert!(align.is_power_of_two());
+                let align = align.max(align_of::<Self>());
+
+                let Some(size) = align_up(size, align) else {
                     return Err(AllocError);
                 };
 
-                // Safety:
-                // size + (align - 1) hasn't overflow above.
-                // `align_of` returns valid align value.
-                let layout = unsafe { Layout::from_size_align_unchecked(size, align_of::<Self>()) };
-                let slice = allocator.allocate(layout)?;
-                Ok(Self::init_chunk(slice, prev))
+                let layout = Layout::from_size_align(size, align).map_err(|_| AllocError)?;
+                let slice = if zeroed {
+                    allocator.allocate_chunk_zeroed(layout)?
+                } else {
+                    allocator.allocate_chunk(layout)?
+                };
+                Ok(Self::init_chunk(slice, prev, align))
             }
 
             #[inline]
             unsafe fn dealloc_chunk(
                 chunk: NonNull<Self>,
-                allocator: impl Allocator,
+                align: usize,
+                allocator: &(impl ChunkSource + ?Sized),
             ) -> Option<NonNull<Self>> {
                 let me = unsafe { chunk.as_ref() };
                 let prev = me.prev;
 
-                let size = unsafe { me.end.offset_from(chunk.as_ptr().cast()) } as usize;
+                #[cfg(not(feature = "header-end"))]
+                let start = chunk.cast::<u8>();
+
+                #[cfg(not(feature = "header-end"))]
+                let size = unsafe { me.end().offset_from(start.as_ptr()) } as usize;
+
+                // Allocation starts at `base` and ends right after the header.
+                #[cfg(feature = "header-end")]
+                let start = unsafe { NonNull::new_unchecked(me.base) };
+
+                #[cfg(feature = "header-end")]
+                let size = me.cap() + size_of::<Self>();
 
                 // Safety:
                 // Making layout of actual allocation.
-                let layout = unsafe { Layout::from_size_align_unchecked(size, align_of::<Self>()) };
+                let layout = unsafe {
+                    Layout::from_size_align_unchecked(size, align.max(align_of::<Self>()))
+                };
 
-                allocator.deallocate(chunk.cast(), layout);
+                allocator.release_chunk(start, layout);
                 prev
             }
 
@@ -224,21 +432,48 @@ macro_rules! with_cursor {
             /// `ptr` must be aligned for `ChunkHeader` structure.
             /// `size` must be the size of the allocation.
             /// `size` must be large enough to fit `Chunk` structure.
+            /// `ptr` must be aligned to `align`.
             #[inline]
             unsafe fn init_chunk(
                 slice: NonNull<[u8]>,
                 prev: Option<NonNull<Self>>,
+                align: usize,
             ) -> NonNull<Self> {
                 let len = slice.len();
                 let ptr = slice.as_ptr().cast::<u8>();
-                debug_assert!(is_aligned_to(ptr as usize, align_of::<Self>()));
+                debug_assert!(is_aligned_to(ptr.addr(), align));
                 debug_assert!(len > size_of::<Self>());
 
+                #[cfg(all(not(feature = "bump-down"), not(feature = "header-end")))]
                 let end = ptr.add(len);
 
+                // Cursor starts at the end, so it must be aligned
+                // at least as `Self` to guarantee that allocations of
+                // `size + align` bytes always fit.
+                // Requested size is a multiple of chunk alignment,
+                // so aligning to it doesn't cut requested memory.
+                #[cfg(all(feature = "bump-down", not(feature = "header-end")))]
+                let end = ptr.add(align_down(len, align));
+
+                #[cfg(not(feature = "header-end"))]
                 let header_ptr = ptr.cast::<Self>();
+
+                #[cfg(not(feature = "header-end"))]
                 let base = header_ptr.add(1).cast::<u8>();
 
+                // Header is placed at the tail of the chunk,
+                // so usable memory starts at the chunk's own alignment.
+                #[cfg(feature = "header-end")]
+                let header_ptr = ptr
+                    .add(align_down(len - size_of::<Self>(), align_of::<Self>()))
+                    .cast::<Self>();
+
+                #[cfg(feature = "header-end")]
+                let base = ptr;
+
+                #[cfg(all(feature = "bump-down", feature = "header-end"))]
+                let end = header_ptr.cast::<u8>();
+
                 let cumulative_size = match prev {
                     None => 0,
                     Some(prev) => {
@@ -247,11 +482,20 @@ macro_rules! with_cursor {
                     }
                 };
 
+                #[cfg(feature = "bump-down")]
+                let _ = base;
+
                 ptr::write(
                     header_ptr,
                     ChunkHeader {
+                        #[cfg(not(feature = "bump-down"))]
                         cursor: <$cursor>::new(base),
+                        #[cfg(feature = "bump-down")]
+                        cursor: <$cursor>::new(end),
+                        #[cfg(not(feature = "header-end"))]
                         end,
+                        #[cfg(feature = "header-end")]
+                        base,
                         prev,
                         cumulative_size,
                     },
@@ -259,11 +503,31 @@ macro_rules! with_cursor {
                 NonNull::new_unchecked(header_ptr)
             }
 
+            #[cfg(not(feature = "header-end"))]
             #[inline(always)]
             fn base(&self) -> *const u8 {
                 unsafe { <*const Self>::add(self, 1).cast() }
             }
 
+            #[cfg(feature = "header-end")]
+            #[inline(always)]
+            fn base(&self) -> *const u8 {
+                self.base
+            }
+
+            #[cfg(not(feature = "header-end"))]
+            #[inline(always)]
+            fn end(&self) -> *mut u8 {
+                self.end
+            }
+
+            #[cfg(feature = "header-end")]
+            #[inline(always)]
+            fn end(&self) -> *mut u8 {
+                // Derived from `base` to keep provenance of the whole chunk.
+                self.base.with_addr((self as *const Self).addr())
+            }
+
             /// # Safety
             ///
             /// `ptr` must be a pointer withing the usable memory of the chunk.
@@ -271,7 +535,7 @@ macro_rules! with_cursor {
             #[inline(always)]
             unsafe fn offset_from_end(&self, ptr: *const u8) -> usize {
                 // Safety: end and base belong to the same memory chunk.
-                let offset = unsafe { self.end.offset_from(ptr) };
+                let offset = unsafe { self.end().offset_from(ptr) };
                 offset as usize
             }
 
@@ -281,7 +545,33 @@ macro_rules! with_cursor {
                 unsafe { self.offset_from_end(self.base()) }
             }
 
+            /// Returns number of bytes left in this chunk.
+            #[allow(dead_code)]
+            #[inline(always)]
+            fn remaining(&self) -> usize {
+                self.cap() - self.used()
+            }
+
+            /// Returns number of bytes used in this chunk.
+            #[inline(always)]
+            fn used(&self) -> usize {
+                let cursor = self.cursor.load(Ordering::Relaxed);
+
+                #[cfg(not(feature = "bump-down"))]
+                {
+                    // Safety: `base` and `cursor` belong to the same memory chunk.
+                    unsafe { cursor.offset_from(self.base()) as usize }
+                }
+
+                #[cfg(feature = "bump-down")]
+                {
+                    // Safety: `cursor` fits `base..=end` range.
+                    unsafe { self.offset_from_end(cursor) }
+                }
+            }
+
             // Safety: `chunk` must be a pointer to the valid chunk allocation.
+            #[cfg(not(feature = "bump-down"))]
             #[inline(always)]
             unsafe fn alloc(chunk: NonNull<Self>, layout: Layout) -> Option<NonNull<[u8]>> {
                 // Safety: `chunk` is a valid pointer to chunk allocation.
@@ -289,7 +579,7 @@ macro_rules! with_cursor {
                 let mut cursor = me.cursor.load(Ordering::Relaxed);
 
                 loop {
-                    let cursor_addr = cursor as usize;
+                    let cursor_addr = cursor.addr();
 
                     let layout_sum = layout_sum(&layout);
 
@@ -308,7 +598,7 @@ macro_rules! with_cursor {
 
                     let next_addr = aligned_addr + layout.size();
 
-                    let end_addr = me.end as usize;
+                    let end_addr = me.end().addr();
                     if next_addr > end_addr {
                         return None;
                     }
@@ -326,6 +616,8 @@ macro_rules! with_cursor {
                         continue;
                     };
 
+                    prefetch(next);
+
                     // Actual allocation length.
                     let len = next_addr - aligned_addr;
                     debug_assert!(len >= layout.size());
@@ -343,13 +635,63 @@ macro_rules! with_cursor {
                 }
             }
 
+            // Safety: `chunk` must be a pointer to the valid chunk allocation.
+            #[cfg(feature = "bump-down")]
+            #[inline(always)]
+            unsafe fn alloc(chunk: NonNull<Self>, layout: Layout) -> Option<NonNull<[u8]>> {
+                // Safety: `chunk` is a valid pointer to chunk allocation.
+                let me = unsafe { chunk.as_ref() };
+                let mut cursor = me.cursor.load(Ordering::Relaxed);
+
+                loop {
+                    let cursor_addr = cursor.addr();
+
+                    let unaligned = cursor_addr.checked_sub(layout.size())?;
+                    let aligned_addr = align_down(unaligned, layout.align());
+
+                    let base_addr = me.base().addr();
+                    if aligned_addr < base_addr {
+                        return None;
+                    }
+
+                    let aligned = unsafe { cursor.sub(cursor_addr - aligned_addr) };
+
+                    if let Err(updated) = me.cursor.compare_exchange_weak(
+                        cursor,
+                        aligned,
+                        Ordering::Acquire, // Memory access valid only *after* this succeeds.
+                        Ordering::Relaxed,
+                    ) {
+                        cursor = updated;
+                        continue;
+                    };
+
+                    prefetch(aligned.wrapping_sub(1));
+
+                    // Actual allocation length.
+                    let len = cursor_addr - aligned_addr;
+                    debug_assert!(len >= layout.size());
+
+                    // Safety:
+                    // offset is within unused allocated memory range ending at `end`.
+                    // base is not null.
+                    let slice = unsafe {
+                        debug_assert_eq!(aligned_addr % layout.align(), 0);
+                        let slice = core::ptr::slice_from_raw_parts_mut(aligned, len);
+                        NonNull::new_unchecked(slice)
+                    };
+
+                    return Some(slice);
+                }
+            }
+
             /// Optimistic resize for arena-allocated memory.
             /// Handles grows, shrinks if new alignment requirement is not met - shifts.
             /// When alignment requirement is already met (checked for pointer itself)
             /// shifts do not happen for both shrinks and grows.
             /// Even more, cheap shrinks are always successful if alignment is met by `ptr`.
             /// Cheap grows are successful if this is the last allocation in the chunk
-            /// and there is enough space for the new allocation.
+            /// it was allocated from and there is enough space for the new allocation.
             /// If cheap shrink or grow is not possible - reallocates.
             ///
             /// Safety: `chunk` must be a pointer to the valid chunk allocation.
@@ -365,57 +707,51 @@ macro_rules! with_cursor {
                 // Safety: `chunk` is a valid pointer to chunk allocation.
                 let me = unsafe { chunk.as_ref() };
 
-                let addr = ptr.as_ptr() as usize;
                 if old_layout.align() >= new_layout.align() {
                     if new_layout.size() <= old_layout.size() {
+                        if let Some(slice) = Self::shrink_last(me, ptr, old_layout, new_layout) {
+                            return Some(slice);
+                        }
                         let slice =
                             core::ptr::slice_from_raw_parts_mut(ptr.as_ptr(), old_layout.size());
                         return Some(NonNull::new_unchecked(slice));
-                    } else {
-                        // Safety:
-                        // `ptr + old_layout.size()` is within allocation or one by past end.
-                        let old_end = unsafe { ptr.as_ptr().add(old_layout.size()) };
-
-                        let cursor = me.cursor.load(Ordering::Relaxed);
-                        if cursor == old_end {
-                            let next_addr = addr.checked_add(new_layout.size())?;
-
-                            let end_addr = me.end as usize;
-                            if next_addr > end_addr {
-                                // Not enough space.
-                                return None;
-                            }
-
-                            let next = unsafe { ptr.as_ptr().add(new_layout.size()) };
-
-                            let result = CasPtr::compare_exchange(
-                                &me.cursor,
-                                cursor,
-                                next,
-                                Ordering::Acquire, // Acquire more memory.
-                                Ordering::Relaxed,
-                            );
-
-                            if let Ok(()) = result {
-                                let len = next_addr - addr;
-                                debug_assert!(len >= new_layout.size());
+                    }
 
-                                let slice = core::ptr::slice_from_raw_parts_mut(ptr.as_ptr(), len);
-                                return Some(NonNull::new_unchecked(slice));
+                    if me.contains(ptr) {
+                        if me.is_last(ptr, old_layout) {
+                            if let Some(slice) = Self::grow_last(me, ptr, old_layout, new_layout) {
+                                return Some(slice);
                             }
-                            cold();
+                        }
+                    } else {
+                        // Allocation may be the last one in older chunk
+                        // with untouched tail space.
+                        let mut prev = me.prev;
+                        while let Some(chunk) = prev {
+                            // Safety: `chunk` is a valid pointer to chunk allocation.
+                            let owner = unsafe { chunk.as_ref() };
+                            if owner.contains(ptr) {
+                                if owner.is_last(ptr, old_layout) {
+                                    if let Some(slice) =
+                                        Self::grow_last(owner, ptr, old_layout, new_layout)
+                                    {
+                                        return Some(slice);
+                                    }
+                                }
+                                break;
+                            }
+                            prev = owner.prev;
                         }
                     }
-                } else {
-                    cold();
                 }
+                cold();
 
                 // Have to reallocate.
                 let new_ptr = ChunkHeader::alloc(chunk, new_layout)?;
 
                 // Copy bytes from old location to new.
                 // Separate allocations cannot overlap.
-                core::ptr::copy_nonoverlapping(
+                crate::copy::copy_bulk(
                     ptr.as_ptr(),
                     new_ptr.as_ptr().cast(),
                     new_layout.size().min(old_layout.size()),
@@ -425,14 +761,245 @@ macro_rules! with_cursor {
                 Some(new_ptr)
             }
 
+            /// Checks if `ptr` points into usable memory of this chunk.
+            #[inline(always)]
+            fn contains(&self, ptr: NonNull<u8>) -> bool {
+                let ptr = ptr.as_ptr().cast_const();
+                self.base() <= ptr && ptr < self.end().cast_const()
+            }
+
+            /// Checks if allocation is the last one in this chunk.
+            #[inline(always)]
+            fn is_last(&self, ptr: NonNull<u8>, layout: Layout) -> bool {
+                let cursor = self.cursor.load(Ordering::Relaxed);
+
+                #[cfg(not(feature = "bump-down"))]
+                {
+                    cursor == ptr.as_ptr().wrapping_add(layout.size())
+                }
+
+                #[cfg(feature = "bump-down")]
+                {
+                    let _ = layout;
+                    cursor == ptr.as_ptr()
+                }
+            }
+
+            /// Grows the last allocation in this chunk in place.
+            /// Returns `None` if there's not enough space.
+            ///
+            /// Safety: `ptr` must be the last allocation in this chunk made with `old_layout`.
+            /// `new_layout` must not be smaller or stricter aligned than `old_layout`.
+            #[cfg(not(feature = "bump-down"))]
+            #[inline(always)]
+            unsafe fn grow_last(
+                &self,
+                ptr: NonNull<u8>,
+                old_layout: Layout,
+                new_layout: Layout,
+            ) -> Option<NonNull<[u8]>> {
+                let addr = ptr.as_ptr().addr();
+                let next_addr = addr.checked_add(new_layout.size())?;
+
+                let end_addr = self.end().addr();
+                if next_addr > end_addr {
+                    // Not enough space.
+                    return None;
+                }
+
+                // Safety:
+                // `ptr + old_layout.size()` is within allocation or one by past end.
+                let old_end = unsafe { ptr.as_ptr().add(old_layout.size()) };
+                let next = unsafe { ptr.as_ptr().add(new_layout.size()) };
+
+                let result = CasPtr::compare_exchange(
+                    &self.cursor,
+                    old_end,
+                    next,
+                    Ordering::Acquire, // Acquire more memory.
+                    Ordering::Relaxed,
+                );
+
+                match result {
+                    Ok(()) => {
+                        let len = next_addr - addr;
+                        debug_assert!(len >= new_layout.size());
+
+                        let slice = core::ptr::slice_from_raw_parts_mut(ptr.as_ptr(), len);
+                        Some(NonNull::new_unchecked(slice))
+                    }
+                    Err(_) => None,
+                }
+            }
+
+            /// Grows the last allocation in this chunk in place.
+            /// Returns `None` if there's not enough space.
+            ///
+            /// Bumping downward, last allocation starts at cursor.
+            /// It can grow only by moving down.
+            ///
+            /// Safety: `ptr` must be the last allocation in this chunk made with `old_layout`.
+            /// `new_layout` must not be smaller or stricter aligned than `old_layout`.
+            #[cfg(feature = "bump-down")]
+            #[inline(always)]
+            unsafe fn grow_last(
+                &self,
+                ptr: NonNull<u8>,
+                old_layout: Layout,
+                new_layout: Layout,
+            ) -> Option<NonNull<[u8]>> {
+                let addr = ptr.as_ptr().addr();
+                let grow = new_layout.size() - old_layout.size();
+                let unaligned = addr.checked_sub(grow)?;
+                let new_addr = align_down(unaligned, new_layout.align());
+
+                let base_addr = self.base().addr();
+                if new_addr < base_addr {
+                    // Not enough space.
+                    return None;
+                }
+
+                let new = unsafe { ptr.as_ptr().sub(addr - new_addr) };
+
+                let result = CasPtr::compare_exchange(
+                    &self.cursor,
+                    ptr.as_ptr(),
+                    new,
+                    Ordering::Acquire, // Acquire more memory.
+                    Ordering::Relaxed,
+                );
+
+                match result {
+                    Ok(()) => {
+                        // Regions may overlap.
+                        core::ptr::copy(ptr.as_ptr(), new, old_layout.size());
+
+                        let len = addr + old_layout.size() - new_addr;
+                        debug_assert!(len >= new_layout.size());
+
+                        let slice = core::ptr::slice_from_raw_parts_mut(new, len);
+                        Some(NonNull::new_unchecked(slice))
+                    }
+                    Err(_) => None,
+                }
+            }
+
+            /// Returns bytes freed by shrinking the last allocation to the chunk.
+            /// Returns `None` if `ptr` is not the last allocation.
+            ///
+            /// Safety: `ptr` must be allocated from this chunk with `old_layout`.
+            /// `new_layout` must not be larger or stricter aligned than `old_layout`.
+            #[cfg(not(feature = "bump-down"))]
+            #[inline(always)]
+            unsafe fn shrink_last(
+                &self,
+                ptr: NonNull<u8>,
+                old_layout: Layout,
+                new_layout: Layout,
+            ) -> Option<NonNull<[u8]>> {
+                // Safety:
+                // `ptr + old_layout.size()` is within allocation or one by past end.
+                let old_end = unsafe { ptr.as_ptr().add(old_layout.size()) };
+                let new_end = unsafe { ptr.as_ptr().add(new_layout.size()) };
+
+                let result = CasPtr::compare_exchange(
+                    &self.cursor,
+                    old_end,
+                    new_end,
+                    Ordering::Release, // Released some memory.
+                    Ordering::Relaxed,
+                );
+
+                match result {
+                    Ok(()) => {
+                        let slice =
+                            core::ptr::slice_from_raw_parts_mut(ptr.as_ptr(), new_layout.size());
+                        Some(NonNull::new_unchecked(slice))
+                    }
+                    Err(_) => None,
+                }
+            }
+
+            /// Bumping downward, last allocation starts at cursor.
+            /// It could shrink only by moving up, while shrinks never move memory.
+            /// So memory is never returned to the chunk and `None` is returned.
+            ///
+            /// Safety: `ptr` must be allocated from this chunk with `old_layout`.
+            /// `new_layout` must not be larger or stricter aligned than `old_layout`.
+            #[cfg(feature = "bump-down")]
+            #[inline(always)]
+            unsafe fn shrink_last(
+                &self,
+                ptr: NonNull<u8>,
+                old_layout: Layout,
+                new_layout: Layout,
+            ) -> Option<NonNull<[u8]>> {
+                let _ = (ptr, old_layout, new_layout);
+                None
+            }
+
             // Safety: `chunk` must be a pointer to the valid chunk allocation.
             #[inline(always)]
             unsafe fn reset(mut chunk: NonNull<Self>) -> Option<NonNull<Self>> {
+                unsafe { Self::rewind(chunk) };
+                unsafe { chunk.as_mut().prev.take() }
+            }
+
+            /// Moves cursor to the start of the chunk.
+            /// Keeps link to the previous chunk.
+            ///
+            /// Safety: `chunk` must be a pointer to the valid chunk allocation.
+            #[inline(always)]
+            unsafe fn rewind(mut chunk: NonNull<Self>) {
                 let me = chunk.as_mut();
-                let base = me.end.sub(me.cap());
-                me.cursor.set(base);
+
+                #[cfg(not(feature = "bump-down"))]
+                {
+                    let base = me.end().sub(me.cap());
+                    me.cursor.set(base);
+                }
+
+                #[cfg(feature = "bump-down")]
+                me.cursor.set(me.end());
+
                 me.cumulative_size = 0;
-                me.prev.take()
+            }
+
+            /// Moves cursor to `cursor` if `ptr` is the last allocation in this chunk.
+            /// Returns `false` otherwise.
+            ///
+            /// Safety: `chunk` must be a pointer to the valid chunk allocation.
+            /// `ptr` must be a pointer to the allocated memory of at least `size` bytes.
+            /// `cursor` must be the cursor position before `ptr` was allocated,
+            /// if `ptr` was allocated from this chunk.
+            #[allow(dead_code)]
+            #[inline(always)]
+            unsafe fn dealloc_to(
+                chunk: NonNull<Self>,
+                ptr: NonNull<u8>,
+                size: usize,
+                cursor: *mut u8,
+            ) -> bool {
+                // Safety: `chunk` is a valid pointer to chunk allocation.
+                let me = unsafe { chunk.as_ref() };
+
+                #[cfg(not(feature = "bump-down"))]
+                let current = unsafe { ptr.as_ptr().add(size) };
+
+                #[cfg(feature = "bump-down")]
+                let current = {
+                    let _ = size;
+                    ptr.as_ptr()
+                };
+
+                me.cursor
+                    .compare_exchange(
+                        current,
+                        cursor,
+                        Ordering::Release, // Released some memory.
+                        Ordering::Relaxed,
+                    )
+                    .is_ok()
             }
 
             // Safety: `chunk` must be a pointer to the valid chunk allocation.
@@ -444,14 +1011,22 @@ macro_rules! with_cursor {
                 let me = unsafe { chunk.as_ref() };
 
                 // Safety: `ptr` is a valid pointer to the allocated memory of at least `size` bytes.
-                let new = unsafe { ptr.as_ptr().add(size) };
+                let end = unsafe { ptr.as_ptr().add(size) };
+
+                // Cursor is at the end of the last allocation when bumping upward
+                // and at the start of it when bumping downward.
+                #[cfg(not(feature = "bump-down"))]
+                let (current, new) = (end, ptr.as_ptr());
+
+                #[cfg(feature = "bump-down")]
+                let (current, new) = (ptr.as_ptr(), end);
 
                 // Single attempt to update cursor.
                 // Fails if `ptr` is not the last memory allocated from this chunk.
                 // Spurious failures in multithreaded environment are possible
                 // but do not affect correctness.
                 let _ = me.cursor.compare_exchange(
-                    ptr.as_ptr(),
+                    current,
                     new,
                     Ordering::Release, // Released some memory.
                     Ordering::Relaxed,
@@ -459,13 +1034,74 @@ macro_rules! with_cursor {
             }
         }
 
+        /// Allocates memory from the first spare chunk that fits the layout.
+        /// That chunk is moved from spare list and becomes the current chunk.
+        ///
+        /// Spare chunks are kept by `reset_keep_all`.
+        /// Their memory is not zeroed.
+        #[inline]
+        pub unsafe fn alloc_spare(
+            root: &Cell<Option<NonNull<ChunkHeader>>>,
+            spare: &Cell<Option<NonNull<ChunkHeader>>>,
+            layout: Layout,
+        ) -> Option<NonNull<[u8]>> {
+            let mut before: Option<NonNull<ChunkHeader>> = None;
+            let mut next = spare.get();
+
+            while let Some(mut chunk) = next {
+                // Safety: all chunks in spare list are valid and rewound.
+                let me = unsafe { chunk.as_mut() };
+
+                if let Some(ptr) = unsafe { ChunkHeader::alloc(chunk, layout) } {
+                    // Unlink from spare list.
+                    match before {
+                        None => spare.set(me.prev),
+                        Some(mut before) => unsafe { before.as_mut().prev = me.prev },
+                    }
+
+                    me.cumulative_size = match root.get() {
+                        None => 0,
+                        Some(prev) => {
+                            let prev = unsafe { prev.as_ref() };
+                            prev.cap() + prev.cumulative_size
+                        }
+                    };
+                    me.prev = root.get();
+                    root.set(Some(chunk));
+                    return Some(ptr);
+                }
+
+                before = Some(chunk);
+                next = me.prev;
+            }
+
+            None
+        }
+
+        /// Allocates new chunk and allocates memory from it.
+        /// If `zeroed` is `true`, chunk memory is allocated zeroed.
+        /// Otherwise spare chunk is used first if one fits the layout.
+        ///
+        /// Chunk size grows with each new chunk but doesn't exceed `max_chunk_size`
+        /// unless allocation doesn't fit into chunk of that size.
         #[cold]
+        #[allow(clippy::too_many_arguments)]
         pub unsafe fn alloc_slow(
             root: &Cell<Option<NonNull<ChunkHeader>>>,
+            spare: &Cell<Option<NonNull<ChunkHeader>>>,
             mut chunk_size: usize,
+            max_chunk_size: usize,
+            chunk_align: usize,
             layout: Layout,
-            allocator: impl Allocator,
+            allocator: &impl ChunkSource,
+            zeroed: bool,
         ) -> Result<NonNull<[u8]>, AllocError> {
+            if !zeroed {
+                if let Some(ptr) = unsafe { alloc_spare(root, spare, layout) } {
+                    return Ok(ptr);
+                }
+            }
+
             if let Some(root) = root.get() {
                 chunk_size = chunk_size.max(root.as_ref().cumulative_size);
                 chunk_size = chunk_size
@@ -475,24 +1111,35 @@ macro_rules! with_cursor {
                 chunk_size = chunk_size.max(layout.size());
             }
 
+            // Size of the smallest chunk that fits the allocation.
+            let mut required = layout.size();
+
             if layout.align() > align_of::<ChunkHeader>() {
                 chunk_size = chunk_size.checked_add(layout.align()).ok_or(AllocError)?;
+                required = required.checked_add(layout.align()).ok_or(AllocError)?;
             }
 
             let Some(mut chunk_size) = chunk_size.checked_add(size_of::<ChunkHeader>()) else {
                 return Err(AllocError);
             };
 
-            // Grow size exponentially until a threshold.
-            if chunk_size < CHUNK_POWER_OF_TWO_THRESHOLD {
-                chunk_size = chunk_size.next_power_of_two();
-            } else {
-                chunk_size =
-                    align_up(chunk_size, CHUNK_POWER_OF_TWO_THRESHOLD).unwrap_or(chunk_size);
+            chunk_size = round_chunk_size(chunk_size);
+
+            let chunk_align = chunk_align.max(align_of::<ChunkHeader>());
+            chunk_size = align_up(chunk_size, chunk_align).ok_or(AllocError)?;
+
+            if chunk_size > max_chunk_size {
+                let required = required
+                    .checked_add(size_of::<ChunkHeader>())
+                    .and_then(|size| align_up(size, chunk_align))
+                    .ok_or(AllocError)?;
+
+                chunk_size = align_down(max_chunk_size, chunk_align).max(required);
             }
 
-            debug_assert_eq!(chunk_size % align_of::<ChunkHeader>(), 0);
-            let new_chunk = ChunkHeader::alloc_chunk(chunk_size, allocator, root.get())?;
+            debug_assert_eq!(chunk_size % chunk_align, 0);
+            let new_chunk =
+                ChunkHeader::alloc_chunk(chunk_size, chunk_align, allocator, root.get(), zeroed)?;
 
             // Safety: `chunk` is a valid pointer to chunk allocation.
             let ptr = unsafe { ChunkHeader::alloc(new_chunk, layout).unwrap_unchecked() };
@@ -502,16 +1149,29 @@ macro_rules! with_cursor {
         }
 
         #[cold]
+        #[allow(clippy::too_many_arguments)]
         pub unsafe fn resize_slow(
             root: &Cell<Option<NonNull<ChunkHeader>>>,
+            spare: &Cell<Option<NonNull<ChunkHeader>>>,
             chunk_size: usize,
+            max_chunk_size: usize,
+            chunk_align: usize,
             ptr: NonNull<u8>,
             old_layout: Layout,
             new_layout: Layout,
-            allocator: impl Allocator,
+            allocator: &impl ChunkSource,
         ) -> Result<NonNull<[u8]>, AllocError> {
-            let new_ptr = alloc_slow(root, chunk_size, new_layout, allocator)?;
-            core::ptr::copy_nonoverlapping(
+            let new_ptr = alloc_slow(
+                root,
+                spare,
+                chunk_size,
+                max_chunk_size,
+                chunk_align,
+                new_layout,
+                allocator,
+                false,
+            )?;
+            crate::copy::copy_bulk(
                 ptr.as_ptr(),
                 new_ptr.as_ptr().cast(),
                 new_layout.size().min(old_layout.size()),
@@ -538,9 +1198,10 @@ macro_rules! with_cursor {
         pub unsafe fn reset<A>(
             root: &Cell<Option<NonNull<ChunkHeader>>>,
             keep_last: bool,
-            allocator: A,
+            chunk_align: usize,
+            allocator: &A,
         ) where
-            A: Allocator,
+            A: ChunkSource + ?Sized,
         {
             let mut prev = if keep_last {
                 let Some(root) = root.get() else {
@@ -557,7 +1218,397 @@ macro_rules! with_cursor {
             while let Some(chunk) = prev {
                 // Safety: `chunk` is a valid pointer to chunk allocation.
                 // Allocated from this allocator with this layout.
-                prev = unsafe { ChunkHeader::dealloc_chunk(chunk, &allocator) };
+                prev = unsafe { ChunkHeader::dealloc_chunk(chunk, chunk_align, allocator) };
+            }
+        }
+
+        /// Rewinds all chunks.
+        /// The most recent chunk stays current,
+        /// all other chunks are moved to the spare list
+        /// to be reused before new chunks are allocated.
+        #[allow(dead_code)]
+        #[inline]
+        pub fn reset_keep_all(
+            root: &Cell<Option<NonNull<ChunkHeader>>>,
+            spare: &Cell<Option<NonNull<ChunkHeader>>>,
+        ) {
+            let Some(head) = root.get() else {
+                return;
+            };
+
+            // Safety: `head` is a valid pointer to chunk allocation.
+            // Link is cleared here and rest of the list is moved to spare list.
+            let Some(first) = (unsafe { ChunkHeader::reset(head) }) else {
+                return;
+            };
+
+            let mut last = first;
+            loop {
+                // Safety: all chunks in the list are valid.
+                unsafe { ChunkHeader::rewind(last) };
+                match unsafe { last.as_ref().prev } {
+                    None => break,
+                    Some(prev) => last = prev,
+                }
+            }
+
+            // More recent chunks are reused first.
+            unsafe { last.as_mut().prev = spare.get() };
+            spare.set(Some(first));
+        }
+
+        /// Deallocates all spare chunks.
+        ///
+        /// Safety:
+        /// `allocator` must be the same allocator that was used in `alloc`.
+        #[inline(always)]
+        pub unsafe fn free_spare<A>(
+            spare: &Cell<Option<NonNull<ChunkHeader>>>,
+            chunk_align: usize,
+            allocator: &A,
+        ) where
+            A: ChunkSource + ?Sized,
+        {
+            let mut next = spare.take();
+            while let Some(chunk) = next {
+                // Safety: `chunk` is a valid pointer to chunk allocation.
+                // Allocated from this allocator with this layout.
+                next = unsafe { ChunkHeader::dealloc_chunk(chunk, chunk_align, allocator) };
+            }
+        }
+
+        /// Returns total capacity of spare chunks.
+        #[inline]
+        pub fn spare_capacity(spare: Option<NonNull<ChunkHeader>>) -> usize {
+            let mut capacity = 0;
+            let mut next = spare;
+            while let Some(chunk) = next {
+                // Safety: all chunks in the list are valid.
+                let me = unsafe { chunk.as_ref() };
+                capacity += me.cap();
+                next = me.prev;
+            }
+            capacity
+        }
+
+        /// Returns number of chunks in the list.
+        #[allow(dead_code)]
+        #[inline]
+        pub fn count_chunks(list: Option<NonNull<ChunkHeader>>) -> usize {
+            let mut count = 0;
+            let mut next = list;
+            while let Some(chunk) = next {
+                count += 1;
+                // Safety: all chunks in the list are valid.
+                next = unsafe { chunk.as_ref().prev };
+            }
+            count
+        }
+
+        /// Checks if `ptr` points into usable memory of any chunk in the list.
+        #[inline]
+        pub fn contains(root: Option<NonNull<ChunkHeader>>, ptr: NonNull<u8>) -> bool {
+            let mut next = root;
+            while let Some(chunk) = next {
+                // Safety: all chunks in the list are valid.
+                let me = unsafe { chunk.as_ref() };
+                if me.contains(ptr) {
+                    return true;
+                }
+                next = me.prev;
+            }
+            false
+        }
+
+        /// Iterator over utilization of chunks
+        /// from the current one to the oldest.
+        pub struct Chunks<'a> {
+            next: Option<NonNull<ChunkHeader>>,
+            current: bool,
+            marker: PhantomData<&'a ChunkHeader>,
+        }
+
+        impl Chunks<'_> {
+            /// Safety:
+            /// Chunks in the list must stay valid for the lifetime of the iterator.
+            #[inline(always)]
+            pub unsafe fn new(root: Option<NonNull<ChunkHeader>>) -> Self {
+                Chunks {
+                    next: root,
+                    current: true,
+                    marker: PhantomData,
+                }
+            }
+        }
+
+        impl Iterator for Chunks<'_> {
+            type Item = ChunkUtilization;
+
+            #[inline]
+            fn next(&mut self) -> Option<ChunkUtilization> {
+                let chunk = self.next?;
+
+                // Safety: all chunks in the list are valid.
+                let me = unsafe { chunk.as_ref() };
+                self.next = me.prev;
+
+                let stranded = if self.current { 0 } else { me.remaining() };
+                self.current = false;
+
+                Some(ChunkUtilization {
+                    capacity: me.cap(),
+                    used: me.used(),
+                    stranded,
+                })
+            }
+        }
+
+        /// Iterator over used memory regions of chunks
+        /// from the current one to the oldest.
+        #[allow(dead_code)]
+        pub struct UsedChunks<'a> {
+            next: Option<NonNull<ChunkHeader>>,
+            marker: PhantomData<&'a ChunkHeader>,
+        }
+
+        #[allow(dead_code)]
+        impl UsedChunks<'_> {
+            /// Safety:
+            /// Chunks in the list must stay valid for the lifetime of the iterator.
+            #[inline(always)]
+            pub unsafe fn new(root: Option<NonNull<ChunkHeader>>) -> Self {
+                UsedChunks {
+                    next: root,
+                    marker: PhantomData,
+                }
+            }
+        }
+
+        impl Iterator for UsedChunks<'_> {
+            type Item = (NonNull<u8>, usize);
+
+            #[inline]
+            fn next(&mut self) -> Option<(NonNull<u8>, usize)> {
+                let chunk = self.next?;
+
+                // Safety: all chunks in the list are valid.
+                let me = unsafe { chunk.as_ref() };
+                self.next = me.prev;
+
+                let cursor = me.cursor.load(Ordering::Relaxed);
+
+                // Used memory is between base and cursor when bumping upward
+                // and between cursor and end when bumping downward.
+                #[cfg(not(feature = "bump-down"))]
+                let start = me.base().cast_mut();
+
+                #[cfg(feature = "bump-down")]
+                let start = cursor;
+
+                let _ = cursor;
+
+                // Safety: chunk's usable memory is never null.
+                let start = unsafe { NonNull::new_unchecked(start) };
+                Some((start, me.used()))
+            }
+        }
+
+        /// Returns bytes consumed by chunk headers
+        /// and unused tails of chunks preceding the current one.
+        #[inline]
+        pub fn overhead_bytes(
+            root: Option<NonNull<ChunkHeader>>,
+            spare: Option<NonNull<ChunkHeader>>,
+        ) -> usize {
+            let mut overhead = 0;
+            let mut next = root;
+            let mut current = true;
+            while let Some(chunk) = next {
+                // Safety: all chunks in the list are valid.
+                let me = unsafe { chunk.as_ref() };
+                overhead += size_of::<ChunkHeader>();
+                if !current {
+                    // New allocations never go to older chunks.
+                    overhead += me.remaining();
+                }
+                current = false;
+                next = me.prev;
+            }
+
+            // Spare chunks are reused entirely.
+            let mut next = spare;
+            while let Some(chunk) = next {
+                // Safety: all chunks in the list are valid.
+                let me = unsafe { chunk.as_ref() };
+                overhead += size_of::<ChunkHeader>();
+                next = me.prev;
+            }
+            overhead
+        }
+
+        /// Moves all memory from `from` list to `to` list.
+        /// Chunks except the last one are deallocated.
+        /// Last chunk is reset and becomes the current chunk of `to` list.
+        ///
+        /// Safety:
+        /// `allocator` must be the same allocator that was used in `alloc` for `from` list.
+        /// Source of `to` list must be able to release chunks allocated from `allocator`.
+        #[allow(dead_code)]
+        #[inline]
+        pub unsafe fn donate<A>(
+            from: &Cell<Option<NonNull<ChunkHeader>>>,
+            to: &Cell<Option<NonNull<ChunkHeader>>>,
+            chunk_align: usize,
+            allocator: &A,
+        ) where
+            A: ChunkSource + ?Sized,
+        {
+            // Safety: `allocator` is the same allocator that was used in `alloc`.
+            unsafe { reset(from, true, chunk_align, allocator) };
+
+            let Some(mut chunk) = from.take() else {
+                return;
+            };
+
+            let prev = to.get();
+            let cumulative_size = match prev {
+                None => 0,
+                Some(prev) => {
+                    let prev = unsafe { prev.as_ref() };
+                    prev.cap() + prev.cumulative_size
+                }
+            };
+
+            // Safety: `chunk` is a valid pointer to chunk allocation
+            // and it is removed from `from` list.
+            let me = unsafe { chunk.as_mut() };
+            debug_assert!(me.prev.is_none());
+            me.prev = prev;
+            me.cumulative_size = cumulative_size;
+
+            to.set(Some(chunk));
+        }
+
+        /// Same as `reset` with `keep_last` set to `true`,
+        /// but selects retained chunk according to the policy.
+        ///
+        /// Safety:
+        /// `allocator` must be the same allocator that was used in `alloc`.
+        #[allow(dead_code)]
+        #[inline]
+        pub unsafe fn reset_retain<A>(
+            root: &Cell<Option<NonNull<ChunkHeader>>>,
+            policy: RetentionPolicy,
+            max_chunk_size: usize,
+            chunk_align: usize,
+            allocator: &A,
+        ) where
+            A: ChunkSource + ?Sized,
+        {
+            match policy {
+                RetentionPolicy::KeepMostRecentlyUsed => {
+                    unsafe { reset(root, true, chunk_align, allocator) };
+                    return;
+                }
+                RetentionPolicy::Consolidate => {
+                    unsafe { reset_consolidate(root, max_chunk_size, chunk_align, allocator) };
+                    return;
+                }
+                _ => {}
+            }
+
+            let Some(head) = root.take() else {
+                return;
+            };
+
+            // Safety: all chunks in the list are valid.
+            let cap = |chunk: NonNull<ChunkHeader>| unsafe { chunk.as_ref().cap() };
+
+            let mut keep = head;
+            let mut next = unsafe { head.as_ref().prev };
+            while let Some(chunk) = next {
+                let better = match policy {
+                    RetentionPolicy::KeepMostRecentlyUsed | RetentionPolicy::Consolidate => false,
+                    RetentionPolicy::KeepLargest => cap(chunk) > cap(keep),
+                    RetentionPolicy::KeepBestFit(target) => {
+                        if cap(keep) < target {
+                            cap(chunk) > cap(keep)
+                        } else {
+                            cap(chunk) >= target && cap(chunk) < cap(keep)
+                        }
+                    }
+                };
+                if better {
+                    keep = chunk;
+                }
+                next = unsafe { chunk.as_ref().prev };
+            }
+
+            let mut next = Some(head);
+            while let Some(chunk) = next {
+                if chunk == keep {
+                    next = unsafe { chunk.as_ref().prev };
+                } else {
+                    // Safety: `chunk` is a valid pointer to chunk allocation.
+                    // Allocated from this allocator with this layout.
+                    next = unsafe { ChunkHeader::dealloc_chunk(chunk, chunk_align, allocator) };
+                }
+            }
+
+            // Safety: `keep` is a valid pointer to chunk allocation.
+            // Previous chunks are deallocated and link is cleared here.
+            unsafe { ChunkHeader::reset(keep) };
+            root.set(Some(keep));
+        }
+
+        /// Replaces all chunks with single chunk that fits
+        /// all memory allocated from them.
+        ///
+        /// Safety:
+        /// `allocator` must be the same allocator that was used in `alloc`.
+        #[allow(dead_code)]
+        #[cold]
+        unsafe fn reset_consolidate<A>(
+            root: &Cell<Option<NonNull<ChunkHeader>>>,
+            max_chunk_size: usize,
+            chunk_align: usize,
+            allocator: &A,
+        ) where
+            A: ChunkSource + ?Sized,
+        {
+            let Some(head) = root.get() else {
+                return;
+            };
+
+            // Safety: `head` is a valid pointer to chunk allocation.
+            let me = unsafe { head.as_ref() };
+            if me.prev.is_none() {
+                // Single chunk already fits everything.
+                unsafe { reset(root, true, chunk_align, allocator) };
+                return;
+            }
+
+            let used = me.used() + me.cumulative_size;
+            let align = chunk_align.max(align_of::<ChunkHeader>());
+            let chunk_size = used
+                .checked_add(size_of::<ChunkHeader>())
+                .map(round_chunk_size)
+                .and_then(|size| align_up(size, align))
+                .map(|size| size.min(align_down(max_chunk_size, align)));
+
+            let chunk = match chunk_size {
+                Some(chunk_size) if chunk_size > size_of::<ChunkHeader>() + me.cap() => unsafe {
+                    ChunkHeader::alloc_chunk(chunk_size, chunk_align, allocator, None, false).ok()
+                },
+                _ => None,
+            };
+
+            match chunk {
+                None => unsafe { reset(root, true, chunk_align, allocator) },
+                Some(chunk) => {
+                    unsafe { reset(root, false, chunk_align, allocator) };
+                    root.set(Some(chunk));
+                }
             }
         }
 
@@ -584,8 +1635,8 @@ macro_rules! with_cursor {
 mod local;
 pub use self::local::ArenaLocal;
 
-#[cfg(feature = "sync")]
+#[cfg(feature = "sync-core")]
 mod sync;
 
-#[cfg(feature = "sync")]
+#[cfg(feature = "sync-core")]
 pub use self::sync::ArenaSync;
@@ -1,57 +1,202 @@
+use core::ptr::null_mut;
+
+#[cfg(not(loom))]
+use core::{
+    hint::spin_loop,
+    sync::atomic::{fence, AtomicUsize},
+};
+
+#[cfg(loom)]
+use loom::{
+    hint::spin_loop,
+    sync::atomic::{fence, AtomicUsize},
+};
+
 use super::*;
 
 with_cursor!(AtomicPtr<u8>);
 
 struct Inner {
     root: Option<NonNull<ChunkHeader>>,
+
+    /// Chunks kept by `reset_keep_all` to be reused.
+    spare: Option<NonNull<ChunkHeader>>,
+
     min_chunk_size: usize,
+    max_chunk_size: usize,
+
+    /// Minimal alignment of new chunks.
+    chunk_align: usize,
 }
 
 unsafe impl Send for Inner {}
 unsafe impl Sync for Inner {}
 
+/// Returns current chunk, cumulative size of previous chunks
+/// and total capacity of the arena.
+#[inline]
+fn snapshot(inner: &Inner) -> (*mut ChunkHeader, usize, usize) {
+    let Some(root) = inner.root else {
+        return (null_mut(), 0, 0);
+    };
+    // Safety: `root` is a valid pointer to chunk allocation.
+    let chunk = unsafe { root.as_ref() };
+    let capacity = chunk.cap() + chunk.cumulative_size + spare_capacity(inner.spare);
+    (root.as_ptr(), chunk.cumulative_size, capacity)
+}
+
 /// Multi-threaded arena allocator.
 pub struct ArenaSync {
     inner: RwLock<Inner>,
+
+    // Statistics are published on slow path
+    // so that they can be read without taking the lock.
+    /// Current chunk. Null while there's no chunk or reset is in progress.
+    current: AtomicPtr<ChunkHeader>,
+    /// Cumulative size of chunks preceding the current one.
+    cumulative_size: AtomicUsize,
+    /// Total capacity of all chunks.
+    capacity: AtomicUsize,
+    /// Number of threads inspecting `current` chunk.
+    readers: AtomicUsize,
+    /// Sum of sizes of live allocations since last reset.
+    #[cfg(feature = "exact-stats")]
+    allocated: AtomicUsize,
 }
 
 impl Drop for ArenaSync {
     #[inline(always)]
     fn drop(&mut self) {
         debug_assert!(
-            self.inner.get_mut().root.is_none(),
+            self.inner.get_mut().root.is_none() && self.inner.get_mut().spare.is_none(),
             "Owner must reset `ArenaSync` with `keep_last` set to `false` before drop"
         );
     }
 }
 
-impl ArenaSync {
-    #[inline(always)]
-    pub const fn new() -> Self {
+macro_rules! new_arena_sync {
+    ($min_chunk_size:expr, $max_chunk_size:expr) => {
         ArenaSync {
             inner: RwLock::new(Inner {
                 root: None,
-                min_chunk_size: CHUNK_START_SIZE,
+                spare: None,
+                min_chunk_size: $min_chunk_size,
+                max_chunk_size: $max_chunk_size,
+                chunk_align: 1,
             }),
+            current: AtomicPtr::new(null_mut()),
+            cumulative_size: AtomicUsize::new(0),
+            capacity: AtomicUsize::new(0),
+            readers: AtomicUsize::new(0),
+            #[cfg(feature = "exact-stats")]
+            allocated: AtomicUsize::new(0),
         }
+    };
+}
+
+impl ArenaSync {
+    #[cfg(not(loom))]
+    #[inline(always)]
+    pub const fn new() -> Self {
+        ArenaSync::with_chunk_size(CHUNK_START_SIZE)
     }
 
+    #[cfg(not(loom))]
     #[inline(always)]
     pub const fn with_chunk_size(min_chunk_size: usize) -> Self {
-        ArenaSync {
-            inner: RwLock::new(Inner {
-                root: None,
-                min_chunk_size,
-            }),
+        ArenaSync::with_chunk_size_range(min_chunk_size, usize::MAX)
+    }
+
+    #[cfg(not(loom))]
+    #[inline(always)]
+    pub const fn with_chunk_size_range(min_chunk_size: usize, max_chunk_size: usize) -> Self {
+        new_arena_sync!(min_chunk_size, max_chunk_size)
+    }
+
+    /// `loom` primitives can't be created in const context.
+    #[cfg(loom)]
+    pub fn with_chunk_size_range(min_chunk_size: usize, max_chunk_size: usize) -> Self {
+        new_arena_sync!(min_chunk_size, max_chunk_size)
+    }
+
+    /// Publishes statistics for lock-free readers.
+    #[inline]
+    fn publish(&self, (current, cumulative_size, capacity): (*mut ChunkHeader, usize, usize)) {
+        self.cumulative_size
+            .store(cumulative_size, Ordering::Relaxed);
+        self.capacity.store(capacity, Ordering::Relaxed);
+        self.current.store(current, Ordering::SeqCst);
+    }
+
+    /// Unpublishes current chunk and waits until
+    /// no reader inspects it.
+    /// Must be called before chunks are modified or freed
+    /// through shared reference.
+    #[inline]
+    fn unpublish(&self) {
+        self.current.store(null_mut(), Ordering::SeqCst);
+
+        // Pairs with fence in `allocated_bytes`.
+        // Either reader observes null chunk or this thread observes the reader.
+        fence(Ordering::SeqCst);
+        while self.readers.load(Ordering::SeqCst) != 0 {
+            spin_loop();
         }
     }
 
+    /// Updates exact allocated bytes counter.
+    #[inline(always)]
+    fn count(&self, added: usize, removed: usize) {
+        #[cfg(feature = "exact-stats")]
+        {
+            if added != 0 {
+                self.allocated.fetch_add(added, Ordering::Relaxed);
+            }
+            if removed != 0 {
+                let _ = self.allocated.fetch_update(
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                    |allocated| Some(allocated.saturating_sub(removed)),
+                );
+            }
+        }
+
+        #[cfg(not(feature = "exact-stats"))]
+        let _ = (added, removed);
+    }
+
+    /// Zeroes exact allocated bytes counter.
+    /// Must be called when all allocations are freed.
+    #[inline(always)]
+    fn clear_count(&self) {
+        #[cfg(feature = "exact-stats")]
+        self.allocated.store(0, Ordering::Relaxed);
+    }
+
+    #[inline(always)]
+    pub fn set_chunk_align(&mut self, align: usize) {
+        debug_assert!(align.is_power_of_two());
+        self.inner.get_mut().chunk_align = align;
+    }
+
+    #[inline(always)]
+    pub fn chunk_align(&self) -> usize {
+        self.inner.read().chunk_align
+    }
+
+    #[inline(always)]
+    pub fn max_chunk_size(&self) -> usize {
+        self.inner.read().max_chunk_size
+    }
+
     #[inline(always)]
     pub unsafe fn alloc_fast(&self, layout: Layout) -> Option<NonNull<[u8]>> {
         let inner = self.inner.read();
 
         if let Some(root) = inner.root {
-            return unsafe { ChunkHeader::alloc(root, layout) };
+            let ptr = unsafe { ChunkHeader::alloc(root, layout) }?;
+            self.count(layout.size(), 0);
+            return Some(ptr);
         }
 
         None
@@ -61,17 +206,26 @@ impl ArenaSync {
     pub unsafe fn alloc_slow(
         &self,
         layout: Layout,
-        allocator: impl Allocator,
+        allocator: &impl ChunkSource,
     ) -> Result<NonNull<[u8]>, AllocError> {
         let mut guard = self.inner.write();
         let inner = &mut *guard;
 
-        alloc_slow(
+        let result = alloc_slow(
             Cell::from_mut(&mut inner.root),
+            Cell::from_mut(&mut inner.spare),
             inner.min_chunk_size,
+            inner.max_chunk_size,
+            inner.chunk_align,
             layout,
-            &allocator,
-        )
+            allocator,
+            false,
+        );
+        self.publish(snapshot(inner));
+        if result.is_ok() {
+            self.count(layout.size(), 0);
+        }
+        result
     }
 
     #[inline(always)]
@@ -84,7 +238,9 @@ impl ArenaSync {
         let inner = self.inner.read();
 
         if let Some(root) = inner.root {
-            return unsafe { ChunkHeader::resize(root, ptr, old_layout, new_layout) };
+            let ptr = unsafe { ChunkHeader::resize(root, ptr, old_layout, new_layout) }?;
+            self.count(new_layout.size(), old_layout.size());
+            return Some(ptr);
         }
         None
     }
@@ -95,41 +251,114 @@ impl ArenaSync {
         ptr: NonNull<u8>,
         old_layout: Layout,
         new_layout: Layout,
-        allocator: impl Allocator,
+        allocator: &impl ChunkSource,
     ) -> Result<NonNull<[u8]>, AllocError> {
         let mut guard = self.inner.write();
         let inner = &mut *guard;
 
-        resize_slow(
+        let result = resize_slow(
             Cell::from_mut(&mut inner.root),
+            Cell::from_mut(&mut inner.spare),
             inner.min_chunk_size,
+            inner.max_chunk_size,
+            inner.chunk_align,
             ptr,
             old_layout,
             new_layout,
-            &allocator,
-        )
+            allocator,
+        );
+        self.publish(snapshot(inner));
+        if result.is_ok() {
+            self.count(new_layout.size(), old_layout.size());
+        }
+        result
     }
 
     #[inline(always)]
     pub unsafe fn dealloc(&self, ptr: NonNull<u8>, size: usize) {
+        self.count(0, size);
         dealloc(self.inner.read().root, ptr, size)
     }
 
     #[inline(always)]
-    pub unsafe fn reset(&mut self, keep_last: bool, allocator: impl Allocator) {
+    pub unsafe fn reset(&mut self, keep_last: bool, allocator: &impl ChunkSource) {
+        let inner = self.inner.get_mut();
         unsafe {
+            free_spare(
+                Cell::from_mut(&mut inner.spare),
+                inner.chunk_align,
+                allocator,
+            );
             reset(
-                Cell::from_mut(&mut self.inner.get_mut().root),
+                Cell::from_mut(&mut inner.root),
                 keep_last,
+                inner.chunk_align,
                 allocator,
             )
         }
+        let snapshot = snapshot(inner);
+        self.publish(snapshot);
+        self.clear_count();
     }
 
     #[inline(always)]
-    pub unsafe fn reset_unchecked(&self, keep_last: bool, allocator: impl Allocator) {
+    pub unsafe fn reset_unchecked(&self, keep_last: bool, allocator: &impl ChunkSource) {
         let mut guard = self.inner.write();
-        unsafe { reset(Cell::from_mut(&mut guard.root), keep_last, allocator) }
+        let inner = &mut *guard;
+
+        // Statistics readers may inspect chunks concurrently.
+        self.unpublish();
+        unsafe {
+            free_spare(
+                Cell::from_mut(&mut inner.spare),
+                inner.chunk_align,
+                allocator,
+            );
+            reset(
+                Cell::from_mut(&mut inner.root),
+                keep_last,
+                inner.chunk_align,
+                allocator,
+            )
+        }
+        self.publish(snapshot(inner));
+        self.clear_count();
+    }
+
+    /// Resets replacing all chunks with a single one
+    /// that fits all allocations since previous reset.
+    #[inline(always)]
+    pub unsafe fn reset_compact(&mut self, allocator: &impl ChunkSource) {
+        let inner = self.inner.get_mut();
+        unsafe {
+            free_spare(
+                Cell::from_mut(&mut inner.spare),
+                inner.chunk_align,
+                allocator,
+            );
+            reset_retain(
+                Cell::from_mut(&mut inner.root),
+                RetentionPolicy::Consolidate,
+                inner.max_chunk_size,
+                inner.chunk_align,
+                allocator,
+            )
+        }
+        let snapshot = snapshot(inner);
+        self.publish(snapshot);
+        self.clear_count();
+    }
+
+    #[inline(always)]
+    pub fn reset_keep_all(&mut self) {
+        let inner = self.inner.get_mut();
+        reset_keep_all(
+            Cell::from_mut(&mut inner.root),
+            Cell::from_mut(&mut inner.spare),
+        );
+        let snapshot = snapshot(inner);
+        self.publish(snapshot);
+        self.clear_count();
     }
 
     // #[inline(always)]
@@ -143,25 +372,59 @@ impl ArenaSync {
     /// (which are ~fully used, minus alignment padding) plus the cursor
     /// offset in the current chunk. After warm-up (when a single chunk
     /// serves all allocations), this is exact.
+    ///
+    /// With `exact-stats` feature returns sum of sizes of live allocations instead.
+    ///
+    /// Wait-free, does not contend with allocations.
     pub fn allocated_bytes(&self) -> usize {
-        let inner = self.inner.read();
-        let Some(root) = inner.root else {
-            return 0;
-        };
-        let chunk = unsafe { root.as_ref() };
-        let cursor = chunk.cursor.load(Ordering::Relaxed) as usize;
-        let base = chunk.base() as usize;
-        let current_used = cursor - base;
-        current_used + chunk.cumulative_size
+        #[cfg(feature = "exact-stats")]
+        {
+            self.allocated.load(Ordering::Relaxed)
+        }
+
+        #[cfg(not(feature = "exact-stats"))]
+        {
+            // Pin current chunk so that `reset_unchecked` can't free it.
+            self.readers.fetch_add(1, Ordering::SeqCst);
+
+            // Pairs with fence in `unpublish`.
+            fence(Ordering::SeqCst);
+            let current = self.current.load(Ordering::SeqCst);
+
+            let used = if current.is_null() {
+                0
+            } else {
+                // Safety: published chunk is not freed or modified
+                // non-atomically while readers are pinned.
+                unsafe { (*current).used() }
+            };
+
+            self.readers.fetch_sub(1, Ordering::SeqCst);
+            used + self.cumulative_size.load(Ordering::Relaxed)
+        }
     }
 
     /// Returns the total capacity of all chunks in this arena.
+    ///
+    /// Wait-free, does not contend with allocations.
     pub fn total_capacity(&self) -> usize {
+        self.capacity.load(Ordering::Relaxed)
+    }
+
+    /// Returns bytes consumed by chunk headers and unreachable chunk tails.
+    pub fn overhead_bytes(&self) -> usize {
         let inner = self.inner.read();
-        let Some(root) = inner.root else {
-            return 0;
-        };
-        let chunk = unsafe { root.as_ref() };
-        chunk.cap() + chunk.cumulative_size
+        overhead_bytes(inner.root, inner.spare)
+    }
+
+    /// Checks if `ptr` points into memory of this arena's chunks.
+    pub fn contains(&self, ptr: NonNull<u8>) -> bool {
+        contains(self.inner.read().root, ptr)
+    }
+
+    /// Returns iterator over utilization of chunks.
+    pub fn utilization(&mut self) -> Chunks<'_> {
+        // Safety: chunks are deallocated only with mutable borrow.
+        unsafe { Chunks::new(self.inner.get_mut().root) }
     }
 }
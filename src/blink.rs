@@ -3,32 +3,67 @@
 use core::{
     alloc::Layout,
     convert::{identity, Infallible},
+    ffi::{CStr, FromBytesWithNulError},
+    future::Future,
     marker::PhantomData,
-    mem::{needs_drop, size_of, ManuallyDrop, MaybeUninit},
+    mem::{align_of, needs_drop, size_of, ManuallyDrop, MaybeUninit},
+    ops::{Deref, DerefMut},
+    pin::Pin,
     ptr::{self, NonNull},
+    str::Utf8Error,
 };
 
+#[cfg(feature = "nightly")]
+use core::alloc::{AllocError, Allocator};
+
+#[cfg(not(feature = "nightly"))]
+use allocator_api2::alloc::{AllocError, Allocator};
+
 #[cfg(all(feature = "nightly", feature = "alloc"))]
 use alloc::alloc::Global;
 
 #[cfg(all(not(feature = "nightly"), feature = "alloc"))]
 use allocator_api2::alloc::Global;
 
+use core::sync::atomic::AtomicUsize;
+
 use crate::{
+    any::BlinkAny,
     api::BlinkAllocator,
+    arc::{ARc, ArcInner},
+    boxed::{abort_leaked, BlinkBox},
     cold,
-    drop_list::{DropItem, DropList},
+    copy::copy_bulk,
+    drop_list::{DropItem, DropList, DropListRef, DROP_ITEM_OVERHEAD},
     in_place,
+    soa::SoaSlices,
+    stats::ScopeBytes,
+    write::ArenaWrite,
 };
 
 #[cfg(not(no_global_oom_handling))]
 use crate::ResultExt;
 
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+
+#[cfg(feature = "std")]
+use std::{ffi::OsStr, path::Path};
+
 #[cfg(feature = "alloc")]
 use crate::local::BlinkAlloc;
 
+#[cfg(feature = "alloc")]
+use crate::reset_hooks::{ResetHookId, ResetHooks, ResetPhase};
+
+#[cfg(all(debug_assertions, feature = "alloc"))]
+use crate::blink_ref::Generation;
+
 #[cfg(not(no_global_oom_handling))]
-use crate::oom::{handle_alloc_error, size_overflow};
+use crate::{
+    deep_copy::BlinkCopy,
+    oom::{handle_alloc_error, size_overflow},
+};
 
 type EmplaceType<T, E> = Result<T, ManuallyDrop<E>>;
 type EmplaceSlot<T, E> = MaybeUninit<EmplaceType<T, E>>;
@@ -99,7 +134,7 @@ pub trait IteratorExt: Iterator {
     fn collect_to_blink<A: BlinkAllocator>(self, blink: &mut Blink<A>) -> &mut [Self::Item]
     where
         Self: Sized,
-        Self::Item: 'static,
+        Self::Item: Send + 'static,
     {
         blink.emplace().from_iter(self)
     }
@@ -133,7 +168,7 @@ pub trait IteratorExt: Iterator {
     ) -> Result<&mut [Self::Item], (&mut [Self::Item], Option<Self::Item>)>
     where
         Self: Sized,
-        Self::Item: 'static,
+        Self::Item: Send + 'static,
     {
         blink.emplace().try_from_iter(self)
     }
@@ -176,6 +211,27 @@ switch_alloc_default! {
     /// [`Blink`] calls [`Drop::drop`] for emplaced values when reset or dropped.
     /// This allows to use [`Blink`] instead of collections in some scenarios without needing to enable [`allocation_api`] feature.
     ///
+    /// Values are dropped in reverse order of emplacement,
+    /// so values emplaced later may safely use values emplaced earlier in their `Drop`.
+    /// Elements of emplaced slices are dropped in order, same as for `Vec`.
+    ///
+    /// Drop bookkeeping is an intrusive list with a small header
+    /// placed in the arena next to each droppable value or slice.
+    /// It has no storage of its own, so there's nothing to grow or rebuild
+    /// after reset, and warmed up arena serves droppable values
+    /// without touching underlying allocator.
+    ///
+    /// [`Blink`] can be sent to another thread and reset there.
+    /// Thus values that are dropped on reset must be [`Send`].
+    /// Values that are not can be emplaced with [`Blink::emplace_no_drop`]
+    /// or into [`LocalBlink`] that stays on its thread.
+    ///
+    /// ```compile_fail
+    /// # use blink_alloc::Blink;
+    /// let blink = Blink::new();
+    /// blink.put(std::rc::Rc::new(42));
+    /// ```
+    ///
     /// A blink-allocator adapter for user-friendly safe allocations
     /// without use of collections.
     ///
@@ -199,12 +255,31 @@ switch_alloc_default! {
     pub struct Blink<A = +BlinkAlloc<Global>> {
         drop_list: DropList,
         alloc: A,
+
+        // Callbacks run on every reset.
+        #[cfg(feature = "alloc")]
+        reset_hooks: ResetHooks,
+
+        // Number of `ARc` payloads not dropped yet.
+        #[cfg(debug_assertions)]
+        live_handles: AtomicUsize,
+
+        // Number of `BlinkBox` values not dropped yet.
+        // Counted in release builds too, as values may be pinned.
+        live_boxes: AtomicUsize,
+
+        // Generation checked by `BlinkRef` handles.
+        #[cfg(all(debug_assertions, feature = "alloc"))]
+        generation: Generation,
     }
 }
 
 impl<A> Drop for Blink<A> {
     #[inline(always)]
     fn drop(&mut self) {
+        if *self.live_boxes.get_mut() != 0 {
+            abort_leaked();
+        }
         self.drop_all();
     }
 }
@@ -213,6 +288,8 @@ impl<A> Drop for Blink<A> {
 // The `DropList` contains pointers to objects allocated from `Blink`.
 // If `Blink` is moved to another thread (or `&mut Blink`) then all returned pointers
 // to allocated objects were invalidated.
+// Safe methods register only `Send` values in the `DropList`,
+// so they may be dropped on another thread.
 unsafe impl<A> Send for Blink<A> where A: Send {}
 
 impl<A> Default for Blink<A>
@@ -266,6 +343,13 @@ impl<A> Blink<A> {
         Blink {
             drop_list: DropList::new(),
             alloc,
+            #[cfg(feature = "alloc")]
+            reset_hooks: ResetHooks::new(),
+            #[cfg(debug_assertions)]
+            live_handles: AtomicUsize::new(0),
+            live_boxes: AtomicUsize::new(0),
+            #[cfg(all(debug_assertions, feature = "alloc"))]
+            generation: Generation::new(),
         }
     }
 
@@ -275,13 +359,152 @@ impl<A> Blink<A> {
         &self.alloc
     }
 
+    /// Leaks this `Blink`, returning reference that lives forever.
+    ///
+    /// Values placed through returned reference are `'static`
+    /// and are never dropped.
+    /// Memory of the arena is never released.
+    ///
+    /// References to values placed before the call can't outlive
+    /// the borrow of `Blink`, so leak it before placing immortal data.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::Blink;
+    ///
+    /// fn config() -> &'static [&'static str] {
+    ///     let blink = Blink::new().leak();
+    ///     let name = blink.copy_str("blink");
+    ///     blink.copy_slice(&[&*name, "alloc"])
+    /// }
+    ///
+    /// assert_eq!(config(), ["blink", "alloc"]);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[inline(always)]
+    pub fn leak(self) -> &'static Self
+    where
+        A: 'static,
+    {
+        alloc::boxed::Box::leak(alloc::boxed::Box::new(self))
+    }
+
+    /// Returns number of entries in the drop list.
+    ///
+    /// Every value placed with drop glue adds one entry.
+    /// Slice placed at once adds single entry for all its elements.
+    /// Values without drop glue and ones placed with
+    /// [`emplace_no_drop`](Blink::emplace_no_drop) add none.
+    ///
+    /// Walks the list, so it takes time proportional to its length.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::Blink;
+    ///
+    /// let mut blink = Blink::new();
+    /// blink.put(42u32);
+    /// blink.put(String::from("foo"));
+    /// blink.emplace().from_iter((0..3).map(|i| i.to_string()));
+    /// assert_eq!(blink.drop_list_len(), 2);
+    ///
+    /// // Each entry takes three words.
+    /// assert_eq!(blink.drop_list_bytes(), 2 * 3 * std::mem::size_of::<usize>());
+    ///
+    /// blink.reset();
+    /// assert_eq!(blink.drop_list_len(), 0);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline]
+    pub fn drop_list_len(&self) -> usize {
+        self.drop_list.count()
+    }
+
+    /// Returns number of bytes taken by drop list entries.
+    ///
+    /// Each entry is placed in front of its value.
+    /// Values aligned stricter than the entry take additional padding
+    /// that is not included.
+    ///
+    /// See [`drop_list_len`](Blink::drop_list_len).
+    #[inline]
+    pub fn drop_list_bytes(&self) -> usize {
+        self.drop_list.count() * DROP_ITEM_OVERHEAD
+    }
+
     /// Drops all allocated values.
     ///
     /// Prefer to use `reset` method if associated allocator instance supports it.
     #[inline(always)]
     pub fn drop_all(&mut self) {
+        #[cfg(all(debug_assertions, feature = "alloc"))]
+        self.generation.bump();
+
         self.drop_list.reset();
     }
+
+    /// Returns generation checked by `BlinkRef` handles.
+    #[cfg(all(debug_assertions, feature = "alloc"))]
+    #[inline(always)]
+    pub(crate) fn generation(&self) -> &Generation {
+        &self.generation
+    }
+
+    /// Registers callback that runs on every [`reset`](Blink::reset)
+    /// and [`reset_final`](Blink::reset_final) at given `phase`.
+    ///
+    /// Callback stays registered across resets
+    /// until removed with [`remove_on_reset`](Blink::remove_on_reset).
+    /// Callbacks of the same phase run in registration order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "std")] fn main() {
+    /// use std::sync::{Arc, Mutex};
+    /// use std::collections::HashMap;
+    /// use blink_alloc::{Blink, ResetPhase};
+    ///
+    /// // Side table keyed by addresses of values in the arena.
+    /// let cache = Arc::new(Mutex::new(HashMap::<usize, u32>::new()));
+    ///
+    /// let mut blink = Blink::new();
+    /// let clear = cache.clone();
+    /// let id = blink.on_reset(ResetPhase::BeforeDrop, move || clear.lock().unwrap().clear());
+    ///
+    /// let value = blink.put(42u32);
+    /// cache.lock().unwrap().insert(value as *const u32 as usize, *value);
+    ///
+    /// blink.reset();
+    /// assert!(cache.lock().unwrap().is_empty());
+    ///
+    /// assert!(blink.remove_on_reset(id));
+    /// # }
+    /// # #[cfg(not(feature = "std"))] fn main() {}
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn on_reset<F>(&mut self, phase: ResetPhase, f: F) -> ResetHookId
+    where
+        F: FnMut() + Send + 'static,
+    {
+        self.reset_hooks.add(phase, alloc::boxed::Box::new(f))
+    }
+
+    /// Removes callback registered with [`on_reset`](Blink::on_reset).
+    /// Returns `false` if callback was already removed.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn remove_on_reset(&mut self, id: ResetHookId) -> bool {
+        self.reset_hooks.remove(id)
+    }
 }
 
 impl<A> Blink<A>
@@ -292,17 +515,198 @@ where
     /// And resets associated allocator instance.
     #[inline(always)]
     pub fn reset(&mut self) {
+        self.check_arcs();
+        #[cfg(feature = "alloc")]
+        self.reset_hooks.run(ResetPhase::BeforeDrop);
+
+        #[cfg(all(debug_assertions, feature = "alloc"))]
+        self.generation.bump();
+
         self.drop_list.reset();
         self.alloc.reset();
+        #[cfg(feature = "alloc")]
+        self.reset_hooks.run(ResetPhase::AfterDrop);
+    }
+
+    /// Drops all allocated values.
+    /// And resets associated allocator instance,
+    /// releasing all its memory.
+    ///
+    /// Allows releasing memory during idle periods
+    /// without dropping this instance.
+    /// See [`BlinkAllocator::reset_final`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::Blink;
+    /// let mut blink = Blink::new();
+    /// blink.put(42);
+    /// blink.reset_final();
+    /// assert_eq!(blink.total_capacity(), Some(0));
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline(always)]
+    pub fn reset_final(&mut self) {
+        self.check_arcs();
+        #[cfg(feature = "alloc")]
+        self.reset_hooks.run(ResetPhase::BeforeDrop);
+
+        #[cfg(all(debug_assertions, feature = "alloc"))]
+        self.generation.bump();
+
+        self.drop_list.reset();
+        self.alloc.reset_final();
+        #[cfg(feature = "alloc")]
+        self.reset_hooks.run(ResetPhase::AfterDrop);
+    }
+
+    /// Returns the number of bytes allocated from associated allocator.
+    /// Returns `None` if allocator does not track it.
+    ///
+    /// See [`BlinkAllocator::allocated_bytes`].
+    #[inline(always)]
+    pub fn allocated_bytes(&self) -> Option<usize> {
+        self.alloc.allocated_bytes()
+    }
+
+    /// Returns the total capacity of associated allocator.
+    /// Returns `None` if allocator does not track it.
+    ///
+    /// See [`BlinkAllocator::total_capacity`].
+    #[inline(always)]
+    pub fn total_capacity(&self) -> Option<usize> {
+        self.alloc.total_capacity()
+    }
+
+    /// Calls `f` and returns its result along with
+    /// number of bytes it allocated from this blink.
+    ///
+    /// Bytes are counted with [`BlinkAllocator::allocated_bytes`],
+    /// so precision is the same as allocator provides.
+    /// Resets within `f` are not allowed as `f` borrows this blink.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::Blink;
+    /// let blink = Blink::new();
+    /// let (slice, bytes) = blink.measure(|blink| blink.copy_slice(&[0u32; 100]));
+    /// assert_eq!(slice.len(), 100);
+    /// assert!(bytes.allocated_bytes >= 400);
+    /// assert!(bytes.allocated_bytes < 64 * 1024);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline]
+    pub fn measure<'a, R>(&'a self, f: impl FnOnce(&'a Self) -> R) -> (R, ScopeBytes) {
+        let allocated_bytes = self.alloc.allocated_bytes().unwrap_or(0);
+        let total_capacity = self.alloc.total_capacity().unwrap_or(0);
+
+        let r = f(self);
+
+        let bytes = ScopeBytes {
+            allocated_bytes: self
+                .alloc
+                .allocated_bytes()
+                .unwrap_or(0)
+                .saturating_sub(allocated_bytes),
+            capacity_growth: self
+                .alloc
+                .total_capacity()
+                .unwrap_or(0)
+                .saturating_sub(total_capacity),
+        };
+        (r, bytes)
+    }
+
+    /// Returns a guard that dereferences to this blink
+    /// and resets it when dropped.
+    ///
+    /// Reset happens even if guard is dropped during unwinding,
+    /// so early returns and panics can't skip it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::Blink;
+    /// let mut blink = Blink::new();
+    /// {
+    ///     let guard = blink.guard();
+    ///     let x = guard.put(42);
+    ///     assert_eq!(*x, 42);
+    /// }
+    /// assert_eq!(blink.allocator().allocated_bytes(), 0);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline(always)]
+    pub fn guard(&mut self) -> BlinkGuard<'_, A> {
+        BlinkGuard { blink: self }
+    }
+
+    /// Returns borrowed view used to emplace values.
+    #[inline(always)]
+    fn raw(&self) -> RawBlink<'_, A> {
+        RawBlink {
+            alloc: &self.alloc,
+            drop_list: DropListRef::Local(&self.drop_list),
+        }
+    }
+
+    /// Checks that memory of `ARc` and `BlinkBox` values is not reclaimed while they are alive.
+    ///
+    /// `BlinkBox` values may be pinned, so they are checked in release builds too.
+    #[inline(always)]
+    fn check_arcs(&mut self) {
+        #[cfg(debug_assertions)]
+        assert_eq!(
+            *self.live_handles.get_mut(),
+            0,
+            "Blink is reset while `ARc` handles are leaked"
+        );
+
+        assert_eq!(
+            *self.live_boxes.get_mut(),
+            0,
+            "Blink is reset while `BlinkBox` handles are leaked"
+        );
+    }
+}
+
+/// Borrowed allocator and drop list of blink adaptor.
+/// Implements emplacement shared by [`Blink`] and [`SyncBlink`].
+///
+/// [`SyncBlink`]: crate::SyncBlink
+pub(crate) struct RawBlink<'a, A> {
+    pub(crate) alloc: &'a A,
+    pub(crate) drop_list: DropListRef<'a>,
+}
+
+impl<A> Clone for RawBlink<'_, A> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
     }
+}
+
+impl<A> Copy for RawBlink<'_, A> {}
 
+impl<'a, A> RawBlink<'a, A>
+where
+    A: BlinkAllocator,
+{
     /// Allocates memory for a copy of the slice.
     /// If allocation fails, returns `Err`.
     /// Otherwise copies the slice into the allocated memory and returns
     /// mutable reference to the copy.
     #[inline]
-    unsafe fn _try_copy_slice<'a, T, E>(
-        &'a self,
+    pub(crate) unsafe fn _try_copy_slice<T, E>(
+        self,
         slice: &[T],
         alloc_err: impl FnOnce(Layout) -> E,
     ) -> Result<&'a mut [T], E>
@@ -315,12 +719,34 @@ where
         };
 
         let ptr = ptr.as_ptr().cast();
-        core::ptr::copy_nonoverlapping(slice.as_ptr(), ptr, slice.len());
+        copy_bulk(slice.as_ptr(), ptr, slice.len());
         Ok(core::slice::from_raw_parts_mut(ptr, slice.len()))
     }
 
-    unsafe fn _try_emplace_drop<'a, T, I, G: 'a, E>(
-        &'a self,
+    /// Copies bytes followed by NUL terminator.
+    /// `bytes` must not contain NUL.
+    pub(crate) unsafe fn _try_copy_cstr<E>(
+        self,
+        bytes: &[u8],
+        alloc_err: impl FnOnce(Layout) -> E,
+    ) -> Result<&'a CStr, E> {
+        debug_assert!(!bytes.contains(&0));
+
+        // Slice length never exceeds `isize::MAX`.
+        let layout = Layout::array::<u8>(bytes.len() + 1).unwrap();
+        let Ok(ptr) = self.alloc.allocate(layout) else {
+            return Err(alloc_err(layout));
+        };
+
+        let ptr = ptr.as_ptr().cast::<u8>();
+        copy_bulk(bytes.as_ptr(), ptr, bytes.len());
+        ptr.add(bytes.len()).write(0);
+        let bytes = core::slice::from_raw_parts(ptr, bytes.len() + 1);
+        Ok(CStr::from_bytes_with_nul_unchecked(bytes))
+    }
+
+    unsafe fn _try_emplace_drop<T, I, G: 'a, E>(
+        self,
         init: I,
         f: impl FnOnce(&mut EmplaceSlot<T, G>, I),
         err: impl FnOnce(G) -> E,
@@ -353,8 +779,8 @@ where
         }
     }
 
-    unsafe fn _try_emplace_no_drop<'a, T, I, G: 'a, E>(
-        &self,
+    unsafe fn _try_emplace_no_drop<T, I, G: 'a, E>(
+        self,
         init: I,
         f: impl FnOnce(&mut EmplaceSlot<T, G>, I),
         err: impl FnOnce(G) -> E,
@@ -389,8 +815,8 @@ where
     /// Otherwise calls closure consuming `init`
     /// and initializes memory with closure result.
     #[inline(always)]
-    unsafe fn _try_emplace<'a, T, I, G: 'a, E>(
-        &'a self,
+    pub(crate) unsafe fn _try_emplace<T, I, G: 'a, E>(
+        self,
         init: I,
         f: impl FnOnce(&mut EmplaceSlot<T, G>, I),
         no_drop: bool,
@@ -404,50 +830,335 @@ where
         }
     }
 
-    unsafe fn _try_emplace_drop_from_iter<'a, T: 'a, I, E>(
-        &'a self,
-        mut iter: I,
-        err: impl FnOnce(&'a mut [T], Option<T>, Option<Layout>) -> E,
-    ) -> Result<&'a mut [T], E>
+    /// Allocates memory for a value aligned to at least `align`
+    /// and moves `value` into it.
+    /// If allocation fails, returns `Err(value)`
+    /// with layout if it is valid.
+    ///
+    /// `align` must be a power of two.
+    #[inline(always)]
+    pub(crate) unsafe fn _try_emplace_aligned<T>(
+        self,
+        value: T,
+        align: usize,
+        no_drop: bool,
+    ) -> Result<&'a mut T, (T, Option<Layout>)> {
+        debug_assert!(align.is_power_of_two());
+
+        if !needs_drop::<T>() || no_drop {
+            let Ok(layout) = Layout::new::<T>().align_to(align) else {
+                return Err((value, None));
+            };
+            let Ok(ptr) = self.alloc.allocate(layout) else {
+                return Err((value, Some(layout)));
+            };
+
+            // Safety: `ptr` is a valid pointer to allocated memory
+            // that fits `T` and is aligned to at least `align_of::<T>()`.
+            let ptr = ptr.as_ptr().cast::<T>();
+            ptr.write(value);
+            return Ok(&mut *ptr);
+        }
+
+        // Drop item header is placed right before the value.
+        // Item is shifted so that the value is aligned to `align`.
+        let offset = DropItem::<T>::value_offset();
+        let align = align.max(align_of::<DropItem<T>>());
+        let Some(head) = offset
+            .checked_add(align - 1)
+            .map(|size| size & !(align - 1))
+        else {
+            return Err((value, None));
+        };
+        let shift = head - offset;
+        let Some(size) = shift.checked_add(size_of::<DropItem<T>>()) else {
+            return Err((value, None));
+        };
+        let Ok(layout) = Layout::from_size_align(size, align) else {
+            return Err((value, None));
+        };
+        let Ok(ptr) = self.alloc.allocate(layout) else {
+            return Err((value, Some(layout)));
+        };
+
+        // Safety: `shift` is a multiple of `DropItem<T>` alignment,
+        // and the item fits into allocated memory after it.
+        let item_ptr = ptr.as_ptr().cast::<u8>().add(shift).cast::<DropItem<T>>();
+        let item = DropItem::init_value(NonNull::new_unchecked(item_ptr), value, |slot, value| {
+            slot.write(value);
+        });
+        Ok(self.drop_list.add(item))
+    }
+
+    /// Allocates memory for a value and lets closure initialize it in place.
+    /// If allocation fails, returns `Err(f)`.
+    ///
+    /// Closure must return reference to the slot it received.
+    /// Panics otherwise, leaving memory uninitialized and not registered for drop.
+    #[inline(always)]
+    unsafe fn _try_emplace_write_with<T, F, E>(
+        self,
+        f: F,
+        no_drop: bool,
+        alloc_err: impl FnOnce(F, Layout) -> E,
+    ) -> Result<&'a mut T, E>
     where
-        I: Iterator<Item = T>,
+        F: FnOnce(&mut MaybeUninit<T>) -> &mut T,
     {
-        if size_of::<T>() == 0 {
-            let item_layout = Layout::new::<DropItem<[T; 0]>>();
-            let Ok(ptr) = self.alloc.allocate(item_layout) else {
-                return Err(err(&mut [], None, Some(item_layout)));
+        #[inline(always)]
+        fn write_with<T>(slot: &mut MaybeUninit<T>, f: impl FnOnce(&mut MaybeUninit<T>) -> &mut T) {
+            let slot_ptr = slot.as_mut_ptr();
+            let value_ptr: *mut T = f(slot);
+            assert!(
+                ptr::eq(slot_ptr, value_ptr),
+                "`write_with` closure must return reference to the provided slot"
+            );
+        }
+
+        if !needs_drop::<T>() || no_drop {
+            let layout = Layout::new::<T>();
+            let Ok(ptr) = self.alloc.allocate(layout) else {
+                return Err(alloc_err(f, layout));
             };
-            // Drain elements from iterator.
-            // Stop at `usize::MAX`.
-            // Drop exactly this number of elements on reset.
-            let count = saturating_drain_iter(iter);
-            let (item, slice) = DropItem::init_slice(ptr.cast(), count);
-            self.drop_list.add(item);
-            return Ok(slice);
+
+            // Safety: `ptr` is a valid pointer to allocated memory.
+            // Allocated with this `T`'s layout.
+            let slot = &mut *ptr.as_ptr().cast::<MaybeUninit<T>>();
+            write_with(slot, f);
+            Ok(slot.assume_init_mut())
+        } else {
+            let layout = Layout::new::<DropItem<T>>();
+            let Ok(ptr) = self.alloc.allocate(layout) else {
+                return Err(alloc_err(f, layout));
+            };
+
+            // Safety: `ptr` is a valid pointer to allocated memory for type `DropItem<T>`.
+            // Item is added to the drop list only after value is initialized.
+            let item = DropItem::init_value(ptr.cast(), f, write_with);
+            Ok(self.drop_list.add(item))
         }
+    }
 
-        struct Guard<'a, T: 'a, A: BlinkAllocator> {
-            ptr: Option<NonNull<DropItem<[T; 0]>>>,
-            count: usize,
-            cap: usize,
-            layout: Layout,
-            alloc: &'a A,
-            drop_list: &'a DropList,
+    /// Allocates memory for an array and initializes elements in place
+    /// with values returned by closure.
+    /// If allocation fails, returns `Err(f)`.
+    ///
+    /// If closure panics, initialized elements are dropped
+    /// and array is not registered for drop.
+    #[inline(always)]
+    unsafe fn _try_emplace_array<T, F, const N: usize>(
+        self,
+        f: F,
+        no_drop: bool,
+    ) -> Result<&'a mut [T; N], F>
+    where
+        F: FnMut(usize) -> T,
+    {
+        /// Drops initialized elements on panic.
+        struct Guard<T> {
+            ptr: *mut T,
+            len: usize,
         }
 
-        impl<'a, T, A> Drop for Guard<'a, T, A>
-        where
-            A: BlinkAllocator,
-        {
+        impl<T> Drop for Guard<T> {
             #[inline(always)]
             fn drop(&mut self) {
-                self.flush();
+                // Safety: first `len` elements are initialized.
+                unsafe { ptr::drop_in_place(ptr::slice_from_raw_parts_mut(self.ptr, self.len)) }
             }
         }
 
-        impl<'a, T, A> Guard<'a, T, A>
+        #[inline(always)]
+        fn fill<T, F, const N: usize>(slot: &mut MaybeUninit<[T; N]>, mut f: F)
         where
-            A: BlinkAllocator,
+            F: FnMut(usize) -> T,
+        {
+            let mut guard = Guard {
+                ptr: slot.as_mut_ptr().cast::<T>(),
+                len: 0,
+            };
+            while guard.len < N {
+                // Safety: `len < N`, so slot is within the array.
+                unsafe { guard.ptr.add(guard.len).write(f(guard.len)) };
+                guard.len += 1;
+            }
+            core::mem::forget(guard);
+        }
+
+        if !needs_drop::<T>() || no_drop {
+            let layout = Layout::new::<[T; N]>();
+            let Ok(ptr) = self.alloc.allocate(layout) else {
+                return Err(f);
+            };
+
+            // Safety: `ptr` is a valid pointer to allocated memory.
+            // Allocated with this array's layout.
+            let slot = &mut *ptr.as_ptr().cast::<MaybeUninit<[T; N]>>();
+            fill(slot, f);
+            Ok(slot.assume_init_mut())
+        } else {
+            let layout = Layout::new::<DropItem<[T; N]>>();
+            let Ok(ptr) = self.alloc.allocate(layout) else {
+                return Err(f);
+            };
+
+            // Safety: `ptr` is a valid pointer to allocated memory for type `DropItem<[T; N]>`.
+            // Item is added to the drop list only after array is initialized.
+            let item = DropItem::init_value(ptr.cast(), f, fill);
+            Ok(self.drop_list.add(item))
+        }
+    }
+
+    /// Allocates memory for a slice of `len` elements
+    /// and initializes them with values returned by closure.
+    /// If allocation fails, returns `Err` with layout if it is valid.
+    ///
+    /// If closure panics, initialized elements are dropped
+    /// and slice is not registered for drop.
+    #[inline(always)]
+    unsafe fn _try_emplace_slice_with<T, F>(
+        self,
+        len: usize,
+        f: F,
+        no_drop: bool,
+    ) -> Result<&'a mut [T], Option<Layout>>
+    where
+        F: FnMut(usize) -> T,
+    {
+        /// Drops initialized elements on panic.
+        struct Guard<T> {
+            ptr: *mut T,
+            len: usize,
+        }
+
+        impl<T> Drop for Guard<T> {
+            #[inline(always)]
+            fn drop(&mut self) {
+                // Safety: first `len` elements are initialized.
+                unsafe { ptr::drop_in_place(ptr::slice_from_raw_parts_mut(self.ptr, self.len)) }
+            }
+        }
+
+        #[inline(always)]
+        unsafe fn fill<T>(ptr: *mut T, len: usize, mut f: impl FnMut(usize) -> T) {
+            let mut guard = Guard { ptr, len: 0 };
+            while guard.len < len {
+                // Safety: `guard.len < len`, so slot is within the slice.
+                unsafe { guard.ptr.add(guard.len).write(f(guard.len)) };
+                guard.len += 1;
+            }
+            core::mem::forget(guard);
+        }
+
+        if len == 0 {
+            return Ok(&mut []);
+        }
+
+        let Ok(array_layout) = Layout::array::<T>(len) else {
+            return Err(None);
+        };
+
+        if !needs_drop::<T>() || no_drop {
+            let Ok(ptr) = self.alloc.allocate(array_layout) else {
+                return Err(Some(array_layout));
+            };
+
+            let ptr = ptr.as_ptr().cast::<T>();
+            fill(ptr, len, f);
+            Ok(core::slice::from_raw_parts_mut(ptr, len))
+        } else {
+            let item_layout = Layout::new::<DropItem<[T; 0]>>();
+            let Ok((layout, offset)) = item_layout.extend(array_layout) else {
+                return Err(None);
+            };
+            debug_assert_eq!(offset, size_of::<DropItem<[T; 0]>>());
+
+            let Ok(ptr) = self.alloc.allocate(layout) else {
+                return Err(Some(layout));
+            };
+
+            // Safety: `ptr` is a valid pointer to allocated memory for
+            // `DropItem<[T; 0]>` followed by `len` elements.
+            // Item is added to the drop list only after elements are initialized.
+            let item_ptr = ptr.cast::<DropItem<[T; 0]>>();
+            fill(item_ptr.as_ptr().add(1).cast::<T>(), len, f);
+            let (item, slice) = DropItem::init_slice(item_ptr, len);
+            self.drop_list.add(item);
+            Ok(slice)
+        }
+    }
+
+    /// Moves elements of the vector into allocated slice.
+    /// Vector's buffer is freed.
+    /// If allocation fails, returns vector back
+    /// with layout if it is valid.
+    #[cfg(feature = "alloc")]
+    #[inline(always)]
+    unsafe fn _try_emplace_from_vec<T>(
+        self,
+        mut vec: alloc::vec::Vec<T>,
+        no_drop: bool,
+    ) -> Result<&'a mut [T], (alloc::vec::Vec<T>, Option<Layout>)> {
+        let len = vec.len();
+        let ptr = vec.as_ptr();
+
+        // Safety: each element is read once and only after allocation succeeded.
+        // Reading never panics.
+        match unsafe { self._try_emplace_slice_with(len, |idx| ptr.add(idx).read(), no_drop) } {
+            Ok(slice) => {
+                // Safety: elements were moved out.
+                unsafe { vec.set_len(0) };
+                Ok(slice)
+            }
+            Err(layout) => Err((vec, layout)),
+        }
+    }
+
+    unsafe fn _try_emplace_drop_from_iter<T: 'a, I, E>(
+        self,
+        mut iter: I,
+        discard: bool,
+        err: impl FnOnce(&'a mut [T], Option<T>, Option<Layout>) -> E,
+    ) -> Result<&'a mut [T], E>
+    where
+        I: Iterator<Item = T>,
+    {
+        if size_of::<T>() == 0 {
+            let item_layout = Layout::new::<DropItem<[T; 0]>>();
+            let Ok(ptr) = self.alloc.allocate(item_layout) else {
+                return Err(err(&mut [], None, Some(item_layout)));
+            };
+            // Drain elements from iterator.
+            // Stop at `usize::MAX`.
+            // Drop exactly this number of elements on reset.
+            let count = saturating_drain_iter(iter);
+            let (item, slice) = DropItem::init_slice(ptr.cast(), count);
+            self.drop_list.add(item);
+            return Ok(slice);
+        }
+
+        struct Guard<'a, T: 'a, A: BlinkAllocator> {
+            ptr: Option<NonNull<DropItem<[T; 0]>>>,
+            count: usize,
+            cap: usize,
+            layout: Layout,
+            alloc: &'a A,
+            drop_list: DropListRef<'a>,
+        }
+
+        impl<'a, T, A> Drop for Guard<'a, T, A>
+        where
+            A: BlinkAllocator,
+        {
+            #[inline(always)]
+            fn drop(&mut self) {
+                self.flush();
+            }
+        }
+
+        impl<'a, T, A> Guard<'a, T, A>
+        where
+            A: BlinkAllocator,
         {
             #[inline(always)]
             fn flush(&mut self) -> &'a mut [T] {
@@ -488,6 +1199,30 @@ where
                 }
             }
 
+            /// Drops values emplaced so far and gives memory back.
+            #[inline(always)]
+            fn discard(&mut self) -> &'a mut [T] {
+                if let Some(ptr) = self.ptr.take() {
+                    // Safety: first `self.count` elements of the array are initialized.
+                    // Item was never added to the drop list.
+                    unsafe {
+                        let array_ptr = ptr.as_ptr().add(1).cast::<T>();
+                        ptr::drop_in_place(ptr::slice_from_raw_parts_mut(array_ptr, self.count));
+                        self.alloc.deallocate(ptr.cast(), self.layout);
+                    }
+                }
+                &mut []
+            }
+
+            #[cold]
+            fn fail(&mut self, discard: bool) -> &'a mut [T] {
+                if discard {
+                    self.discard()
+                } else {
+                    self.flush()
+                }
+            }
+
             #[inline(always)]
             fn fill(
                 &mut self,
@@ -569,15 +1304,26 @@ where
             count: 0,
             cap: 0,
             layout: Layout::new::<()>(),
-            alloc: &self.alloc,
-            drop_list: &self.drop_list,
+            alloc: self.alloc,
+            drop_list: self.drop_list,
         };
 
-        let (lower, _) = iter.size_hint();
+        let (lower, upper) = iter.size_hint();
 
-        if lower != 0 {
-            if let Err(layout) = guard.fill(lower.max(FASTER_START), &mut None, &mut iter) {
-                return Err(err(guard.flush(), None, layout));
+        if upper == Some(lower) {
+            // Exact size is known. Allocate whole slice at once.
+            // Growing is still possible if iterator lies about its size.
+            if lower != 0 {
+                if let Err(layout) = guard.fill(lower, &mut None, &mut iter) {
+                    return Err(err(guard.fail(discard), None, layout));
+                }
+            }
+        } else {
+            let reserve = initial_reserve::<T>(lower, upper);
+            if reserve != 0 {
+                if let Err(layout) = guard.fill(reserve, &mut None, &mut iter) {
+                    return Err(err(guard.fail(discard), None, layout));
+                }
             }
         }
 
@@ -591,11 +1337,11 @@ where
             let (lower, upper) = iter.size_hint();
             let Some(size_hint) = size_hint_and_one(lower, upper, guard.count.max(FASTER_START))
             else {
-                return Err(err(guard.flush(), one_more, None));
+                return Err(err(guard.fail(discard), one_more, None));
             };
 
             if let Err(layout) = guard.fill(size_hint, &mut one_more, &mut iter) {
-                return Err(err(guard.flush(), one_more, layout));
+                return Err(err(guard.fail(discard), one_more, layout));
             }
 
             one_more = iter.next();
@@ -605,9 +1351,10 @@ where
         }
     }
 
-    unsafe fn _try_emplace_no_drop_from_iter<'a, T: 'a, I, E>(
-        &'a self,
+    unsafe fn _try_emplace_no_drop_from_iter<T: 'a, I, E>(
+        self,
         mut iter: I,
+        discard: bool,
         err: impl FnOnce(&'a mut [T], Option<T>, Option<Layout>) -> E,
     ) -> Result<&'a mut [T], E>
     where
@@ -667,6 +1414,28 @@ where
                 }
             }
 
+            /// Drops values emplaced so far and gives memory back.
+            #[inline(always)]
+            fn discard(&mut self) -> &'a mut [T] {
+                if let Some(ptr) = self.ptr.take() {
+                    // Safety: first `self.count` elements of the array are initialized.
+                    unsafe {
+                        ptr::drop_in_place(ptr::slice_from_raw_parts_mut(ptr.as_ptr(), self.count));
+                        self.alloc.deallocate(ptr.cast(), self.layout);
+                    }
+                }
+                &mut []
+            }
+
+            #[cold]
+            fn fail(&mut self, discard: bool) -> &'a mut [T] {
+                if discard {
+                    self.discard()
+                } else {
+                    self.flush()
+                }
+            }
+
             #[inline(always)]
             fn fill(
                 &mut self,
@@ -738,14 +1507,25 @@ where
             count: 0,
             cap: 0,
             layout: Layout::new::<T>(),
-            alloc: &self.alloc,
+            alloc: self.alloc,
         };
 
-        let (lower, _) = iter.size_hint();
+        let (lower, upper) = iter.size_hint();
 
-        if lower != 0 {
-            if let Err(layout) = guard.fill(lower.max(FASTER_START), &mut None, &mut iter) {
-                return Err(err(guard.flush(), None, layout));
+        if upper == Some(lower) {
+            // Exact size is known. Allocate whole slice at once.
+            // Growing is still possible if iterator lies about its size.
+            if lower != 0 {
+                if let Err(layout) = guard.fill(lower, &mut None, &mut iter) {
+                    return Err(err(guard.fail(discard), None, layout));
+                }
+            }
+        } else {
+            let reserve = initial_reserve::<T>(lower, upper);
+            if reserve != 0 {
+                if let Err(layout) = guard.fill(reserve, &mut None, &mut iter) {
+                    return Err(err(guard.fail(discard), None, layout));
+                }
             }
         }
 
@@ -759,11 +1539,11 @@ where
             let (lower, upper) = iter.size_hint();
             let Some(size_hint) = size_hint_and_one(lower, upper, guard.count.max(FASTER_START))
             else {
-                return Err(err(guard.flush(), one_more, None));
+                return Err(err(guard.fail(discard), one_more, None));
             };
 
             if let Err(layout) = guard.fill(size_hint, &mut one_more, &mut iter) {
-                return Err(err(guard.flush(), one_more, layout));
+                return Err(err(guard.fail(discard), one_more, layout));
             }
 
             one_more = iter.next();
@@ -779,19 +1559,92 @@ where
     /// Otherwise calls closure consuming `init`
     /// and initializes memory with closure result.
     #[inline(always)]
-    unsafe fn _try_emplace_from_iter<'a, T: 'a, I, E>(
-        &'a self,
+    unsafe fn _try_emplace_from_iter<T: 'a, I, E>(
+        self,
         iter: I,
         no_drop: bool,
+        discard: bool,
         err: impl FnOnce(&'a mut [T], Option<T>, Option<Layout>) -> E,
     ) -> Result<&'a mut [T], E>
     where
         I: IntoIterator<Item = T>,
     {
         if !needs_drop::<T>() || no_drop {
-            self._try_emplace_no_drop_from_iter(iter.into_iter(), err)
+            self._try_emplace_no_drop_from_iter(iter.into_iter(), discard, err)
+        } else {
+            self._try_emplace_drop_from_iter(iter.into_iter(), discard, err)
+        }
+    }
+
+    /// Allocates memory for an array of exact length of parallel iterator
+    /// and fills it from multiple threads.
+    /// Each value is written at its index, so order matches the iterator.
+    /// If allocation fails, returns iterator back.
+    #[cfg(feature = "rayon")]
+    unsafe fn _try_emplace_from_par_iter<T: Send + 'a, I>(
+        self,
+        iter: I,
+        no_drop: bool,
+    ) -> Result<&'a mut [T], (I, Option<Layout>)>
+    where
+        I: rayon::iter::IndexedParallelIterator<Item = T>,
+    {
+        use rayon::iter::ParallelIterator;
+
+        struct SendPtr<T>(*mut T);
+
+        // Safety: each thread writes to different element of the array.
+        unsafe impl<T: Send> Send for SendPtr<T> {}
+        unsafe impl<T: Send> Sync for SendPtr<T> {}
+
+        let len = iter.len();
+        if len == 0 {
+            iter.for_each(drop);
+            return Ok(&mut []);
+        }
+
+        let drop_item = needs_drop::<T>() && !no_drop;
+
+        let Ok(array_layout) = Layout::array::<T>(len) else {
+            return Err((iter, None));
+        };
+
+        let (full_layout, array_offset) = if drop_item {
+            let item_layout = Layout::new::<DropItem<[T; 0]>>();
+            let Ok((full_layout, array_offset)) = item_layout.extend(array_layout) else {
+                return Err((iter, None));
+            };
+            (full_layout, array_offset)
+        } else {
+            (array_layout, 0)
+        };
+
+        let ptr = if full_layout.size() == 0 {
+            NonNull::<T>::dangling().cast::<u8>()
+        } else {
+            let Ok(ptr) = self.alloc.allocate(full_layout) else {
+                return Err((iter, Some(full_layout)));
+            };
+            ptr.cast::<u8>()
+        };
+
+        let array_ptr = SendPtr(ptr.as_ptr().add(array_offset).cast::<T>());
+        let array_ptr = &array_ptr;
+
+        // If any thread panics, already written values are leaked.
+        iter.enumerate().for_each(move |(idx, value)| {
+            debug_assert!(idx < len);
+            // Safety: `idx` is less than `len`,
+            // and indexed iterator yields each index exactly once.
+            unsafe { ptr::write(array_ptr.0.add(idx), value) }
+        });
+
+        if drop_item {
+            let (item, slice) = DropItem::init_slice(ptr.cast(), len);
+            self.drop_list.add(item);
+            Ok(slice)
         } else {
-            self._try_emplace_drop_from_iter(iter.into_iter(), err)
+            Ok(core::slice::from_raw_parts_mut(array_ptr.0, len))
         }
     }
 }
@@ -800,11 +1653,22 @@ where
 /// Created by [`Blink::emplace`], [`Blink::emplace_no_drop`]
 /// and [`Blink::emplace_unchecked`].
 pub struct Emplace<'a, A, T, R = &'a mut T, S = &'a mut [T]> {
-    blink: &'a Blink<A>,
+    blink: RawBlink<'a, A>,
     no_drop: bool,
     marker: PhantomData<fn(T) -> (R, S)>,
 }
 
+impl<'a, A, T, R, S> Emplace<'a, A, T, R, S> {
+    #[inline(always)]
+    pub(crate) fn new(blink: RawBlink<'a, A>, no_drop: bool) -> Self {
+        Emplace {
+            blink,
+            no_drop,
+            marker: PhantomData,
+        }
+    }
+}
+
 impl<'a, A, T, R, S> Emplace<'a, A, T, R, S>
 where
     A: BlinkAllocator,
@@ -901,6 +1765,82 @@ where
             .safe_ok(),
         )
     }
+    /// Allocates memory for a value.
+    /// On success invokes closure with uninitialized slot
+    /// to initialize the value in place.
+    /// Returns reference to the value.
+    /// If allocation fails, returns error with closure.
+    ///
+    /// Unlike [`Emplace::try_with`], the value is never constructed on the stack.
+    /// Closure must return reference to the slot it received,
+    /// as returned by [`MaybeUninit::write`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if closure returns reference to a different value.
+    #[inline(always)]
+    pub fn try_write_with<F>(&self, f: F) -> Result<R, F>
+    where
+        F: FnOnce(&mut MaybeUninit<T>) -> &mut T,
+    {
+        unsafe {
+            self.blink
+                ._try_emplace_write_with(f, self.no_drop, |f, _| f)
+        }
+        .map(R::coerce)
+    }
+
+    /// Allocates memory for a value.
+    /// On success invokes closure with uninitialized slot
+    /// to initialize the value in place.
+    /// Returns reference to the value.
+    /// If allocation fails, diverges.
+    ///
+    /// Unlike [`Emplace::with`], the value is never constructed on the stack.
+    /// This allows emplacing values too large for the stack.
+    /// Closure must return reference to the slot it received,
+    /// as returned by [`MaybeUninit::write`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if closure returns reference to a different value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::Blink;
+    /// use std::mem::MaybeUninit;
+    ///
+    /// let mut blink = Blink::new();
+    /// let chunk = blink.emplace().write_with(|slot: &mut MaybeUninit<[u16; 1 << 20]>| {
+    ///     let ptr = slot.as_mut_ptr().cast::<u16>();
+    ///     for i in 0..1 << 20 {
+    ///         unsafe { ptr.add(i).write(i as u16) };
+    ///     }
+    ///     unsafe { slot.assume_init_mut() }
+    /// });
+    /// assert_eq!(chunk[12345], 12345);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    pub fn write_with<F>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut MaybeUninit<T>) -> &mut T,
+    {
+        R::coerce(
+            unsafe {
+                self.blink
+                    ._try_emplace_write_with(f, self.no_drop, |_, layout| -> Infallible {
+                        handle_alloc_error(layout)
+                    })
+            }
+            .safe_ok(),
+        )
+    }
+
     /// Allocates memory for a value.
     /// If allocation fails, returns error with closure.
     /// On success invokes closure and initialize the value.
@@ -959,20 +1899,68 @@ where
     /// grows allocation and moves next values to extended array.
     /// Repeats until iterator is exhausted.
     /// Works best on iterators that report accurate upper size hint.
+    /// Iterators with exact size hint, such as [`ExactSizeIterator`]s,
+    /// are emplaced with a single allocation.
     /// Grows allocated memory potentially reducing number of allocations
     /// and copies.
     /// If allocation fails, returns slice of values emplaced so far.
     /// And one element that was taken from iterator and not emplaced.
     #[inline(always)]
     pub fn try_from_iter<I>(&self, iter: I) -> Result<S, (S, Option<T>)>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        unsafe {
+            self.blink._try_emplace_from_iter(
+                iter,
+                self.no_drop,
+                false,
+                |slice: &'a mut [T], value, _| (S::coerce(slice), value),
+            )
+        }
+        .map(S::coerce)
+    }
+
+    /// Allocates memory for an array and initializes it with
+    /// values from iterator.
+    /// Uses iterator hints to allocate memory.
+    /// If iterator yields more values than allocated array can hold,
+    /// grows allocation and moves next values to extended array.
+    /// Repeats until iterator is exhausted.
+    /// Works best on iterators that report accurate upper size hint.
+    /// Iterators with exact size hint, such as [`ExactSizeIterator`]s,
+    /// are emplaced with a single allocation.
+    ///
+    /// Unlike [`Emplace::try_from_iter`], either all values are emplaced
+    /// or none.
+    /// If allocation fails, values emplaced so far and one element
+    /// that was taken from iterator and not emplaced are dropped
+    /// before this method returns, memory is given back
+    /// and `Err(AllocError)` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::Blink;
+    ///
+    /// let blink = Blink::new();
+    /// let slice = blink
+    ///     .emplace()
+    ///     .try_from_iter_all((0..10).filter(|x| x % 2 == 0))
+    ///     .unwrap();
+    /// assert_eq!(slice, [0, 2, 4, 6, 8]);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline(always)]
+    pub fn try_from_iter_all<I>(&self, iter: I) -> Result<S, AllocError>
     where
         I: IntoIterator<Item = T>,
     {
         unsafe {
             self.blink
-                ._try_emplace_from_iter(iter, self.no_drop, |slice: &'a mut [T], value, _| {
-                    (S::coerce(slice), value)
-                })
+                ._try_emplace_from_iter(iter, self.no_drop, true, |_, _, _| AllocError)
         }
         .map(S::coerce)
     }
@@ -984,6 +1972,8 @@ where
     /// grows allocation and moves next values to extended array.
     /// Repeats until iterator is exhausted.
     /// Works best on iterators that report accurate upper size hint.
+    /// Iterators with exact size hint, such as [`ExactSizeIterator`]s,
+    /// are emplaced with a single allocation.
     /// Grows allocated memory potentially reducing number of allocations
     /// and copies.
     /// If allocation fails, diverges.
@@ -999,25 +1989,252 @@ where
         S::coerce(
             unsafe {
                 self.blink
-                    ._try_emplace_from_iter(iter, self.no_drop, |_, _, layout| match layout {
-                        Some(layout) => handle_alloc_error(layout),
-                        None => size_overflow(),
-                    })
+                    ._try_emplace_from_iter(
+                        iter,
+                        self.no_drop,
+                        false,
+                        |_, _, layout| match layout {
+                            Some(layout) => handle_alloc_error(layout),
+                            None => size_overflow(),
+                        },
+                    )
             }
             .safe_ok(),
         )
     }
-}
 
-impl<A> Blink<A>
-where
-    A: BlinkAllocator,
-{
-    /// Puts value into this `Blink` instance.
-    /// Returns reference to the value.
+    /// Allocates memory for an array and moves elements of the vector into it.
+    /// Vector's heap buffer is freed, elements are not cloned.
+    /// If allocation fails, returns vector back.
+    #[cfg(feature = "alloc")]
+    #[inline(always)]
+    pub fn try_from_vec(&self, vec: alloc::vec::Vec<T>) -> Result<S, alloc::vec::Vec<T>> {
+        unsafe { self.blink._try_emplace_from_vec(vec, self.no_drop) }
+            .map(S::coerce)
+            .map_err(|(vec, _)| vec)
+    }
+
+    /// Allocates memory for an array and moves elements of the vector into it.
+    /// Vector's heap buffer is freed, elements are not cloned.
+    /// If allocation fails, diverges.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::Blink;
+    ///
+    /// let blink = Blink::new();
+    /// let names = vec![String::from("a"), String::from("b")];
+    /// let names = blink.emplace().from_vec(names);
+    /// assert_eq!(names, ["a", "b"]);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(all(feature = "alloc", not(no_global_oom_handling)))]
+    #[inline(always)]
+    pub fn from_vec(&self, vec: alloc::vec::Vec<T>) -> S {
+        S::coerce(
+            unsafe { self.blink._try_emplace_from_vec(vec, self.no_drop) }
+                .map_err(|(_, layout)| match layout {
+                    Some(layout) => handle_alloc_error(layout),
+                    None => size_overflow(),
+                })
+                .safe_ok(),
+        )
+    }
+
+    /// Allocates memory for an array and initializes it with
+    /// values from indexed parallel iterator.
+    /// Array is filled from multiple threads,
+    /// yet each value is placed at its index in the iterator.
+    /// So resulting slice is the same as if iterator was sequential.
+    /// If allocation fails, returns iterator back.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::Blink;
+    /// use rayon::prelude::*;
+    ///
+    /// let mut blink = Blink::new();
+    /// let slice = blink
+    ///     .emplace()
+    ///     .try_par_from_indexed_iter((0..1000).into_par_iter().map(|x| x * 2))
+    ///     .ok()
+    ///     .unwrap();
+    /// assert!(slice.iter().copied().eq((0..1000).map(|x| x * 2)));
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(feature = "rayon")]
+    #[inline(always)]
+    pub fn try_par_from_indexed_iter<I>(&self, iter: I) -> Result<S, I>
+    where
+        T: Send,
+        I: rayon::iter::IndexedParallelIterator<Item = T>,
+    {
+        unsafe { self.blink._try_emplace_from_par_iter(iter, self.no_drop) }
+            .map(S::coerce)
+            .map_err(|(iter, _)| iter)
+    }
+
+    /// Allocates memory for an array and initializes it with
+    /// values from indexed parallel iterator.
+    /// Array is filled from multiple threads,
+    /// yet each value is placed at its index in the iterator.
+    /// So resulting slice is the same as if iterator was sequential.
+    /// If allocation fails, diverges.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::Blink;
+    /// use rayon::prelude::*;
+    ///
+    /// let mut blink = Blink::new();
+    /// let words = ["a", "bb", "ccc"];
+    /// let slice = blink
+    ///     .emplace()
+    ///     .par_from_indexed_iter(words.par_iter().map(|w| w.len()));
+    /// assert_eq!(slice, [1, 2, 3]);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(all(feature = "rayon", not(no_global_oom_handling)))]
+    #[inline(always)]
+    pub fn par_from_indexed_iter<I>(&self, iter: I) -> S
+    where
+        T: Send,
+        I: rayon::iter::IndexedParallelIterator<Item = T>,
+    {
+        S::coerce(
+            unsafe { self.blink._try_emplace_from_par_iter(iter, self.no_drop) }
+                .map_err(|(_, layout)| match layout {
+                    Some(layout) => handle_alloc_error(layout),
+                    None => size_overflow(),
+                })
+                .safe_ok(),
+        )
+    }
+}
+
+impl<'a, A, T> Emplace<'a, A, T>
+where
+    A: BlinkAllocator,
+    T: 'a,
+{
+    /// Allocates memory for an array.
+    /// On success initializes each element in place
+    /// with value returned by closure for its index.
+    /// Returns reference to the array.
+    /// If allocation fails, returns error with closure.
+    ///
+    /// If closure panics, elements initialized so far are dropped.
+    #[inline(always)]
+    pub fn try_array<const N: usize, F>(&self, f: F) -> Result<&'a mut [T; N], F>
+    where
+        F: FnMut(usize) -> T,
+    {
+        unsafe { self.blink._try_emplace_array(f, self.no_drop) }
+    }
+
+    /// Allocates memory for an array.
+    /// Initializes each element in place
+    /// with value returned by closure for its index.
+    /// Returns reference to the array.
+    /// If allocation fails, diverges.
+    ///
+    /// Unlike `value([...])`, the array is never constructed on the stack.
+    /// If closure panics, elements initialized so far are dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::Blink;
+    ///
+    /// let mut blink = Blink::new();
+    /// let squares = blink.emplace().array::<1024, _>(|i| i * i);
+    /// assert_eq!(squares[32], 1024);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    pub fn array<const N: usize, F>(&self, f: F) -> &'a mut [T; N]
+    where
+        F: FnMut(usize) -> T,
+    {
+        match self.try_array(f) {
+            Ok(array) => array,
+            Err(_) => handle_alloc_error(Layout::new::<[T; N]>()),
+        }
+    }
+
+    /// Allocates memory for an array and initializes it in place
+    /// with first `N` values from iterator.
+    /// Returns reference to the array.
+    /// If allocation fails, returns `Err(AllocError)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if iterator yields less than `N` values.
+    /// Elements initialized so far are dropped.
+    #[inline(always)]
+    pub fn try_from_iter_array<const N: usize, I>(
+        &self,
+        iter: I,
+    ) -> Result<&'a mut [T; N], AllocError>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut iter = iter.into_iter();
+        self.try_array(move |_| next_array_item(&mut iter))
+            .map_err(|_| AllocError)
+    }
+
+    /// Allocates memory for an array and initializes it in place
+    /// with first `N` values from iterator.
+    /// Returns reference to the array.
+    /// If allocation fails, diverges.
+    ///
+    /// # Panics
+    ///
+    /// Panics if iterator yields less than `N` values.
+    /// Elements initialized so far are dropped.
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    pub fn from_iter_array<const N: usize, I>(&self, iter: I) -> &'a mut [T; N]
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut iter = iter.into_iter();
+        self.array(move |_| next_array_item(&mut iter))
+    }
+}
+
+#[inline(always)]
+fn next_array_item<T>(iter: &mut impl Iterator<Item = T>) -> T {
+    match iter.next() {
+        Some(value) => value,
+        None => panic!("Iterator yielded less values than array length"),
+    }
+}
+
+impl<A> Blink<A>
+where
+    A: BlinkAllocator,
+{
+    /// Puts value into this `Blink` instance.
+    /// Returns reference to the value.
     ///
     /// Effectively extends lifetime of the value
     /// from local scope to the reset scope.
+    /// Value must be [`Send`] as it is dropped on reset,
+    /// possibly on another thread.
     ///
     /// For more flexible value placement see
     /// [`Blink::emplace`], [`Blink::emplace_no_drop`] and
@@ -1040,9 +2257,9 @@ where
     #[cfg(not(no_global_oom_handling))]
     #[inline(always)]
     #[allow(clippy::mut_from_ref)]
-    pub fn put<T: 'static>(&self, value: T) -> &mut T {
+    pub fn put<T: Send + 'static>(&self, value: T) -> &mut T {
         unsafe {
-            self._try_emplace(
+            self.raw()._try_emplace(
                 value,
                 |slot, value| {
                     slot.write(Ok::<_, ManuallyDrop<Infallible>>(value));
@@ -1055,6 +2272,225 @@ where
         .safe_ok()
     }
 
+    /// Puts value into this `Blink` instance.
+    /// Returns reference to the value.
+    /// If allocation fails, returns `Err(value)`.
+    ///
+    /// Fallible counterpart of [`Blink::put`] that never panics
+    /// on allocation failure.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::Blink;
+    /// let mut blink = Blink::new();
+    /// let foo = blink.try_put(42).unwrap();
+    /// assert_eq!(*foo, 42);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn try_put<T: Send + 'static>(&self, value: T) -> Result<&mut T, T> {
+        unsafe {
+            self.raw()._try_emplace(
+                value,
+                |slot, value| {
+                    slot.write(Ok::<_, ManuallyDrop<Infallible>>(value));
+                },
+                false,
+                |never| match never {},
+                |init, _| init,
+            )
+        }
+    }
+
+    /// Puts value into this `Blink` instance.
+    /// Returns type-erased handle to the value.
+    ///
+    /// Value is dropped on reset, same as with [`Blink::put`].
+    /// See [`BlinkAny`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::Blink;
+    /// let mut blink = Blink::new();
+    /// let mut any = blink.put_any(42u32);
+    /// assert!(any.is::<u32>());
+    /// *any.downcast_mut::<u32>().unwrap() += 1;
+    /// assert_eq!(any.downcast::<u32>().ok(), Some(&mut 43));
+    /// blink.reset();
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    pub fn put_any<T: Send + 'static>(&self, value: T) -> BlinkAny<'_> {
+        BlinkAny::new(self.put(value))
+    }
+
+    /// Puts value into this `Blink` instance.
+    /// Returns type-erased handle to the value.
+    /// If allocation fails, returns `Err(value)`.
+    ///
+    /// Fallible counterpart of [`Blink::put_any`].
+    #[inline(always)]
+    pub fn try_put_any<T: Send + 'static>(&self, value: T) -> Result<BlinkAny<'_>, T> {
+        self.try_put(value).map(BlinkAny::new)
+    }
+
+    /// Moves elements of the vector into this `Blink` instance.
+    /// Returns reference to the slice of moved elements.
+    /// Vector's heap buffer is freed, elements are not cloned.
+    ///
+    /// Elements are dropped on reset.
+    /// See [`Emplace::from_vec`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::Blink;
+    /// let mut blink = Blink::new();
+    /// let words = blink.put_vec(vec![String::from("arena"), String::from("slice")]);
+    /// words[0].push('s');
+    /// assert_eq!(words, ["arenas", "slice"]);
+    /// blink.reset();
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(all(feature = "alloc", not(no_global_oom_handling)))]
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn put_vec<T: Send + 'static>(&self, vec: alloc::vec::Vec<T>) -> &mut [T] {
+        self.emplace().from_vec(vec)
+    }
+
+    /// Moves elements of the vector into this `Blink` instance.
+    /// Returns reference to the slice of moved elements.
+    /// If allocation fails, returns vector back.
+    ///
+    /// Fallible counterpart of [`Blink::put_vec`].
+    #[cfg(feature = "alloc")]
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn try_put_vec<T: Send + 'static>(
+        &self,
+        vec: alloc::vec::Vec<T>,
+    ) -> Result<&mut [T], alloc::vec::Vec<T>> {
+        self.emplace().try_from_vec(vec)
+    }
+
+    /// Puts value into this `Blink` instance
+    /// at address aligned to at least `align`.
+    /// Returns reference to the value.
+    ///
+    /// Useful for SIMD and DMA buffers that require
+    /// alignment stricter than alignment of their type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `align` is not a power of two.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::Blink;
+    /// let mut blink = Blink::new();
+    /// let buffer = blink.put_aligned([0u8; 256], 64);
+    /// assert_eq!(buffer.as_ptr() as usize % 64, 0);
+    ///
+    /// let page = blink.put_aligned(vec![1u32, 2, 3], 4096);
+    /// assert_eq!(page as *const Vec<u32> as usize % 4096, 0);
+    /// blink.reset();
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn put_aligned<T: Send + 'static>(&self, value: T, align: usize) -> &mut T {
+        assert!(align.is_power_of_two(), "Alignment must be a power of two");
+        unsafe { self.raw()._try_emplace_aligned(value, align, false) }
+            .map_err(|(_, layout)| match layout {
+                Some(layout) => handle_alloc_error(layout),
+                None => size_overflow(),
+            })
+            .safe_ok()
+    }
+
+    /// Puts value into this `Blink` instance
+    /// at address aligned to at least `align`.
+    /// Returns reference to the value.
+    /// If allocation fails, returns `Err(value)`.
+    ///
+    /// See [`Blink::put_aligned`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `align` is not a power of two.
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn try_put_aligned<T: Send + 'static>(&self, value: T, align: usize) -> Result<&mut T, T> {
+        assert!(align.is_power_of_two(), "Alignment must be a power of two");
+        unsafe { self.raw()._try_emplace_aligned(value, align, false) }.map_err(|(value, _)| value)
+    }
+
+    /// Puts value into this `Blink` instance.
+    /// Returns pinned reference to the value.
+    ///
+    /// Values put into `Blink` are never moved
+    /// and are dropped before their memory is reused,
+    /// so the value may rely on its address,
+    /// e.g. to be part of intrusive structures.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::Blink;
+    /// use core::marker::PhantomPinned;
+    ///
+    /// struct Node {
+    ///     value: u32,
+    ///     _pin: PhantomPinned,
+    /// }
+    ///
+    /// let mut blink = Blink::new();
+    /// let node = blink.put_pin(Node { value: 42, _pin: PhantomPinned });
+    /// assert_eq!(node.value, 42);
+    /// blink.reset();
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn put_pin<T: Send + 'static>(&self, value: T) -> Pin<&mut T> {
+        // Safety: value is registered in drop list and is never moved.
+        // Memory is reused only after drop list is reset.
+        unsafe { Pin::new_unchecked(self.put(value)) }
+    }
+
+    /// Puts value into this `Blink` instance.
+    /// Returns pinned reference to the value.
+    /// If allocation fails, returns `Err(value)`.
+    ///
+    /// Fallible counterpart of [`Blink::put_pin`].
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn try_put_pin<T: Send + 'static>(&self, value: T) -> Result<Pin<&mut T>, T> {
+        let value = self.try_put(value)?;
+
+        // Safety: value is registered in drop list and is never moved.
+        // Memory is reused only after drop list is reset.
+        Ok(unsafe { Pin::new_unchecked(value) })
+    }
+
     /// Puts value into this `Blink` instance.
     /// Returns reference to the value.
     ///
@@ -1086,7 +2522,7 @@ where
     #[allow(clippy::mut_from_ref)]
     pub fn put_no_drop<T>(&self, value: T) -> &mut T {
         unsafe {
-            self._try_emplace(
+            self.raw()._try_emplace(
                 value,
                 |slot, value| {
                     slot.write(Ok::<_, ManuallyDrop<Infallible>>(value));
@@ -1100,17 +2536,18 @@ where
     }
 
     /// Allocates memory for a value.
-    /// Returns some reference to the uninitialized value.
-    /// If allocation fails, returns none.
+    /// Returns reference to the uninitialized value.
+    /// If allocation fails, returns `Err(AllocError)`.
     #[inline(always)]
-    pub fn try_uninit<T>(&self) -> Option<&mut MaybeUninit<T>> {
+    #[allow(clippy::mut_from_ref)]
+    pub fn try_uninit<T>(&self) -> Result<&mut MaybeUninit<T>, AllocError> {
         let layout = Layout::new::<T>();
-        let ptr = self.alloc.allocate(layout).ok()?;
+        let ptr = self.alloc.allocate(layout)?;
 
         // Safety:
         // - `ptr` is valid for `layout`.
         // - `MaybeUninit` is always initialized.
-        Some(unsafe { &mut *ptr.as_ptr().cast() })
+        Ok(unsafe { &mut *ptr.as_ptr().cast() })
     }
 
     /// Allocates memory for a value.
@@ -1131,6 +2568,257 @@ where
         unsafe { &mut *ptr.as_ptr().cast() }
     }
 
+    /// Allocates several slices of length `len` back-to-back
+    /// in one allocation and initializes them with values returned by `f`,
+    /// called for each index.
+    ///
+    /// Values are not dropped on reset, same as with [`Blink::emplace_no_drop`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::Blink;
+    ///
+    /// let blink = Blink::new();
+    /// let (ids, positions): (&mut [u32], &mut [[f32; 2]]) =
+    ///     blink.soa_from_fn(4, |i| (i as u32, [i as f32, 0.0]));
+    ///
+    /// assert_eq!(ids, [0, 1, 2, 3]);
+    /// assert_eq!(positions[3], [3.0, 0.0]);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    pub fn soa_from_fn<'a, S>(&'a self, len: usize, f: impl FnMut(usize) -> S::Elems) -> S
+    where
+        S: SoaSlices<'a>,
+    {
+        let (layout, offsets) = S::layout(len).ok_or_else(size_overflow).safe_ok();
+        let ptr = self
+            .alloc
+            .allocate(layout)
+            .unwrap_or_else(|_| handle_alloc_error(layout));
+
+        // Safety: `ptr` is allocated with `layout` for `len`.
+        unsafe { soa_init(ptr.as_ptr().cast(), offsets, len, f) }
+    }
+
+    /// Allocates several slices of length `len` back-to-back
+    /// in one allocation and initializes them with values returned by `f`,
+    /// called for each index.
+    /// If allocation fails, returns `Err(AllocError)`.
+    ///
+    /// Values are not dropped on reset, same as with [`Blink::emplace_no_drop`].
+    #[inline(always)]
+    pub fn try_soa_from_fn<'a, S>(
+        &'a self,
+        len: usize,
+        f: impl FnMut(usize) -> S::Elems,
+    ) -> Result<S, AllocError>
+    where
+        S: SoaSlices<'a>,
+    {
+        let (layout, offsets) = S::layout(len).ok_or(AllocError)?;
+        let ptr = self.alloc.allocate(layout)?;
+
+        // Safety: `ptr` is allocated with `layout` for `len`.
+        Ok(unsafe { soa_init(ptr.as_ptr().cast(), offsets, len, f) })
+    }
+
+    /// Allocates header followed by slice of values from the iterator
+    /// in one contiguous allocation.
+    ///
+    /// Slice starts right after the header, at the offset returned by
+    /// `Layout::new::<H>().extend(Layout::array::<T>(len))`,
+    /// where `len` is the length of the iterator.
+    /// If iterator yields fewer items than it reported,
+    /// the slice is shorter, extra items are not taken.
+    ///
+    /// Values are not dropped on reset, same as with [`Blink::emplace_no_drop`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::Blink;
+    ///
+    /// struct Packet {
+    ///     kind: u8,
+    ///     len: u16,
+    /// }
+    ///
+    /// let blink = Blink::new();
+    /// let payload = [1u32, 2, 3];
+    /// let (header, body) = blink.header_with_slice(
+    ///     Packet { kind: 1, len: payload.len() as u16 },
+    ///     payload.iter().copied(),
+    /// );
+    ///
+    /// assert_eq!(header.kind, 1);
+    /// assert_eq!(body, [1, 2, 3]);
+    /// assert_eq!(
+    ///     body.as_ptr() as usize - header as *mut Packet as usize,
+    ///     core::mem::size_of::<Packet>(),
+    /// );
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn header_with_slice<H, T, I>(&self, header: H, iter: I) -> (&mut H, &mut [T])
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let iter = iter.into_iter();
+        let (layout, offset) = header_slice_layout::<H, T>(iter.len())
+            .ok_or_else(size_overflow)
+            .safe_ok();
+        let ptr = self
+            .alloc
+            .allocate(layout)
+            .unwrap_or_else(|_| handle_alloc_error(layout));
+
+        // Safety: `ptr` is allocated with `layout` for iterator length.
+        unsafe { header_slice_init(ptr.as_ptr().cast(), offset, header, iter) }
+    }
+
+    /// Allocates header followed by slice of values from the iterator
+    /// in one contiguous allocation.
+    /// If allocation fails, returns `Err(AllocError)`.
+    ///
+    /// See [`Blink::header_with_slice`].
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn try_header_with_slice<H, T, I>(
+        &self,
+        header: H,
+        iter: I,
+    ) -> Result<(&mut H, &mut [T]), AllocError>
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let iter = iter.into_iter();
+        let (layout, offset) = header_slice_layout::<H, T>(iter.len()).ok_or(AllocError)?;
+        let ptr = self.alloc.allocate(layout)?;
+
+        // Safety: `ptr` is allocated with `layout` for iterator length.
+        Ok(unsafe { header_slice_init(ptr.as_ptr().cast(), offset, header, iter) })
+    }
+
+    /// Allocates slice of `len` default values.
+    ///
+    /// Values are dropped on reset.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::Blink;
+    ///
+    /// let blink = Blink::new();
+    /// let names: &mut [String] = blink.emplace_default_slice(3);
+    /// names[1].push_str("blink");
+    /// assert_eq!(names, ["", "blink", ""]);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn emplace_default_slice<T>(&self, len: usize) -> &mut [T]
+    where
+        T: Default + Send + 'static,
+    {
+        unsafe {
+            self.raw()
+                ._try_emplace_slice_with(len, |_| T::default(), false)
+        }
+        .map_err(|layout| match layout {
+            Some(layout) => handle_alloc_error(layout),
+            None => size_overflow(),
+        })
+        .safe_ok()
+    }
+
+    /// Allocates slice of `len` default values.
+    /// If allocation fails, returns `Err(AllocError)`.
+    ///
+    /// See [`Blink::emplace_default_slice`].
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn try_emplace_default_slice<T>(&self, len: usize) -> Result<&mut [T], AllocError>
+    where
+        T: Default + Send + 'static,
+    {
+        unsafe {
+            self.raw()
+                ._try_emplace_slice_with(len, |_| T::default(), false)
+        }
+        .map_err(|_| AllocError)
+    }
+
+    /// Allocates slice of `len` clones of `value`.
+    /// `value` itself is moved into the last element.
+    ///
+    /// Values are dropped on reset.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::Blink;
+    ///
+    /// let blink = Blink::new();
+    /// let rows = blink.fill_slice(vec![0u8; 4], 2);
+    /// rows[0][0] = 1;
+    /// assert_eq!(rows, [vec![1, 0, 0, 0], vec![0; 4]]);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn fill_slice<T>(&self, value: T, len: usize) -> &mut [T]
+    where
+        T: Clone + Send + 'static,
+    {
+        let mut value = Some(value);
+        unsafe {
+            self.raw()
+                ._try_emplace_slice_with(len, |idx| fill_next(&mut value, idx, len), false)
+        }
+        .map_err(|layout| match layout {
+            Some(layout) => handle_alloc_error(layout),
+            None => size_overflow(),
+        })
+        .safe_ok()
+    }
+
+    /// Allocates slice of `len` clones of `value`.
+    /// If allocation fails, returns `Err(value)`.
+    ///
+    /// See [`Blink::fill_slice`].
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn try_fill_slice<T>(&self, value: T, len: usize) -> Result<&mut [T], T>
+    where
+        T: Clone + Send + 'static,
+    {
+        let mut value = Some(value);
+        let result = unsafe {
+            self.raw()
+                ._try_emplace_slice_with(len, |idx| fill_next(&mut value, idx, len), false)
+        };
+
+        // Closure is not called if allocation fails.
+        result.map_err(|_| value.take().unwrap())
+    }
+
     /// Copies the slice to the allocated memory
     /// and returns reference to the new slice.
     #[cfg(not(no_global_oom_handling))]
@@ -1140,54 +2828,639 @@ where
     where
         T: Copy,
     {
-        let result = unsafe { self._try_copy_slice(slice, handle_alloc_error) };
-        match result {
-            Ok(slice) => slice,
-            Err(never) => never,
-        }
+        let result = unsafe { self.raw()._try_copy_slice(slice, handle_alloc_error) };
+        match result {
+            Ok(slice) => slice,
+            Err(never) => never,
+        }
+    }
+
+    /// Allocates memory for a copy of the slice.
+    /// Copies the slice to the allocated memory
+    /// and returns reference to the new slice.
+    /// If allocation fails, returns `Err(AllocError)`.
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn try_copy_slice<T>(&self, slice: &[T]) -> Result<&mut [T], AllocError>
+    where
+        T: Copy,
+    {
+        unsafe { self.raw()._try_copy_slice(slice, |_| AllocError) }
+    }
+
+    /// Copies the slice to the allocated memory
+    /// and returns reference to the new slice.
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn copy_str(&self, string: &str) -> &mut str {
+        let result = unsafe {
+            self.raw()
+                ._try_copy_slice(string.as_bytes(), handle_alloc_error)
+        };
+        match result {
+            Ok(slice) => unsafe { core::str::from_utf8_unchecked_mut(slice) },
+            Err(never) => never,
+        }
+    }
+
+    /// Validates that bytes are UTF-8 and copies them
+    /// to the allocated memory as a string.
+    ///
+    /// Returns error without allocating if bytes are not valid UTF-8.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::Blink;
+    ///
+    /// let blink = Blink::new();
+    /// let input = b"key=value";
+    /// assert_eq!(blink.copy_utf8(&input[..3]).unwrap(), "key");
+    /// assert!(blink.copy_utf8(&[0xff, 0xfe]).is_err());
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    pub fn copy_utf8(&self, bytes: &[u8]) -> Result<&mut str, Utf8Error> {
+        let string = core::str::from_utf8(bytes)?;
+        Ok(self.copy_str(string))
+    }
+
+    /// Allocates memory for a copy of the slice.
+    /// Copies the slice to the allocated memory
+    /// and returns reference to the new slice.
+    /// If allocation fails, returns `Err(AllocError)`.
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn try_copy_str(&self, string: &str) -> Result<&mut str, AllocError> {
+        unsafe {
+            self.raw()
+                ._try_copy_slice(string.as_bytes(), |_| AllocError)
+        }
+        .map(|bytes| unsafe { core::str::from_utf8_unchecked_mut(bytes) })
+    }
+
+    /// Copies the string followed by NUL terminator
+    /// to the allocated memory and returns reference to the new C string.
+    ///
+    /// Useful to pass temporary strings to C APIs
+    /// without allocating `CString`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `string` contains NUL byte.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::Blink;
+    ///
+    /// let blink = Blink::new();
+    /// let name = blink.copy_cstr("main");
+    /// assert_eq!(name.to_bytes_with_nul(), b"main\0");
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    pub fn copy_cstr(&self, string: &str) -> &CStr {
+        assert!(!string.as_bytes().contains(&0), "string contains NUL byte");
+        let result = unsafe {
+            self.raw()
+                ._try_copy_cstr(string.as_bytes(), handle_alloc_error)
+        };
+        match result {
+            Ok(cstr) => cstr,
+            Err(never) => never,
+        }
+    }
+
+    /// Copies the string followed by NUL terminator
+    /// to the allocated memory and returns reference to the new C string.
+    /// If allocation fails, returns `Err(AllocError)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `string` contains NUL byte.
+    #[inline(always)]
+    pub fn try_copy_cstr(&self, string: &str) -> Result<&CStr, AllocError> {
+        assert!(!string.as_bytes().contains(&0), "string contains NUL byte");
+        unsafe { self.raw()._try_copy_cstr(string.as_bytes(), |_| AllocError) }
+    }
+
+    /// Copies NUL-terminated bytes to the allocated memory
+    /// and returns reference to the new C string.
+    ///
+    /// Returns error if `bytes` are not NUL-terminated
+    /// or contain NUL byte before the end,
+    /// see [`CStr::from_bytes_with_nul`].
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    pub fn copy_cstr_from_bytes_with_nul(
+        &self,
+        bytes: &[u8],
+    ) -> Result<&CStr, FromBytesWithNulError> {
+        let cstr = CStr::from_bytes_with_nul(bytes)?;
+        let result = unsafe {
+            self.raw()
+                ._try_copy_cstr(cstr.to_bytes(), handle_alloc_error)
+        };
+        match result {
+            Ok(cstr) => Ok(cstr),
+            Err(never) => never,
+        }
+    }
+
+    /// Copies the OS string to the allocated memory
+    /// and returns reference to the new OS string.
+    #[cfg(all(feature = "std", not(no_global_oom_handling)))]
+    #[inline(always)]
+    pub fn copy_os_str(&self, string: &OsStr) -> &OsStr {
+        let bytes = self.copy_slice(string.as_encoded_bytes());
+
+        // Safety: bytes are copied from valid `OsStr`.
+        unsafe { OsStr::from_encoded_bytes_unchecked(bytes) }
+    }
+
+    /// Copies the OS string to the allocated memory
+    /// and returns reference to the new OS string.
+    /// If allocation fails, returns `Err(AllocError)`.
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    pub fn try_copy_os_str(&self, string: &OsStr) -> Result<&OsStr, AllocError> {
+        let bytes = self.try_copy_slice(string.as_encoded_bytes())?;
+
+        // Safety: bytes are copied from valid `OsStr`.
+        Ok(unsafe { OsStr::from_encoded_bytes_unchecked(bytes) })
+    }
+
+    /// Copies the path to the allocated memory
+    /// and returns reference to the new path.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "std")] fn main() {
+    /// use std::path::{Path, PathBuf};
+    /// use blink_alloc::Blink;
+    ///
+    /// let blink = Blink::new();
+    /// let path = blink.copy_path(&PathBuf::from("assets").join("frame.png"));
+    /// assert_eq!(path, Path::new("assets").join("frame.png"));
+    /// # }
+    /// # #[cfg(not(feature = "std"))] fn main() {}
+    /// ```
+    #[cfg(all(feature = "std", not(no_global_oom_handling)))]
+    #[inline(always)]
+    pub fn copy_path(&self, path: &Path) -> &Path {
+        Path::new(self.copy_os_str(path.as_os_str()))
+    }
+
+    /// Copies the path to the allocated memory
+    /// and returns reference to the new path.
+    /// If allocation fails, returns `Err(AllocError)`.
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    pub fn try_copy_path(&self, path: &Path) -> Result<&Path, AllocError> {
+        self.try_copy_os_str(path.as_os_str()).map(Path::new)
+    }
+
+    /// Returns string from `Cow` with lifetime of this `Blink` instance.
+    ///
+    /// Borrowed string is returned as is.
+    /// Owned string is copied to the allocated memory.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use std::borrow::Cow;
+    /// use blink_alloc::Blink;
+    ///
+    /// let blink = Blink::new();
+    /// let input = "plain";
+    /// let borrowed = blink.copy_cow(Cow::Borrowed(input));
+    /// let owned = blink.copy_cow(Cow::Owned(input.replace('p', "P")));
+    /// assert!(core::ptr::eq(borrowed, input));
+    /// assert_eq!(owned, "Plain");
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(all(feature = "alloc", not(no_global_oom_handling)))]
+    #[inline(always)]
+    pub fn copy_cow<'a, 'b: 'a>(&'a self, cow: Cow<'b, str>) -> &'a str {
+        match cow {
+            Cow::Borrowed(string) => string,
+            Cow::Owned(string) => self.copy_str(&string),
+        }
+    }
+
+    /// Returns string from `Cow` with lifetime of this `Blink` instance.
+    ///
+    /// Borrowed string is returned as is.
+    /// Owned string is copied to the allocated memory.
+    /// If allocation fails, returns `Err(AllocError)`.
+    #[cfg(feature = "alloc")]
+    #[inline(always)]
+    pub fn try_copy_cow<'a, 'b: 'a>(&'a self, cow: Cow<'b, str>) -> Result<&'a str, AllocError> {
+        match cow {
+            Cow::Borrowed(string) => Ok(string),
+            Cow::Owned(string) => self.try_copy_str(&string).map(|string| &*string),
+        }
+    }
+
+    /// Returns slice from `Cow` with lifetime of this `Blink` instance.
+    ///
+    /// Borrowed slice is returned as is.
+    /// Owned slice is copied to the allocated memory.
+    #[cfg(all(feature = "alloc", not(no_global_oom_handling)))]
+    #[inline(always)]
+    pub fn copy_cow_slice<'a, 'b: 'a, T>(&'a self, cow: Cow<'b, [T]>) -> &'a [T]
+    where
+        T: Copy,
+    {
+        match cow {
+            Cow::Borrowed(slice) => slice,
+            Cow::Owned(vec) => self.copy_slice(&vec),
+        }
+    }
+
+    /// Returns slice from `Cow` with lifetime of this `Blink` instance.
+    ///
+    /// Borrowed slice is returned as is.
+    /// Owned slice is copied to the allocated memory.
+    /// If allocation fails, returns `Err(AllocError)`.
+    #[cfg(feature = "alloc")]
+    #[inline(always)]
+    pub fn try_copy_cow_slice<'a, 'b: 'a, T>(
+        &'a self,
+        cow: Cow<'b, [T]>,
+    ) -> Result<&'a [T], AllocError>
+    where
+        T: Copy,
+    {
+        match cow {
+            Cow::Borrowed(slice) => Ok(slice),
+            Cow::Owned(vec) => self.try_copy_slice(&vec).map(|slice| &*slice),
+        }
+    }
+
+    /// Copies strings and the slice of them into this `Blink` instance.
+    /// Returns slice of copied strings.
+    ///
+    /// Same as [`Blink::deep_copy`] for `&[&str]`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::Blink;
+    ///
+    /// let blink = Blink::new();
+    /// let args = vec![String::from("run"), String::from("--release")];
+    /// let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    /// let copied = blink.copy_str_slice(&args);
+    /// drop(args);
+    /// assert_eq!(copied, ["run", "--release"]);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn copy_str_slice(&self, strings: &[&str]) -> &mut [&str] {
+        self.emplace_slice_with_no_drop(strings.len(), |idx| &*self.copy_str(strings[idx]))
+    }
+
+    /// Copies inner slices and the outer slice into this `Blink` instance.
+    /// Returns slice of copied slices.
+    ///
+    /// Same as [`Blink::deep_copy`] for `&[&[T]]` with `T: Copy`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::Blink;
+    ///
+    /// let blink = Blink::new();
+    /// let rows = vec![vec![1, 2], vec![], vec![3]];
+    /// let rows: Vec<&[u32]> = rows.iter().map(Vec::as_slice).collect();
+    /// let copied = blink.copy_nested(&rows);
+    /// drop(rows);
+    /// assert_eq!(copied, [&[1, 2][..], &[], &[3]]);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn copy_nested<T>(&self, slices: &[&[T]]) -> &mut [&[T]]
+    where
+        T: Copy,
+    {
+        self.emplace_slice_with_no_drop(slices.len(), |idx| &*self.copy_slice(slices[idx]))
+    }
+
+    /// Deep-copies the value into this `Blink` instance.
+    /// Returns copy with references tied to this `Blink` instance.
+    ///
+    /// See [`BlinkCopy`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::Blink;
+    ///
+    /// let persistent = Blink::new();
+    /// let mut frame = Blink::new();
+    /// let names: &[&str] = frame.copy_slice(&[&*frame.copy_str("a"), &*frame.copy_str("b")]);
+    ///
+    /// let names = persistent.deep_copy(&names);
+    /// frame.reset();
+    /// assert_eq!(names, ["a", "b"]);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    pub fn deep_copy<'b, T>(&'b self, value: &T) -> T::Output
+    where
+        T: BlinkCopy<'b> + ?Sized,
+    {
+        value.blink_copy(self)
+    }
+
+    /// Allocates slice of `len` values produced by `f`.
+    /// Values are not dropped on reset.
+    ///
+    /// `f` may allocate from this `Blink` instance.
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub(crate) fn emplace_slice_with_no_drop<T>(
+        &self,
+        len: usize,
+        f: impl FnMut(usize) -> T,
+    ) -> &mut [T] {
+        unsafe { self.raw()._try_emplace_slice_with(len, f, true) }
+            .map_err(|layout| match layout {
+                Some(layout) => handle_alloc_error(layout),
+                None => size_overflow(),
+            })
+            .safe_ok()
+    }
+
+    /// Places value into allocated memory together with reference counter.
+    /// Returns shared handle to the value.
+    ///
+    /// Value is dropped when the last handle is dropped.
+    /// See [`ARc`].
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    pub fn arc<T>(&self, value: T) -> ARc<'_, T> {
+        match self.try_arc(value) {
+            Ok(arc) => arc,
+            Err(_) => handle_alloc_error(Layout::new::<ArcInner<'_, T>>()),
+        }
+    }
+
+    /// Places value into allocated memory together with reference counter.
+    /// Returns shared handle to the value.
+    /// If allocation fails, returns `Err(value)`.
+    ///
+    /// See [`ARc`].
+    #[inline(always)]
+    pub fn try_arc<T>(&self, value: T) -> Result<ARc<'_, T>, T> {
+        let ptr = match self.alloc.allocate(Layout::new::<ArcInner<'_, T>>()) {
+            Ok(ptr) => ptr.cast::<ArcInner<'_, T>>(),
+            Err(_) => return Err(value),
+        };
+
+        #[cfg(debug_assertions)]
+        let inner = ArcInner::new(value, &self.live_handles);
+
+        #[cfg(not(debug_assertions))]
+        let inner = ArcInner::new(value);
+
+        // Safety: memory is allocated for the control block
+        // and stays valid while `self` is borrowed.
+        unsafe {
+            ptr.as_ptr().write(inner);
+            Ok(ARc::from_inner(ptr))
+        }
+    }
+
+    /// Places value into allocated memory.
+    /// Returns owning pointer to the value.
+    ///
+    /// Value is dropped when the box is dropped.
+    /// It can be moved out with [`BlinkBox::into_inner`].
+    /// See [`BlinkBox`].
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    pub fn boxed<T>(&self, value: T) -> BlinkBox<'_, T> {
+        match self.try_boxed(value) {
+            Ok(boxed) => boxed,
+            Err(_) => handle_alloc_error(Layout::new::<T>()),
+        }
+    }
+
+    /// Places value into allocated memory.
+    /// Returns owning pointer to the value.
+    /// If allocation fails, returns `Err(value)`.
+    ///
+    /// See [`BlinkBox`].
+    #[inline(always)]
+    pub fn try_boxed<T>(&self, value: T) -> Result<BlinkBox<'_, T>, T> {
+        let ptr = match self.alloc.allocate(Layout::new::<T>()) {
+            Ok(ptr) => ptr.cast::<T>(),
+            Err(_) => return Err(value),
+        };
+
+        // Safety: memory is allocated for the value
+        // and stays valid while `self` is borrowed.
+        unsafe {
+            ptr.as_ptr().write(value);
+
+            Ok(BlinkBox::from_raw(ptr, &self.live_boxes))
+        }
+    }
+
+    /// Creates empty [`Vec`](allocator_api2::vec::Vec)
+    /// that uses this blink allocator.
+    ///
+    /// For [`Box`](allocator_api2::boxed::Box) use [`blink_box!`](crate::blink_box).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::Blink;
+    ///
+    /// let mut blink = Blink::new();
+    /// let mut vec = blink.vec();
+    /// vec.extend(["foo", "bar"]);
+    /// assert_eq!(vec, ["foo", "bar"]);
+    ///
+    /// drop(vec);
+    /// blink.reset();
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[inline(always)]
+    pub fn vec<T>(&self) -> allocator_api2::vec::Vec<T, &Self> {
+        allocator_api2::vec::Vec::new_in(self)
+    }
+
+    /// Creates [`Vec`](allocator_api2::vec::Vec) with space
+    /// for at least `capacity` elements that uses this blink allocator.
+    #[cfg(all(feature = "alloc", not(no_global_oom_handling)))]
+    #[inline(always)]
+    pub fn vec_with_capacity<T>(&self, capacity: usize) -> allocator_api2::vec::Vec<T, &Self> {
+        allocator_api2::vec::Vec::with_capacity_in(capacity, self)
+    }
+
+    /// Places future into allocated memory.
+    /// Returns pinned type-erased future.
+    ///
+    /// Allows executors to allocate task futures
+    /// from arena instead of global heap.
+    ///
+    /// Futures must be dropped before reset.
+    /// Reset panics if any of them is leaked, see [`BlinkBox`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use core::{future::Future, pin::Pin, task::{Context, Poll, Waker}};
+    /// use blink_alloc::{Blink, BlinkBox};
+    ///
+    /// let mut blink = Blink::new();
+    /// let mut tasks: Vec<Pin<BlinkBox<dyn Future<Output = u32>>>> = Vec::new();
+    /// for i in 0..3 {
+    ///     tasks.push(blink.box_future(async move { i * 2 }));
+    /// }
+    ///
+    /// let mut cx = Context::from_waker(Waker::noop());
+    /// let mut sum = 0;
+    /// for task in &mut tasks {
+    ///     match task.as_mut().poll(&mut cx) {
+    ///         Poll::Ready(value) => sum += value,
+    ///         Poll::Pending => unreachable!(),
+    ///     }
+    /// }
+    /// assert_eq!(sum, 6);
+    ///
+    /// drop(tasks);
+    /// blink.reset();
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    pub fn box_future<'a, F>(
+        &'a self,
+        future: F,
+    ) -> Pin<BlinkBox<'a, dyn Future<Output = F::Output> + 'a>>
+    where
+        F: Future + 'a,
+    {
+        let boxed = BlinkBox::map_ptr(
+            self.boxed(future),
+            |ptr| -> NonNull<dyn Future<Output = F::Output> + 'a> { ptr },
+        );
+        BlinkBox::into_pin(boxed)
     }
 
-    /// Allocates memory for a copy of the slice.
-    /// Copies the slice to the allocated memory
-    /// and returns reference to the new slice.
-    /// If allocation fails, returns `None`.
+    /// Places future into allocated memory.
+    /// Returns pinned type-erased future that can be sent to other threads.
+    ///
+    /// See [`Blink::box_future`].
+    #[cfg(not(no_global_oom_handling))]
     #[inline(always)]
-    pub fn try_copy_slice<T>(&self, slice: &[T]) -> Option<&mut [T]>
+    pub fn box_future_send<'a, F>(
+        &'a self,
+        future: F,
+    ) -> Pin<BlinkBox<'a, dyn Future<Output = F::Output> + Send + 'a>>
     where
-        T: Copy,
+        F: Future + Send + 'a,
     {
-        unsafe { self._try_copy_slice(slice, |_| ()) }.ok()
+        let boxed = BlinkBox::map_ptr(
+            self.boxed(future),
+            |ptr| -> NonNull<dyn Future<Output = F::Output> + Send + 'a> { ptr },
+        );
+        BlinkBox::into_pin(boxed)
     }
 
-    /// Copies the slice to the allocated memory
-    /// and returns reference to the new slice.
-    #[cfg(not(no_global_oom_handling))]
+    /// Returns a writer that appends bytes into a buffer
+    /// allocated from this `Blink` instance.
+    ///
+    /// Finished buffer is valid until reset.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use core::fmt::Write;
+    /// use blink_alloc::Blink;
+    ///
+    /// let mut blink = Blink::new();
+    /// let mut writer = blink.writer();
+    /// for i in 0..3 {
+    ///     write!(writer, "{i} ").unwrap();
+    /// }
+    /// assert_eq!(writer.finish_str().unwrap(), "0 1 2 ");
+    /// blink.reset();
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
     #[inline(always)]
-    #[allow(clippy::mut_from_ref)]
-    pub fn copy_str(&self, string: &str) -> &mut str {
-        let result = unsafe { self._try_copy_slice(string.as_bytes(), handle_alloc_error) };
-        match result {
-            Ok(slice) => unsafe { core::str::from_utf8_unchecked_mut(slice) },
-            Err(never) => never,
-        }
+    pub fn writer(&self) -> ArenaWrite<'_, A> {
+        ArenaWrite::new(&self.alloc)
     }
 
-    /// Allocates memory for a copy of the slice.
-    /// Copies the slice to the allocated memory
-    /// and returns reference to the new slice.
-    /// If allocation fails, returns `None`.
-    #[inline(always)]
-    pub fn try_copy_str(&self, string: &str) -> Option<&mut str> {
-        unsafe { self._try_copy_slice(string.as_bytes(), |_| ()) }
-            .ok()
-            .map(|bytes| unsafe { core::str::from_utf8_unchecked_mut(bytes) })
+    /// Reads all bytes until EOF from `reader` into single allocation.
+    /// Returns reference to the read bytes.
+    ///
+    /// Allocation grows in place while it stays the last one,
+    /// avoiding temporary buffer on the global heap.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "std")] fn main() {
+    /// use blink_alloc::Blink;
+    ///
+    /// let blink = Blink::new();
+    /// let mut file: &[u8] = b"key = value";
+    /// let bytes = blink.read_all(&mut file).unwrap();
+    /// assert_eq!(bytes, b"key = value");
+    /// # }
+    /// # #[cfg(not(feature = "std"))] fn main() {}
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn read_all<R>(&self, reader: &mut R) -> std::io::Result<&mut [u8]>
+    where
+        R: std::io::Read + ?Sized,
+    {
+        let mut writer = self.writer();
+        writer.read_to_end(reader)?;
+        Ok(writer.finish())
     }
 
     /// Returns an `Emplace` adaptor that can emplace values into
     /// the blink allocator.
     ///
-    /// This version requires the value type to be `'static`.
-    /// To use with non-static types consider using one of the following:
+    /// This version requires the value type to be `'static` and [`Send`].
+    /// To use with other types consider using one of the following:
     ///
     /// * [`Blink::emplace_no_drop`]
     ///   Causes emplaced value to not be dropped on reset.
@@ -1214,12 +3487,8 @@ where
     /// # #[cfg(not(feature = "alloc"))] fn main() {}
     /// ```
     #[inline(always)]
-    pub fn emplace<T: 'static>(&self) -> Emplace<'_, A, T> {
-        Emplace {
-            blink: self,
-            no_drop: false,
-            marker: PhantomData,
-        }
+    pub fn emplace<T: Send + 'static>(&self) -> Emplace<'_, A, T> {
+        Emplace::new(self.raw(), false)
     }
 
     /// Returns an `Emplace` adaptor that can emplace values into
@@ -1229,7 +3498,7 @@ where
     /// To drop returned value on reset, consider one of the following:
     ///
     /// * [`Blink::emplace`]
-    ///   Requires the value type to be `'static`.
+    ///   Requires the value type to be `'static` and [`Send`].
     /// * [`Blink::emplace_shared`]
     ///   Returns shared reference to emplaced values.
     /// * [`Blink::emplace_unchecked`]
@@ -1237,6 +3506,11 @@ where
     ///   User must guarantee that the value won't have access to references
     ///   allocated by the blink allocator later.
     ///
+    /// Returned adaptor has the same fallible methods as [`Blink::emplace`],
+    /// like [`Emplace::try_value`], [`Emplace::try_with`] and
+    /// [`Emplace::try_from_iter`], that give the value back
+    /// when underlying allocator fails.
+    ///
     /// # Example
     ///
     /// ```
@@ -1264,11 +3538,7 @@ where
     /// ```
     #[inline(always)]
     pub fn emplace_no_drop<T>(&self) -> Emplace<'_, A, T> {
-        Emplace {
-            blink: self,
-            no_drop: true,
-            marker: PhantomData,
-        }
+        Emplace::new(self.raw(), true)
     }
 
     /// Returns an `Emplace` adaptor that can emplace values into
@@ -1281,7 +3551,7 @@ where
     /// To drop returned value on reset, consider one of the following:
     ///
     /// * [`Blink::emplace`]
-    ///   Requires the value type to be `'static`.
+    ///   Requires the value type to be `'static` and [`Send`].
     /// * [`Blink::emplace_no_drop`]
     ///   Causes emplaced value to not be dropped on reset.
     ///   Avoiding potential unsoundness in `Drop` implementation.
@@ -1316,11 +3586,7 @@ where
     /// ```
     #[inline(always)]
     pub fn emplace_shared<T>(&self) -> Emplace<'_, A, T, &T, &[T]> {
-        Emplace {
-            blink: self,
-            no_drop: true,
-            marker: PhantomData,
-        }
+        Emplace::new(self.raw(), true)
     }
 
     /// Returns an `Emplace` adaptor that can emplace values into
@@ -1332,7 +3598,7 @@ where
     /// For safe code consider using one of the following:
     ///
     /// * [`Blink::emplace`]
-    ///   Requires the value type to be `'static`.
+    ///   Requires the value type to be `'static` and [`Send`].
     /// * [`Blink::emplace_no_drop`]
     ///   Causes emplaced value to not be dropped on reset.
     ///   Avoiding potential unsoundness in `Drop` implementation.
@@ -1342,6 +3608,8 @@ where
     /// # Safety
     ///
     /// Avoid incorrect usage. See below.
+    /// If values are not [`Send`], [`Blink`] must not be sent
+    /// to another thread before it is reset.
     ///
     /// # Incorrect usage example
     ///
@@ -1374,18 +3642,131 @@ where
     /// ```
     #[inline(always)]
     pub unsafe fn emplace_unchecked<T>(&self) -> Emplace<'_, A, T> {
-        Emplace {
-            blink: self,
-            no_drop: false,
-            marker: PhantomData,
-        }
+        Emplace::new(self.raw(), false)
+    }
+}
+
+switch_alloc_default! {
+    /// Guard returned by [`Blink::guard`].
+    ///
+    /// Dereferences to the [`Blink`] and resets it when dropped.
+    pub struct BlinkGuard<'a, A: BlinkAllocator = +BlinkAlloc<Global>> {
+        blink: &'a mut Blink<A>,
+    }
+}
+
+impl<A> Deref for BlinkGuard<'_, A>
+where
+    A: BlinkAllocator,
+{
+    type Target = Blink<A>;
+
+    #[inline(always)]
+    fn deref(&self) -> &Blink<A> {
+        self.blink
+    }
+}
+
+impl<A> DerefMut for BlinkGuard<'_, A>
+where
+    A: BlinkAllocator,
+{
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Blink<A> {
+        self.blink
+    }
+}
+
+impl<A> Drop for BlinkGuard<'_, A>
+where
+    A: BlinkAllocator,
+{
+    #[inline(always)]
+    fn drop(&mut self) {
+        self.blink.reset();
+    }
+}
+
+/// [`Blink`] allocates memory from associated allocator.
+/// This allows passing [`Blink`] reference directly to collections.
+///
+/// # Example
+///
+/// ```
+/// # #![cfg_attr(feature = "nightly", feature(allocator_api))]
+/// # #[cfg(feature = "alloc")] fn main() {
+/// # use blink_alloc::Blink;
+/// # #[cfg(feature = "nightly")]
+/// # use std::vec::Vec;
+/// # #[cfg(not(feature = "nightly"))]
+/// # use allocator_api2::vec::Vec;
+/// let mut blink = Blink::new();
+/// let x = blink.put(42);
+/// let mut vec = Vec::new_in(&blink);
+/// vec.push(*x);
+/// assert_eq!(vec, [42]);
+/// drop(vec);
+/// blink.reset();
+/// # }
+/// # #[cfg(not(feature = "alloc"))] fn main() {}
+/// ```
+unsafe impl<A> Allocator for Blink<A>
+where
+    A: Allocator,
+{
+    #[inline(always)]
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.alloc.allocate(layout)
+    }
+
+    #[inline(always)]
+    fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.alloc.allocate_zeroed(layout)
+    }
+
+    #[inline(always)]
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.alloc.deallocate(ptr, layout)
+    }
+
+    #[inline(always)]
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        self.alloc.grow(ptr, old_layout, new_layout)
+    }
+
+    #[inline(always)]
+    unsafe fn grow_zeroed(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        self.alloc.grow_zeroed(ptr, old_layout, new_layout)
+    }
+
+    #[inline(always)]
+    unsafe fn shrink(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        self.alloc.shrink(ptr, old_layout, new_layout)
     }
 }
 
+#[cfg(feature = "nightly")]
+impl_api2_allocator!(Blink<A>);
+
 /// Wrapper for [`Blink`] that implements [`Send`].
 ///
-/// Normally it is impossible to send [`Blink`] to another thread
-/// due to the fact that it will drop non-sendable types on reset.
+/// [`Blink`] itself is [`Send`] since only [`Send`] values are dropped on reset.
+/// Values emplaced with [`Blink::emplace_unchecked`] may not be.
 ///
 /// This wrapper resets [`Blink`] on construction and thus safe to send.
 ///
@@ -1396,7 +3777,7 @@ where
 /// # use blink_alloc::{SendBlink, Blink};
 /// let mut blink = Blink::new();
 /// let rc = std::rc::Rc::new(42);
-/// let rc = blink.put(rc);
+/// let rc = unsafe { blink.emplace_unchecked().value(rc) };
 /// assert_eq!(**rc, 42);
 /// let send_blink = SendBlink::new(blink);
 ///
@@ -1430,13 +3811,270 @@ where
     }
 }
 
+switch_alloc_default! {
+    /// Wrapper for [`Blink`] that is not [`Send`].
+    ///
+    /// Methods of [`Blink`] that drop values on reset require them to be [`Send`],
+    /// since [`Blink`] can be reset on another thread.
+    /// [`LocalBlink`] never leaves its thread,
+    /// so its methods accept values that are not [`Send`].
+    /// Other methods of [`Blink`] are available through [`Deref`].
+    ///
+    /// ```compile_fail
+    /// # use blink_alloc::LocalBlink;
+    /// fn assert_send<T: Send>(_: T) {}
+    /// assert_send(LocalBlink::new());
+    /// ```
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::LocalBlink;
+    /// let mut blink = LocalBlink::new();
+    /// let rc = blink.put(std::rc::Rc::new(42));
+    /// assert_eq!(**rc, 42);
+    /// blink.reset();
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    pub struct LocalBlink<A = +BlinkAlloc<Global>> {
+        blink: Blink<A>,
+        marker: PhantomData<*const ()>,
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl LocalBlink<BlinkAlloc<Global>> {
+    /// Creates new [`LocalBlink`] instance with `BlinkAlloc` baked by `Global`
+    /// allocator.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        LocalBlink::new_in(BlinkAlloc::new())
+    }
+}
+
+impl<A> LocalBlink<A> {
+    /// Creates new [`LocalBlink`] instance with provided allocator instance.
+    #[inline(always)]
+    pub const fn new_in(alloc: A) -> Self {
+        LocalBlink {
+            blink: Blink::new_in(alloc),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<A> Default for LocalBlink<A>
+where
+    A: Default,
+{
+    #[inline(always)]
+    fn default() -> Self {
+        LocalBlink::new_in(Default::default())
+    }
+}
+
+impl<A> From<Blink<A>> for LocalBlink<A> {
+    #[inline(always)]
+    fn from(blink: Blink<A>) -> Self {
+        LocalBlink {
+            blink,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<A> Deref for LocalBlink<A> {
+    type Target = Blink<A>;
+
+    #[inline(always)]
+    fn deref(&self) -> &Blink<A> {
+        &self.blink
+    }
+}
+
+impl<A> LocalBlink<A>
+where
+    A: BlinkAllocator,
+{
+    /// Drops all allocated values.
+    /// And resets associated allocator instance.
+    ///
+    /// See [`Blink::reset`].
+    #[inline(always)]
+    pub fn reset(&mut self) {
+        self.blink.reset();
+    }
+
+    /// Returns inner [`Blink`] value.
+    /// Resets it first to drop values that are not [`Send`] on this thread.
+    #[inline(always)]
+    pub fn into_inner(mut self) -> Blink<A> {
+        self.blink.reset();
+        self.blink
+    }
+
+    /// Puts value into this [`LocalBlink`] instance.
+    /// Returns reference to the value.
+    ///
+    /// Same as [`Blink::put`] but value is not required to be [`Send`].
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn put<T: 'static>(&self, value: T) -> &mut T {
+        self.emplace().value(value)
+    }
+
+    /// Puts value into this [`LocalBlink`] instance.
+    /// Returns reference to the value.
+    /// If allocation fails, returns `Err(value)`.
+    ///
+    /// Same as [`Blink::try_put`] but value is not required to be [`Send`].
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn try_put<T: 'static>(&self, value: T) -> Result<&mut T, T> {
+        self.emplace().try_value(value)
+    }
+
+    /// Puts value into this [`LocalBlink`] instance.
+    /// Returns pinned reference to the value.
+    ///
+    /// Same as [`Blink::put_pin`] but value is not required to be [`Send`].
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn put_pin<T: 'static>(&self, value: T) -> Pin<&mut T> {
+        // Safety: value is registered in drop list and is never moved.
+        // Memory is reused only after drop list is reset.
+        unsafe { Pin::new_unchecked(self.put(value)) }
+    }
+
+    /// Puts value into this [`LocalBlink`] instance.
+    /// Returns pinned reference to the value.
+    /// If allocation fails, returns `Err(value)`.
+    ///
+    /// Same as [`Blink::try_put_pin`] but value is not required to be [`Send`].
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn try_put_pin<T: 'static>(&self, value: T) -> Result<Pin<&mut T>, T> {
+        let value = self.try_put(value)?;
+
+        // Safety: value is registered in drop list and is never moved.
+        // Memory is reused only after drop list is reset.
+        Ok(unsafe { Pin::new_unchecked(value) })
+    }
+
+    /// Returns an `Emplace` adaptor that can emplace values into
+    /// the blink allocator.
+    ///
+    /// Same as [`Blink::emplace`] but values are not required to be [`Send`].
+    #[inline(always)]
+    pub fn emplace<T: 'static>(&self) -> Emplace<'_, A, T> {
+        Emplace::new(self.blink.raw(), false)
+    }
+}
+
 #[inline(always)]
 fn never<T>(never: Infallible) -> T {
     match never {}
 }
 
+/// Initializes struct-of-arrays slices with values returned by `f`.
+///
+/// # Safety
+///
+/// `base` must be allocated with layout returned by `S::layout(len)`.
+#[inline(always)]
+unsafe fn soa_init<'a, S>(
+    base: *mut u8,
+    offsets: S::Offsets,
+    len: usize,
+    mut f: impl FnMut(usize) -> S::Elems,
+) -> S
+where
+    S: SoaSlices<'a>,
+{
+    for idx in 0..len {
+        // Values written so far are leaked if `f` panics.
+        S::write(base, offsets, idx, f(idx));
+    }
+    S::slices(base, offsets, len)
+}
+
+/// Returns layout of header followed by `len` values
+/// and offset of the values.
+#[inline(always)]
+fn header_slice_layout<H, T>(len: usize) -> Option<(Layout, usize)> {
+    Layout::new::<H>()
+        .extend(Layout::array::<T>(len).ok()?)
+        .ok()
+}
+
+/// Writes header and values from iterator into memory at `base`.
+///
+/// # Safety
+///
+/// `base` must be allocated with layout returned by
+/// `header_slice_layout::<H, T>(iter.len())`
+/// and `offset` must be returned with it.
+#[inline(always)]
+unsafe fn header_slice_init<'a, H, T>(
+    base: *mut u8,
+    offset: usize,
+    header: H,
+    iter: impl ExactSizeIterator<Item = T>,
+) -> (&'a mut H, &'a mut [T]) {
+    let len = iter.len();
+    let values = base.add(offset).cast::<T>();
+
+    let mut count = 0;
+    for value in iter.take(len) {
+        // Values written so far are leaked if iterator panics.
+        values.add(count).write(value);
+        count += 1;
+    }
+
+    let header_ptr = base.cast::<H>();
+    header_ptr.write(header);
+    (
+        &mut *header_ptr,
+        core::slice::from_raw_parts_mut(values, count),
+    )
+}
+
+/// Returns clone of `value` for all indices but the last one,
+/// that takes `value` itself.
+#[inline(always)]
+fn fill_next<T: Clone>(value: &mut Option<T>, idx: usize, len: usize) -> T {
+    if idx + 1 == len {
+        value.take().unwrap()
+    } else {
+        value.as_ref().unwrap().clone()
+    }
+}
+
 const FASTER_START: usize = 8;
 
+/// Memory size up to which upper bound of iterator size hint is trusted.
+const UPPER_HINT_BYTES: usize = 4096;
+
+/// Returns number of elements to reserve before iteration
+/// when exact size of iterator is unknown.
+///
+/// Upper bound is used only while it fits into [`UPPER_HINT_BYTES`],
+/// so iterators with loose upper bound, like `Filter` over a long range,
+/// don't reserve excessive memory.
+#[inline]
+fn initial_reserve<T>(lower: usize, upper: Option<usize>) -> usize {
+    let limit = UPPER_HINT_BYTES / size_of::<T>().max(1);
+    let upper = upper.map_or(0, |upper| upper.min(limit));
+    match lower.max(upper) {
+        0 => 0,
+        reserve => reserve.max(FASTER_START),
+    }
+}
+
 #[inline]
 fn size_hint_and_one(lower: usize, upper: Option<usize>, count: usize) -> Option<usize> {
     // Upper bound is limited by current size.
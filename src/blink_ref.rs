@@ -0,0 +1,189 @@
+//! This module provides `BlinkRef` handle
+//! that detects use after reset in debug builds.
+
+use core::{fmt, ops::Deref, ptr::NonNull};
+
+#[cfg(all(debug_assertions, feature = "alloc"))]
+use {
+    alloc::sync::Arc,
+    core::{
+        cell::Cell,
+        sync::atomic::{AtomicUsize, Ordering},
+    },
+};
+
+use crate::blink::Blink;
+
+/// Counter of `Blink` resets shared with [`BlinkRef`] handles.
+/// Allocated lazily when the first handle is created.
+#[cfg(all(debug_assertions, feature = "alloc"))]
+pub(crate) struct Generation {
+    counter: Cell<Option<Arc<AtomicUsize>>>,
+}
+
+#[cfg(all(debug_assertions, feature = "alloc"))]
+impl Generation {
+    #[inline(always)]
+    pub(crate) const fn new() -> Self {
+        Generation {
+            counter: Cell::new(None),
+        }
+    }
+
+    /// Returns shared counter and its current value.
+    #[inline]
+    fn snapshot(&self) -> (Arc<AtomicUsize>, usize) {
+        let counter = self
+            .counter
+            .take()
+            .unwrap_or_else(|| Arc::new(AtomicUsize::new(0)));
+        let generation = counter.load(Ordering::Acquire);
+        self.counter.set(Some(counter.clone()));
+        (counter, generation)
+    }
+
+    /// Invalidates all handles created so far.
+    #[inline(always)]
+    pub(crate) fn bump(&mut self) {
+        if let Some(counter) = self.counter.get_mut() {
+            counter.fetch_add(1, Ordering::Release);
+        }
+    }
+}
+
+/// Handle to a value emplaced into [`Blink`]
+/// that is not bound to the lifetime of the borrow.
+///
+/// In debug builds handle records generation of the [`Blink`]
+/// and panics on dereference after [`Blink`] was reset,
+/// values were dropped or [`Blink`] itself was dropped.
+/// This turns use after reset into immediate panic during development.
+///
+/// In release builds handle is a plain pointer
+/// and use after reset is undefined behavior.
+/// Checks require `"alloc"` feature.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "alloc")] fn main() {
+/// use blink_alloc::{Blink, BlinkRef};
+///
+/// let mut blink = Blink::new();
+/// let value = blink.put(42);
+///
+/// // Safety: handle is not used after `blink` is reset.
+/// let handle = unsafe { BlinkRef::new(&blink, value) };
+/// assert_eq!(*handle, 42);
+///
+/// blink.reset();
+/// // `*handle` would panic here in debug builds.
+/// # }
+/// # #[cfg(not(feature = "alloc"))] fn main() {}
+/// ```
+pub struct BlinkRef<T: ?Sized> {
+    ptr: NonNull<T>,
+
+    #[cfg(all(debug_assertions, feature = "alloc"))]
+    counter: Arc<AtomicUsize>,
+
+    #[cfg(all(debug_assertions, feature = "alloc"))]
+    generation: usize,
+}
+
+// Safety: `BlinkRef` is a shared reference to `T`.
+unsafe impl<T> Send for BlinkRef<T> where T: Sync + ?Sized {}
+unsafe impl<T> Sync for BlinkRef<T> where T: Sync + ?Sized {}
+
+impl<T> BlinkRef<T>
+where
+    T: ?Sized,
+{
+    /// Creates handle to the value emplaced into `blink`.
+    ///
+    /// # Safety
+    ///
+    /// `value` must be emplaced into `blink`.
+    /// Handle must not be dereferenced after `blink` is reset,
+    /// its values are dropped or `blink` is dropped.
+    /// Debug builds check this and panic.
+    #[inline(always)]
+    pub unsafe fn new<A>(blink: &Blink<A>, value: &T) -> Self {
+        #[cfg(all(debug_assertions, feature = "alloc"))]
+        let (counter, generation) = blink.generation().snapshot();
+
+        #[cfg(not(all(debug_assertions, feature = "alloc")))]
+        let _ = blink;
+
+        BlinkRef {
+            ptr: NonNull::from(value),
+            #[cfg(all(debug_assertions, feature = "alloc"))]
+            counter,
+            #[cfg(all(debug_assertions, feature = "alloc"))]
+            generation,
+        }
+    }
+
+    /// Panics if the `Blink` was reset since handle creation.
+    #[inline(always)]
+    fn check(&self) {
+        #[cfg(all(debug_assertions, feature = "alloc"))]
+        assert_eq!(
+            self.counter.load(Ordering::Acquire),
+            self.generation,
+            "`BlinkRef` is used after `Blink` was reset"
+        );
+    }
+}
+
+impl<T> Clone for BlinkRef<T>
+where
+    T: ?Sized,
+{
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        BlinkRef {
+            ptr: self.ptr,
+            #[cfg(all(debug_assertions, feature = "alloc"))]
+            counter: self.counter.clone(),
+            #[cfg(all(debug_assertions, feature = "alloc"))]
+            generation: self.generation,
+        }
+    }
+}
+
+impl<T> Deref for BlinkRef<T>
+where
+    T: ?Sized,
+{
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        self.check();
+
+        // Safety: value is alive until `Blink` is reset.
+        // Creator of the handle guarantees it is not used after that.
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+impl<T> fmt::Debug for BlinkRef<T>
+where
+    T: fmt::Debug + ?Sized,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        T::fmt(self, f)
+    }
+}
+
+impl<T> fmt::Display for BlinkRef<T>
+where
+    T: fmt::Display + ?Sized,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        T::fmt(self, f)
+    }
+}
@@ -0,0 +1,221 @@
+//! This module provides owning pointer to the value
+//! placed into blink allocator.
+
+use core::{
+    fmt,
+    future::Future,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    pin::Pin,
+    ptr::NonNull,
+    sync::atomic::{AtomicUsize, Ordering},
+    task::{Context, Poll},
+};
+
+/// Owning pointer to the value placed into blink allocator.
+///
+/// Created with [`Blink::boxed`](crate::Blink::boxed)
+/// or [`Blink::box_future`](crate::Blink::box_future).
+/// Value is dropped when [`BlinkBox`] is dropped,
+/// while memory is reclaimed when allocator is reset.
+///
+/// Value is never moved, so [`BlinkBox`] can be pinned
+/// with [`BlinkBox::into_pin`].
+/// Pinned value must be dropped before memory is reused,
+/// thus [`BlinkBox`] must not be leaked.
+/// [`Blink::reset`](crate::Blink::reset) panics if any [`BlinkBox`] is leaked,
+/// and dropping the [`Blink`](crate::Blink) aborts the process,
+/// so memory of leaked value is never reused.
+pub struct BlinkBox<'a, T: ?Sized> {
+    ptr: NonNull<T>,
+
+    /// Counter of live boxes in the owning `Blink`.
+    live: &'a AtomicUsize,
+
+    marker: PhantomData<(&'a (), T)>,
+}
+
+// Safety: `BlinkBox` owns the value.
+unsafe impl<T> Send for BlinkBox<'_, T> where T: Send + ?Sized {}
+unsafe impl<T> Sync for BlinkBox<'_, T> where T: Sync + ?Sized {}
+
+// Pointer is stable even when `BlinkBox` is moved.
+impl<T> Unpin for BlinkBox<'_, T> where T: ?Sized {}
+
+impl<'a, T> BlinkBox<'a, T>
+where
+    T: ?Sized,
+{
+    /// Creates box from pointer to initialized value.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to initialized value not owned by anything else.
+    /// Memory must be valid for `'a`
+    /// and must not be reused while `live` is not zero.
+    #[inline(always)]
+    pub(crate) unsafe fn from_raw(ptr: NonNull<T>, live: &'a AtomicUsize) -> Self {
+        live.fetch_add(1, Ordering::Relaxed);
+        BlinkBox {
+            ptr,
+            live,
+            marker: PhantomData,
+        }
+    }
+
+    /// Converts pointer to the value into pointer to unsized value.
+    #[inline(always)]
+    pub(crate) fn map_ptr<U>(
+        this: Self,
+        f: impl FnOnce(NonNull<T>) -> NonNull<U>,
+    ) -> BlinkBox<'a, U>
+    where
+        U: ?Sized,
+    {
+        let this = core::mem::ManuallyDrop::new(this);
+        BlinkBox {
+            ptr: f(this.ptr),
+            live: this.live,
+            marker: PhantomData,
+        }
+    }
+
+    /// Converts [`BlinkBox`] into pinned [`BlinkBox`].
+    #[inline(always)]
+    pub fn into_pin(this: Self) -> Pin<Self> {
+        // Safety: value is never moved out of the `BlinkBox`
+        // and memory is not reused while the box is alive, even if it is leaked.
+        unsafe { Pin::new_unchecked(this) }
+    }
+}
+
+impl<T> BlinkBox<'_, T> {
+    /// Moves value out of the [`BlinkBox`].
+    ///
+    /// Values placed with [`Blink::boxed`](crate::Blink::boxed)
+    /// are not registered for drop on reset,
+    /// so they can be taken out of the arena when they
+    /// turn out to be needed after reset.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::{Blink, BlinkBox};
+    ///
+    /// let mut blink = Blink::new();
+    /// let result = blink.boxed(String::from("keep me"));
+    /// let result = BlinkBox::into_inner(result);
+    /// blink.reset();
+    /// assert_eq!(result, "keep me");
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline(always)]
+    pub fn into_inner(this: Self) -> T {
+        let this = core::mem::ManuallyDrop::new(this);
+        this.live.fetch_sub(1, Ordering::Release);
+
+        // Safety: value is owned by this box
+        // and box is not used after the value is moved out.
+        unsafe { this.ptr.as_ptr().read() }
+    }
+}
+
+impl<T> Drop for BlinkBox<'_, T>
+where
+    T: ?Sized,
+{
+    #[inline]
+    fn drop(&mut self) {
+        // Safety: value is owned by this box.
+        // Memory itself is owned by the allocator.
+        unsafe { core::ptr::drop_in_place(self.ptr.as_ptr()) }
+        self.live.fetch_sub(1, Ordering::Release);
+    }
+}
+
+impl<T> Deref for BlinkBox<'_, T>
+where
+    T: ?Sized,
+{
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        // Safety: value is owned by this box.
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+impl<T> DerefMut for BlinkBox<'_, T>
+where
+    T: ?Sized,
+{
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        // Safety: value is owned by this box.
+        unsafe { self.ptr.as_mut() }
+    }
+}
+
+impl<T> Future for BlinkBox<'_, T>
+where
+    T: Future + Unpin + ?Sized,
+{
+    type Output = T::Output;
+
+    #[inline(always)]
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T::Output> {
+        T::poll(Pin::new(&mut **self), cx)
+    }
+}
+
+impl<T> fmt::Debug for BlinkBox<'_, T>
+where
+    T: fmt::Debug + ?Sized,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        T::fmt(self, f)
+    }
+}
+
+impl<T> fmt::Display for BlinkBox<'_, T>
+where
+    T: fmt::Display + ?Sized,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        T::fmt(self, f)
+    }
+}
+
+/// Aborts the process when [`Blink`](crate::Blink) is dropped
+/// while some [`BlinkBox`] is leaked.
+///
+/// Unwinding would free memory of the value that may be pinned.
+#[cold]
+#[inline(never)]
+pub(crate) fn abort_leaked() -> ! {
+    #[cfg(feature = "std")]
+    {
+        std::eprintln!("Blink is dropped while `BlinkBox` handles are leaked");
+        std::process::abort()
+    }
+
+    #[cfg(not(feature = "std"))]
+    {
+        // Panic while panicking aborts.
+        struct Abort;
+
+        impl Drop for Abort {
+            fn drop(&mut self) {
+                panic!("Blink is dropped while `BlinkBox` handles are leaked");
+            }
+        }
+
+        let _abort = Abort;
+        panic!("Blink is dropped while `BlinkBox` handles are leaked");
+    }
+}
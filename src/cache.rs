@@ -7,15 +7,15 @@ use core::{
 use alloc::vec::Vec;
 
 #[cfg(feature = "nightly")]
-use alloc::alloc::{Allocator, Global};
+use alloc::alloc::Global;
 #[cfg(not(feature = "nightly"))]
-use allocator_api2::alloc::{Allocator, Global};
+use allocator_api2::alloc::Global;
 
-use parking_lot::RwLock;
+use crate::lock::RwLock;
 
-use crate::local::BlinkAlloc;
+use crate::{local::BlinkAlloc, source::ChunkSource};
 
-struct Inner<A: Allocator> {
+struct Inner<A: ChunkSource> {
     /// Array of [`BlinkAlloc`] instances ready to pop.
     pop_array: Vec<UnsafeCell<ManuallyDrop<BlinkAlloc<A>>>>,
 
@@ -31,7 +31,7 @@ struct Inner<A: Allocator> {
 
 unsafe impl<A> Sync for Inner<A>
 where
-    A: Allocator,
+    A: ChunkSource,
     BlinkAlloc<A>: Send,
 {
 }
@@ -42,13 +42,13 @@ where
 ///
 /// This type is internally synchronized with hybrid
 /// blocking + wait-free algorithm.
-pub struct BlinkAllocCache<A: Allocator = Global> {
+pub struct BlinkAllocCache<A: ChunkSource = Global> {
     inner: RwLock<Inner<A>>,
 }
 
 impl<A> Default for BlinkAllocCache<A>
 where
-    A: Allocator,
+    A: ChunkSource,
 {
     fn default() -> Self {
         Self::new()
@@ -57,7 +57,7 @@ where
 
 impl<A> BlinkAllocCache<A>
 where
-    A: Allocator,
+    A: ChunkSource,
 {
     /// Creates a new empty [`BlinkAllocCache`].
     pub const fn new() -> Self {
@@ -0,0 +1,70 @@
+//! This module provides bulk copy used for copying
+//! and relocating large values in arena memory.
+
+/// Copies larger than this use non-temporal stores
+/// when `"non-temporal"` feature is enabled.
+///
+/// Such copies likely exceed cache size,
+/// so bypassing cache avoids evicting hot data.
+#[cfg(all(feature = "non-temporal", target_arch = "x86_64"))]
+const NON_TEMPORAL_THRESHOLD: usize = 1 << 20;
+
+/// Copies `count` values from `src` to `dst`.
+///
+/// # Safety
+///
+/// Same as for [`core::ptr::copy_nonoverlapping`].
+#[inline(always)]
+pub(crate) unsafe fn copy_bulk<T>(src: *const T, dst: *mut T, count: usize) {
+    #[cfg(all(feature = "non-temporal", target_arch = "x86_64"))]
+    {
+        let size = count * core::mem::size_of::<T>();
+        if size >= NON_TEMPORAL_THRESHOLD {
+            unsafe { copy_non_temporal(src.cast(), dst.cast(), size) };
+            return;
+        }
+    }
+
+    unsafe { core::ptr::copy_nonoverlapping(src, dst, count) }
+}
+
+/// Copies bytes with streaming stores that bypass cache.
+#[cfg(all(feature = "non-temporal", target_arch = "x86_64"))]
+#[cold]
+unsafe fn copy_non_temporal(mut src: *const u8, mut dst: *mut u8, mut len: usize) {
+    use core::arch::x86_64::{__m128i, _mm_loadu_si128, _mm_sfence, _mm_stream_si128};
+
+    // Streaming stores require aligned destination.
+    let head = dst.align_offset(16).min(len);
+    unsafe {
+        core::ptr::copy_nonoverlapping(src, dst, head);
+        src = src.add(head);
+        dst = dst.add(head);
+    }
+    len -= head;
+
+    // Safety: SSE2 is always available on x86_64.
+    // `dst` is aligned to 16 bytes and both ranges hold `len` bytes.
+    unsafe {
+        while len >= 64 {
+            let s = src.cast::<__m128i>();
+            let d = dst.cast::<__m128i>();
+            let a = _mm_loadu_si128(s);
+            let b = _mm_loadu_si128(s.add(1));
+            let c = _mm_loadu_si128(s.add(2));
+            let e = _mm_loadu_si128(s.add(3));
+            _mm_stream_si128(d, a);
+            _mm_stream_si128(d.add(1), b);
+            _mm_stream_si128(d.add(2), c);
+            _mm_stream_si128(d.add(3), e);
+            src = src.add(64);
+            dst = dst.add(64);
+            len -= 64;
+        }
+
+        // Make streaming stores visible before following regular stores.
+        _mm_sfence();
+
+        core::ptr::copy_nonoverlapping(src, dst, len);
+    }
+}
@@ -0,0 +1,273 @@
+//! This module provides headers written in front of every allocation
+//! with `"debug-headers"` feature to detect misuse of allocators.
+//!
+//! Without the feature all functions forward to the allocator directly.
+
+use core::{alloc::Layout, ptr::NonNull};
+
+#[cfg(feature = "nightly")]
+use core::alloc::AllocError;
+
+#[cfg(not(feature = "nightly"))]
+use allocator_api2::alloc::AllocError;
+
+#[cfg(feature = "debug-headers")]
+use core::mem::{align_of, size_of};
+
+#[cfg(feature = "debug-headers")]
+use crate::arena::pad_layout;
+
+/// Value of `magic` field of live allocation.
+#[cfg(feature = "debug-headers")]
+const MAGIC_ALIVE: usize = 0xB11C_A110_C8ED_0001_u64 as usize;
+
+/// Value of `magic` field of deallocated memory.
+#[cfg(feature = "debug-headers")]
+const MAGIC_FREED: usize = 0xB11C_F8EE_D000_0002_u64 as usize;
+
+/// Header placed right before memory returned to the user.
+#[cfg(feature = "debug-headers")]
+#[repr(C)]
+struct Header {
+    size: usize,
+    align: usize,
+    magic: usize,
+}
+
+/// Returns layout of allocation with header
+/// and offset of user memory in it.
+#[cfg(feature = "debug-headers")]
+#[inline]
+fn outer_layout(layout: Layout) -> Result<(Layout, usize), AllocError> {
+    let align = layout.align().max(align_of::<Header>());
+    let offset = size_of::<Header>()
+        .checked_add(align - 1)
+        .ok_or(AllocError)?
+        & !(align - 1);
+    let size = offset.checked_add(layout.size()).ok_or(AllocError)?;
+    let outer = Layout::from_size_align(size, align).map_err(|_| AllocError)?;
+    Ok((outer, offset))
+}
+
+/// Returns pointer to header of the allocation.
+#[cfg(feature = "debug-headers")]
+#[inline(always)]
+fn header_ptr(ptr: NonNull<u8>) -> *mut Header {
+    ptr.as_ptr().wrapping_sub(size_of::<Header>()).cast()
+}
+
+/// Validates header of the allocation and returns
+/// pointer to the start of the allocation and its size.
+/// Panics if header doesn't match.
+#[cfg(feature = "debug-headers")]
+#[inline(never)]
+unsafe fn check(
+    ptr: NonNull<u8>,
+    size: usize,
+    align: Option<usize>,
+    min_align: usize,
+    op: &str,
+) -> (NonNull<u8>, usize) {
+    // Safety: pointers from this allocator are preceded by header.
+    // Foreign pointers are not, reading garbage is the best effort here.
+    let header = unsafe { header_ptr(ptr).read_unaligned() };
+
+    match header.magic {
+        MAGIC_ALIVE => {}
+        MAGIC_FREED => panic!("{} of memory at {:p} that was already deallocated", op, ptr),
+        _ => panic!(
+            "{} of pointer {:p} that was not allocated by this allocator",
+            op, ptr
+        ),
+    }
+
+    // Any size that fits the allocation is accepted,
+    // from requested size up to usable size padded to alignment.
+    let usable_align = header.align.max(min_align);
+    let padded = size
+        .checked_add(usable_align - 1)
+        .map(|size| size & !(usable_align - 1));
+    assert!(
+        size <= header.size && padded == Some(header.size),
+        "{} of memory at {:p} with size {} that was allocated with size {}",
+        op,
+        ptr,
+        size,
+        header.size
+    );
+
+    if let Some(align) = align {
+        assert_eq!(
+            header.align, align,
+            "{} of memory at {:p} with align {} that was allocated with align {}",
+            op, ptr, align, header.align
+        );
+    }
+
+    let layout = Layout::from_size_align(header.size, usable_align).unwrap();
+    let (outer, offset) = outer_layout(layout).unwrap();
+
+    // Safety: `ptr` is `offset` bytes into the allocation.
+    let base = unsafe { NonNull::new_unchecked(ptr.as_ptr().sub(offset)) };
+    (base, outer.pad_to_align().size())
+}
+
+/// Allocates memory for `layout` using `alloc`.
+#[cfg(not(feature = "debug-headers"))]
+#[inline(always)]
+pub(crate) fn allocate(
+    layout: Layout,
+    min_align: usize,
+    alloc: impl FnOnce(Layout) -> Result<NonNull<[u8]>, AllocError>,
+) -> Result<NonNull<[u8]>, AllocError> {
+    let _ = min_align;
+    alloc(layout)
+}
+
+/// Allocates memory for `layout` with header in front of it using `alloc`.
+/// User memory is aligned to at least `min_align` of the allocator.
+#[cfg(feature = "debug-headers")]
+#[inline]
+pub(crate) fn allocate(
+    layout: Layout,
+    min_align: usize,
+    alloc: impl FnOnce(Layout) -> Result<NonNull<[u8]>, AllocError>,
+) -> Result<NonNull<[u8]>, AllocError> {
+    // Usable memory is padded to alignment, same as without headers.
+    let usable = pad_layout(layout, min_align)?;
+    let (outer, offset) = outer_layout(usable)?;
+    let block = alloc(outer)?;
+
+    // Safety: `block` fits `outer` layout,
+    // header and user memory are within it.
+    unsafe {
+        let ptr = NonNull::new_unchecked(block.as_ptr().cast::<u8>().add(offset));
+        header_ptr(ptr).write(Header {
+            size: usable.size(),
+            align: layout.align(),
+            magic: MAGIC_ALIVE,
+        });
+        Ok(NonNull::slice_from_raw_parts(ptr, usable.size()))
+    }
+}
+
+/// Resizes memory allocation using `resize`.
+///
+/// # Safety
+///
+/// Same as for [`Allocator::grow`](allocator_api2::alloc::Allocator::grow).
+#[cfg(not(feature = "debug-headers"))]
+#[inline(always)]
+pub(crate) unsafe fn resize(
+    ptr: NonNull<u8>,
+    old_layout: Layout,
+    new_layout: Layout,
+    min_align: usize,
+    alloc: impl FnOnce(Layout) -> Result<NonNull<[u8]>, AllocError>,
+    resize: impl FnOnce(NonNull<u8>, Layout, Layout) -> Result<NonNull<[u8]>, AllocError>,
+    dealloc: impl FnOnce(NonNull<u8>, usize),
+) -> Result<NonNull<[u8]>, AllocError> {
+    let _ = (min_align, alloc, dealloc);
+    resize(ptr, old_layout, new_layout)
+}
+
+/// Validates header of the allocation and moves it
+/// into new allocation with header, using `alloc` and `dealloc`.
+/// Shrinks that keep alignment are done in place using `resize`
+/// and only update the header.
+///
+/// # Safety
+///
+/// Same as for [`Allocator::grow`](allocator_api2::alloc::Allocator::grow).
+#[cfg(feature = "debug-headers")]
+#[inline]
+pub(crate) unsafe fn resize(
+    ptr: NonNull<u8>,
+    old_layout: Layout,
+    new_layout: Layout,
+    min_align: usize,
+    alloc: impl FnOnce(Layout) -> Result<NonNull<[u8]>, AllocError>,
+    resize: impl FnOnce(NonNull<u8>, Layout, Layout) -> Result<NonNull<[u8]>, AllocError>,
+    dealloc: impl FnOnce(NonNull<u8>, usize),
+) -> Result<NonNull<[u8]>, AllocError> {
+    let _ = resize;
+
+    // Safety: `ptr` is claimed to be allocated with `old_layout`.
+    let (base, outer_size) = unsafe {
+        check(
+            ptr,
+            old_layout.size(),
+            Some(old_layout.align()),
+            min_align,
+            "resize",
+        )
+    };
+
+    if new_layout.align() == old_layout.align() && new_layout.size() <= old_layout.size() {
+        let usable = pad_layout(new_layout, min_align)?;
+        let (outer, _) = outer_layout(usable)?;
+        let old_outer =
+            Layout::from_size_align(outer_size, outer.align()).map_err(|_| AllocError)?;
+
+        // Shrinks never move memory, header stays in front of user memory.
+        let block = resize(base, old_outer, outer)?;
+        assert_eq!(block.cast::<u8>(), base, "Shrink moved memory");
+
+        // Safety: header is valid as checked above.
+        unsafe { (*header_ptr(ptr)).size = usable.size() };
+        return Ok(NonNull::slice_from_raw_parts(ptr, usable.size()));
+    }
+
+    let new_ptr = allocate(new_layout, min_align, alloc)?;
+
+    // Safety: both allocations are valid for the copied size
+    // and don't overlap since old one is still allocated.
+    unsafe {
+        core::ptr::copy_nonoverlapping(
+            ptr.as_ptr(),
+            new_ptr.as_ptr().cast::<u8>(),
+            old_layout.size().min(new_layout.size()),
+        );
+        (*header_ptr(ptr)).magic = MAGIC_FREED;
+    }
+    dealloc(base, outer_size);
+    Ok(new_ptr)
+}
+
+/// Deallocates memory using `dealloc`.
+///
+/// # Safety
+///
+/// Same as for [`Allocator::deallocate`](allocator_api2::alloc::Allocator::deallocate).
+#[cfg(not(feature = "debug-headers"))]
+#[inline(always)]
+pub(crate) unsafe fn deallocate(
+    ptr: NonNull<u8>,
+    size: usize,
+    min_align: usize,
+    dealloc: impl FnOnce(NonNull<u8>, usize),
+) {
+    let _ = min_align;
+    dealloc(ptr, size)
+}
+
+/// Validates header of the allocation and deallocates it using `dealloc`.
+///
+/// # Safety
+///
+/// Same as for [`Allocator::deallocate`](allocator_api2::alloc::Allocator::deallocate).
+#[cfg(feature = "debug-headers")]
+#[inline]
+pub(crate) unsafe fn deallocate(
+    ptr: NonNull<u8>,
+    size: usize,
+    min_align: usize,
+    dealloc: impl FnOnce(NonNull<u8>, usize),
+) {
+    // Safety: `ptr` is claimed to be allocated with `size`.
+    let (base, outer_size) = unsafe { check(ptr, size, None, min_align, "deallocate") };
+
+    // Safety: header is valid as checked above.
+    unsafe { (*header_ptr(ptr)).magic = MAGIC_FREED };
+    dealloc(base, outer_size)
+}
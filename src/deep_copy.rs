@@ -0,0 +1,187 @@
+//! This module provides trait for deep copying
+//! of borrowed structures into blink allocator.
+
+use crate::{api::BlinkAllocator, blink::Blink};
+
+/// Values that can be deep-copied into blink allocator.
+///
+/// Copy has the same structure as the original value,
+/// with all referenced data copied into the destination [`Blink`]
+/// and references tied to it.
+/// This allows promoting results built in a short-lived arena
+/// into a longer-lived one before the former is reset.
+///
+/// Implemented for primitives, strings, slices, references,
+/// arrays, tuples and `Option`.
+/// Structures implement it by copying each field.
+///
+/// Copied values are not dropped on reset,
+/// same as with [`Blink::put_no_drop`].
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "alloc")] fn main() {
+/// use blink_alloc::{Blink, BlinkAllocator, BlinkCopy};
+///
+/// #[derive(Clone, Copy)]
+/// struct Node<'a> {
+///     name: &'a str,
+///     children: &'a [Node<'a>],
+/// }
+///
+/// impl<'b> BlinkCopy<'b> for Node<'_> {
+///     type Output = Node<'b>;
+///
+///     fn blink_copy<A: BlinkAllocator>(&self, blink: &'b Blink<A>) -> Node<'b> {
+///         Node {
+///             name: self.name.blink_copy(blink),
+///             children: self.children.blink_copy(blink),
+///         }
+///     }
+/// }
+///
+/// let persistent = Blink::new();
+/// let mut frame = Blink::new();
+///
+/// let leaf = Node { name: frame.copy_str("leaf"), children: &[] };
+/// let root = Node { name: frame.copy_str("root"), children: frame.copy_slice(&[leaf]) };
+///
+/// let root = persistent.deep_copy(&root);
+/// frame.reset();
+///
+/// assert_eq!(root.name, "root");
+/// assert_eq!(root.children[0].name, "leaf");
+/// # }
+/// # #[cfg(not(feature = "alloc"))] fn main() {}
+/// ```
+pub trait BlinkCopy<'b> {
+    /// Type of the copy with references into destination arena.
+    type Output: 'b;
+
+    /// Copies the value with all referenced data into `blink`.
+    fn blink_copy<A: BlinkAllocator>(&self, blink: &'b Blink<A>) -> Self::Output;
+}
+
+macro_rules! impl_by_value {
+    ($($ty:ty),* $(,)?) => {$(
+        impl<'b> BlinkCopy<'b> for $ty {
+            type Output = $ty;
+
+            #[inline(always)]
+            fn blink_copy<A: BlinkAllocator>(&self, _blink: &'b Blink<A>) -> $ty {
+                *self
+            }
+        }
+    )*};
+}
+
+impl_by_value!(
+    (),
+    bool,
+    char,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64,
+);
+
+impl<'b> BlinkCopy<'b> for &str {
+    type Output = &'b str;
+
+    #[inline(always)]
+    fn blink_copy<A: BlinkAllocator>(&self, blink: &'b Blink<A>) -> &'b str {
+        blink.copy_str(self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'b> BlinkCopy<'b> for alloc::string::String {
+    type Output = &'b str;
+
+    #[inline(always)]
+    fn blink_copy<A: BlinkAllocator>(&self, blink: &'b Blink<A>) -> &'b str {
+        blink.copy_str(self)
+    }
+}
+
+impl<'b, T> BlinkCopy<'b> for &[T]
+where
+    T: BlinkCopy<'b>,
+{
+    type Output = &'b [T::Output];
+
+    #[inline]
+    fn blink_copy<A: BlinkAllocator>(&self, blink: &'b Blink<A>) -> &'b [T::Output] {
+        blink.emplace_slice_with_no_drop(self.len(), |idx| self[idx].blink_copy(blink))
+    }
+}
+
+impl<'b, T> BlinkCopy<'b> for &T
+where
+    T: BlinkCopy<'b>,
+{
+    type Output = &'b T::Output;
+
+    #[inline]
+    fn blink_copy<A: BlinkAllocator>(&self, blink: &'b Blink<A>) -> &'b T::Output {
+        blink.put_no_drop((**self).blink_copy(blink))
+    }
+}
+
+impl<'b, T> BlinkCopy<'b> for Option<T>
+where
+    T: BlinkCopy<'b>,
+{
+    type Output = Option<T::Output>;
+
+    #[inline]
+    fn blink_copy<A: BlinkAllocator>(&self, blink: &'b Blink<A>) -> Option<T::Output> {
+        self.as_ref().map(|value| value.blink_copy(blink))
+    }
+}
+
+impl<'b, T, const N: usize> BlinkCopy<'b> for [T; N]
+where
+    T: BlinkCopy<'b>,
+{
+    type Output = [T::Output; N];
+
+    #[inline]
+    fn blink_copy<A: BlinkAllocator>(&self, blink: &'b Blink<A>) -> [T::Output; N] {
+        core::array::from_fn(|idx| self[idx].blink_copy(blink))
+    }
+}
+
+macro_rules! impl_for_tuple {
+    ($($name:ident),+) => {
+        impl<'b, $($name),+> BlinkCopy<'b> for ($($name,)+)
+        where
+            $($name: BlinkCopy<'b>,)+
+        {
+            type Output = ($($name::Output,)+);
+
+            #[inline]
+            #[allow(non_snake_case)]
+            fn blink_copy<A: BlinkAllocator>(&self, blink: &'b Blink<A>) -> Self::Output {
+                let ($($name,)+) = self;
+                ($($name.blink_copy(blink),)+)
+            }
+        }
+    };
+}
+
+impl_for_tuple!(T0);
+impl_for_tuple!(T0, T1);
+impl_for_tuple!(T0, T1, T2);
+impl_for_tuple!(T0, T1, T2, T3);
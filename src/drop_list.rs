@@ -3,11 +3,15 @@
 //!
 
 use core::{
+    alloc::Layout,
     cell::Cell,
-    mem::MaybeUninit,
+    mem::{size_of, MaybeUninit},
     ptr::{self, addr_of_mut, slice_from_raw_parts_mut, NonNull},
 };
 
+#[cfg(feature = "sync-core")]
+use core::sync::atomic::{AtomicPtr, Ordering};
+
 /// Single drop item.
 /// Drops associated value when invoked.
 struct Drops {
@@ -31,6 +35,23 @@ impl Drops {
     }
 }
 
+/// Returns number of items in the list starting at `root`.
+///
+/// # Safety
+///
+/// All items in the list must be valid.
+unsafe fn count_items(mut next: Option<NonNull<Drops>>) -> usize {
+    let mut count = 0;
+    while let Some(item_ptr) = next {
+        count += 1;
+        next = unsafe { item_ptr.as_ref().next };
+    }
+    count
+}
+
+/// Number of bytes taken by drop list bookkeeping of one item.
+pub const DROP_ITEM_OVERHEAD: usize = size_of::<Drops>();
+
 #[repr(C)]
 pub struct DropItem<T: ?Sized> {
     drops: Drops,
@@ -38,6 +59,15 @@ pub struct DropItem<T: ?Sized> {
 }
 
 impl<T> DropItem<T> {
+    /// Offset of the value from the start of the item.
+    #[inline(always)]
+    pub fn value_offset() -> usize {
+        match Layout::new::<Drops>().extend(Layout::new::<T>()) {
+            Ok((_, offset)) => offset,
+            Err(_) => unreachable!("`DropItem<T>` layout is valid"),
+        }
+    }
+
     pub unsafe fn init_value<'a, I>(
         mut ptr: NonNull<DropItem<T>>,
         init: I,
@@ -80,6 +110,10 @@ impl<T> DropItem<[T; 0]> {
 }
 
 /// Intrusive linked list of drop functions.
+///
+/// Items are pushed to the front of the list and dropped from the front,
+/// so values are dropped in reverse order of addition.
+/// `Blink` documents this order as guaranteed.
 pub struct DropList {
     // Root item of the list.
     // Contains `None` if list is empty.
@@ -107,20 +141,142 @@ impl DropList {
         &mut *addr_of_mut!((*item.as_ptr()).value)
     }
 
+    /// Returns number of items in the list.
+    pub fn count(&self) -> usize {
+        // Safety: items are valid until `reset`, that requires exclusive borrow.
+        unsafe { count_items(self.root.get()) }
+    }
+
     /// Drops all items in the list.
+    ///
+    /// Items that are not dropped yet stay in the list,
+    /// so they are still dropped if one of the drops panics.
     pub fn reset(&mut self) {
-        let mut next = self.root.take();
+        while let Some(item_ptr) = self.root.get() {
+            // Safety: `item` is a valid pointer to `DropItem`.
+            // And it didn't move since it was added to the list.
+            unsafe {
+                self.root.set(item_ptr.as_ref().next);
+                Drops::drop(item_ptr);
+            }
+        }
+    }
+}
+
+impl Drop for DropList {
+    #[inline(always)]
+    fn drop(&mut self) {
+        // Owner drops all items before releasing their memory.
+        // Only items left by panicking drop may remain here.
+        self.reset();
+    }
+}
+
+/// Intrusive linked list of drop functions
+/// that can be appended from multiple threads.
+#[cfg(feature = "sync-core")]
+pub struct SyncDropList {
+    // Root item of the list.
+    // Contains null if list is empty.
+    // Lifetime of the items is bound to `SyncDropList::reset` method calls.
+    root: AtomicPtr<Drops>,
+}
+
+#[cfg(feature = "sync-core")]
+impl SyncDropList {
+    pub const fn new() -> Self {
+        SyncDropList {
+            root: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+
+    /// Adds new drop item for given typed pointer.
+    ///
+    /// # Safety
+    ///
+    /// `item` reference must be valid until next call to [`SyncDropList::reset`].
+    /// Value must be safe to drop on the thread that calls [`SyncDropList::reset`].
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn add<'a, 'b: 'a, T: ?Sized>(&'a self, item: &'b mut DropItem<T>) -> &'a mut T {
+        let item = NonNull::from(item);
+        let drops = item.cast::<Drops>().as_ptr();
 
-        while let Some(item_ptr) = next {
+        let mut root = self.root.load(Ordering::Relaxed);
+        loop {
+            (*drops).next = NonNull::new(root);
+            match self
+                .root
+                .compare_exchange_weak(root, drops, Ordering::Release, Ordering::Relaxed)
+            {
+                Ok(_) => break,
+                Err(actual) => root = actual,
+            }
+        }
+
+        &mut *addr_of_mut!((*item.as_ptr()).value)
+    }
+
+    /// Returns number of items in the list.
+    pub fn count(&self) -> usize {
+        // Safety: items are valid until `reset`, that requires exclusive borrow.
+        // Items are fully written before they are published with `Release`.
+        unsafe { count_items(NonNull::new(self.root.load(Ordering::Acquire))) }
+    }
+
+    /// Drops all items in the list.
+    ///
+    /// Items that are not dropped yet stay in the list,
+    /// so they are still dropped if one of the drops panics.
+    pub fn reset(&mut self) {
+        while let Some(item_ptr) = NonNull::new(*self.root.get_mut()) {
             // Safety: `item` is a valid pointer to `DropItem`.
             // And it didn't move since it was added to the list.
+            // Exclusive borrow synchronizes with all `add` calls.
             unsafe {
-                next = Drops::drop(item_ptr);
+                *self.root.get_mut() = item_ptr
+                    .as_ref()
+                    .next
+                    .map_or(ptr::null_mut(), NonNull::as_ptr);
+                Drops::drop(item_ptr);
             }
         }
     }
 }
 
+#[cfg(feature = "sync-core")]
+impl Drop for SyncDropList {
+    #[inline(always)]
+    fn drop(&mut self) {
+        // Owner drops all items before releasing their memory.
+        // Only items left by panicking drop may remain here.
+        self.reset();
+    }
+}
+
+/// Reference to one of drop list kinds.
+#[derive(Clone, Copy)]
+pub enum DropListRef<'a> {
+    Local(&'a DropList),
+    #[cfg(feature = "sync-core")]
+    Sync(&'a SyncDropList),
+}
+
+impl<'a> DropListRef<'a> {
+    /// Adds new drop item for given typed pointer.
+    ///
+    /// # Safety
+    ///
+    /// See [`DropList::add`] and [`SyncDropList::add`].
+    #[inline(always)]
+    pub unsafe fn add<'b: 'a, T: ?Sized>(self, item: &'b mut DropItem<T>) -> &'a mut T {
+        match self {
+            DropListRef::Local(list) => list.add(item),
+            #[cfg(feature = "sync-core")]
+            DropListRef::Sync(list) => list.add(item),
+        }
+    }
+}
+
 /// Type-erased `core::ptr::drop_in_place` wrapper.
 unsafe fn drop_from_item<T>(ptr: NonNull<Drops>, count: usize) {
     let ptr = ptr.cast::<DropItem<T>>();
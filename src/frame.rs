@@ -0,0 +1,272 @@
+//! This module provides frame arenas
+//! that keep allocations alive for several frames.
+
+#[cfg(all(feature = "nightly", feature = "alloc"))]
+use alloc::alloc::Global;
+
+#[cfg(all(not(feature = "nightly"), feature = "alloc"))]
+use allocator_api2::alloc::Global;
+
+#[cfg(feature = "alloc")]
+use crate::local::BlinkAlloc;
+
+use crate::{api::BlinkAllocator, blink::Blink};
+
+// `switch_alloc_default!` can't parse const generics.
+
+/// Ring of frame arenas.
+///
+/// Holds `FRAMES` [`Blink`] instances.
+/// One is current and serves allocations of the current frame,
+/// others keep allocations of previous frames.
+/// [`RingBlink::advance`] starts new frame reusing arena
+/// of the oldest frame.
+///
+/// So memory allocated in frame N stays valid until frame N+`FRAMES`
+/// when it is recycled and values allocated in frame N are dropped.
+/// This fits GPU upload staging where few frames are in flight.
+///
+/// Borrow checker ties references to values to the borrow of [`RingBlink`],
+/// so they can't be used after [`RingBlink::advance`].
+/// Consumers that read older frames data may keep raw pointers
+/// until the frame is recycled.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "alloc")] fn main() {
+/// use core::ptr::NonNull;
+/// use blink_alloc::RingBlink;
+///
+/// let mut frames = RingBlink::<3>::new();
+///
+/// let mut in_flight: Vec<NonNull<[u32]>> = Vec::new();
+/// for frame in 0..10u32 {
+///     if in_flight.len() == 2 {
+///         // Safety: frame N-2 data is valid during frame N with 3 frames ring.
+///         let oldest = unsafe { in_flight.remove(0).as_ref() };
+///         assert_eq!(oldest[0], frame - 2);
+///     }
+///
+///     let data = frames.current().copy_slice(&[frame; 16]);
+///     in_flight.push(NonNull::from(data));
+///
+///     frames.advance();
+/// }
+/// # }
+/// # #[cfg(not(feature = "alloc"))] fn main() {}
+/// ```
+#[cfg(feature = "alloc")]
+pub struct RingBlink<const FRAMES: usize, A = BlinkAlloc<Global>> {
+    blinks: [Blink<A>; FRAMES],
+    current: usize,
+}
+
+/// Ring of frame arenas.
+///
+/// See documentation with `"alloc"` feature enabled.
+#[cfg(not(feature = "alloc"))]
+pub struct RingBlink<const FRAMES: usize, A> {
+    blinks: [Blink<A>; FRAMES],
+    current: usize,
+}
+
+impl<const FRAMES: usize, A> Default for RingBlink<FRAMES, A>
+where
+    A: Default,
+{
+    #[inline]
+    fn default() -> Self {
+        RingBlink::from_blinks(core::array::from_fn(|_| Blink::default()))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const FRAMES: usize> RingBlink<FRAMES, BlinkAlloc<Global>> {
+    /// Creates new ring of frame arenas
+    /// with `BlinkAlloc` baked by `Global` allocator.
+    #[inline]
+    pub fn new() -> Self {
+        RingBlink::from_blinks(core::array::from_fn(|_| Blink::new()))
+    }
+}
+
+impl<const FRAMES: usize, A> RingBlink<FRAMES, A> {
+    /// Creates new ring of frame arenas from [`Blink`] instances.
+    /// First one becomes current.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `FRAMES` is zero.
+    #[inline(always)]
+    pub const fn from_blinks(blinks: [Blink<A>; FRAMES]) -> Self {
+        assert!(FRAMES > 0, "RingBlink requires at least one frame");
+        RingBlink { blinks, current: 0 }
+    }
+
+    /// Returns arena of the current frame.
+    #[inline(always)]
+    pub fn current(&self) -> &Blink<A> {
+        &self.blinks[self.current]
+    }
+
+    /// Returns arena of the current frame.
+    #[inline(always)]
+    pub fn current_mut(&mut self) -> &mut Blink<A> {
+        &mut self.blinks[self.current]
+    }
+
+    /// Returns arena of the frame `age` frames before current.
+    /// Zero age is current frame.
+    ///
+    /// Returns `None` if `age` is not less than `FRAMES`.
+    #[inline(always)]
+    pub fn frame(&self, age: usize) -> Option<&Blink<A>> {
+        if age >= FRAMES {
+            return None;
+        }
+        Some(&self.blinks[(self.current + FRAMES - age) % FRAMES])
+    }
+}
+
+impl<const FRAMES: usize, A> RingBlink<FRAMES, A>
+where
+    A: BlinkAllocator,
+{
+    /// Starts new frame.
+    ///
+    /// Arena of the oldest frame is reset and becomes current.
+    #[inline(always)]
+    pub fn advance(&mut self) {
+        self.current = (self.current + 1) % FRAMES;
+        self.blinks[self.current].reset();
+    }
+
+    /// Resets all arenas.
+    #[inline(always)]
+    pub fn reset(&mut self) {
+        for blink in &mut self.blinks {
+            blink.reset();
+        }
+    }
+}
+
+switch_alloc_default! {
+    /// Double-buffered frame arena.
+    ///
+    /// This is [`RingBlink`] with two frames.
+    ///
+    /// Holds two [`Blink`] instances.
+    /// One is current and serves allocations of the current frame,
+    /// another one keeps allocations of the previous frame.
+    /// [`DoubleBlink::swap`] starts new frame reusing arena
+    /// of the frame before previous.
+    ///
+    /// So memory allocated in frame N stays valid during frame N+1
+    /// and is recycled at the start of frame N+2.
+    /// Values allocated in frame N are dropped at that point.
+    ///
+    /// Borrow checker ties references to values to the borrow of [`DoubleBlink`],
+    /// so they can't be used after [`DoubleBlink::swap`].
+    /// Pipelined consumers that read previous frame data,
+    /// like GPU uploads, may keep raw pointers until the end of the next frame.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use core::ptr::NonNull;
+    /// use blink_alloc::DoubleBlink;
+    ///
+    /// let mut frames = DoubleBlink::new();
+    ///
+    /// let mut in_flight: Option<NonNull<[u32]>> = None;
+    /// for frame in 0..10u32 {
+    ///     if let Some(previous) = in_flight {
+    ///         // Safety: previous frame data is valid during this frame.
+    ///         let previous = unsafe { previous.as_ref() };
+    ///         assert_eq!(previous[0], frame - 1);
+    ///     }
+    ///
+    ///     let data = frames.current().copy_slice(&[frame; 16]);
+    ///     in_flight = Some(NonNull::from(data));
+    ///
+    ///     frames.swap();
+    /// }
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    pub struct DoubleBlink<A = +BlinkAlloc<Global>> {
+        ring: RingBlink<2, A>,
+    }
+}
+
+impl<A> Default for DoubleBlink<A>
+where
+    A: Default,
+{
+    #[inline]
+    fn default() -> Self {
+        DoubleBlink::from_blinks(Blink::default(), Blink::default())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl DoubleBlink<BlinkAlloc<Global>> {
+    /// Creates new double-buffered frame arena
+    /// with `BlinkAlloc` baked by `Global` allocator.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        DoubleBlink::from_blinks(Blink::new(), Blink::new())
+    }
+}
+
+impl<A> DoubleBlink<A> {
+    /// Creates new double-buffered frame arena from two [`Blink`] instances.
+    /// First one becomes current.
+    #[inline(always)]
+    pub const fn from_blinks(first: Blink<A>, second: Blink<A>) -> Self {
+        DoubleBlink {
+            ring: RingBlink::from_blinks([first, second]),
+        }
+    }
+
+    /// Returns arena of the current frame.
+    #[inline(always)]
+    pub fn current(&self) -> &Blink<A> {
+        self.ring.current()
+    }
+
+    /// Returns arena of the current frame.
+    #[inline(always)]
+    pub fn current_mut(&mut self) -> &mut Blink<A> {
+        self.ring.current_mut()
+    }
+
+    /// Returns arena of the previous frame.
+    #[inline(always)]
+    pub fn previous(&self) -> &Blink<A> {
+        &self.ring.blinks[self.ring.current ^ 1]
+    }
+}
+
+impl<A> DoubleBlink<A>
+where
+    A: BlinkAllocator,
+{
+    /// Starts new frame.
+    ///
+    /// Current frame becomes previous,
+    /// and arena of the frame before previous is reset
+    /// and becomes current.
+    #[inline(always)]
+    pub fn swap(&mut self) {
+        self.ring.advance();
+    }
+
+    /// Resets both arenas.
+    #[inline(always)]
+    pub fn reset(&mut self) {
+        self.ring.reset();
+    }
+}
@@ -0,0 +1,244 @@
+//! This module provides read-only sealed blink allocator.
+
+use core::{mem::size_of, ptr::NonNull};
+
+#[cfg(all(feature = "nightly", feature = "alloc"))]
+use alloc::alloc::Global;
+
+#[cfg(all(not(feature = "nightly"), feature = "alloc"))]
+use allocator_api2::alloc::Global;
+
+use crate::{
+    local::BlinkAlloc,
+    snapshot::{lead_padding, padded_len, ArenaOffset, SNAPSHOT_ALIGN},
+    source::ChunkSource,
+};
+
+switch_alloc_default! {
+    /// Sealed [`BlinkAlloc`] created with [`BlinkAlloc::freeze`].
+    ///
+    /// No allocations can be made from [`FrozenBlink`].
+    /// Memory allocated before freezing stays valid until
+    /// [`FrozenBlink`] is dropped or [thawed](FrozenBlink::thaw).
+    ///
+    /// Unlike [`BlinkAlloc`], [`FrozenBlink`] can be shared between threads,
+    /// since its state cannot change anymore.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "std")] fn main() {
+    /// use blink_alloc::BlinkAlloc;
+    /// let blink = BlinkAlloc::new();
+    /// let layout = std::alloc::Layout::new::<[u32; 4]>();
+    /// let ptr = blink.allocate(layout).unwrap().cast::<[u32; 4]>();
+    /// unsafe { ptr.as_ptr().write([1, 2, 3, 4]) };
+    ///
+    /// let frozen = blink.freeze();
+    ///
+    /// // Safety: `ptr` is allocated from `frozen` and initialized.
+    /// let data = unsafe { frozen.get(ptr) };
+    ///
+    /// std::thread::scope(|scope| {
+    ///     scope.spawn(|| assert_eq!(data[0], 1));
+    ///     scope.spawn(|| assert_eq!(data[3], 4));
+    /// });
+    /// # }
+    /// # #[cfg(not(feature = "std"))] fn main() {}
+    /// ```
+    pub struct FrozenBlink<A: ChunkSource = +Global> {
+        blink: BlinkAlloc<A>,
+    }
+}
+
+// Safety: `FrozenBlink` gives no access to the allocator
+// and its state is never modified through shared reference.
+unsafe impl<A> Sync for FrozenBlink<A> where A: ChunkSource + Sync {}
+
+impl<A> FrozenBlink<A>
+where
+    A: ChunkSource,
+{
+    #[inline(always)]
+    pub(crate) fn new(blink: BlinkAlloc<A>) -> Self {
+        FrozenBlink { blink }
+    }
+
+    /// Returns shared reference to the value allocated
+    /// from the allocator before it was frozen.
+    /// Reference is valid as long as [`FrozenBlink`] is borrowed.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to initialized value placed into memory
+    /// allocated from the blink allocator this [`FrozenBlink`] was created from.
+    /// The value must not be mutated while returned reference is alive.
+    #[inline(always)]
+    pub unsafe fn get<T: ?Sized>(&self, ptr: NonNull<T>) -> &T {
+        unsafe { ptr.as_ref() }
+    }
+
+    /// Returns the number of bytes allocated before freezing.
+    ///
+    /// See [`BlinkAlloc::allocated_bytes`].
+    #[inline(always)]
+    pub fn allocated_bytes(&self) -> usize {
+        self.blink.allocated_bytes()
+    }
+
+    /// Returns the total capacity of all chunks.
+    ///
+    /// See [`BlinkAlloc::total_capacity`].
+    #[inline(always)]
+    pub fn total_capacity(&self) -> usize {
+        self.blink.total_capacity()
+    }
+
+    /// Returns snapshot of memory allocated before freezing.
+    ///
+    /// Snapshot starts with chunk map followed by contents of used
+    /// memory of each chunk, from the current chunk to the oldest one.
+    /// All numbers are little-endian `u64`.
+    ///
+    /// ```text
+    /// chunk count
+    /// address and length of each chunk's used memory
+    /// used memory of each chunk, padded with zeros
+    /// ```
+    ///
+    /// Contents of each chunk are padded to keep their
+    /// alignment modulo 8.
+    /// Addresses allow relocating pointers stored in the arena
+    /// when snapshot is loaded elsewhere.
+    /// See [`BlinkSnapshot`] for loading snapshots.
+    ///
+    /// # Safety
+    ///
+    /// Values placed into live allocations must be fully initialized,
+    /// including padding bytes inside them.
+    /// The rest of used memory is kept zeroed by the allocator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if allocator was not created with
+    /// [`with_zeroed_memory`](BlinkAlloc::with_zeroed_memory).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(all(feature = "alloc", not(feature = "debug-headers")))] fn main() {
+    /// use blink_alloc::BlinkAlloc;
+    /// let blink = BlinkAlloc::new().with_zeroed_memory();
+    /// let ptr = blink.allocate(std::alloc::Layout::new::<u64>()).unwrap();
+    /// unsafe { ptr.cast::<u64>().as_ptr().write(42) };
+    ///
+    /// let frozen = blink.freeze();
+    ///
+    /// // Safety: the only allocation is initialized.
+    /// let bytes = unsafe { frozen.as_bytes() };
+    /// assert_eq!(bytes[..8], 1u64.to_le_bytes());
+    /// assert_eq!(bytes[16..24], 8u64.to_le_bytes());
+    /// assert_eq!(bytes[24..], 42u64.to_le_bytes());
+    /// # }
+    /// # #[cfg(not(all(feature = "alloc", not(feature = "debug-headers"))))] fn main() {}
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub unsafe fn as_bytes(&self) -> alloc::vec::Vec<u8> {
+        let mut bytes = alloc::vec::Vec::new();
+        let result: Result<(), core::convert::Infallible> = unsafe {
+            self.dump(|chunk| {
+                bytes.extend_from_slice(chunk);
+                Ok(())
+            })
+        };
+        match result {
+            Ok(()) => bytes,
+            Err(never) => match never {},
+        }
+    }
+
+    /// Writes snapshot of memory allocated before freezing.
+    ///
+    /// See [`FrozenBlink::as_bytes`] for the format.
+    ///
+    /// # Safety
+    ///
+    /// Values placed into live allocations must be fully initialized,
+    /// including padding bytes inside them.
+    /// The rest of used memory is kept zeroed by the allocator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if allocator was not created with
+    /// [`with_zeroed_memory`](BlinkAlloc::with_zeroed_memory).
+    #[cfg(feature = "std")]
+    #[inline]
+    pub unsafe fn write_to(&self, mut write: impl std::io::Write) -> std::io::Result<()> {
+        unsafe { self.dump(|chunk| write.write_all(chunk)) }
+    }
+
+    /// Feeds snapshot to `write` piece by piece.
+    ///
+    /// Safety: live allocations must be fully initialized.
+    #[allow(dead_code)]
+    unsafe fn dump<E>(&self, mut write: impl FnMut(&[u8]) -> Result<(), E>) -> Result<(), E> {
+        assert!(
+            self.blink.zeroes_free_memory(),
+            "Only `BlinkAlloc` created with `with_zeroed_memory` can be dumped"
+        );
+
+        let count = self.blink.used_chunks().count();
+        write(&(count as u64).to_le_bytes())?;
+
+        for (start, len) in self.blink.used_chunks() {
+            write(&(start.as_ptr() as usize as u64).to_le_bytes())?;
+            write(&(len as u64).to_le_bytes())?;
+        }
+
+        let zeros = [0; SNAPSHOT_ALIGN];
+        for (start, len) in self.blink.used_chunks() {
+            let addr = start.as_ptr() as usize;
+            let lead = lead_padding(addr);
+            write(&zeros[..lead])?;
+
+            // Safety: memory is used by allocations made before freezing,
+            // stays valid and is not mutated while `self` is borrowed.
+            // Caller guarantees live allocations are initialized
+            // and the rest of used memory is zeroed.
+            write(unsafe { core::slice::from_raw_parts(start.as_ptr(), len) })?;
+
+            write(&zeros[..padded_len(addr, len) - lead - len])?;
+        }
+        Ok(())
+    }
+
+    /// Returns offset of the value in the snapshot
+    /// dumped with [`FrozenBlink::as_bytes`] or [`FrozenBlink::write_to`].
+    /// Returns `None` if value is not allocated from this arena.
+    ///
+    /// See [`BlinkSnapshot`] for loading snapshots.
+    #[inline]
+    pub fn offset_of<T>(&self, ptr: *const T) -> Option<ArenaOffset<T>> {
+        let addr = ptr as usize;
+        let mut offset = 0;
+        for (start, len) in self.blink.used_chunks() {
+            let start = start.as_ptr() as usize;
+            if start <= addr && addr.checked_add(size_of::<T>())? <= start + len {
+                let offset = offset + lead_padding(start) + (addr - start);
+                return Some(ArenaOffset::new(offset));
+            }
+            offset += padded_len(start, len);
+        }
+        None
+    }
+
+    /// Unseals the allocator, returning original [`BlinkAlloc`].
+    ///
+    /// Since [`FrozenBlink`] is consumed, no references
+    /// to allocated memory obtained via [`FrozenBlink::get`] can be alive.
+    #[inline(always)]
+    pub fn thaw(self) -> BlinkAlloc<A> {
+        self.blink
+    }
+}
@@ -2,5 +2,5 @@
 //!
 
 pub mod local;
-#[cfg(feature = "sync")]
+#[cfg(all(feature = "sync-core", not(loom)))]
 pub mod sync;
@@ -83,13 +83,16 @@ switch_std_default! {
     /// ```
     /// use blink_alloc::GlobalBlinkAlloc;
     ///
+    /// # #[cfg(feature = "std")]
     /// #[global_allocator]
     /// static GLOBAL_ALLOC: GlobalBlinkAlloc = GlobalBlinkAlloc::new();
     ///
+    /// # #[cfg(feature = "std")]
     /// fn main() {
     ///     let _ = Box::new(42);
     ///     let _ = vec![1, 2, 3];
     /// }
+    /// # #[cfg(not(feature = "std"))] fn main() {}
     /// ```
     pub struct GlobalBlinkAlloc<A: Allocator = +std::alloc::System> {
         state: UnsafeCell<State<A>>,
@@ -162,13 +165,16 @@ where
     /// ```
     /// use blink_alloc::GlobalBlinkAlloc;
     ///
+    /// # #[cfg(feature = "std")]
     /// #[global_allocator]
     /// static GLOBAL_ALLOC: GlobalBlinkAlloc<std::alloc::System> = GlobalBlinkAlloc::new_in(std::alloc::System);
     ///
+    /// # #[cfg(feature = "std")]
     /// fn main() {
     ///     let _ = Box::new(42);
     ///     let _ = vec![1, 2, 3];
     /// }
+    /// # #[cfg(not(feature = "std"))] fn main() {}
     /// ```
     pub const fn new_in(allocator: A) -> Self {
         GlobalBlinkAlloc {
@@ -193,13 +199,16 @@ where
     /// ```
     /// use blink_alloc::GlobalBlinkAlloc;
     ///
+    /// # #[cfg(feature = "std")]
     /// #[global_allocator]
     /// static GLOBAL_ALLOC: GlobalBlinkAlloc<std::alloc::System> = GlobalBlinkAlloc::new_in(std::alloc::System);
     ///
+    /// # #[cfg(feature = "std")]
     /// fn main() {
     ///     let _ = Box::new(42);
     ///     let _ = vec![1, 2, 3];
     /// }
+    /// # #[cfg(not(feature = "std"))] fn main() {}
     /// ```
     pub const fn with_chunk_size_in(chunk_size: usize, allocator: A) -> Self {
         GlobalBlinkAlloc {
@@ -318,9 +327,9 @@ where
     /// # use blink_alloc::GlobalBlinkAlloc;
     /// # #[cfg(feature = "nightly")]
     /// # use std::vec::Vec;
-    /// # #[cfg(not(feature = "nightly"))]
+    /// # #[cfg(all(not(feature = "nightly"), feature = "std"))]
     /// # use allocator_api2::vec::Vec;
-    /// # fn main() {
+    /// # #[cfg(feature = "std")] fn main() {
     /// static BLINK: GlobalBlinkAlloc = GlobalBlinkAlloc::new();
     ///
     /// for _ in 0..3 {
@@ -341,6 +350,7 @@ where
     ///     }
     /// }
     /// # }
+    /// # #[cfg(not(feature = "std"))] fn main() {}
     /// ```
     pub fn local(&self) -> LocalBlinkAlloc<'_, A> {
         unsafe { (*self.state.get()).blink.local() }
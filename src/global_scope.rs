@@ -0,0 +1,67 @@
+//! This module provides process-wide [`SyncBlinkAlloc`] instance
+//! that is reset between top-level scopes.
+
+use crate::{lock::Mutex, sync::SyncBlinkAlloc};
+
+/// Process-wide blink-allocator with number of active scopes.
+struct GlobalScopeBlink {
+    active: Mutex<usize>,
+    blink: SyncBlinkAlloc,
+}
+
+static GLOBAL_SCOPE_BLINK: GlobalScopeBlink = GlobalScopeBlink {
+    active: Mutex::new(0),
+    blink: SyncBlinkAlloc::new(),
+};
+
+/// Leaves scope when dropped, even if closure panics.
+struct ScopeGuard;
+
+impl Drop for ScopeGuard {
+    #[inline]
+    fn drop(&mut self) {
+        let mut active = GLOBAL_SCOPE_BLINK.active.lock();
+        *active -= 1;
+        if *active == 0 {
+            // Safety: no scope is active, so no references to the allocator
+            // and memory allocated from it exist.
+            // New scopes can't be entered while lock is held.
+            unsafe { GLOBAL_SCOPE_BLINK.blink.reset_unchecked() }
+        }
+    }
+}
+
+/// Runs closure with process-wide blink-allocator.
+///
+/// Scopes may be entered concurrently from multiple threads and nested.
+/// All of them share the same [`SyncBlinkAlloc`].
+/// When last active scope exits, allocator is reset,
+/// so memory is reused by following top-level scopes.
+///
+/// Reference to the allocator can't escape the closure,
+/// making reset sound.
+///
+/// # Example
+///
+/// ```
+/// # #![cfg_attr(feature = "nightly", feature(allocator_api))]
+/// # #[cfg(feature = "sync")] fn main() {
+/// use blink_alloc::{global_scope, Blink};
+///
+/// for item in 0..3u32 {
+///     let sum = global_scope(|blink| {
+///         let blink = Blink::new_in(blink);
+///         let squares = blink.emplace().from_iter((0..=item).map(|x| x * x));
+///         squares.iter().sum::<u32>()
+///     });
+///     assert_eq!(sum, (0..=item).map(|x| x * x).sum());
+/// }
+/// # }
+/// # #[cfg(not(feature = "sync"))] fn main() {}
+/// ```
+#[inline]
+pub fn global_scope<R>(f: impl FnOnce(&SyncBlinkAlloc) -> R) -> R {
+    *GLOBAL_SCOPE_BLINK.active.lock() += 1;
+    let _guard = ScopeGuard;
+    f(&GLOBAL_SCOPE_BLINK.blink)
+}
@@ -0,0 +1,164 @@
+//! This module provides histogram of allocation sizes
+//! recorded under `"debug-stats"` feature.
+
+use core::cell::Cell;
+
+#[cfg(feature = "sync-core")]
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// Number of size classes.
+/// One for sizes `0..=1` and one for each power of two up to `usize::MAX`.
+const SIZE_CLASSES: usize = usize::BITS as usize + 1;
+
+/// Returns index of the size class for the allocation size.
+#[inline(always)]
+fn size_class(size: usize) -> usize {
+    if size <= 1 {
+        0
+    } else {
+        (usize::BITS - (size - 1).leading_zeros()) as usize
+    }
+}
+
+/// Histogram of allocation sizes with power-of-two size classes.
+///
+/// Size class `n` counts allocations with size in range `2^(n-1)+1..=2^n`,
+/// and size class `0` counts allocations of `0` and `1` bytes.
+///
+/// Retrieved with `size_histogram` method of blink allocators
+/// when `"debug-stats"` feature is enabled.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "alloc")] fn main() {
+/// use blink_alloc::BlinkAlloc;
+/// let blink = BlinkAlloc::new();
+/// blink.allocate(std::alloc::Layout::new::<[u8; 100]>()).unwrap();
+/// blink.allocate(std::alloc::Layout::new::<[u8; 120]>()).unwrap();
+/// blink.allocate(std::alloc::Layout::new::<[u8; 1000]>()).unwrap();
+///
+/// let histogram = blink.size_histogram();
+/// assert_eq!(histogram.count(128), 2);
+/// assert_eq!(histogram.count(1024), 1);
+/// assert_eq!(histogram.total(), 3);
+/// assert_eq!(histogram.max_size_class(), Some(1024));
+/// # }
+/// # #[cfg(not(feature = "alloc"))] fn main() {}
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SizeHistogram {
+    counts: [usize; SIZE_CLASSES],
+}
+
+impl SizeHistogram {
+    /// Returns number of allocations in the size class of `size`.
+    #[inline]
+    pub fn count(&self, size: usize) -> usize {
+        self.counts[size_class(size)]
+    }
+
+    /// Returns total number of recorded allocations.
+    #[inline]
+    pub fn total(&self) -> usize {
+        self.counts.iter().sum()
+    }
+
+    /// Returns upper bound of the largest size class with recorded allocations.
+    #[inline]
+    pub fn max_size_class(&self) -> Option<usize> {
+        let class = self.counts.iter().rposition(|&count| count > 0)?;
+        Some(class_max_size(class))
+    }
+
+    /// Returns iterator over non-empty size classes.
+    /// Yields upper bound of the size class and number of allocations in it.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.counts
+            .iter()
+            .enumerate()
+            .filter(|(_, &count)| count > 0)
+            .map(|(class, &count)| (class_max_size(class), count))
+    }
+}
+
+#[inline(always)]
+fn class_max_size(class: usize) -> usize {
+    1usize.checked_shl(class as u32).unwrap_or(usize::MAX)
+}
+
+/// Histogram recorder for single-threaded allocators.
+pub(crate) struct LocalHistogram {
+    counts: [Cell<usize>; SIZE_CLASSES],
+}
+
+impl LocalHistogram {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const ZERO: Cell<usize> = Cell::new(0);
+
+    #[inline(always)]
+    pub(crate) const fn new() -> Self {
+        LocalHistogram {
+            counts: [Self::ZERO; SIZE_CLASSES],
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn record(&self, size: usize) {
+        let count = &self.counts[size_class(size)];
+        count.set(count.get() + 1);
+    }
+
+    #[inline]
+    pub(crate) fn get(&self) -> SizeHistogram {
+        SizeHistogram {
+            counts: core::array::from_fn(|class| self.counts[class].get()),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn reset(&self) {
+        for count in &self.counts {
+            count.set(0);
+        }
+    }
+}
+
+/// Histogram recorder for multi-threaded allocators.
+#[cfg(feature = "sync-core")]
+pub(crate) struct SyncHistogram {
+    counts: [AtomicUsize; SIZE_CLASSES],
+}
+
+#[cfg(feature = "sync-core")]
+impl SyncHistogram {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const ZERO: AtomicUsize = AtomicUsize::new(0);
+
+    #[inline(always)]
+    pub(crate) const fn new() -> Self {
+        SyncHistogram {
+            counts: [Self::ZERO; SIZE_CLASSES],
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn record(&self, size: usize) {
+        self.counts[size_class(size)].fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub(crate) fn get(&self) -> SizeHistogram {
+        SizeHistogram {
+            counts: core::array::from_fn(|class| self.counts[class].load(Ordering::Relaxed)),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn reset(&self) {
+        for count in &self.counts {
+            count.store(0, Ordering::Relaxed);
+        }
+    }
+}
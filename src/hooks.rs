@@ -0,0 +1,306 @@
+//! This module provides hooks to observe events of blink allocators.
+
+use core::{alloc::Layout, ptr::NonNull};
+
+#[cfg(feature = "nightly")]
+use core::alloc::AllocError;
+
+#[cfg(not(feature = "nightly"))]
+use allocator_api2::alloc::AllocError;
+
+use crate::{source::ChunkSource, stats::BlinkStats};
+
+/// Hooks called by blink allocators on arena events.
+///
+/// All methods have empty default implementation,
+/// so implementors may override only events they are interested in.
+///
+/// Hooks are installed with [`BlinkAlloc::with_hooks`](crate::BlinkAlloc::with_hooks)
+/// or [`SyncBlinkAlloc::with_hooks`](crate::SyncBlinkAlloc::with_hooks).
+/// Allocation fast path is not affected by hooks.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "alloc")] fn main() {
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use blink_alloc::{ArenaHooks, BlinkAlloc};
+///
+/// struct ChunkBytes(AtomicUsize);
+///
+/// impl ArenaHooks for ChunkBytes {
+///     fn on_chunk_allocated(&self, size: usize) {
+///         self.0.fetch_add(size, Ordering::Relaxed);
+///     }
+///
+///     fn on_chunk_freed(&self, size: usize) {
+///         self.0.fetch_sub(size, Ordering::Relaxed);
+///     }
+/// }
+///
+/// static CHUNK_BYTES: ChunkBytes = ChunkBytes(AtomicUsize::new(0));
+///
+/// let mut blink = BlinkAlloc::new().with_hooks(&CHUNK_BYTES);
+/// blink.allocate(std::alloc::Layout::new::<[u8; 1000]>()).unwrap();
+/// assert!(CHUNK_BYTES.0.load(Ordering::Relaxed) >= 1000);
+///
+/// blink.reset_final();
+/// assert_eq!(CHUNK_BYTES.0.load(Ordering::Relaxed), 0);
+/// # }
+/// # #[cfg(not(feature = "alloc"))] fn main() {}
+/// ```
+pub trait ArenaHooks: Sync {
+    /// Called after new chunk of `size` bytes
+    /// is allocated from underlying allocator.
+    #[inline]
+    fn on_chunk_allocated(&self, size: usize) {
+        let _ = size;
+    }
+
+    /// Called before chunk of `size` bytes
+    /// is returned to underlying allocator.
+    #[inline]
+    fn on_chunk_freed(&self, size: usize) {
+        let _ = size;
+    }
+
+    /// Called when allocator is reset.
+    /// Receives number of bytes allocated before reset.
+    #[inline]
+    fn on_reset(&self, allocated_bytes: usize) {
+        let _ = allocated_bytes;
+    }
+
+    /// Called when allocation doesn't fit into current chunk.
+    #[inline]
+    fn on_alloc_slow_path(&self, layout: Layout) {
+        let _ = layout;
+    }
+
+    /// Called when memory for `layout` can't be allocated,
+    /// e.g. when underlying allocator fails.
+    /// Receives statistics of the allocator at the moment of failure.
+    /// Only byte counts are reported by multi-threaded allocators.
+    ///
+    /// Hook may free caches or trim other arenas and return `true`
+    /// to ask allocator to retry once.
+    /// Failure is reported to the caller otherwise.
+    #[inline]
+    fn on_alloc_failed(&self, layout: Layout, stats: &BlinkStats) -> bool {
+        let _ = (layout, stats);
+        false
+    }
+}
+
+/// Receiver of arena events.
+/// Calls installed hooks and emits tracing events.
+#[derive(Clone, Copy)]
+pub(crate) struct Events {
+    pub(crate) hooks: Option<&'static dyn ArenaHooks>,
+    pub(crate) name: Option<&'static str>,
+
+    /// Name of Tracy memory pool.
+    #[cfg(feature = "tracy")]
+    tracy_name: Option<&'static core::ffi::CStr>,
+}
+
+impl Events {
+    #[inline(always)]
+    pub(crate) const fn new() -> Self {
+        Events {
+            hooks: None,
+            name: None,
+            #[cfg(feature = "tracy")]
+            tracy_name: None,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn set_name(&mut self, name: &'static str) {
+        self.name = Some(name);
+
+        #[cfg(feature = "tracy")]
+        {
+            // Tracy identifies memory pools by name pointer.
+            // It must be nul-terminated and live as long as the program.
+            let name = std::ffi::CString::new(name).unwrap_or_default();
+            self.tracy_name = Some(Box::leak(name.into_boxed_c_str()));
+        }
+    }
+
+    #[inline(always)]
+    fn chunk_allocated(&self, ptr: NonNull<u8>, size: usize) {
+        #[cfg(feature = "tracy")]
+        if let Some(name) = self.tracy_name {
+            // Safety: `name` is nul-terminated and never freed.
+            unsafe {
+                tracy_client_sys::___tracy_emit_memory_alloc_named(
+                    ptr.as_ptr().cast(),
+                    size,
+                    name.as_ptr(),
+                );
+            }
+        }
+
+        #[cfg(not(feature = "tracy"))]
+        let _ = ptr;
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            arena = self.name.unwrap_or_default(),
+            size,
+            "chunk allocated"
+        );
+
+        if let Some(hooks) = self.hooks {
+            hooks.on_chunk_allocated(size);
+        }
+    }
+
+    #[inline(always)]
+    fn chunk_freed(&self, ptr: NonNull<u8>, size: usize) {
+        #[cfg(feature = "tracy")]
+        if let Some(name) = self.tracy_name {
+            // Safety: `name` is nul-terminated and never freed.
+            unsafe {
+                tracy_client_sys::___tracy_emit_memory_free_named(
+                    ptr.as_ptr().cast(),
+                    name.as_ptr(),
+                );
+            }
+        }
+
+        #[cfg(not(feature = "tracy"))]
+        let _ = ptr;
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(arena = self.name.unwrap_or_default(), size, "chunk freed");
+
+        if let Some(hooks) = self.hooks {
+            hooks.on_chunk_freed(size);
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn reset(&self, allocated_bytes: impl FnOnce() -> usize) {
+        if self.hooks.is_none() && !cfg!(feature = "tracing") {
+            return;
+        }
+
+        let allocated_bytes = allocated_bytes();
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            arena = self.name.unwrap_or_default(),
+            allocated_bytes,
+            "reset"
+        );
+
+        if let Some(hooks) = self.hooks {
+            hooks.on_reset(allocated_bytes);
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn alloc_slow_path(&self, layout: Layout) {
+        #[cfg(feature = "tracing")]
+        tracing::trace!(
+            arena = self.name.unwrap_or_default(),
+            size = layout.size(),
+            align = layout.align(),
+            "allocation slow path"
+        );
+
+        if let Some(hooks) = self.hooks {
+            hooks.on_alloc_slow_path(layout);
+        }
+    }
+}
+
+impl Events {
+    /// Calls `f` again if it fails and hook asks to retry.
+    #[inline(always)]
+    pub(crate) fn retry_failed<T>(
+        &self,
+        layout: Layout,
+        stats: impl FnOnce() -> BlinkStats,
+        mut f: impl FnMut() -> Result<T, AllocError>,
+    ) -> Result<T, AllocError> {
+        match f() {
+            Err(AllocError) if self.alloc_failed(layout, stats) => f(),
+            result => result,
+        }
+    }
+
+    #[cold]
+    fn alloc_failed(&self, layout: Layout, stats: impl FnOnce() -> BlinkStats) -> bool {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(
+            arena = self.name.unwrap_or_default(),
+            size = layout.size(),
+            align = layout.align(),
+            "allocation failed"
+        );
+
+        match self.hooks {
+            None => false,
+            Some(hooks) => hooks.on_alloc_failed(layout, &stats()),
+        }
+    }
+}
+
+/// Chunk source wrapper that reports chunk allocations as events.
+pub(crate) struct Hooked<'a, A> {
+    allocator: &'a A,
+    events: Events,
+}
+
+impl<'a, A> Hooked<'a, A> {
+    #[inline(always)]
+    pub(crate) fn new(allocator: &'a A, events: Events) -> Self {
+        Hooked { allocator, events }
+    }
+}
+
+unsafe impl<A> ChunkSource for Hooked<'_, A>
+where
+    A: ChunkSource,
+{
+    #[inline(always)]
+    fn allocate_chunk(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let ptr = self.allocator.allocate_chunk(layout)?;
+        self.events.chunk_allocated(ptr.cast(), layout.size());
+        Ok(ptr)
+    }
+
+    #[inline(always)]
+    fn allocate_chunk_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let ptr = self.allocator.allocate_chunk_zeroed(layout)?;
+        self.events.chunk_allocated(ptr.cast(), layout.size());
+        Ok(ptr)
+    }
+
+    #[inline(always)]
+    unsafe fn grow_chunk_in_place(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<usize, AllocError> {
+        let len = unsafe {
+            self.allocator
+                .grow_chunk_in_place(ptr, old_layout, new_layout)?
+        };
+
+        // Reported as replacement of the chunk to keep byte counts balanced.
+        self.events.chunk_freed(ptr, old_layout.size());
+        self.events.chunk_allocated(ptr, len);
+        Ok(len)
+    }
+
+    #[inline(always)]
+    unsafe fn release_chunk(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.events.chunk_freed(ptr, layout.size());
+        unsafe { self.allocator.release_chunk(ptr, layout) }
+    }
+}
@@ -0,0 +1,162 @@
+//! This module provides string interner backed by blink allocator.
+
+use core::{alloc::Layout, cell::RefCell};
+
+use std::collections::HashSet;
+
+#[cfg(feature = "nightly")]
+use core::alloc::AllocError;
+
+#[cfg(not(feature = "nightly"))]
+use allocator_api2::alloc::AllocError;
+
+#[cfg(feature = "nightly")]
+use alloc::alloc::Global;
+
+#[cfg(not(feature = "nightly"))]
+use allocator_api2::alloc::Global;
+
+use crate::{api::BlinkAllocator, local::BlinkAlloc};
+
+#[cfg(not(no_global_oom_handling))]
+use crate::oom::handle_alloc_error;
+
+/// String interner that stores unique strings in blink allocator.
+///
+/// Interning the same string twice returns the same arena-backed `&str`,
+/// so interned strings can be compared by pointer.
+/// Strings are freed all at once when interner is [reset](BlinkInterner::reset).
+///
+/// Uses [`BlinkAlloc`] by default.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "std")] fn main() {
+/// use blink_alloc::BlinkInterner;
+///
+/// let mut interner = BlinkInterner::new();
+/// let a = interner.intern("ident");
+/// let b = interner.intern(&String::from("ident"));
+/// assert!(core::ptr::eq(a, b));
+/// assert_eq!(interner.len(), 1);
+///
+/// interner.reset();
+/// assert!(interner.is_empty());
+/// # }
+/// # #[cfg(not(feature = "std"))] fn main() {}
+/// ```
+pub struct BlinkInterner<A = BlinkAlloc<Global>> {
+    // Strings point into memory of `alloc`.
+    // `'static` is never exposed, strings are returned
+    // with lifetime bound to `self`.
+    set: RefCell<HashSet<&'static str>>,
+    alloc: A,
+}
+
+impl<A> Default for BlinkInterner<A>
+where
+    A: Default,
+{
+    #[inline(always)]
+    fn default() -> Self {
+        BlinkInterner::new_in(A::default())
+    }
+}
+
+impl BlinkInterner<BlinkAlloc<Global>> {
+    /// Creates new interner with `BlinkAlloc` baked by `Global` allocator.
+    #[inline(always)]
+    pub fn new() -> Self {
+        BlinkInterner::new_in(BlinkAlloc::new())
+    }
+}
+
+impl<A> BlinkInterner<A> {
+    /// Creates new interner with provided allocator instance.
+    #[inline(always)]
+    pub fn new_in(alloc: A) -> Self {
+        BlinkInterner {
+            set: RefCell::new(HashSet::new()),
+            alloc,
+        }
+    }
+
+    /// Returns reference to allocator instance.
+    #[inline(always)]
+    pub fn allocator(&self) -> &A {
+        &self.alloc
+    }
+
+    /// Returns number of interned strings.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.set.borrow().len()
+    }
+
+    /// Returns `true` if no strings are interned.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.set.borrow().is_empty()
+    }
+
+    /// Returns interned copy of the string if it was interned before.
+    #[inline]
+    pub fn get(&self, string: &str) -> Option<&str> {
+        self.set.borrow().get(string).copied()
+    }
+}
+
+impl<A> BlinkInterner<A>
+where
+    A: BlinkAllocator,
+{
+    /// Returns interned copy of the string.
+    /// Copies the string into allocator if it is not interned yet.
+    #[cfg(not(no_global_oom_handling))]
+    #[inline]
+    pub fn intern(&self, string: &str) -> &str {
+        match self.try_intern(string) {
+            Ok(interned) => interned,
+            Err(AllocError) => handle_alloc_error(Layout::for_value(string)),
+        }
+    }
+
+    /// Returns interned copy of the string.
+    /// Copies the string into allocator if it is not interned yet.
+    /// If allocation fails, returns `Err(AllocError)`.
+    #[inline]
+    pub fn try_intern(&self, string: &str) -> Result<&str, AllocError> {
+        let mut set = self.set.borrow_mut();
+        if let Some(interned) = set.get(string) {
+            return Ok(interned);
+        }
+
+        let interned: &'static str = if string.is_empty() {
+            ""
+        } else {
+            let ptr = self.alloc.allocate(Layout::for_value(string))?.cast::<u8>();
+
+            // Safety: allocated memory is large enough to hold the string.
+            // Memory stays valid until allocator is reset,
+            // which requires mutable borrow of `self` and clears the set.
+            unsafe {
+                core::ptr::copy_nonoverlapping(string.as_ptr(), ptr.as_ptr(), string.len());
+                core::str::from_utf8_unchecked(core::slice::from_raw_parts(
+                    ptr.as_ptr(),
+                    string.len(),
+                ))
+            }
+        };
+
+        set.insert(interned);
+        Ok(interned)
+    }
+
+    /// Forgets all interned strings and resets associated allocator instance.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.set.get_mut().clear();
+        self.alloc.reset();
+    }
+}
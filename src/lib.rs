@@ -1,6 +1,9 @@
 #![doc = include_str!("../README.md")]
 #![cfg_attr(not(feature = "std"), no_std)]
 #![cfg_attr(feature = "nightly", feature(allocator_api))]
+// Under `loom` multi-threaded allocators are not built,
+// leaving sync arena to model-checking tests.
+#![cfg_attr(loom, allow(dead_code, unused_imports))]
 
 #[cfg(feature = "alloc")]
 extern crate alloc;
@@ -19,7 +22,7 @@ macro_rules! feature_switch {
 macro_rules! with_default {
     ($(#[$meta:meta])* $v:vis struct $name:ident<$($lt:lifetime,)* $($generic:ident $(: $bound:path $(: $bounds:path )*)? $(= +$default:ty)? $(= $default_type:ty)?),+> { $($(#[$fmeta:meta])*  $fvis:vis $fname:ident: $ftype:ty),* $(,)? }) => {
         $(#[$meta])*
-        $v struct $name<$($lt,)* $($generic $(: $bound $(+ $bounds)*)? $(= $default)? $(= $default_type)?)+> {
+        $v struct $name<$($lt,)* $($generic $(: $bound $(+ $bounds)*)? $(= $default)? $(= $default_type)?),+> {
             $($(#[$fmeta])* $fvis $fname: $ftype,)*
         }
     };
@@ -29,7 +32,7 @@ macro_rules! with_default {
 macro_rules! without_default {
     ($(#[$meta:meta])* $v:vis struct $name:ident<$($lt:lifetime,)* $($generic:ident $(: $bound:path $(: $bounds:path )*)? $(= +$default:ty)? $(= $default_type:ty)?),+> { $($(#[$fmeta:meta])* $fvis:vis $fname:ident: $ftype:ty),* $(,)? }) => {
         $(#[$meta])*
-        $v struct $name<$($lt,)* $($generic $(: $bound $(+ $bounds)*)? $(= $default_type)?)+> {
+        $v struct $name<$($lt,)* $($generic $(: $bound $(+ $bounds)*)? $(= $default_type)?),+> {
             $($(#[$fmeta])* $fvis $fname: $ftype,)*
         }
     };
@@ -47,41 +50,246 @@ macro_rules! switch_std_default {
     };
 }
 
+/// Implements [`allocator_api2::alloc::Allocator`] for types
+/// implementing [`core::alloc::Allocator`] by forwarding to it.
+/// So that allocators can be used with both traits under `"nightly"` feature.
+#[cfg(feature = "nightly")]
+macro_rules! impl_api2_allocator {
+    (impl<$($generic:ident),*> $ty:ty where $($bounds:tt)*) => {
+        unsafe impl<$($generic),*> allocator_api2::alloc::Allocator for $ty
+        where
+            $($bounds)*
+        {
+            #[inline(always)]
+            fn allocate(
+                &self,
+                layout: core::alloc::Layout,
+            ) -> Result<core::ptr::NonNull<[u8]>, allocator_api2::alloc::AllocError> {
+                core::alloc::Allocator::allocate(self, layout)
+                    .map_err(|_| allocator_api2::alloc::AllocError)
+            }
+
+            #[inline(always)]
+            fn allocate_zeroed(
+                &self,
+                layout: core::alloc::Layout,
+            ) -> Result<core::ptr::NonNull<[u8]>, allocator_api2::alloc::AllocError> {
+                core::alloc::Allocator::allocate_zeroed(self, layout)
+                    .map_err(|_| allocator_api2::alloc::AllocError)
+            }
+
+            #[inline(always)]
+            unsafe fn deallocate(&self, ptr: core::ptr::NonNull<u8>, layout: core::alloc::Layout) {
+                unsafe { core::alloc::Allocator::deallocate(self, ptr, layout) }
+            }
+
+            #[inline(always)]
+            unsafe fn grow(
+                &self,
+                ptr: core::ptr::NonNull<u8>,
+                old_layout: core::alloc::Layout,
+                new_layout: core::alloc::Layout,
+            ) -> Result<core::ptr::NonNull<[u8]>, allocator_api2::alloc::AllocError> {
+                unsafe { core::alloc::Allocator::grow(self, ptr, old_layout, new_layout) }
+                    .map_err(|_| allocator_api2::alloc::AllocError)
+            }
+
+            #[inline(always)]
+            unsafe fn grow_zeroed(
+                &self,
+                ptr: core::ptr::NonNull<u8>,
+                old_layout: core::alloc::Layout,
+                new_layout: core::alloc::Layout,
+            ) -> Result<core::ptr::NonNull<[u8]>, allocator_api2::alloc::AllocError> {
+                unsafe { core::alloc::Allocator::grow_zeroed(self, ptr, old_layout, new_layout) }
+                    .map_err(|_| allocator_api2::alloc::AllocError)
+            }
+
+            #[inline(always)]
+            unsafe fn shrink(
+                &self,
+                ptr: core::ptr::NonNull<u8>,
+                old_layout: core::alloc::Layout,
+                new_layout: core::alloc::Layout,
+            ) -> Result<core::ptr::NonNull<[u8]>, allocator_api2::alloc::AllocError> {
+                unsafe { core::alloc::Allocator::shrink(self, ptr, old_layout, new_layout) }
+                    .map_err(|_| allocator_api2::alloc::AllocError)
+            }
+        }
+    };
+    ($($ty:ty),* $(,)?) => {$(
+        impl_api2_allocator!(impl<A> $ty where A: core::alloc::Allocator);
+    )*};
+}
+
+mod any;
 mod api;
+mod arc;
 mod arena;
 mod blink;
+mod blink_ref;
+mod boxed;
+mod copy;
+mod debug_header;
 mod drop_list;
+mod frame;
+mod frozen;
 mod global;
+mod hooks;
 mod local;
+mod macros;
+mod object_pool;
+mod pool;
+mod shared;
+mod snapshot;
+mod soa;
+mod source;
+mod stats;
+mod typed;
+mod write;
+
+pub mod testing;
+
+#[cfg(feature = "debug-stats")]
+mod histogram;
+
+#[cfg(any(feature = "debug-stats", feature = "tag-quotas"))]
+mod tags;
+
+#[cfg(feature = "alloc")]
+mod map;
+
+#[cfg(feature = "alloc")]
+mod owned;
+
+#[cfg(feature = "alloc")]
+mod owned_slice;
+
+#[cfg(feature = "alloc")]
+mod reset_hooks;
+
+#[cfg(feature = "alloc")]
+mod shared_blink;
+
+#[cfg(feature = "sync-core")]
+mod lock;
+
+#[cfg(all(feature = "sync-core", not(loom)))]
+mod queue;
+
+#[cfg(feature = "std")]
+mod interner;
 
-#[cfg(feature = "sync")]
+#[cfg(feature = "std")]
+mod scratch;
+
+#[cfg(all(feature = "sync", not(loom)))]
+mod global_scope;
+
+#[cfg(all(feature = "sync", not(loom)))]
+mod sharded;
+
+#[cfg(all(feature = "sync-core", not(loom)))]
 mod sync;
 
-#[cfg(all(feature = "sync", feature = "alloc"))]
+#[cfg(all(feature = "sync-core", not(loom)))]
+mod sync_blink;
+
+#[cfg(all(feature = "sync-core", feature = "alloc", not(loom)))]
 mod cache;
 
-#[cfg(test)]
+#[cfg(all(feature = "smallvec", feature = "alloc"))]
+mod small_vec;
+
+#[cfg(all(test, not(loom)))]
 mod tests;
 
+#[cfg(all(test, loom))]
+mod loom_tests;
+
+#[cfg(not(no_global_oom_handling))]
+mod deep_copy;
 #[cfg(not(no_global_oom_handling))]
 mod oom;
 
 pub use self::{
+    any::BlinkAny,
     api::BlinkAllocator,
-    blink::{Blink, Emplace, IteratorExt, SendBlink},
+    arc::ARc,
+    arena::{ChunkUtilization, RetentionPolicy, Utilization},
+    blink::{Blink, BlinkGuard, Emplace, IteratorExt, LocalBlink, SendBlink},
+    blink_ref::BlinkRef,
+    boxed::BlinkBox,
+    frame::{DoubleBlink, RingBlink},
+    frozen::FrozenBlink,
     global::local::UnsafeGlobalBlinkAlloc,
-    local::BlinkAlloc,
+    hooks::ArenaHooks,
+    local::{BlinkAlloc, ResetGuard, SplitRegion},
+    object_pool::BlinkPool,
+    pool::ChunkPool,
+    shared::SharedAlloc,
+    snapshot::{ArenaOffset, BlinkSnapshot},
+    soa::SoaSlices,
+    source::{ChunkSource, StaticBuffer},
+    stats::{BlinkStats, BlinkStatsDiff, ScopeBytes},
+    typed::{TypedBlink, TypedBlinkIter},
+    write::ArenaWrite,
+};
+
+#[cfg(feature = "debug-stats")]
+pub use self::{histogram::SizeHistogram, tags::TagStats};
+
+#[cfg(any(feature = "debug-stats", feature = "tag-quotas"))]
+pub use self::tags::{TagGuard, MAX_TAGS};
+
+#[cfg(feature = "alloc")]
+pub use self::{
+    map::{BlinkMap, BlinkMapIter, BlinkMapIterMut},
+    owned::{BlinkRoot, OwnedBlink},
+    owned_slice::{OwnedSlice, OwnedStr},
+    reset_hooks::{ResetHookId, ResetPhase},
+    shared_blink::SharedBlinkAlloc,
 };
 
-#[cfg(feature = "sync")]
-pub use self::sync::{LocalBlinkAlloc, SyncBlinkAlloc};
+#[cfg(feature = "std")]
+pub use self::{interner::BlinkInterner, scratch::with_scratch};
+
+#[cfg(all(feature = "sync-core", not(loom)))]
+pub use self::queue::{SpscConsumer, SpscProducer, SpscQueue};
+
+#[cfg(all(feature = "sync-core", not(loom)))]
+pub use self::sync::{LocalBlinkAlloc, ScopedBlinkHandle, ScopedSyncBlink, SyncBlinkAlloc};
+
+#[cfg(all(feature = "sync", not(loom)))]
+pub use self::global_scope::global_scope;
+
+#[cfg(all(feature = "sync", not(loom)))]
+pub use self::sharded::ShardedBlinkAlloc;
+
+#[cfg(all(feature = "sync-core", not(loom)))]
+pub use self::sync_blink::SyncBlink;
 
-#[cfg(feature = "sync")]
+#[cfg(all(feature = "sync-core", not(loom)))]
 pub use self::global::sync::GlobalBlinkAlloc;
 
-#[cfg(all(feature = "sync", feature = "alloc"))]
+#[cfg(feature = "sync-core")]
+pub use self::pool::SyncChunkPool;
+
+#[cfg(all(feature = "sync-core", feature = "alloc", not(loom)))]
 pub use self::cache::BlinkAllocCache;
 
+#[cfg(all(feature = "smallvec", feature = "alloc"))]
+pub use self::small_vec::BlinkSmallVec;
+
+#[cfg(not(no_global_oom_handling))]
+pub use self::deep_copy::BlinkCopy;
+
+#[cfg(all(feature = "alloc", not(no_global_oom_handling)))]
+#[doc(hidden)]
+pub mod __private {
+    pub use allocator_api2::{boxed::Box, vec::from_elem_in, vec::Vec};
+}
+
 pub(crate) trait ResultExt<T> {
     fn safe_ok(self) -> T;
 }
@@ -1,7 +1,13 @@
 //! This module provides multi-threaded blink allocator\
 //! with sync resets.
 
-use core::{alloc::Layout, mem::ManuallyDrop, ptr::NonNull};
+use core::{
+    alloc::Layout,
+    cell::Cell,
+    mem::{ManuallyDrop, MaybeUninit},
+    ops::{Deref, DerefMut},
+    ptr::NonNull,
+};
 
 #[cfg(feature = "nightly")]
 use core::alloc::{AllocError, Allocator};
@@ -15,7 +21,28 @@ use alloc::alloc::Global;
 #[cfg(all(not(feature = "nightly"), feature = "alloc"))]
 use allocator_api2::alloc::Global;
 
-use crate::{api::BlinkAllocator, arena::ArenaLocal};
+#[cfg(feature = "debug-stats")]
+use crate::{
+    histogram::{LocalHistogram, SizeHistogram},
+    tags::TagStats,
+};
+
+#[cfg(any(feature = "debug-stats", feature = "tag-quotas"))]
+use crate::tags::{LocalTags, TagGuard};
+
+use crate::{
+    api::BlinkAllocator,
+    arena::{pad_layout, pad_size, ArenaLocal, RetentionPolicy, Utilization},
+    copy::copy_bulk,
+    debug_header,
+    frozen::FrozenBlink,
+    hooks::{ArenaHooks, Events, Hooked},
+    source::{ChunkSource, StaticBuffer},
+    stats::BlinkStats,
+};
+
+#[cfg(all(feature = "alloc", not(no_global_oom_handling)))]
+use crate::oom::handle_alloc_error;
 
 switch_alloc_default! {
     /// Single-threaded blink allocator.
@@ -47,8 +74,8 @@ switch_alloc_default! {
     /// Internally it uses [`Cell`](core::cell::Cell) for interior mutability and requires
     /// that state cannot be changed from another thread.
     ///
-    #[cfg_attr(feature = "sync", doc = "For multi-threaded version see [`SyncBlinkAlloc`](crate::sync::SyncBlinkAlloc).")]
-    #[cfg_attr(not(feature = "sync"), doc = "For multi-threaded version see `SyncBlinkAlloc`.")]
+    #[cfg_attr(feature = "sync-core", doc = "For multi-threaded version see [`SyncBlinkAlloc`](crate::sync::SyncBlinkAlloc).")]
+    #[cfg_attr(not(feature = "sync-core"), doc = "For multi-threaded version see `SyncBlinkAlloc`.")]
     /// Requires `"sync"` feature.
     ///
     /// # Example
@@ -94,29 +121,92 @@ switch_alloc_default! {
     /// # }
     /// # #[cfg(not(feature = "alloc"))] fn main() {}
     /// ```
-    pub struct BlinkAlloc<A: Allocator = +Global> {
+    pub struct BlinkAlloc<A: ChunkSource = +Global> {
         arena: ArenaLocal,
         allocator: A,
+        min_align: usize,
+        events: Events,
+        allocations: Cell<usize>,
+        fast_path_hits: Cell<usize>,
+        slow_path_hits: Cell<usize>,
+        /// Fast and slow path hits at last reset.
+        reset_path_hits: Cell<(usize, usize)>,
+        peak_allocated_bytes: Cell<usize>,
+        #[cfg(feature = "debug-stats")]
+        histogram: LocalHistogram,
+        #[cfg(any(feature = "debug-stats", feature = "tag-quotas"))]
+        tags: LocalTags,
+        #[cfg(debug_assertions)]
+        live: Cell<Option<usize>>,
     }
 }
 
 impl<A> Drop for BlinkAlloc<A>
 where
-    A: Allocator,
+    A: ChunkSource,
 {
     #[inline]
     fn drop(&mut self) {
         // Safety:
         // Same instance is used for all allocations and resets.
         unsafe {
-            self.arena.reset(false, &self.allocator);
+            self.arena
+                .reset(false, &Hooked::new(&self.allocator, self.events));
+        }
+    }
+}
+
+/// Creates new empty allocator with the same configuration
+/// and a clone of the underlying allocator.
+///
+/// Chunk sizes, retention policy, minimal alignment, name and hooks are copied.
+/// Neither memory nor statistics are shared with the original.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "alloc")] fn main() {
+/// use blink_alloc::BlinkAlloc;
+///
+/// let prototype = BlinkAlloc::with_chunk_size_range(256, 4096)
+///     .with_min_align(16)
+///     .with_name("worker");
+///
+/// let workers: Vec<BlinkAlloc> = (0..4).map(|_| prototype.clone()).collect();
+/// assert_eq!(workers[0].name(), Some("worker"));
+/// assert_eq!(workers[0].total_capacity(), 0);
+/// # }
+/// # #[cfg(not(feature = "alloc"))] fn main() {}
+/// ```
+impl<A> Clone for BlinkAlloc<A>
+where
+    A: ChunkSource + Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        BlinkAlloc {
+            min_align: self.min_align,
+            arena: self.arena.empty_like(),
+            allocator: self.allocator.clone(),
+            events: self.events,
+            allocations: Cell::new(0),
+            fast_path_hits: Cell::new(0),
+            slow_path_hits: Cell::new(0),
+            reset_path_hits: Cell::new((0, 0)),
+            peak_allocated_bytes: Cell::new(0),
+            #[cfg(feature = "debug-stats")]
+            histogram: LocalHistogram::new(),
+            #[cfg(any(feature = "debug-stats", feature = "tag-quotas"))]
+            tags: LocalTags::new(),
+            #[cfg(debug_assertions)]
+            live: Cell::new(self.live.get().map(|_| 0)),
         }
     }
 }
 
 impl<A> Default for BlinkAlloc<A>
 where
-    A: Allocator + Default,
+    A: ChunkSource + Default,
 {
     #[inline]
     fn default() -> Self {
@@ -144,253 +234,1778 @@ impl BlinkAlloc<Global> {
     pub const fn with_chunk_size(chunk_size: usize) -> Self {
         BlinkAlloc::with_chunk_size_in(chunk_size, Global)
     }
-}
 
-impl<A> BlinkAlloc<A>
-where
-    A: Allocator,
-{
-    /// Creates new blink allocator that uses provided allocator
+    /// Creates new blink allocator that uses global allocator
     /// to allocate memory chunks.
+    /// With this method you can specify initial chunk size
+    /// and maximum chunk size.
     ///
-    /// See [`BlinkAlloc::new`] for using global allocator.
+    /// See [`BlinkAlloc::with_chunk_size_range_in`] for details.
     #[inline]
-    pub const fn new_in(allocator: A) -> Self {
-        BlinkAlloc {
-            arena: ArenaLocal::new(),
-            allocator,
+    pub const fn with_chunk_size_range(min_chunk_size: usize, max_chunk_size: usize) -> Self {
+        BlinkAlloc::with_chunk_size_range_in(min_chunk_size, max_chunk_size, Global)
+    }
+
+    /// Creates new blink allocator that uses global allocator
+    /// to allocate single chunk with at least `capacity` bytes
+    /// of usable memory up front.
+    ///
+    /// See [`BlinkAlloc::try_with_capacity_exact_in`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Calls [`handle_alloc_error`](alloc::alloc::handle_alloc_error)
+    /// if chunk allocation fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(all(feature = "alloc", not(feature = "debug-headers")))] fn main() {
+    /// use std::alloc::Layout;
+    /// use blink_alloc::BlinkAlloc;
+    ///
+    /// let mut blink = BlinkAlloc::with_capacity_exact(1024);
+    /// let capacity = blink.total_capacity();
+    /// assert!(capacity >= 1024);
+    ///
+    /// blink.allocate(Layout::new::<[u8; 1024]>()).unwrap();
+    /// assert!(blink.allocate(Layout::new::<u8>()).is_err());
+    /// assert_eq!(blink.total_capacity(), capacity);
+    ///
+    /// blink.reset();
+    /// blink.allocate(Layout::new::<[u8; 1024]>()).unwrap();
+    /// # }
+    /// # #[cfg(not(all(feature = "alloc", not(feature = "debug-headers"))))] fn main() {}
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[inline]
+    pub fn with_capacity_exact(capacity: usize) -> Self {
+        match BlinkAlloc::try_with_capacity_exact_in(capacity, Global) {
+            Ok(blink) => blink,
+            Err(AllocError) => handle_alloc_error(
+                Layout::from_size_align(capacity, 1).unwrap_or(Layout::new::<u8>()),
+            ),
         }
     }
+}
 
-    /// Returns reference to the underlying allocator used by this blink allocator.
+#[cfg(feature = "alloc")]
+impl BlinkAlloc<Global> {
+    /// Moves memory chunks of this allocator to `other`.
+    ///
+    /// All chunks except the last one are freed.
+    /// The last chunk becomes current chunk of `other`,
+    /// so capacity built by this allocator is not lost
+    /// when this allocator is no longer needed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::BlinkAlloc;
+    /// let mut stage1 = BlinkAlloc::new();
+    /// stage1.allocate(std::alloc::Layout::new::<[u8; 4000]>()).unwrap();
+    /// let capacity = stage1.total_capacity();
+    ///
+    /// let mut stage2 = BlinkAlloc::new();
+    /// stage1.donate_capacity(&mut stage2);
+    /// assert_eq!(stage1.total_capacity(), 0);
+    /// assert_eq!(stage2.total_capacity(), capacity);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
     #[inline(always)]
-    pub const fn inner(&self) -> &A {
-        &self.allocator
+    pub fn donate_capacity(&mut self, other: &mut Self) {
+        // Safety:
+        // Any `Global` instance can deallocate memory allocated by another.
+        unsafe { self.donate_capacity_unchecked(other) }
     }
+}
 
-    /// Creates new blink allocator that uses global allocator
+impl<A> BlinkAlloc<A>
+where
+    A: ChunkSource,
+{
+    /// Creates new blink allocator that uses provided allocator
     /// to allocate memory chunks.
-    /// With this method you can specify initial chunk size.
+    /// Any [`ChunkSource`] can be used in place of allocator.
     ///
-    /// See [`BlinkAlloc::new_in`] for using custom allocator.
+    /// See [`BlinkAlloc::new`] for using global allocator.
     #[inline]
-    pub const fn with_chunk_size_in(chunk_size: usize, allocator: A) -> Self {
+    pub const fn new_in(allocator: A) -> Self {
         BlinkAlloc {
-            arena: ArenaLocal::with_chunk_size(chunk_size),
+            min_align: 1,
+            arena: ArenaLocal::new(),
             allocator,
+            events: Events::new(),
+            allocations: Cell::new(0),
+            fast_path_hits: Cell::new(0),
+            slow_path_hits: Cell::new(0),
+            reset_path_hits: Cell::new((0, 0)),
+            peak_allocated_bytes: Cell::new(0),
+            #[cfg(feature = "debug-stats")]
+            histogram: LocalHistogram::new(),
+            #[cfg(any(feature = "debug-stats", feature = "tag-quotas"))]
+            tags: LocalTags::new(),
+            #[cfg(debug_assertions)]
+            live: Cell::new(None),
         }
     }
 
-    /// Allocates memory with specified layout from this allocator.
-    /// If needed it will allocate new chunk using underlying allocator.
-    /// If chunk allocation fails, it will return `Err`.
-    #[inline(always)]
-    pub fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
-        // Safety:
-        // Same instance is used for all allocations and resets.
-        if let Some(ptr) = unsafe { self.arena.alloc_fast(layout) } {
-            return Ok(ptr);
-        }
-        unsafe { self.arena.alloc_slow(layout, &self.allocator) }
-    }
-
-    /// Resizes memory allocation.
-    /// Potentially happens in-place.
+    /// Sets policy that selects which chunk is retained on [`reset`](BlinkAlloc::reset).
     ///
-    /// # Safety
+    /// By default the chunk that served the most recent allocations is kept.
     ///
-    /// `ptr` must be a pointer previously returned by [`allocate`](BlinkAlloc::allocate).
-    /// `old_size` must be in range `layout.size()..=slice.len()`
-    /// where `layout` is the layout used in the call to [`allocate`](BlinkAlloc::allocate).
-    /// and `slice` is the slice pointer returned by [`allocate`](BlinkAlloc::allocate).
+    /// # Example
     ///
-    /// On success, the old pointer is invalidated and the new pointer is returned.
-    /// On error old allocation is still valid.
-    #[inline(always)]
-    pub unsafe fn resize(
-        &self,
-        ptr: NonNull<u8>,
-        old_layout: Layout,
-        new_layout: Layout,
-    ) -> Result<NonNull<[u8]>, AllocError> {
-        if let Some(ptr) = unsafe { self.arena.resize_fast(ptr, old_layout, new_layout) } {
-            return Ok(ptr);
-        }
-
-        // Safety:
-        // Same instance is used for all allocations and resets.
-        // `ptr` was allocated by this allocator.
-        unsafe {
-            self.arena
-                .resize_slow(ptr, old_layout, new_layout, &self.allocator)
-        }
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::{BlinkAlloc, RetentionPolicy};
+    /// let mut blink = BlinkAlloc::new().with_retention(RetentionPolicy::KeepBestFit(1024));
+    /// blink.allocate(std::alloc::Layout::new::<[u8; 2000]>()).unwrap();
+    /// blink.reset();
+    /// assert!(blink.total_capacity() >= 1024);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline]
+    pub fn with_retention(mut self, policy: RetentionPolicy) -> Self {
+        self.arena.set_retention(policy);
+        self
     }
 
-    /// Deallocates memory previously allocated from this allocator.
+    /// Serves small allocations with alignment below `usize` alignment
+    /// from separate blocks carved from chunks.
     ///
-    /// This call may not actually free memory.
-    /// All memory is guaranteed to be freed on [`reset`](BlinkAlloc::reset) call.
+    /// Interleaved allocations of bytes and words otherwise misalign
+    /// the cursor and pay alignment padding on every word allocation.
+    /// With size classes enabled the cursor stays aligned
+    /// and bytes are packed together.
     ///
-    /// # Safety
+    /// # Example
     ///
-    /// `ptr` must be a pointer previously returned by [`allocate`](BlinkAlloc::allocate).
-    /// `size` must be in range `layout.size()..=slice.len()`
-    /// where `layout` is the layout used in the call to [`allocate`](BlinkAlloc::allocate).
-    /// and `slice` is the slice pointer returned by [`allocate`](BlinkAlloc::allocate).
-    #[inline(always)]
-    pub unsafe fn deallocate(&self, ptr: NonNull<u8>, size: usize) {
-        // Safety:
-        // `ptr` was allocated by this allocator.
-        unsafe {
-            self.arena.dealloc(ptr, size);
-        }
-    }
-
-    /// Resets this allocator, deallocating all chunks except the last one.
-    /// Last chunk will be reused.
-    /// With steady memory usage after few iterations
-    /// one chunk should be sufficient for all allocations between resets.
-    #[inline(always)]
-    pub fn reset(&mut self) {
-        // Safety:
-        // Same instance is used for all allocations and resets.
-        unsafe {
-            self.arena.reset(true, &self.allocator);
-        }
-    }
-
-    /// Resets this allocator, deallocating all chunks.
-    #[inline(always)]
-    pub fn reset_final(&mut self) {
-        // Safety:
-        // Same instance is used for all allocations and resets.
-        unsafe {
-            self.arena.reset(false, &self.allocator);
-        }
+    /// ```
+    /// # #[cfg(all(feature = "alloc", not(feature = "debug-headers")))] fn main() {
+    /// use std::alloc::Layout;
+    /// use blink_alloc::BlinkAlloc;
+    ///
+    /// let blink = BlinkAlloc::with_chunk_size(4096).with_size_classes();
+    /// let mut words = Vec::new();
+    /// for _ in 0..100 {
+    ///     blink.allocate(Layout::new::<u8>()).unwrap();
+    ///     words.push(blink.allocate(Layout::new::<u64>()).unwrap().cast::<u8>());
+    /// }
+    ///
+    /// // Words are allocated back to back without padding.
+    /// let distance = (words[1].as_ptr() as isize - words[0].as_ptr() as isize).abs();
+    /// assert_eq!(distance, 8);
+    /// # }
+    /// # #[cfg(not(all(feature = "alloc", not(feature = "debug-headers"))))] fn main() {}
+    /// ```
+    #[inline]
+    pub fn with_size_classes(mut self) -> Self {
+        self.arena.set_size_classes(true);
+        self
     }
 
-    /// Resets this allocator, deallocating all chunks except the last one.
-    /// Last chunk will be reused.
-    /// With steady memory usage after few iterations
-    /// one chunk should be sufficient for all allocations between resets.
+    /// Keeps memory that is not used by live allocations zeroed.
     ///
-    /// # Safety
+    /// Chunks are requested zeroed from the underlying allocator
+    /// and memory is zeroed again when allocations are deallocated,
+    /// shrunk or moved and when allocator is reset.
+    /// This makes alignment padding between allocations and unused
+    /// tails of blocks zeroed, so that used memory can be dumped
+    /// after [freezing](BlinkAlloc::freeze).
     ///
-    /// Blink-allocators guarantee that memory can be used while shared
-    /// borrow to the allocator is held, preventing safe `fn reset` call.
+    /// # Example
     ///
-    /// With this method it becomes caller responsibility to ensure
-    /// that allocated memory won't be used after reset.
-    #[inline(always)]
-    pub unsafe fn reset_unchecked(&self) {
-        // Safety:
-        // Same instance is used for all allocations and resets.
-        unsafe {
-            self.arena.reset_unchecked(true, &self.allocator);
-        }
+    /// ```
+    /// # #[cfg(all(feature = "alloc", not(feature = "debug-headers")))] fn main() {
+    /// use std::alloc::Layout;
+    /// use blink_alloc::BlinkAlloc;
+    ///
+    /// let blink = BlinkAlloc::new().with_zeroed_memory();
+    /// let layout = Layout::new::<u64>();
+    /// let ptr = blink.allocate(layout).unwrap().cast::<u64>();
+    /// unsafe {
+    ///     ptr.as_ptr().write(u64::MAX);
+    ///     blink.deallocate(ptr.cast(), layout.size());
+    /// }
+    ///
+    /// let ptr = blink.allocate(layout).unwrap().cast::<u64>();
+    /// assert_eq!(unsafe { ptr.as_ptr().read() }, 0);
+    /// # }
+    /// # #[cfg(not(all(feature = "alloc", not(feature = "debug-headers"))))] fn main() {}
+    /// ```
+    #[inline]
+    pub fn with_zeroed_memory(mut self) -> Self {
+        self.arena.set_zero_free();
+        self
     }
 
-    /// Returns the approximate number of bytes allocated from this allocator.
+    /// Sets minimal alignment of all allocations.
     ///
-    /// This is the sum of the capacity of all previous (fully used) chunks
-    /// plus the bytes used in the current chunk. After warm-up (when
-    /// [`reset`](BlinkAlloc::reset) has been called enough times that a single
-    /// chunk serves all allocations), this value is exact.
+    /// Alignment and size of every allocation are rounded up to `min_align`,
+    /// so no two allocations share a block of `min_align` bytes.
+    /// Set to cache line size, e.g. `64`, for data accessed concurrently
+    /// by multiple threads to avoid false sharing.
     ///
-    /// # Examples
+    /// # Panics
+    ///
+    /// Panics if `min_align` is not a power of two.
+    ///
+    /// # Example
     ///
     /// ```
     /// # #[cfg(feature = "alloc")] fn main() {
     /// use blink_alloc::BlinkAlloc;
-    /// let mut blink = BlinkAlloc::new();
-    /// assert_eq!(blink.allocated_bytes(), 0);
-    /// let layout = std::alloc::Layout::new::<u64>();
-    /// blink.allocate(layout).unwrap();
-    /// assert!(blink.allocated_bytes() >= 8);
-    /// blink.reset();
-    /// assert_eq!(blink.allocated_bytes(), 0);
+    /// let blink = BlinkAlloc::new().with_min_align(64);
+    /// let a = blink.allocate(std::alloc::Layout::new::<u8>()).unwrap();
+    /// let b = blink.allocate(std::alloc::Layout::new::<u8>()).unwrap();
+    /// assert_eq!(a.as_ptr().cast::<u8>() as usize % 64, 0);
+    /// assert_eq!(b.as_ptr().cast::<u8>() as usize % 64, 0);
     /// # }
     /// # #[cfg(not(feature = "alloc"))] fn main() {}
     /// ```
-    pub fn allocated_bytes(&self) -> usize {
-        self.arena.allocated_bytes()
+    #[inline]
+    pub fn with_min_align(mut self, min_align: usize) -> Self {
+        assert!(
+            min_align.is_power_of_two(),
+            "Minimal alignment must be a power of two"
+        );
+        self.min_align = min_align;
+        self
     }
 
-    /// Returns the total capacity of all chunks in this allocator.
+    /// Sets minimal alignment of chunks allocated from underlying allocator.
     ///
-    /// This is the total amount of memory obtained from the underlying
-    /// allocator (excluding chunk headers). After
-    /// [`reset`](BlinkAlloc::reset), only the last chunk is retained, so
-    /// this equals the capacity of that single chunk.
+    /// By default chunks are aligned as chunk header requires.
+    /// Chunks aligned to page size, e.g. `4096`, can be passed
+    /// to `mprotect` or GPU mapping APIs.
+    /// Chunk sizes are rounded up to multiple of the alignment.
     ///
-    /// # Examples
+    /// With either `"header-end"` or `"bump-down"` feature, but not both,
+    /// allocations aligned to chunk alignment are placed without padding
+    /// at the start or end of the chunk respectively.
+    ///
+    /// Chunks allocated before this call are deallocated,
+    /// same as with [`reset_final`](BlinkAlloc::reset_final).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `align` is not a power of two.
+    ///
+    /// # Example
     ///
     /// ```
     /// # #[cfg(feature = "alloc")] fn main() {
     /// use blink_alloc::BlinkAlloc;
-    /// let mut blink = BlinkAlloc::new();
-    /// assert_eq!(blink.total_capacity(), 0);
-    /// let layout = std::alloc::Layout::new::<u64>();
-    /// blink.allocate(layout).unwrap();
-    /// assert!(blink.total_capacity() >= 8);
+    /// let blink = BlinkAlloc::new().with_chunk_align(4096);
+    /// blink.allocate(std::alloc::Layout::new::<u8>()).unwrap();
+    /// let chunk_size = blink.total_capacity() + blink.overhead_bytes();
+    /// assert_eq!(chunk_size % 4096, 0);
     /// # }
     /// # #[cfg(not(feature = "alloc"))] fn main() {}
     /// ```
-    pub fn total_capacity(&self) -> usize {
-        self.arena.total_capacity()
+    #[inline]
+    pub fn with_chunk_align(mut self, align: usize) -> Self {
+        assert!(
+            align.is_power_of_two(),
+            "Chunk alignment must be a power of two"
+        );
+        self.reset_final();
+        self.arena.set_chunk_align(align);
+        self
     }
 
-    /// Unwrap this allocator, returning the underlying allocator.
-    /// Leaks allocated chunks.
+    /// Installs hooks called on arena events.
     ///
-    /// To deallocate all chunks call [`reset_final`](BlinkAlloc::reset_final) first.
-    pub fn into_inner(self) -> A {
-        let me = ManuallyDrop::new(self);
-        unsafe { core::ptr::read(&me.allocator) }
-    }
-}
-
-unsafe impl<A> Allocator for BlinkAlloc<A>
-where
-    A: Allocator,
-{
-    #[inline(always)]
-    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
-        BlinkAlloc::allocate(self, layout)
+    /// See [`ArenaHooks`] for details.
+    #[inline]
+    pub fn with_hooks(mut self, hooks: &'static dyn ArenaHooks) -> Self {
+        self.events.hooks = Some(hooks);
+        self
     }
 
-    #[inline(always)]
-    unsafe fn shrink(
-        &self,
-        ptr: NonNull<u8>,
-        old_layout: Layout,
-        new_layout: Layout,
-    ) -> Result<NonNull<[u8]>, AllocError> {
-        BlinkAlloc::resize(self, ptr, old_layout, new_layout)
+    /// Sets name of this allocator.
+    ///
+    /// Name is reported in tracing events under `"tracing"` feature.
+    /// Under `"tracy"` feature chunks of named allocator are reported
+    /// to Tracy profiler as memory pool with this name.
+    #[inline]
+    pub fn with_name(mut self, name: &'static str) -> Self {
+        self.events.set_name(name);
+        self
     }
 
-    #[inline(always)]
-    unsafe fn grow(
-        &self,
-        ptr: NonNull<u8>,
-        old_layout: Layout,
-        new_layout: Layout,
-    ) -> Result<NonNull<[u8]>, AllocError> {
-        BlinkAlloc::resize(self, ptr, old_layout, new_layout)
+    /// Enables tracking of live allocations in debug builds.
+    ///
+    /// Allocator counts allocations that are not deallocated yet
+    /// and panics if it is reset while any remain.
+    /// This catches collections that were leaked
+    /// or still in use when [`reset_unchecked`](BlinkAlloc::reset_unchecked) is called.
+    ///
+    /// Only suitable for allocators used by collections that deallocate their memory.
+    /// [`Blink`](crate::Blink) never deallocates memory of values it emplaces.
+    ///
+    /// Does nothing when `debug_assertions` are disabled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #![cfg_attr(feature = "nightly", feature(allocator_api))]
+    /// # #[cfg(feature = "alloc")]
+    /// # fn main() {
+    /// # use blink_alloc::BlinkAlloc;
+    /// # #[cfg(feature = "nightly")]
+    /// # use std::vec::Vec;
+    /// # #[cfg(not(feature = "nightly"))]
+    /// # use allocator_api2::vec::Vec;
+    /// let mut blink = BlinkAlloc::new().with_live_check();
+    ///
+    /// let mut vec = Vec::new_in(&blink);
+    /// vec.push(1);
+    /// if let Some(live) = blink.live_allocations() {
+    ///     assert_eq!(live, 1);
+    /// }
+    ///
+    /// // Resetting here would panic.
+    /// drop(vec);
+    /// blink.reset();
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline]
+    pub fn with_live_check(self) -> Self {
+        #[cfg(debug_assertions)]
+        self.live.set(Some(self.live.get().unwrap_or(0)));
+        self
+    }
+
+    /// Returns number of allocations that are not deallocated yet
+    /// if tracking is enabled with [`with_live_check`](BlinkAlloc::with_live_check).
+    ///
+    /// Always returns `None` when `debug_assertions` are disabled.
+    #[inline(always)]
+    pub fn live_allocations(&self) -> Option<usize> {
+        #[cfg(debug_assertions)]
+        {
+            self.live.get()
+        }
+        #[cfg(not(debug_assertions))]
+        {
+            None
+        }
+    }
+
+    /// Counts new live allocation if tracking is enabled.
+    #[inline(always)]
+    fn track_allocate(&self) {
+        #[cfg(debug_assertions)]
+        if let Some(live) = self.live.get() {
+            self.live.set(Some(live + 1));
+        }
+    }
+
+    /// Counts deallocation if tracking is enabled.
+    #[inline(always)]
+    fn track_deallocate(&self) {
+        #[cfg(debug_assertions)]
+        if let Some(live) = self.live.get() {
+            self.live.set(Some(live.saturating_sub(1)));
+        }
+    }
+
+    /// Panics if tracking is enabled and some allocations are still live.
+    #[inline(always)]
+    fn check_live(&self) {
+        #[cfg(debug_assertions)]
+        if let Some(live) = self.live.get() {
+            assert!(
+                live == 0,
+                "BlinkAlloc is reset with {} live allocations",
+                live
+            );
+        }
+    }
+
+    /// Returns name of this allocator if set with [`with_name`](BlinkAlloc::with_name).
+    #[inline(always)]
+    pub fn name(&self) -> Option<&'static str> {
+        self.events.name
+    }
+
+    /// Returns reference to the underlying allocator used by this blink allocator.
+    #[inline(always)]
+    pub const fn inner(&self) -> &A {
+        &self.allocator
+    }
+
+    /// Creates new blink allocator that uses global allocator
+    /// to allocate memory chunks.
+    /// With this method you can specify initial chunk size.
+    ///
+    /// See [`BlinkAlloc::new_in`] for using custom allocator.
+    #[inline]
+    pub const fn with_chunk_size_in(chunk_size: usize, allocator: A) -> Self {
+        BlinkAlloc {
+            min_align: 1,
+            arena: ArenaLocal::with_chunk_size(chunk_size),
+            allocator,
+            events: Events::new(),
+            allocations: Cell::new(0),
+            fast_path_hits: Cell::new(0),
+            slow_path_hits: Cell::new(0),
+            reset_path_hits: Cell::new((0, 0)),
+            peak_allocated_bytes: Cell::new(0),
+            #[cfg(feature = "debug-stats")]
+            histogram: LocalHistogram::new(),
+            #[cfg(any(feature = "debug-stats", feature = "tag-quotas"))]
+            tags: LocalTags::new(),
+            #[cfg(debug_assertions)]
+            live: Cell::new(None),
+        }
+    }
+
+    /// Creates new blink allocator that uses provided allocator
+    /// to allocate memory chunks.
+    /// With this method you can specify initial chunk size
+    /// and maximum chunk size.
+    ///
+    /// Chunk size grows with each new chunk until it reaches `max_chunk_size`,
+    /// after that new chunks are allocated with `max_chunk_size`.
+    /// Allocations that don't fit into chunk of `max_chunk_size`
+    /// get chunks of required size.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::BlinkAlloc;
+    /// let blink = BlinkAlloc::with_chunk_size_range(256, 4096);
+    /// for _ in 0..100 {
+    ///     blink.allocate(std::alloc::Layout::new::<[u8; 1000]>()).unwrap();
+    /// }
+    ///
+    /// // Large allocations are still possible.
+    /// blink.allocate(std::alloc::Layout::new::<[u8; 10000]>()).unwrap();
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline]
+    pub const fn with_chunk_size_range_in(
+        min_chunk_size: usize,
+        max_chunk_size: usize,
+        allocator: A,
+    ) -> Self {
+        BlinkAlloc {
+            min_align: 1,
+            arena: ArenaLocal::with_chunk_size_range(min_chunk_size, max_chunk_size),
+            allocator,
+            events: Events::new(),
+            allocations: Cell::new(0),
+            fast_path_hits: Cell::new(0),
+            slow_path_hits: Cell::new(0),
+            reset_path_hits: Cell::new((0, 0)),
+            peak_allocated_bytes: Cell::new(0),
+            #[cfg(feature = "debug-stats")]
+            histogram: LocalHistogram::new(),
+            #[cfg(any(feature = "debug-stats", feature = "tag-quotas"))]
+            tags: LocalTags::new(),
+            #[cfg(debug_assertions)]
+            live: Cell::new(None),
+        }
+    }
+
+    /// Creates new blink allocator that uses provided allocator
+    /// to allocate single chunk with at least `capacity` bytes
+    /// of usable memory up front.
+    ///
+    /// No more chunks are allocated while this one is held.
+    /// Allocations that don't fit into it return `Err(AllocError)`,
+    /// and [`reset`](BlinkAlloc::reset) keeps the chunk for the next cycle,
+    /// so memory is never requested from `allocator` after construction.
+    /// If the chunk is released, e.g. with [`reset_final`](BlinkAlloc::reset_final),
+    /// next allocation allocates it again.
+    ///
+    /// Returns `Err(AllocError)` if chunk allocation fails.
+    #[inline]
+    pub fn try_with_capacity_exact_in(capacity: usize, allocator: A) -> Result<Self, AllocError> {
+        let mut blink = BlinkAlloc::new_in(allocator);
+        blink.arena = ArenaLocal::with_capacity_exact(capacity);
+
+        // Safety:
+        // Same instance is used for all allocations and resets.
+        unsafe {
+            blink
+                .arena
+                .reserve_chunk(&Hooked::new(&blink.allocator, blink.events))
+        }?;
+        Ok(blink)
+    }
+
+    /// Allocates memory with specified layout from this allocator.
+    /// If needed it will allocate new chunk using underlying allocator.
+    /// If chunk allocation fails, it will return `Err`.
+    ///
+    /// Returned slice covers whole usable region of the allocation,
+    /// with size rounded up to the next alignment boundary.
+    #[inline(always)]
+    pub fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        #[cfg(feature = "debug-stats")]
+        self.histogram.record(layout.size());
+        #[cfg(feature = "tag-quotas")]
+        self.tags.check_quota(layout.size())?;
+        self.allocations.set(self.allocations.get().wrapping_add(1));
+
+        let ptr =
+            debug_header::allocate(layout, self.min_align, |layout| self.allocate_raw(layout))?;
+        self.track_allocate();
+        #[cfg(any(feature = "debug-stats", feature = "tag-quotas"))]
+        self.tags.record(layout.size());
+        Ok(ptr)
+    }
+
+    /// Allocates at least `layout.size()` bytes with specified alignment.
+    /// Returns the whole usable region, which may be larger than requested.
+    ///
+    /// Same as [`allocate`](BlinkAlloc::allocate), provided for symmetry
+    /// with `Allocator::allocate` contract that allows returning more memory.
+    /// Any size in range `layout.size()..=slice.len()` may be used
+    /// to resize or deallocate the memory.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::BlinkAlloc;
+    /// let blink = BlinkAlloc::new();
+    /// let layout = std::alloc::Layout::from_size_align(10, 8).unwrap();
+    /// let ptr = blink.allocate_at_least(layout).unwrap();
+    /// assert!(ptr.len() >= 16);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline(always)]
+    pub fn allocate_at_least(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.allocate(layout)
+    }
+
+    #[inline(always)]
+    fn allocate_raw(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let layout = pad_layout(layout, self.min_align)?;
+
+        // Safety:
+        // Same instance is used for all allocations and resets.
+        if let Some(ptr) = unsafe { self.arena.alloc_fast(layout) } {
+            self.fast_path_hits
+                .set(self.fast_path_hits.get().wrapping_add(1));
+            return Ok(ptr);
+        }
+        self.slow_path_hits
+            .set(self.slow_path_hits.get().wrapping_add(1));
+        self.events.alloc_slow_path(layout);
+        self.events.retry_failed(
+            layout,
+            || self.stats(),
+            || unsafe {
+                self.arena
+                    .alloc_slow(layout, &Hooked::new(&self.allocator, self.events))
+            },
+        )
+    }
+
+    /// Allocates zeroed memory with specified layout from this allocator.
+    /// If needed it will allocate new chunk using underlying allocator.
+    /// If chunk allocation fails, it will return `Err`.
+    ///
+    /// New chunks for zeroed allocations are requested zeroed
+    /// from the underlying allocator, and memory of the chunk
+    /// that was never handed out is not zeroed again.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::BlinkAlloc;
+    /// let blink = BlinkAlloc::new();
+    /// let ptr = blink.allocate_zeroed(std::alloc::Layout::new::<[u8; 64]>()).unwrap();
+    /// assert!(unsafe { ptr.as_ref() }.iter().all(|&b| b == 0));
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline(always)]
+    pub fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        #[cfg(feature = "debug-stats")]
+        self.histogram.record(layout.size());
+        #[cfg(feature = "tag-quotas")]
+        self.tags.check_quota(layout.size())?;
+        self.allocations.set(self.allocations.get().wrapping_add(1));
+
+        let ptr = debug_header::allocate(layout, self.min_align, |layout| {
+            let layout = pad_layout(layout, self.min_align)?;
+
+            // Safety:
+            // Same instance is used for all allocations and resets.
+            self.events.retry_failed(
+                layout,
+                || self.stats(),
+                || unsafe {
+                    self.arena
+                        .alloc_zeroed(layout, &Hooked::new(&self.allocator, self.events))
+                },
+            )
+        })?;
+        self.track_allocate();
+        #[cfg(any(feature = "debug-stats", feature = "tag-quotas"))]
+        self.tags.record(layout.size());
+        Ok(ptr)
+    }
+
+    /// Allocates memory for a value of type `T`.
+    /// Returns reference to uninitialized memory.
+    /// If chunk allocation fails, it will return `Err`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::BlinkAlloc;
+    /// let blink = BlinkAlloc::new();
+    /// let value = blink.allocate_uninit::<u32>().unwrap();
+    /// let value = value.write(42);
+    /// assert_eq!(*value, 42);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn allocate_uninit<T>(&self) -> Result<&mut MaybeUninit<T>, AllocError> {
+        let ptr = self.allocate(Layout::new::<T>())?;
+        // Safety: memory is allocated for `T` and valid until reset.
+        Ok(unsafe { &mut *ptr.as_ptr().cast::<MaybeUninit<T>>() })
+    }
+
+    /// Allocates memory for an array of `len` values of type `T`.
+    /// Returns slice of uninitialized values.
+    /// If chunk allocation fails or array size overflows, it will return `Err`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::BlinkAlloc;
+    /// let blink = BlinkAlloc::new();
+    /// let slice = blink.allocate_uninit_slice::<u32>(4).unwrap();
+    /// for (idx, elem) in slice.iter_mut().enumerate() {
+    ///     elem.write(idx as u32);
+    /// }
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn allocate_uninit_slice<T>(
+        &self,
+        len: usize,
+    ) -> Result<&mut [MaybeUninit<T>], AllocError> {
+        let layout = Layout::array::<T>(len).map_err(|_| AllocError)?;
+        let ptr = self.allocate(layout)?;
+        // Safety: memory is allocated for `[T; len]` and valid until reset.
+        Ok(unsafe { core::slice::from_raw_parts_mut(ptr.as_ptr().cast::<MaybeUninit<T>>(), len) })
+    }
+
+    /// Allocates memory for a copy of the slice and copies it there.
+    /// Returns pointer to the copy, valid until reset.
+    /// If chunk allocation fails, it will return `Err`.
+    ///
+    /// Low-level counterpart of [`Blink::copy_slice`](crate::Blink::copy_slice)
+    /// for containers built directly on the allocator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::BlinkAlloc;
+    /// let blink = BlinkAlloc::new();
+    /// let copy = blink.allocate_slice_copy(&[1u32, 2, 3]).unwrap();
+    /// assert_eq!(unsafe { copy.as_ref() }, [1, 2, 3]);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline(always)]
+    pub fn allocate_slice_copy<T>(&self, slice: &[T]) -> Result<NonNull<[T]>, AllocError>
+    where
+        T: Copy,
+    {
+        let ptr = self.allocate(Layout::for_value(slice))?.cast::<T>();
+        // Safety: memory is allocated for the copy of the slice
+        // and can't overlap with memory borrowed by the slice.
+        unsafe { copy_bulk(slice.as_ptr(), ptr.as_ptr(), slice.len()) };
+        Ok(NonNull::slice_from_raw_parts(ptr, slice.len()))
+    }
+
+    /// Resizes memory allocation.
+    /// Potentially happens in-place.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be a pointer previously returned by [`allocate`](BlinkAlloc::allocate).
+    /// `old_size` must be in range `layout.size()..=slice.len()`
+    /// where `layout` is the layout used in the call to [`allocate`](BlinkAlloc::allocate).
+    /// and `slice` is the slice pointer returned by [`allocate`](BlinkAlloc::allocate).
+    ///
+    /// On success, the old pointer is invalidated and the new pointer is returned.
+    /// On error old allocation is still valid.
+    #[inline(always)]
+    pub unsafe fn resize(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        #[cfg(feature = "debug-stats")]
+        self.histogram.record(new_layout.size());
+        #[cfg(any(feature = "debug-stats", feature = "tag-quotas"))]
+        let grown = new_layout.size().saturating_sub(old_layout.size());
+        #[cfg(feature = "tag-quotas")]
+        self.tags.check_quota(grown)?;
+
+        let ptr = unsafe {
+            debug_header::resize(
+                ptr,
+                old_layout,
+                new_layout,
+                self.min_align,
+                |layout| self.allocate_raw(layout),
+                |ptr, old_layout, new_layout| self.resize_raw(ptr, old_layout, new_layout),
+                |ptr, size| self.deallocate_raw(ptr, size),
+            )
+        }?;
+        #[cfg(any(feature = "debug-stats", feature = "tag-quotas"))]
+        self.tags.record(grown);
+        Ok(ptr)
+    }
+
+    #[inline(always)]
+    unsafe fn resize_raw(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let old_layout = pad_layout(old_layout, self.min_align)?;
+        let new_layout = pad_layout(new_layout, self.min_align)?;
+
+        if let Some(ptr) = unsafe { self.arena.resize_fast(ptr, old_layout, new_layout) } {
+            self.fast_path_hits
+                .set(self.fast_path_hits.get().wrapping_add(1));
+            return Ok(ptr);
+        }
+
+        self.slow_path_hits
+            .set(self.slow_path_hits.get().wrapping_add(1));
+        self.events.alloc_slow_path(new_layout);
+
+        // Safety:
+        // Same instance is used for all allocations and resets.
+        // `ptr` was allocated by this allocator.
+        // Old allocation stays valid if resize fails.
+        self.events.retry_failed(
+            new_layout,
+            || self.stats(),
+            || unsafe {
+                self.arena.resize_slow(
+                    ptr,
+                    old_layout,
+                    new_layout,
+                    &Hooked::new(&self.allocator, self.events),
+                )
+            },
+        )
+    }
+
+    /// Deallocates memory previously allocated from this allocator.
+    ///
+    /// This call may not actually free memory.
+    /// All memory is guaranteed to be freed on [`reset`](BlinkAlloc::reset) call.
+    ///
+    /// Memory of the last allocation is reused.
+    /// Few most recent allocations deallocated in reverse order
+    /// are reused together with alignment padding preceding them.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be a pointer previously returned by [`allocate`](BlinkAlloc::allocate).
+    /// `size` must be in range `layout.size()..=slice.len()`
+    /// where `layout` is the layout used in the call to [`allocate`](BlinkAlloc::allocate).
+    /// and `slice` is the slice pointer returned by [`allocate`](BlinkAlloc::allocate).
+    #[inline(always)]
+    pub unsafe fn deallocate(&self, ptr: NonNull<u8>, size: usize) {
+        self.track_deallocate();
+        unsafe {
+            debug_header::deallocate(ptr, size, self.min_align, |ptr, size| {
+                self.deallocate_raw(ptr, size)
+            })
+        }
+    }
+
+    #[inline(always)]
+    unsafe fn deallocate_raw(&self, ptr: NonNull<u8>, size: usize) {
+        // Safety:
+        // `ptr` was allocated by this allocator.
+        unsafe {
+            self.arena.dealloc(ptr, pad_size(size, self.min_align));
+        }
+    }
+
+    /// Resets this allocator, deallocating all chunks except the last one.
+    /// Last chunk will be reused.
+    /// With steady memory usage after few iterations
+    /// one chunk should be sufficient for all allocations between resets.
+    #[inline(always)]
+    pub fn reset(&mut self) {
+        self.check_live();
+        let allocated_bytes = self.update_peak();
+        self.events.reset(|| allocated_bytes);
+        // Safety:
+        // Same instance is used for all allocations and resets.
+        unsafe {
+            self.arena
+                .reset(true, &Hooked::new(&self.allocator, self.events));
+        }
+    }
+
+    /// Resets this allocator, deallocating all chunks.
+    #[inline(always)]
+    pub fn reset_final(&mut self) {
+        self.check_live();
+        let allocated_bytes = self.update_peak();
+        self.events.reset(|| allocated_bytes);
+        // Safety:
+        // Same instance is used for all allocations and resets.
+        unsafe {
+            self.arena
+                .reset(false, &Hooked::new(&self.allocator, self.events));
+        }
+    }
+
+    /// Resets this allocator, replacing all chunks with a single chunk
+    /// sized to fit all allocations made since previous reset.
+    /// Next cycle with similar usage runs entirely in that chunk.
+    ///
+    /// This is one-off [`RetentionPolicy::Consolidate`]
+    /// regardless of configured retention policy.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::BlinkAlloc;
+    /// use std::alloc::Layout;
+    ///
+    /// let mut blink = BlinkAlloc::new();
+    /// for _ in 0..100 {
+    ///     blink.allocate(Layout::new::<[u8; 1000]>()).unwrap();
+    /// }
+    ///
+    /// blink.reset_compact();
+    /// let capacity = blink.total_capacity();
+    ///
+    /// for _ in 0..100 {
+    ///     blink.allocate(Layout::new::<[u8; 1000]>()).unwrap();
+    /// }
+    /// assert_eq!(blink.total_capacity(), capacity);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline(always)]
+    pub fn reset_compact(&mut self) {
+        self.check_live();
+        let allocated_bytes = self.update_peak();
+        self.events.reset(|| allocated_bytes);
+        // Safety:
+        // Same instance is used for all allocations and resets.
+        unsafe {
+            self.arena
+                .reset_compact(&Hooked::new(&self.allocator, self.events));
+        }
+    }
+
+    /// Resets this allocator, keeping all chunks.
+    /// Chunks other than the last one are reused
+    /// before new chunks are allocated.
+    /// Useful for bursty workloads that need multiple chunks every cycle,
+    /// as they stop allocating from underlying allocator after first cycle.
+    ///
+    /// Following [`reset`](BlinkAlloc::reset) deallocates kept chunks
+    /// except the last one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::BlinkAlloc;
+    /// use std::alloc::Layout;
+    ///
+    /// let mut blink = BlinkAlloc::new();
+    /// for _ in 0..100 {
+    ///     blink.allocate(Layout::new::<[u8; 1000]>()).unwrap();
+    /// }
+    /// let capacity = blink.total_capacity();
+    ///
+    /// blink.reset_keep_all();
+    /// assert_eq!(blink.total_capacity(), capacity);
+    ///
+    /// for _ in 0..100 {
+    ///     blink.allocate(Layout::new::<[u8; 1000]>()).unwrap();
+    /// }
+    /// assert_eq!(blink.total_capacity(), capacity);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline(always)]
+    pub fn reset_keep_all(&mut self) {
+        self.check_live();
+        let allocated_bytes = self.update_peak();
+        self.events.reset(|| allocated_bytes);
+        self.arena.reset_keep_all();
+    }
+
+    /// Resets this allocator, deallocating all chunks except the last one.
+    /// Last chunk will be reused.
+    /// With steady memory usage after few iterations
+    /// one chunk should be sufficient for all allocations between resets.
+    ///
+    /// # Safety
+    ///
+    /// Blink-allocators guarantee that memory can be used while shared
+    /// borrow to the allocator is held, preventing safe `fn reset` call.
+    ///
+    /// With this method it becomes caller responsibility to ensure
+    /// that allocated memory won't be used after reset.
+    #[inline(always)]
+    pub unsafe fn reset_unchecked(&self) {
+        self.check_live();
+        let allocated_bytes = self.update_peak();
+        self.events.reset(|| allocated_bytes);
+        // Safety:
+        // Same instance is used for all allocations and resets.
+        unsafe {
+            self.arena
+                .reset_unchecked(true, &Hooked::new(&self.allocator, self.events));
+        }
+    }
+
+    /// Returns the approximate number of bytes allocated from this allocator.
+    ///
+    /// This is the sum of the capacity of all previous (fully used) chunks
+    /// plus the bytes used in the current chunk. After warm-up (when
+    /// [`reset`](BlinkAlloc::reset) has been called enough times that a single
+    /// chunk serves all allocations), this value is exact.
+    ///
+    /// With `exact-stats` feature this is the sum of sizes
+    /// of allocations that are not deallocated since last reset.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::BlinkAlloc;
+    /// let mut blink = BlinkAlloc::new();
+    /// assert_eq!(blink.allocated_bytes(), 0);
+    /// let layout = std::alloc::Layout::new::<u64>();
+    /// blink.allocate(layout).unwrap();
+    /// assert!(blink.allocated_bytes() >= 8);
+    /// blink.reset();
+    /// assert_eq!(blink.allocated_bytes(), 0);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    pub fn allocated_bytes(&self) -> usize {
+        self.arena.allocated_bytes()
+    }
+
+    /// Returns the total capacity of all chunks in this allocator.
+    ///
+    /// This is the total amount of memory obtained from the underlying
+    /// allocator (excluding chunk headers). After
+    /// [`reset`](BlinkAlloc::reset), only the last chunk is retained, so
+    /// this equals the capacity of that single chunk.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::BlinkAlloc;
+    /// let mut blink = BlinkAlloc::new();
+    /// assert_eq!(blink.total_capacity(), 0);
+    /// let layout = std::alloc::Layout::new::<u64>();
+    /// blink.allocate(layout).unwrap();
+    /// assert!(blink.total_capacity() >= 8);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    pub fn total_capacity(&self) -> usize {
+        self.arena.total_capacity()
+    }
+
+    /// Returns the number of bytes consumed by chunk headers
+    /// and tails of chunks left unused when allocation
+    /// switched to a newer chunk.
+    ///
+    /// Together with [`total_capacity`](BlinkAlloc::total_capacity)
+    /// it accounts for all memory obtained from the underlying allocator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::BlinkAlloc;
+    /// let blink = BlinkAlloc::new();
+    /// assert_eq!(blink.overhead_bytes(), 0);
+    /// let layout = std::alloc::Layout::new::<u64>();
+    /// blink.allocate(layout).unwrap();
+    /// assert!(blink.overhead_bytes() > 0);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    pub fn overhead_bytes(&self) -> usize {
+        self.arena.overhead_bytes()
+    }
+
+    /// Checks if `ptr` points into memory allocated from this allocator
+    /// since last reset.
+    ///
+    /// Walks the list of chunks, so the cost grows with number of chunks.
+    /// After warm-up there's usually a single chunk.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::BlinkAlloc;
+    /// let blink = BlinkAlloc::new();
+    /// let ptr = blink.allocate(std::alloc::Layout::new::<u64>()).unwrap();
+    /// assert!(blink.contains(ptr.cast()));
+    ///
+    /// let heap = Box::new(0u64);
+    /// assert!(!blink.contains(std::ptr::NonNull::from(&*heap).cast()));
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    pub fn contains(&self, ptr: NonNull<u8>) -> bool {
+        self.arena.contains(ptr)
+    }
+
+    /// Returns snapshot of allocator statistics.
+    ///
+    /// Compare snapshots taken at two points with [`BlinkStats::diff`]
+    /// to find how much allocator usage grew between them.
+    /// Snapshot implements `Display` for one-line logging.
+    #[inline]
+    pub fn stats(&self) -> BlinkStats {
+        let allocated_bytes = self.arena.allocated_bytes();
+        BlinkStats {
+            allocated_bytes,
+            total_capacity: self.arena.total_capacity(),
+            chunks: self.arena.chunk_count(),
+            allocations: self.allocations.get(),
+            peak_allocated_bytes: self.peak_allocated_bytes.get().max(allocated_bytes),
+            fast_path_hits: self.fast_path_hits.get(),
+            slow_path_hits: self.slow_path_hits.get(),
+        }
+    }
+
+    /// Returns number of allocations and resizes
+    /// served from the current chunk since last reset.
+    ///
+    /// Together with [`slow_path_hits`](BlinkAlloc::slow_path_hits)
+    /// it shows how well chunk size fits the workload.
+    /// Frequent slow path hits in steady state suggest larger chunk size.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::BlinkAlloc;
+    /// let mut blink = BlinkAlloc::new();
+    /// for _ in 0..10 {
+    ///     blink.allocate(std::alloc::Layout::new::<u64>()).unwrap();
+    /// }
+    /// assert_eq!(blink.fast_path_hits(), 9);
+    /// assert_eq!(blink.slow_path_hits(), 1);
+    ///
+    /// blink.reset();
+    /// assert_eq!(blink.fast_path_hits(), 0);
+    /// assert_eq!(blink.slow_path_hits(), 0);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline]
+    pub fn fast_path_hits(&self) -> usize {
+        let (fast, _) = self.reset_path_hits.get();
+        self.fast_path_hits.get().wrapping_sub(fast)
+    }
+
+    /// Returns number of allocations and resizes
+    /// that didn't fit into the current chunk since last reset.
+    ///
+    /// See [`fast_path_hits`](BlinkAlloc::fast_path_hits).
+    #[inline]
+    pub fn slow_path_hits(&self) -> usize {
+        let (_, slow) = self.reset_path_hits.get();
+        self.slow_path_hits.get().wrapping_sub(slow)
+    }
+
+    /// Logs snapshot of allocator statistics with [`defmt::info!`].
+    ///
+    /// Includes allocator name if set with [`with_name`](BlinkAlloc::with_name).
+    #[cfg(feature = "defmt")]
+    #[inline]
+    pub fn log_stats(&self) {
+        let stats = self.stats();
+        match self.name() {
+            Some(name) => defmt::info!("{=str}: {}", name, stats),
+            None => defmt::info!("{}", stats),
+        }
+    }
+
+    /// Records allocated bytes before reset into the peak.
+    /// Restarts path hit counters.
+    #[inline(always)]
+    fn update_peak(&self) -> usize {
+        self.reset_path_hits
+            .set((self.fast_path_hits.get(), self.slow_path_hits.get()));
+        let allocated_bytes = self.arena.allocated_bytes();
+        if allocated_bytes > self.peak_allocated_bytes.get() {
+            self.peak_allocated_bytes.set(allocated_bytes);
+        }
+        allocated_bytes
+    }
+
+    /// Returns iterator over utilization of memory chunks,
+    /// from the current chunk to the oldest one.
+    ///
+    /// Reports capacity and used bytes of each chunk,
+    /// as well as bytes stranded in older chunks when allocation
+    /// switched to a newer chunk.
+    /// Large stranded totals suggest to start with larger chunk size.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::BlinkAlloc;
+    /// let blink = BlinkAlloc::with_chunk_size(64);
+    /// blink.allocate(std::alloc::Layout::new::<[u8; 48]>()).unwrap();
+    /// blink.allocate(std::alloc::Layout::new::<[u8; 200]>()).unwrap();
+    ///
+    /// let stranded: usize = blink.utilization().map(|chunk| chunk.stranded).sum();
+    /// assert!(stranded > 0);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    pub fn utilization(&self) -> Utilization<'_> {
+        self.arena.utilization().into()
+    }
+
+    /// Returns used memory regions of chunks
+    /// from the current chunk to the oldest one.
+    #[inline(always)]
+    pub(crate) fn used_chunks(&self) -> impl Iterator<Item = (NonNull<u8>, usize)> + '_ {
+        self.arena.used_chunks()
+    }
+
+    /// Checks if memory not used by live allocations is kept zeroed.
+    #[inline(always)]
+    pub(crate) fn zeroes_free_memory(&self) -> bool {
+        self.arena.zero_free()
+    }
+
+    /// Returns histogram of allocation sizes recorded since creation
+    /// or last call to [`reset_size_histogram`](BlinkAlloc::reset_size_histogram).
+    ///
+    /// Allocations and resizes are recorded with requested size.
+    #[cfg(feature = "debug-stats")]
+    #[inline]
+    pub fn size_histogram(&self) -> SizeHistogram {
+        self.histogram.get()
+    }
+
+    /// Clears histogram of allocation sizes.
+    #[cfg(feature = "debug-stats")]
+    #[inline]
+    pub fn reset_size_histogram(&self) {
+        self.histogram.reset();
+    }
+
+    /// Tags allocations made from this allocator until returned guard is dropped.
+    ///
+    /// With `"debug-stats"` feature bytes allocated under each tag are retrieved
+    /// with `tag_stats`, see `TagStats` for details.
+    /// With `"tag-quotas"` feature allocations under the tag are limited
+    /// by quota set with `set_tag_quota`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(all(feature = "alloc", feature = "debug-stats"))] fn main() {
+    /// use blink_alloc::Blink;
+    /// let blink = Blink::new();
+    /// {
+    ///     let _physics = blink.allocator().tag("physics");
+    ///     blink.put([0u64; 4]);
+    /// }
+    /// assert_eq!(blink.allocator().tag_stats().bytes("physics"), 32);
+    /// # }
+    /// # #[cfg(not(all(feature = "alloc", feature = "debug-stats")))] fn main() {}
+    /// ```
+    #[cfg(any(feature = "debug-stats", feature = "tag-quotas"))]
+    #[inline(always)]
+    pub fn tag(&self, tag: &'static str) -> TagGuard<'_> {
+        self.tags.tag(tag)
+    }
+
+    /// Returns bytes allocated under each tag since creation
+    /// or last call to [`reset_tag_stats`](BlinkAlloc::reset_tag_stats).
+    #[cfg(feature = "debug-stats")]
+    #[inline]
+    pub fn tag_stats(&self) -> TagStats {
+        self.tags.get()
+    }
+
+    /// Clears per-tag byte counters.
+    ///
+    /// Bytes counted against tag quotas are cleared as well,
+    /// so calling this once per frame makes quotas per-frame budgets.
+    #[cfg(any(feature = "debug-stats", feature = "tag-quotas"))]
+    #[inline]
+    pub fn reset_tag_stats(&self) {
+        self.tags.reset();
+    }
+
+    /// Limits bytes allocated under the tag to `bytes`
+    /// until next call to [`reset_tag_stats`](BlinkAlloc::reset_tag_stats).
+    ///
+    /// Allocations and resizes under the tag that would exceed the quota
+    /// fail with `AllocError` without touching the arena,
+    /// while allocations under other tags are not affected.
+    /// Growing resize counts the difference.
+    /// Bytes are counted only when allocation succeeds.
+    /// Allocation failure hooks are not called for quota failures.
+    ///
+    /// Setting quota again for the same tag replaces the limit
+    /// and keeps bytes already counted against it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`MAX_TAGS`](crate::MAX_TAGS) other tags already have quotas.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::BlinkAlloc;
+    /// let blink = BlinkAlloc::new();
+    /// blink.set_tag_quota("ui", 100);
+    /// {
+    ///     let _ui = blink.tag("ui");
+    ///     blink.allocate(std::alloc::Layout::new::<[u8; 80]>()).unwrap();
+    ///     assert!(blink.allocate(std::alloc::Layout::new::<[u8; 80]>()).is_err());
+    /// }
+    /// {
+    ///     let _physics = blink.tag("physics");
+    ///     blink.allocate(std::alloc::Layout::new::<[u8; 80]>()).unwrap();
+    /// }
+    /// assert_eq!(blink.tag_quota_remaining("ui"), Some(20));
+    ///
+    /// blink.reset_tag_stats();
+    /// assert_eq!(blink.tag_quota_remaining("ui"), Some(100));
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(feature = "tag-quotas")]
+    #[inline]
+    pub fn set_tag_quota(&self, tag: &'static str, bytes: usize) {
+        self.tags.set_quota(tag, bytes);
+    }
+
+    /// Removes quota set with [`set_tag_quota`](BlinkAlloc::set_tag_quota).
+    #[cfg(feature = "tag-quotas")]
+    #[inline]
+    pub fn remove_tag_quota(&self, tag: &'static str) {
+        self.tags.remove_quota(tag);
+    }
+
+    /// Returns bytes left in quota of the tag,
+    /// or `None` if the tag has no quota.
+    #[cfg(feature = "tag-quotas")]
+    #[inline]
+    pub fn tag_quota_remaining(&self, tag: &'static str) -> Option<usize> {
+        self.tags.quota_remaining(tag)
+    }
+
+    /// Moves memory chunks of this allocator to `other`.
+    ///
+    /// All chunks except the last one are freed.
+    /// The last chunk becomes current chunk of `other`.
+    /// If chunk alignments differ, all chunks are freed.
+    ///
+    /// # Safety
+    ///
+    /// Allocator of `other` must be able to deallocate memory
+    /// allocated by allocator of this instance.
+    /// E.g. both are clones of the same allocator.
+    #[inline(always)]
+    pub unsafe fn donate_capacity_unchecked(&mut self, other: &mut Self) {
+        // Safety:
+        // Same instance is used for all allocations and resets.
+        // Caller guarantees that `other` can deallocate donated chunks.
+        unsafe {
+            self.arena
+                .donate(&mut other.arena, &Hooked::new(&self.allocator, self.events));
+        }
+    }
+
+    /// Returns a guard that dereferences to this allocator
+    /// and resets it when dropped.
+    ///
+    /// Reset happens even if guard is dropped during unwinding,
+    /// so early returns and panics can't skip it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::BlinkAlloc;
+    /// let mut blink = BlinkAlloc::new();
+    /// {
+    ///     let guard = blink.guard();
+    ///     let layout = std::alloc::Layout::new::<u64>();
+    ///     guard.allocate(layout).unwrap();
+    ///     assert!(guard.allocated_bytes() >= 8);
+    /// }
+    /// assert_eq!(blink.allocated_bytes(), 0);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline(always)]
+    pub fn guard(&mut self) -> ResetGuard<'_, A> {
+        ResetGuard { blink: self }
+    }
+
+    /// Seals this allocator, disallowing further allocations.
+    ///
+    /// Memory allocated so far stays valid until returned [`FrozenBlink`]
+    /// is dropped and can be shared between threads.
+    #[inline(always)]
+    pub fn freeze(self) -> FrozenBlink<A> {
+        FrozenBlink::new(self)
+    }
+
+    /// Carves `bytes` of memory from this allocator
+    /// into independent arena with its own cursor and resets.
+    ///
+    /// Returned arena never allocates more memory,
+    /// its allocations fail once the region is exhausted,
+    /// which makes it a hard budget for a subsystem.
+    /// Part of the region is used for chunk header.
+    ///
+    /// Dropping returned arena gives the region back to this allocator
+    /// if nothing was allocated from this allocator after it.
+    /// Otherwise region is reclaimed on reset.
+    ///
+    /// Returns `Err(AllocError)` if region can't be allocated.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use std::alloc::Layout;
+    /// use blink_alloc::BlinkAlloc;
+    ///
+    /// let mut blink = BlinkAlloc::new();
+    ///
+    /// let mut audio = blink.split_off(1024).unwrap();
+    /// audio.allocate(Layout::new::<[u8; 512]>()).unwrap();
+    /// assert!(audio.allocate(Layout::new::<[u8; 1024]>()).is_err());
+    ///
+    /// audio.reset();
+    /// audio.allocate(Layout::new::<[u8; 512]>()).unwrap();
+    ///
+    /// drop(audio);
+    /// assert_eq!(blink.allocated_bytes(), 0);
+    /// blink.reset();
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline]
+    pub fn split_off(&self, bytes: usize) -> Result<BlinkAlloc<SplitRegion<'_, A>>, AllocError> {
+        let layout = Layout::from_size_align(bytes, SPLIT_ALIGN).map_err(|_| AllocError)?;
+        let slice = self.allocate(layout)?;
+        let ptr = slice.cast::<u8>();
+
+        // Safety: memory is allocated above and stays valid
+        // until `SplitRegion` returns it, since `self` is borrowed.
+        let buffer = unsafe {
+            core::slice::from_raw_parts_mut(ptr.as_ptr().cast::<MaybeUninit<u8>>(), bytes)
+        };
+
+        let region = SplitRegion {
+            buffer: StaticBuffer::new(buffer),
+            parent: self,
+            ptr,
+            size: slice.len(),
+        };
+        Ok(BlinkAlloc::with_chunk_size_range_in(bytes, bytes, region))
+    }
+
+    /// Returns the number of bytes left in the current chunk.
+    ///
+    /// Allocations that fit into this many bytes,
+    /// including padding required for alignment,
+    /// won't allocate new chunk from the underlying allocator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(all(feature = "alloc", not(feature = "debug-headers")))] fn main() {
+    /// use blink_alloc::BlinkAlloc;
+    /// let blink = BlinkAlloc::new();
+    /// assert_eq!(blink.remaining_capacity(), 0);
+    /// let layout = std::alloc::Layout::new::<u64>();
+    /// blink.allocate(layout).unwrap();
+    /// let remaining = blink.remaining_capacity();
+    /// assert_eq!(remaining, blink.total_capacity() - blink.allocated_bytes());
+    ///
+    /// blink.allocate(layout).unwrap();
+    /// assert_eq!(blink.remaining_capacity(), remaining - 8);
+    /// # }
+    /// # #[cfg(not(all(feature = "alloc", not(feature = "debug-headers"))))] fn main() {}
+    /// ```
+    pub fn remaining_capacity(&self) -> usize {
+        self.arena.remaining_capacity()
+    }
+
+    /// Allocates `value` in [`Box`](allocator_api2::boxed::Box)
+    /// that uses this allocator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::BlinkAlloc;
+    ///
+    /// let blink = BlinkAlloc::new();
+    /// let boxed = blink.boxed(42u32);
+    /// assert_eq!(*boxed, 42);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(all(feature = "alloc", not(no_global_oom_handling)))]
+    #[inline(always)]
+    pub fn boxed<T>(&self, value: T) -> allocator_api2::boxed::Box<T, &Self> {
+        allocator_api2::boxed::Box::new_in(value, self)
+    }
+
+    /// Creates empty [`Vec`](allocator_api2::vec::Vec)
+    /// that uses this allocator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::BlinkAlloc;
+    ///
+    /// let mut blink = BlinkAlloc::new();
+    /// let mut vec = blink.vec();
+    /// vec.extend(0..10u32);
+    /// assert_eq!(vec.iter().sum::<u32>(), 45);
+    ///
+    /// drop(vec);
+    /// blink.reset();
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[inline(always)]
+    pub fn vec<T>(&self) -> allocator_api2::vec::Vec<T, &Self> {
+        allocator_api2::vec::Vec::new_in(self)
+    }
+
+    /// Creates [`Vec`](allocator_api2::vec::Vec) with space
+    /// for at least `capacity` elements that uses this allocator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::BlinkAlloc;
+    ///
+    /// let blink = BlinkAlloc::new();
+    /// let vec = blink.vec_with_capacity::<u64>(16);
+    /// assert!(vec.capacity() >= 16);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(all(feature = "alloc", not(no_global_oom_handling)))]
+    #[inline(always)]
+    pub fn vec_with_capacity<T>(&self, capacity: usize) -> allocator_api2::vec::Vec<T, &Self> {
+        allocator_api2::vec::Vec::with_capacity_in(capacity, self)
+    }
+
+    /// Leaks this allocator, returning reference that lives forever.
+    ///
+    /// Memory allocated through returned reference is never released,
+    /// so collections using it can be leaked as `'static` data.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #![cfg_attr(feature = "nightly", feature(allocator_api))]
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # #[cfg(feature = "nightly")]
+    /// # use std::vec::Vec;
+    /// # #[cfg(not(feature = "nightly"))]
+    /// # use allocator_api2::vec::Vec;
+    /// use blink_alloc::BlinkAlloc;
+    ///
+    /// let blink: &'static BlinkAlloc = BlinkAlloc::new().leak();
+    /// let mut vec = Vec::new_in(blink);
+    /// vec.extend(0..10);
+    /// let numbers: &'static mut [u32] = vec.leak();
+    /// assert_eq!(numbers.len(), 10);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[inline(always)]
+    pub fn leak(self) -> &'static Self
+    where
+        A: 'static,
+    {
+        alloc::boxed::Box::leak(alloc::boxed::Box::new(self))
+    }
+
+    /// Unwrap this allocator, returning the underlying allocator.
+    ///
+    /// All chunks are deallocated first, same as with [`reset_final`](BlinkAlloc::reset_final).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::{BlinkAlloc, ChunkPool};
+    ///
+    /// let pool = ChunkPool::new();
+    /// let blink = BlinkAlloc::new_in(pool);
+    /// blink.allocate(std::alloc::Layout::new::<u64>()).unwrap();
+    ///
+    /// // Chunk is returned to the pool.
+    /// let pool = blink.into_inner();
+    /// assert!(pool.cached_bytes() > 0);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    pub fn into_inner(self) -> A {
+        let mut me = ManuallyDrop::new(self);
+        me.reset_final();
+
+        // Safety: `me` is not used after this and is not dropped.
+        // Other fields have no resources to release after `reset_final`.
+        unsafe { core::ptr::read(&me.allocator) }
+    }
+
+    /// Replaces underlying allocator, returning the previous one.
+    ///
+    /// All chunks are deallocated to the previous allocator first,
+    /// same as with [`reset_final`](BlinkAlloc::reset_final),
+    /// so new chunks are allocated from the new one.
+    ///
+    /// To pick backing allocator at runtime use an enum
+    /// that implements [`ChunkSource`] and routes to the selected variant.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::{BlinkAlloc, ChunkPool};
+    ///
+    /// let mut blink = BlinkAlloc::new_in(ChunkPool::new());
+    /// blink.allocate(std::alloc::Layout::new::<u64>()).unwrap();
+    ///
+    /// let old = blink.set_allocator(ChunkPool::new());
+    /// assert!(old.cached_bytes() > 0);
+    /// assert_eq!(blink.inner().cached_bytes(), 0);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    pub fn set_allocator(&mut self, allocator: A) -> A {
+        self.reset_final();
+        core::mem::replace(&mut self.allocator, allocator)
+    }
+}
+
+switch_alloc_default! {
+    /// Guard returned by [`BlinkAlloc::guard`].
+    ///
+    /// Dereferences to the [`BlinkAlloc`] and resets it when dropped.
+    pub struct ResetGuard<'a, A: ChunkSource = +Global> {
+        blink: &'a mut BlinkAlloc<A>,
+    }
+}
+
+impl<A> Deref for ResetGuard<'_, A>
+where
+    A: ChunkSource,
+{
+    type Target = BlinkAlloc<A>;
+
+    #[inline(always)]
+    fn deref(&self) -> &BlinkAlloc<A> {
+        self.blink
+    }
+}
+
+impl<A> DerefMut for ResetGuard<'_, A>
+where
+    A: ChunkSource,
+{
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut BlinkAlloc<A> {
+        self.blink
+    }
+}
+
+impl<A> Drop for ResetGuard<'_, A>
+where
+    A: ChunkSource,
+{
+    #[inline(always)]
+    fn drop(&mut self) {
+        self.blink.reset();
+    }
+}
+
+/// Alignment of regions carved with [`BlinkAlloc::split_off`].
+const SPLIT_ALIGN: usize = 16;
+
+switch_alloc_default! {
+    /// Chunk source of arena returned by [`BlinkAlloc::split_off`].
+    ///
+    /// Provides region of the parent allocator as the only chunk
+    /// and gives it back to the parent when dropped.
+    pub struct SplitRegion<'a, A: ChunkSource = +Global> {
+        buffer: StaticBuffer<'a>,
+        parent: &'a BlinkAlloc<A>,
+        ptr: NonNull<u8>,
+        size: usize,
+    }
+}
+
+impl<A> SplitRegion<'_, A>
+where
+    A: ChunkSource,
+{
+    /// Returns size of the region.
+    #[inline(always)]
+    pub fn capacity(&self) -> usize {
+        self.size
+    }
+}
+
+impl<A> Drop for SplitRegion<'_, A>
+where
+    A: ChunkSource,
+{
+    #[inline]
+    fn drop(&mut self) {
+        // Safety: region was allocated from parent in `split_off`.
+        // Arena released the chunk before dropping its source.
+        unsafe { self.parent.deallocate(self.ptr, self.size) }
+    }
+}
+
+unsafe impl<A> ChunkSource for SplitRegion<'_, A>
+where
+    A: ChunkSource,
+{
+    #[inline(always)]
+    fn allocate_chunk(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.buffer.allocate_chunk(layout)
+    }
+
+    #[inline(always)]
+    unsafe fn release_chunk(&self, ptr: NonNull<u8>, layout: Layout) {
+        unsafe { self.buffer.release_chunk(ptr, layout) }
+    }
+}
+
+unsafe impl<A> Allocator for BlinkAlloc<A>
+where
+    A: ChunkSource,
+{
+    #[inline(always)]
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        BlinkAlloc::allocate(self, layout)
+    }
+
+    #[inline(always)]
+    fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        BlinkAlloc::allocate_zeroed(self, layout)
+    }
+
+    #[inline(always)]
+    unsafe fn shrink(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        BlinkAlloc::resize(self, ptr, old_layout, new_layout)
+    }
+
+    #[inline(always)]
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        BlinkAlloc::resize(self, ptr, old_layout, new_layout)
     }
 
     #[inline(always)]
     unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
-        BlinkAlloc::deallocate(self, ptr, layout.size());
+        BlinkAlloc::deallocate(self, ptr, layout.pad_to_align().size());
     }
 }
 
+#[cfg(feature = "nightly")]
+impl_api2_allocator!(impl<A> BlinkAlloc<A> where A: crate::ChunkSource);
+
 unsafe impl<A> BlinkAllocator for BlinkAlloc<A>
 where
-    A: Allocator,
+    A: ChunkSource,
 {
     #[inline(always)]
     fn reset(&mut self) {
         BlinkAlloc::reset(self)
     }
+
+    #[inline(always)]
+    fn reset_final(&mut self) {
+        BlinkAlloc::reset_final(self)
+    }
+
+    #[inline(always)]
+    fn allocated_bytes(&self) -> Option<usize> {
+        Some(BlinkAlloc::allocated_bytes(self))
+    }
+
+    #[inline(always)]
+    fn total_capacity(&self) -> Option<usize> {
+        Some(BlinkAlloc::total_capacity(self))
+    }
 }
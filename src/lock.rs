@@ -0,0 +1,308 @@
+//! This module provides locks used by multi-threaded allocators.
+//!
+//! Locks from `parking_lot` are used when it is enabled,
+//! otherwise `std` locks are used when `"std"` feature is enabled.
+//! Without both simple spin-locks are used,
+//! making multi-threaded allocators available in `no_std` environment.
+
+#[cfg(feature = "parking_lot")]
+pub(crate) use parking_lot::Mutex;
+
+#[cfg(all(feature = "parking_lot", not(loom)))]
+pub(crate) use parking_lot::RwLock;
+
+#[cfg(all(not(feature = "parking_lot"), feature = "std"))]
+pub(crate) use self::std_lock::Mutex;
+
+#[cfg(all(not(feature = "parking_lot"), feature = "std", not(loom)))]
+pub(crate) use self::std_lock::RwLock;
+
+#[cfg(all(not(feature = "parking_lot"), not(feature = "std")))]
+pub(crate) use self::spin::Mutex;
+
+#[cfg(all(not(feature = "parking_lot"), not(feature = "std"), not(loom)))]
+pub(crate) use self::spin::RwLock;
+
+#[cfg(loom)]
+pub(crate) use self::loom_lock::RwLock;
+
+/// Wrappers over `std` locks that ignore poisoning like `parking_lot` does.
+/// Locks are never held while user code runs, so poisoning can't leave
+/// protected state inconsistent.
+#[cfg(all(not(feature = "parking_lot"), feature = "std"))]
+mod std_lock {
+    use std::sync::{self, MutexGuard, PoisonError, RwLockReadGuard, RwLockWriteGuard};
+
+    pub(crate) struct RwLock<T: ?Sized>(sync::RwLock<T>);
+
+    #[cfg_attr(loom, allow(dead_code))]
+    impl<T> RwLock<T> {
+        #[inline(always)]
+        pub const fn new(value: T) -> Self {
+            RwLock(sync::RwLock::new(value))
+        }
+    }
+
+    #[cfg_attr(loom, allow(dead_code))]
+    impl<T: ?Sized> RwLock<T> {
+        #[inline(always)]
+        pub fn read(&self) -> RwLockReadGuard<'_, T> {
+            self.0.read().unwrap_or_else(PoisonError::into_inner)
+        }
+
+        #[inline(always)]
+        pub fn write(&self) -> RwLockWriteGuard<'_, T> {
+            self.0.write().unwrap_or_else(PoisonError::into_inner)
+        }
+
+        #[inline(always)]
+        pub fn get_mut(&mut self) -> &mut T {
+            self.0.get_mut().unwrap_or_else(PoisonError::into_inner)
+        }
+    }
+
+    pub(crate) struct Mutex<T: ?Sized>(sync::Mutex<T>);
+
+    impl<T> Mutex<T> {
+        #[inline(always)]
+        pub const fn new(value: T) -> Self {
+            Mutex(sync::Mutex::new(value))
+        }
+    }
+
+    impl<T: ?Sized> Mutex<T> {
+        #[inline(always)]
+        pub fn lock(&self) -> MutexGuard<'_, T> {
+            self.0.lock().unwrap_or_else(PoisonError::into_inner)
+        }
+
+        #[inline(always)]
+        pub fn get_mut(&mut self) -> &mut T {
+            self.0.get_mut().unwrap_or_else(PoisonError::into_inner)
+        }
+    }
+}
+
+/// `RwLock` from `loom` to model-check multi-threaded arena.
+/// Unlike other locks it can't be created in const context.
+#[cfg(loom)]
+mod loom_lock {
+    use loom::sync::{self, RwLockReadGuard, RwLockWriteGuard};
+
+    pub(crate) struct RwLock<T>(sync::RwLock<T>);
+
+    impl<T> RwLock<T> {
+        #[inline(always)]
+        pub fn new(value: T) -> Self {
+            RwLock(sync::RwLock::new(value))
+        }
+
+        #[inline(always)]
+        pub fn read(&self) -> RwLockReadGuard<'_, T> {
+            self.0.read().unwrap_or_else(|err| err.into_inner())
+        }
+
+        #[inline(always)]
+        pub fn write(&self) -> RwLockWriteGuard<'_, T> {
+            self.0.write().unwrap_or_else(|err| err.into_inner())
+        }
+
+        #[inline(always)]
+        pub fn get_mut(&mut self) -> &mut T {
+            self.0.get_mut().unwrap_or_else(|err| err.into_inner())
+        }
+    }
+}
+
+/// Spin-locks for `no_std` targets.
+/// Critical sections of allocators are short,
+/// so spinning is acceptable.
+#[cfg(all(not(feature = "parking_lot"), not(feature = "std")))]
+mod spin {
+    use core::{
+        cell::UnsafeCell,
+        hint::spin_loop,
+        ops::{Deref, DerefMut},
+        sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    };
+
+    /// Set while lock is held by a writer.
+    const WRITER: usize = 1;
+
+    /// Added for every reader holding the lock.
+    const READER: usize = 2;
+
+    pub(crate) struct RwLock<T: ?Sized> {
+        state: AtomicUsize,
+        value: UnsafeCell<T>,
+    }
+
+    unsafe impl<T: ?Sized + Send> Send for RwLock<T> {}
+    unsafe impl<T: ?Sized + Send + Sync> Sync for RwLock<T> {}
+
+    impl<T> RwLock<T> {
+        #[inline(always)]
+        pub const fn new(value: T) -> Self {
+            RwLock {
+                state: AtomicUsize::new(0),
+                value: UnsafeCell::new(value),
+            }
+        }
+    }
+
+    impl<T: ?Sized> RwLock<T> {
+        #[inline]
+        pub fn read(&self) -> RwLockReadGuard<'_, T> {
+            loop {
+                let state = self.state.load(Ordering::Relaxed);
+                if state & WRITER == 0
+                    && self
+                        .state
+                        .compare_exchange_weak(
+                            state,
+                            state + READER,
+                            Ordering::Acquire,
+                            Ordering::Relaxed,
+                        )
+                        .is_ok()
+                {
+                    return RwLockReadGuard { lock: self };
+                }
+                spin_loop();
+            }
+        }
+
+        #[inline]
+        pub fn write(&self) -> RwLockWriteGuard<'_, T> {
+            while self
+                .state
+                .compare_exchange_weak(0, WRITER, Ordering::Acquire, Ordering::Relaxed)
+                .is_err()
+            {
+                spin_loop();
+            }
+            RwLockWriteGuard { lock: self }
+        }
+
+        #[inline(always)]
+        pub fn get_mut(&mut self) -> &mut T {
+            self.value.get_mut()
+        }
+    }
+
+    pub(crate) struct RwLockReadGuard<'a, T: ?Sized> {
+        lock: &'a RwLock<T>,
+    }
+
+    impl<T: ?Sized> Deref for RwLockReadGuard<'_, T> {
+        type Target = T;
+
+        #[inline(always)]
+        fn deref(&self) -> &T {
+            // Safety: Writers are excluded while read guard is alive.
+            unsafe { &*self.lock.value.get() }
+        }
+    }
+
+    impl<T: ?Sized> Drop for RwLockReadGuard<'_, T> {
+        #[inline(always)]
+        fn drop(&mut self) {
+            self.lock.state.fetch_sub(READER, Ordering::Release);
+        }
+    }
+
+    pub(crate) struct RwLockWriteGuard<'a, T: ?Sized> {
+        lock: &'a RwLock<T>,
+    }
+
+    impl<T: ?Sized> Deref for RwLockWriteGuard<'_, T> {
+        type Target = T;
+
+        #[inline(always)]
+        fn deref(&self) -> &T {
+            // Safety: Lock is held exclusively while write guard is alive.
+            unsafe { &*self.lock.value.get() }
+        }
+    }
+
+    impl<T: ?Sized> DerefMut for RwLockWriteGuard<'_, T> {
+        #[inline(always)]
+        fn deref_mut(&mut self) -> &mut T {
+            // Safety: Lock is held exclusively while write guard is alive.
+            unsafe { &mut *self.lock.value.get() }
+        }
+    }
+
+    impl<T: ?Sized> Drop for RwLockWriteGuard<'_, T> {
+        #[inline(always)]
+        fn drop(&mut self) {
+            self.lock.state.store(0, Ordering::Release);
+        }
+    }
+
+    pub(crate) struct Mutex<T: ?Sized> {
+        locked: AtomicBool,
+        value: UnsafeCell<T>,
+    }
+
+    unsafe impl<T: ?Sized + Send> Send for Mutex<T> {}
+    unsafe impl<T: ?Sized + Send> Sync for Mutex<T> {}
+
+    impl<T> Mutex<T> {
+        #[inline(always)]
+        pub const fn new(value: T) -> Self {
+            Mutex {
+                locked: AtomicBool::new(false),
+                value: UnsafeCell::new(value),
+            }
+        }
+    }
+
+    impl<T: ?Sized> Mutex<T> {
+        #[inline]
+        pub fn lock(&self) -> MutexGuard<'_, T> {
+            while self
+                .locked
+                .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+                .is_err()
+            {
+                spin_loop();
+            }
+            MutexGuard { lock: self }
+        }
+
+        #[inline(always)]
+        pub fn get_mut(&mut self) -> &mut T {
+            self.value.get_mut()
+        }
+    }
+
+    pub(crate) struct MutexGuard<'a, T: ?Sized> {
+        lock: &'a Mutex<T>,
+    }
+
+    impl<T: ?Sized> Deref for MutexGuard<'_, T> {
+        type Target = T;
+
+        #[inline(always)]
+        fn deref(&self) -> &T {
+            // Safety: Lock is held while guard is alive.
+            unsafe { &*self.lock.value.get() }
+        }
+    }
+
+    impl<T: ?Sized> DerefMut for MutexGuard<'_, T> {
+        #[inline(always)]
+        fn deref_mut(&mut self) -> &mut T {
+            // Safety: Lock is held while guard is alive.
+            unsafe { &mut *self.lock.value.get() }
+        }
+    }
+
+    impl<T: ?Sized> Drop for MutexGuard<'_, T> {
+        #[inline(always)]
+        fn drop(&mut self) {
+            self.lock.locked.store(false, Ordering::Release);
+        }
+    }
+}
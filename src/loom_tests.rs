@@ -0,0 +1,163 @@
+//! Model-checking of `ArenaSync` under `loom`.
+//!
+//! Run with
+//! `RUSTFLAGS="--cfg loom" cargo test --release --features sync --lib loom_tests`.
+
+use core::{alloc::Layout, ptr::NonNull};
+
+#[cfg(feature = "nightly")]
+use alloc::{alloc::Global, vec::Vec};
+#[cfg(not(feature = "nightly"))]
+use allocator_api2::{alloc::Global, vec::Vec};
+
+use loom::{sync::Arc, thread};
+
+use crate::arena::ArenaSync;
+
+/// Small chunks, so that few allocations hit the slow path.
+const CHUNK_SIZE: usize = 64;
+
+const LAYOUT: Layout = Layout::new::<[u8; 24]>();
+
+/// Allocates on fast path, falling back to slow path like `SyncBlinkAlloc` does.
+fn alloc(arena: &ArenaSync, layout: Layout) -> NonNull<[u8]> {
+    // Safety: `Global` is used for all allocations and resets of the arena.
+    unsafe {
+        match arena.alloc_fast(layout) {
+            Some(ptr) => ptr,
+            None => arena.alloc_slow(layout, &Global).unwrap(),
+        }
+    }
+}
+
+/// Allocates and fills memory with `tag`.
+/// Returns address of the allocation.
+fn alloc_tagged(arena: &ArenaSync, tag: u8) -> usize {
+    let ptr = alloc(arena, LAYOUT);
+    assert!(ptr.len() >= LAYOUT.size());
+
+    let ptr = ptr.cast::<u8>();
+    unsafe { ptr.as_ptr().write_bytes(tag, LAYOUT.size()) };
+    ptr.as_ptr() as usize
+}
+
+/// Checks that allocations don't overlap and weren't overwritten.
+fn check_tagged(allocations: &[(usize, u8)]) {
+    for (i, &(a, tag)) in allocations.iter().enumerate() {
+        let bytes = unsafe { core::slice::from_raw_parts(a as *const u8, LAYOUT.size()) };
+        assert!(bytes.iter().all(|&b| b == tag));
+
+        for &(b, _) in &allocations[i + 1..] {
+            assert!(a + LAYOUT.size() <= b || b + LAYOUT.size() <= a);
+        }
+    }
+}
+
+fn finish(arena: Arc<ArenaSync>) {
+    let Ok(mut arena) = Arc::try_unwrap(arena) else {
+        panic!("All threads are joined");
+    };
+    unsafe { arena.reset(false, &Global) };
+}
+
+#[test]
+fn loom_concurrent_alloc() {
+    loom::model(|| {
+        let arena = Arc::new(ArenaSync::with_chunk_size_range(CHUNK_SIZE, usize::MAX));
+
+        let threads: Vec<_> = (1..=2u8)
+            .map(|tag| {
+                let arena = arena.clone();
+                thread::spawn(move || {
+                    let a = alloc_tagged(&arena, tag);
+                    let b = alloc_tagged(&arena, tag);
+                    [(a, tag), (b, tag)]
+                })
+            })
+            .collect();
+
+        let mut allocations = Vec::new();
+        for thread in threads {
+            allocations.extend(thread.join().unwrap());
+        }
+        check_tagged(&allocations);
+
+        assert!(arena.allocated_bytes() >= 4 * LAYOUT.size());
+        finish(arena);
+    });
+}
+
+#[test]
+fn loom_alloc_and_dealloc() {
+    loom::model(|| {
+        let arena = Arc::new(ArenaSync::with_chunk_size_range(CHUNK_SIZE, usize::MAX));
+        let kept = alloc_tagged(&arena, 1);
+
+        let thread = {
+            let arena = arena.clone();
+            thread::spawn(move || {
+                let a = alloc_tagged(&arena, 2);
+                // Safety: `a` is the allocation of `LAYOUT` made above.
+                unsafe {
+                    arena.dealloc(NonNull::new_unchecked(a as *mut u8), LAYOUT.size());
+                }
+            })
+        };
+
+        let b = alloc_tagged(&arena, 3);
+        thread.join().unwrap();
+
+        check_tagged(&[(kept, 1), (b, 3)]);
+        finish(arena);
+    });
+}
+
+#[test]
+fn loom_reset_unchecked_with_stats() {
+    loom::model(|| {
+        let arena = Arc::new(ArenaSync::with_chunk_size_range(CHUNK_SIZE, usize::MAX));
+        alloc_tagged(&arena, 1);
+        alloc_tagged(&arena, 1);
+
+        let reader = {
+            let arena = arena.clone();
+            thread::spawn(move || arena.allocated_bytes())
+        };
+
+        // Memory allocated before reset is never touched again.
+        // Safety: `Global` is used for all allocations and resets of the arena.
+        unsafe { arena.reset_unchecked(true, &Global) };
+
+        let allocated = reader.join().unwrap();
+        assert!(allocated <= 2 * CHUNK_SIZE);
+        assert_eq!(arena.allocated_bytes(), 0);
+
+        finish(arena);
+    });
+}
+
+#[test]
+fn loom_reset_unchecked_with_alloc() {
+    loom::model(|| {
+        let arena = Arc::new(ArenaSync::with_chunk_size_range(CHUNK_SIZE, usize::MAX));
+        alloc_tagged(&arena, 1);
+
+        let thread = {
+            let arena = arena.clone();
+            thread::spawn(move || {
+                // Allocation may happen before or after the reset,
+                // so memory is not touched.
+                let _ = alloc(&arena, LAYOUT);
+            })
+        };
+
+        // Safety: memory allocated before reset is never touched again.
+        unsafe { arena.reset_unchecked(true, &Global) };
+        let a = alloc_tagged(&arena, 3);
+
+        thread.join().unwrap();
+        check_tagged(&[(a, 3)]);
+
+        finish(arena);
+    });
+}
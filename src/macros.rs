@@ -0,0 +1,80 @@
+//! This module provides constructor macros for
+//! collections allocated from blink allocators.
+
+/// Creates [`Vec`](allocator_api2::vec::Vec) allocated from
+/// specified allocator with the same syntax as `vec!`.
+///
+/// Any allocator can be used, including [`Blink`](crate::Blink),
+/// [`BlinkAlloc`](crate::BlinkAlloc) and references to them.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "alloc")] fn main() {
+/// use blink_alloc::{blink_vec, Blink, BlinkAlloc};
+///
+/// let mut blink = BlinkAlloc::new();
+/// let vec = blink_vec![in &blink; 1, 2, 3];
+/// assert_eq!(vec, [1, 2, 3]);
+///
+/// let zeros = blink_vec![in &blink; 0u8; 16];
+/// assert_eq!(zeros.len(), 16);
+///
+/// let empty: allocator_api2::vec::Vec<u32, _> = blink_vec![in &blink];
+/// assert!(empty.is_empty());
+///
+/// drop((vec, zeros, empty));
+/// blink.reset();
+///
+/// let blink = Blink::new();
+/// let names = blink_vec![in &blink; "foo", "bar"];
+/// assert_eq!(names, ["foo", "bar"]);
+/// # }
+/// # #[cfg(not(feature = "alloc"))] fn main() {}
+/// ```
+#[cfg(all(feature = "alloc", not(no_global_oom_handling)))]
+#[macro_export]
+macro_rules! blink_vec {
+    (in $alloc:expr $(;)?) => (
+        $crate::__private::Vec::new_in($alloc)
+    );
+    (in $alloc:expr; $elem:expr; $n:expr) => (
+        $crate::__private::from_elem_in($elem, $n, $alloc)
+    );
+    (in $alloc:expr; $($x:expr),+ $(,)?) => (
+        $crate::__private::Box::<[_], _>::into_vec(
+            $crate::__private::Box::slice(
+                $crate::__private::Box::new_in([$($x),+], $alloc)
+            )
+        )
+    );
+}
+
+/// Creates [`Box`](allocator_api2::boxed::Box) allocated from
+/// specified allocator.
+///
+/// Any allocator can be used, including [`Blink`](crate::Blink),
+/// [`BlinkAlloc`](crate::BlinkAlloc) and references to them.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "alloc")] fn main() {
+/// use blink_alloc::{blink_box, BlinkAlloc};
+///
+/// let mut blink = BlinkAlloc::new();
+/// let value = blink_box!(in &blink; 42);
+/// assert_eq!(*value, 42);
+///
+/// drop(value);
+/// blink.reset();
+/// # }
+/// # #[cfg(not(feature = "alloc"))] fn main() {}
+/// ```
+#[cfg(all(feature = "alloc", not(no_global_oom_handling)))]
+#[macro_export]
+macro_rules! blink_box {
+    (in $alloc:expr; $value:expr $(,)?) => {
+        $crate::__private::Box::new_in($value, $alloc)
+    };
+}
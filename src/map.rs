@@ -0,0 +1,504 @@
+//! This module provides ordered map that keeps entries in blink allocator.
+
+use core::{
+    borrow::Borrow,
+    cmp::Ordering,
+    fmt,
+    iter::FusedIterator,
+    ops::{Bound, RangeBounds},
+    slice,
+};
+
+use allocator_api2::{alloc::Allocator, vec::Vec};
+
+/// Ordered map that keeps entries sorted by key
+/// in a vector allocated from allocator `A`.
+///
+/// Intended to be used with blink allocators for short-lived indexes,
+/// where `BTreeMap` would allocate its nodes from the heap.
+///
+/// Lookups are binary searches over contiguous memory.
+/// Insertions and removals shift entries after the position,
+/// which is cheap for maps built in key order
+/// or small enough to fit in cache.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "alloc")] fn main() {
+/// use blink_alloc::{BlinkAlloc, BlinkMap};
+///
+/// let mut blink = BlinkAlloc::new();
+///
+/// let mut map = BlinkMap::new_in(&blink);
+/// map.insert("b", 2);
+/// map.insert("c", 3);
+/// map.insert("a", 1);
+///
+/// assert_eq!(map.get("b"), Some(&2));
+/// assert_eq!(map.keys().copied().collect::<Vec<_>>(), ["a", "b", "c"]);
+/// assert_eq!(map.range("b"..).map(|(_, v)| *v).sum::<i32>(), 5);
+///
+/// drop(map);
+/// blink.reset();
+/// # }
+/// # #[cfg(not(feature = "alloc"))] fn main() {}
+/// ```
+pub struct BlinkMap<K, V, A: Allocator> {
+    entries: Vec<(K, V), A>,
+}
+
+impl<K, V, A> BlinkMap<K, V, A>
+where
+    A: Allocator,
+{
+    /// Creates new empty map that allocates from `alloc`.
+    #[inline(always)]
+    pub fn new_in(alloc: A) -> Self {
+        BlinkMap {
+            entries: Vec::new_in(alloc),
+        }
+    }
+
+    /// Creates new empty map with space for at least `capacity` entries.
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    pub fn with_capacity_in(capacity: usize, alloc: A) -> Self {
+        BlinkMap {
+            entries: Vec::with_capacity_in(capacity, alloc),
+        }
+    }
+
+    /// Returns reference to the allocator.
+    #[inline(always)]
+    pub fn allocator(&self) -> &A {
+        self.entries.allocator()
+    }
+
+    /// Returns number of entries in the map.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the map contains no entries.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns number of entries map can hold
+    /// without allocating more memory.
+    #[inline(always)]
+    pub fn capacity(&self) -> usize {
+        self.entries.capacity()
+    }
+
+    /// Reserves capacity for at least `additional` more entries.
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    pub fn reserve(&mut self, additional: usize) {
+        self.entries.reserve(additional);
+    }
+
+    /// Removes all entries.
+    /// Memory from allocator is kept for reuse.
+    #[inline(always)]
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Returns iterator over entries in key order.
+    #[inline(always)]
+    pub fn iter(&self) -> BlinkMapIter<'_, K, V> {
+        BlinkMapIter {
+            iter: self.entries.iter(),
+        }
+    }
+
+    /// Returns iterator over entries in key order
+    /// with mutable references to values.
+    #[inline(always)]
+    pub fn iter_mut(&mut self) -> BlinkMapIterMut<'_, K, V> {
+        BlinkMapIterMut {
+            iter: self.entries.iter_mut(),
+        }
+    }
+
+    /// Returns iterator over keys in order.
+    #[inline(always)]
+    pub fn keys(&self) -> impl DoubleEndedIterator<Item = &K> + ExactSizeIterator {
+        self.entries.iter().map(|(k, _)| k)
+    }
+
+    /// Returns iterator over values in key order.
+    #[inline(always)]
+    pub fn values(&self) -> impl DoubleEndedIterator<Item = &V> + ExactSizeIterator {
+        self.entries.iter().map(|(_, v)| v)
+    }
+
+    /// Returns iterator over mutable references to values in key order.
+    #[inline(always)]
+    pub fn values_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut V> + ExactSizeIterator {
+        self.entries.iter_mut().map(|(_, v)| v)
+    }
+
+    /// Returns entry with the smallest key.
+    #[inline(always)]
+    pub fn first_key_value(&self) -> Option<(&K, &V)> {
+        self.entries.first().map(|(k, v)| (k, v))
+    }
+
+    /// Returns entry with the largest key.
+    #[inline(always)]
+    pub fn last_key_value(&self) -> Option<(&K, &V)> {
+        self.entries.last().map(|(k, v)| (k, v))
+    }
+
+    /// Removes and returns entry with the smallest key.
+    #[inline]
+    pub fn pop_first(&mut self) -> Option<(K, V)> {
+        if self.entries.is_empty() {
+            None
+        } else {
+            Some(self.entries.remove(0))
+        }
+    }
+
+    /// Removes and returns entry with the largest key.
+    #[inline(always)]
+    pub fn pop_last(&mut self) -> Option<(K, V)> {
+        self.entries.pop()
+    }
+
+    /// Retains only entries for which `f` returns `true`.
+    #[inline]
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        self.entries.retain_mut(|(k, v)| f(k, v));
+    }
+}
+
+impl<K, V, A> BlinkMap<K, V, A>
+where
+    K: Ord,
+    A: Allocator,
+{
+    /// Searches for the key.
+    /// Returns index of the entry if found,
+    /// otherwise index where entry with the key would be inserted.
+    #[inline]
+    fn search<Q>(&self, key: &Q) -> Result<usize, usize>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.entries.binary_search_by(|(k, _)| k.borrow().cmp(key))
+    }
+
+    /// Returns index of the first entry that is within the bound.
+    #[inline]
+    fn lower_bound<Q>(&self, bound: Bound<&Q>) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match bound {
+            Bound::Included(key) => self
+                .entries
+                .partition_point(|(k, _)| k.borrow().cmp(key) == Ordering::Less),
+            Bound::Excluded(key) => self
+                .entries
+                .partition_point(|(k, _)| k.borrow().cmp(key) != Ordering::Greater),
+            Bound::Unbounded => 0,
+        }
+    }
+
+    /// Returns index past the last entry that is within the bound.
+    #[inline]
+    fn upper_bound<Q>(&self, bound: Bound<&Q>) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match bound {
+            Bound::Included(key) => self
+                .entries
+                .partition_point(|(k, _)| k.borrow().cmp(key) != Ordering::Greater),
+            Bound::Excluded(key) => self
+                .entries
+                .partition_point(|(k, _)| k.borrow().cmp(key) == Ordering::Less),
+            Bound::Unbounded => self.entries.len(),
+        }
+    }
+
+    /// Inserts key-value pair into the map.
+    ///
+    /// If the map already had the key, value is replaced
+    /// and old value is returned. The key is not updated.
+    ///
+    /// Inserting keys in ascending order appends to the end
+    /// without shifting entries.
+    #[cfg(not(no_global_oom_handling))]
+    #[inline]
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        // Fast path for keys inserted in order.
+        let idx = match self.entries.last() {
+            None => 0,
+            Some((last, _)) if *last < key => self.entries.len(),
+            Some(_) => match self.search(&key) {
+                Ok(idx) => return Some(core::mem::replace(&mut self.entries[idx].1, value)),
+                Err(idx) => idx,
+            },
+        };
+        self.entries.insert(idx, (key, value));
+        None
+    }
+
+    /// Returns reference to the value corresponding to the key.
+    #[inline]
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let idx = self.search(key).ok()?;
+        Some(&self.entries[idx].1)
+    }
+
+    /// Returns key-value pair corresponding to the key.
+    #[inline]
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let idx = self.search(key).ok()?;
+        let (k, v) = &self.entries[idx];
+        Some((k, v))
+    }
+
+    /// Returns mutable reference to the value corresponding to the key.
+    #[inline]
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let idx = self.search(key).ok()?;
+        Some(&mut self.entries[idx].1)
+    }
+
+    /// Returns `true` if the map contains value for the key.
+    #[inline]
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.search(key).is_ok()
+    }
+
+    /// Removes the key from the map,
+    /// returning the value if the key was in the map.
+    #[inline]
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.remove_entry(key).map(|(_, v)| v)
+    }
+
+    /// Removes the key from the map,
+    /// returning stored key and value if the key was in the map.
+    #[inline]
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let idx = self.search(key).ok()?;
+        Some(self.entries.remove(idx))
+    }
+
+    /// Returns iterator over entries with keys in the range, in key order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if range start is greater than range end.
+    #[inline]
+    pub fn range<Q, R>(&self, range: R) -> BlinkMapIter<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let start = self.lower_bound(range.start_bound());
+        let end = self.upper_bound(range.end_bound());
+        assert!(start <= end, "range start is greater than range end");
+        BlinkMapIter {
+            iter: self.entries[start..end].iter(),
+        }
+    }
+
+    /// Returns iterator over entries with keys in the range, in key order,
+    /// with mutable references to values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if range start is greater than range end.
+    #[inline]
+    pub fn range_mut<Q, R>(&mut self, range: R) -> BlinkMapIterMut<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let start = self.lower_bound(range.start_bound());
+        let end = self.upper_bound(range.end_bound());
+        assert!(start <= end, "range start is greater than range end");
+        BlinkMapIterMut {
+            iter: self.entries[start..end].iter_mut(),
+        }
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+impl<K, V, A> Extend<(K, V)> for BlinkMap<K, V, A>
+where
+    K: Ord,
+    A: Allocator,
+{
+    #[inline]
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K, V, A> fmt::Debug for BlinkMap<K, V, A>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+    A: Allocator,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, K, V, A> IntoIterator for &'a BlinkMap<K, V, A>
+where
+    A: Allocator,
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = BlinkMapIter<'a, K, V>;
+
+    #[inline(always)]
+    fn into_iter(self) -> BlinkMapIter<'a, K, V> {
+        self.iter()
+    }
+}
+
+impl<'a, K, V, A> IntoIterator for &'a mut BlinkMap<K, V, A>
+where
+    A: Allocator,
+{
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = BlinkMapIterMut<'a, K, V>;
+
+    #[inline(always)]
+    fn into_iter(self) -> BlinkMapIterMut<'a, K, V> {
+        self.iter_mut()
+    }
+}
+
+impl<K, V, A> IntoIterator for BlinkMap<K, V, A>
+where
+    A: Allocator,
+{
+    type Item = (K, V);
+    type IntoIter = allocator_api2::vec::IntoIter<(K, V), A>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+/// Iterator over entries of [`BlinkMap`] in key order.
+pub struct BlinkMapIter<'a, K, V> {
+    iter: slice::Iter<'a, (K, V)>,
+}
+
+impl<K, V> Clone for BlinkMapIter<'_, K, V> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        BlinkMapIter {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for BlinkMapIter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<(&'a K, &'a V)> {
+        self.iter.next().map(|(k, v)| (k, v))
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for BlinkMapIter<'_, K, V> {
+    #[inline(always)]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|(k, v)| (k, v))
+    }
+}
+
+impl<K, V> ExactSizeIterator for BlinkMapIter<'_, K, V> {}
+
+impl<K, V> FusedIterator for BlinkMapIter<'_, K, V> {}
+
+/// Iterator over entries of [`BlinkMap`] in key order
+/// with mutable references to values.
+pub struct BlinkMapIterMut<'a, K, V> {
+    iter: slice::IterMut<'a, (K, V)>,
+}
+
+impl<'a, K, V> Iterator for BlinkMapIterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<(&'a K, &'a mut V)> {
+        self.iter.next().map(|(k, v)| (&*k, v))
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for BlinkMapIterMut<'_, K, V> {
+    #[inline(always)]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|(k, v)| (&*k, v))
+    }
+}
+
+impl<K, V> ExactSizeIterator for BlinkMapIterMut<'_, K, V> {}
+
+impl<K, V> FusedIterator for BlinkMapIterMut<'_, K, V> {}
@@ -0,0 +1,269 @@
+//! This module provides pool of same-sized objects
+//! with slots carved from blink allocator memory.
+
+use core::{
+    alloc::Layout,
+    cell::Cell,
+    mem::{self, ManuallyDrop},
+    ptr::NonNull,
+};
+
+#[cfg(all(feature = "nightly", feature = "alloc"))]
+use alloc::alloc::Global;
+
+#[cfg(all(not(feature = "nightly"), feature = "alloc"))]
+use allocator_api2::alloc::Global;
+
+use crate::api::BlinkAllocator;
+
+#[cfg(feature = "alloc")]
+use crate::local::BlinkAlloc;
+
+#[cfg(not(no_global_oom_handling))]
+use crate::oom::handle_alloc_error;
+
+/// Number of slots in the first batch.
+const MIN_BATCH: usize = 16;
+
+/// Maximal number of slots in a batch.
+const MAX_BATCH: usize = 1024;
+
+/// Slot holds either a live value or a link to the next free slot.
+union Slot<T> {
+    value: ManuallyDrop<T>,
+    next: Option<NonNull<Slot<T>>>,
+}
+
+switch_alloc_default! {
+    /// Pool of objects of type `T` with slots allocated from blink allocator.
+    ///
+    /// Slots are carved in batches from allocator memory.
+    /// Freed slots are kept in a free list and reused by following allocations,
+    /// so objects can be allocated and freed at high rate
+    /// without growing the arena.
+    /// All slots are reclaimed at once when pool is [reset](BlinkPool::reset).
+    ///
+    /// Values that are not freed before reset or drop are not dropped.
+    ///
+    /// Uses [`BlinkAlloc`] by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::BlinkPool;
+    ///
+    /// struct Particle { pos: [f32; 2], vel: [f32; 2] }
+    ///
+    /// let mut pool = BlinkPool::new();
+    /// let a = pool.alloc(Particle { pos: [0.0; 2], vel: [1.0; 2] });
+    /// let a_ptr: *const Particle = a;
+    ///
+    /// // Safety: `a` is allocated from `pool` and not used after.
+    /// unsafe { pool.free(a) };
+    ///
+    /// // Freed slot is reused.
+    /// let b = pool.alloc(Particle { pos: [1.0; 2], vel: [0.0; 2] });
+    /// assert_eq!(b as *const Particle, a_ptr);
+    /// assert_eq!(pool.len(), 1);
+    ///
+    /// pool.reset();
+    /// assert!(pool.is_empty());
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    pub struct BlinkPool<T, A = +BlinkAlloc<Global>> {
+        free: Cell<Option<NonNull<Slot<T>>>>,
+        batch_ptr: Cell<Option<NonNull<Slot<T>>>>,
+        batch_left: Cell<usize>,
+        batch_size: Cell<usize>,
+        len: Cell<usize>,
+        alloc: A,
+    }
+}
+
+// Safety: `BlinkPool` is not auto-send because of raw pointers.
+// All pointers point into memory owned by the allocator
+// that is moved together with the pool.
+unsafe impl<T, A> Send for BlinkPool<T, A>
+where
+    T: Send,
+    A: Send,
+{
+}
+
+impl<T, A> Default for BlinkPool<T, A>
+where
+    A: Default,
+{
+    #[inline(always)]
+    fn default() -> Self {
+        BlinkPool::new_in(A::default())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> BlinkPool<T, BlinkAlloc<Global>> {
+    /// Creates new pool with `BlinkAlloc` baked by `Global` allocator.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        BlinkPool::new_in(BlinkAlloc::new())
+    }
+}
+
+impl<T, A> BlinkPool<T, A> {
+    /// Creates new pool with provided allocator instance.
+    #[inline(always)]
+    pub const fn new_in(alloc: A) -> Self {
+        BlinkPool {
+            free: Cell::new(None),
+            batch_ptr: Cell::new(None),
+            batch_left: Cell::new(0),
+            batch_size: Cell::new(MIN_BATCH),
+            len: Cell::new(0),
+            alloc,
+        }
+    }
+
+    /// Returns reference to allocator instance.
+    #[inline(always)]
+    pub fn allocator(&self) -> &A {
+        &self.alloc
+    }
+
+    /// Returns number of live objects in the pool.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.len.get()
+    }
+
+    /// Returns `true` if pool has no live objects.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len.get() == 0
+    }
+
+    /// Drops the value and returns its slot to the pool.
+    ///
+    /// # Safety
+    ///
+    /// `value` must be allocated from this pool and not freed yet.
+    /// The value must not be accessed after this call.
+    #[inline]
+    pub unsafe fn free(&self, value: &mut T) {
+        let slot = NonNull::from(value).cast::<Slot<T>>();
+
+        // Safety: `value` is the live value in the slot of this pool.
+        unsafe {
+            ManuallyDrop::drop(&mut (*slot.as_ptr()).value);
+            (*slot.as_ptr()).next = self.free.get();
+        }
+        self.free.set(Some(slot));
+        self.len.set(self.len.get() - 1);
+    }
+}
+
+impl<T, A> BlinkPool<T, A>
+where
+    A: BlinkAllocator,
+{
+    /// Moves value into a slot of the pool.
+    /// Returns reference to the value.
+    #[cfg(not(no_global_oom_handling))]
+    #[inline]
+    #[allow(clippy::mut_from_ref)]
+    pub fn alloc(&self, value: T) -> &mut T {
+        match self.take_slot() {
+            Some(slot) => Self::write(slot, value),
+            None => handle_alloc_error(Layout::new::<Slot<T>>()),
+        }
+    }
+
+    /// Moves value into a slot of the pool.
+    /// Returns reference to the value.
+    /// If allocation fails, returns `Err(value)`.
+    #[inline]
+    #[allow(clippy::mut_from_ref)]
+    pub fn try_alloc(&self, value: T) -> Result<&mut T, T> {
+        match self.take_slot() {
+            Some(slot) => Ok(Self::write(slot, value)),
+            None => Err(value),
+        }
+    }
+
+    /// Forgets all objects and resets associated allocator instance.
+    ///
+    /// Values that were not freed are not dropped.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.free.set(None);
+        self.batch_ptr.set(None);
+        self.batch_left.set(0);
+        self.len.set(0);
+        self.alloc.reset();
+    }
+
+    #[inline(always)]
+    fn write<'a>(slot: NonNull<Slot<T>>, value: T) -> &'a mut T {
+        // Safety: slot is free and not used by anything else.
+        unsafe {
+            slot.as_ptr().write(Slot {
+                value: ManuallyDrop::new(value),
+            });
+            &mut (*slot.as_ptr()).value
+        }
+    }
+
+    #[inline(always)]
+    fn take_slot(&self) -> Option<NonNull<Slot<T>>> {
+        let slot = match self.free.get() {
+            Some(slot) => {
+                // Safety: slots in the free list hold links.
+                self.free.set(unsafe { (*slot.as_ptr()).next });
+                slot
+            }
+            None => self.carve_slot()?,
+        };
+        self.len.set(self.len.get() + 1);
+        Some(slot)
+    }
+
+    /// Takes slot from the current batch, allocating new batch if needed.
+    #[inline]
+    fn carve_slot(&self) -> Option<NonNull<Slot<T>>> {
+        if self.batch_left.get() == 0 {
+            self.new_batch()?;
+        }
+
+        let slot = self.batch_ptr.get()?;
+        self.batch_left.set(self.batch_left.get() - 1);
+
+        // Safety: batch has at least one more slot.
+        self.batch_ptr.set(Some(unsafe {
+            NonNull::new_unchecked(slot.as_ptr().add(1))
+        }));
+        Some(slot)
+    }
+
+    #[cold]
+    fn new_batch(&self) -> Option<()> {
+        let size = self.batch_size.get();
+        let batch = Layout::array::<Slot<T>>(size)
+            .ok()
+            .and_then(|layout| self.alloc.allocate(layout).ok());
+
+        let ptr = match batch {
+            Some(ptr) => {
+                self.batch_size.set((size * 2).min(MAX_BATCH));
+                ptr
+            }
+            // Fallback to single slot when batch can't be allocated.
+            None => self.alloc.allocate(Layout::new::<Slot<T>>()).ok()?,
+        };
+
+        // Allocator may return more memory than requested.
+        self.batch_ptr.set(Some(ptr.cast()));
+        self.batch_left.set(ptr.len() / mem::size_of::<Slot<T>>());
+        Some(())
+    }
+}
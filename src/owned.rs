@@ -0,0 +1,196 @@
+//! This module provides container that owns [`Blink`]
+//! together with root value borrowing from it.
+
+use core::mem::ManuallyDrop;
+
+use alloc::boxed::Box;
+
+#[cfg(feature = "nightly")]
+use alloc::alloc::Global;
+
+#[cfg(not(feature = "nightly"))]
+use allocator_api2::alloc::Global;
+
+use crate::{blink::Blink, local::BlinkAlloc};
+
+/// Family of root types stored in [`OwnedBlink`].
+///
+/// `Root<'a>` is the type of the root value that borrows
+/// data allocated from arena with lifetime `'a`.
+///
+/// # Example
+///
+/// ```
+/// use blink_alloc::BlinkRoot;
+///
+/// enum Expr<'a> {
+///     Num(u32),
+///     Add(&'a Expr<'a>, &'a Expr<'a>),
+/// }
+///
+/// struct ExprRoot;
+///
+/// impl BlinkRoot for ExprRoot {
+///     type Root<'a> = &'a Expr<'a>;
+/// }
+/// ```
+pub trait BlinkRoot {
+    /// Root value type borrowing from arena with lifetime `'a`.
+    type Root<'a>;
+}
+
+/// Container that owns [`Blink`] and a root value
+/// that borrows data allocated from it.
+///
+/// Unlike pair of [`Blink`] and references to its values,
+/// [`OwnedBlink`] can be returned from functions and stored in structs.
+/// For example it can hold arena-allocated AST built by a parser.
+///
+/// Root value is accessed through closures receiving
+/// it with arena's lifetime.
+/// Root value is dropped before the arena.
+///
+/// # Example
+///
+/// ```
+/// use blink_alloc::{Blink, BlinkRoot, OwnedBlink};
+///
+/// enum Expr<'a> {
+///     Num(u32),
+///     Add(&'a Expr<'a>, &'a Expr<'a>),
+/// }
+///
+/// fn eval(expr: &Expr<'_>) -> u32 {
+///     match expr {
+///         Expr::Num(n) => *n,
+///         Expr::Add(lhs, rhs) => eval(lhs) + eval(rhs),
+///     }
+/// }
+///
+/// struct ExprRoot;
+///
+/// impl BlinkRoot for ExprRoot {
+///     type Root<'a> = &'a Expr<'a>;
+/// }
+///
+/// fn parse(numbers: &[u32]) -> OwnedBlink<ExprRoot> {
+///     OwnedBlink::new(|blink| {
+///         let mut expr: &Expr<'_> = blink.put_no_drop(Expr::Num(0));
+///         for &n in numbers {
+///             let num = blink.put_no_drop(Expr::Num(n));
+///             expr = blink.put_no_drop(Expr::Add(expr, num));
+///         }
+///         expr
+///     })
+/// }
+///
+/// let ast = parse(&[1, 2, 3]);
+/// assert_eq!(ast.with_root(|expr| eval(expr)), 6);
+/// ```
+pub struct OwnedBlink<R: BlinkRoot, A = BlinkAlloc<Global>> {
+    // Declared before `blink` to be dropped first.
+    // `'static` is never exposed, root is accessed only
+    // with lifetime bound to `self`.
+    root: ManuallyDrop<R::Root<'static>>,
+
+    // Boxed so that root may hold references to the `Blink` itself.
+    blink: Box<Blink<A>>,
+}
+
+impl<R, A> Drop for OwnedBlink<R, A>
+where
+    R: BlinkRoot,
+{
+    #[inline(always)]
+    fn drop(&mut self) {
+        // Safety: root is not used after this point.
+        unsafe { ManuallyDrop::drop(&mut self.root) }
+    }
+}
+
+impl<R> OwnedBlink<R>
+where
+    R: BlinkRoot,
+{
+    /// Creates new [`OwnedBlink`] with `Blink` using `BlinkAlloc` baked by
+    /// `Global` allocator.
+    /// Root value is constructed by the closure.
+    #[inline(always)]
+    pub fn new<F>(f: F) -> Self
+    where
+        F: for<'a> FnOnce(&'a Blink) -> R::Root<'a>,
+    {
+        OwnedBlink::with_blink(Blink::new(), f)
+    }
+}
+
+impl<R, A> OwnedBlink<R, A>
+where
+    R: BlinkRoot,
+{
+    /// Creates new [`OwnedBlink`] from existing [`Blink`].
+    /// Root value is constructed by the closure.
+    #[inline]
+    pub fn with_blink<F>(blink: Blink<A>, f: F) -> Self
+    where
+        F: for<'a> FnOnce(&'a Blink<A>) -> R::Root<'a>,
+    {
+        let blink = Box::new(blink);
+        let root = f(&blink);
+
+        // Safety: `R::Root<'a>` and `R::Root<'static>` differ only in lifetime.
+        // Root is never accessed with lifetime longer than borrow of `self`
+        // and dropped before `Blink`.
+        let root = unsafe { core::mem::transmute_copy(&ManuallyDrop::new(root)) };
+
+        OwnedBlink {
+            root: ManuallyDrop::new(root),
+            blink,
+        }
+    }
+
+    /// Returns reference to the owned [`Blink`].
+    #[inline(always)]
+    pub fn blink(&self) -> &Blink<A> {
+        &self.blink
+    }
+
+    /// Calls closure with reference to the root value.
+    #[inline(always)]
+    pub fn with_root<F, T>(&self, f: F) -> T
+    where
+        F: for<'a> FnOnce(&'a R::Root<'a>) -> T,
+    {
+        let root: *const R::Root<'static> = &*self.root;
+
+        // Safety: lifetime is bound to borrow of `self`
+        // and can't be chosen by the caller.
+        f(unsafe { &*root.cast::<R::Root<'_>>() })
+    }
+
+    /// Calls closure with mutable reference to the root value
+    /// and [`Blink`] to allocate new values that root may reference.
+    #[inline(always)]
+    pub fn with_root_mut<F, T>(&mut self, f: F) -> T
+    where
+        F: for<'a> FnOnce(&'a Blink<A>, &'a mut R::Root<'a>) -> T,
+    {
+        let root: *mut R::Root<'static> = &mut *self.root;
+
+        // Safety: lifetime is bound to borrow of `self`
+        // and can't be chosen by the caller.
+        f(&self.blink, unsafe { &mut *root.cast::<R::Root<'_>>() })
+    }
+
+    /// Drops root value and returns owned [`Blink`].
+    #[inline]
+    pub fn into_blink(self) -> Blink<A> {
+        let mut me = ManuallyDrop::new(self);
+
+        // Safety: `me` is not used after fields are taken.
+        unsafe {
+            ManuallyDrop::drop(&mut me.root);
+            *core::ptr::read(&me.blink)
+        }
+    }
+}
@@ -0,0 +1,310 @@
+//! This module provides slice and string handles
+//! that co-own the arena they are allocated from.
+
+use core::{
+    borrow::Borrow,
+    fmt,
+    ops::{Deref, DerefMut},
+};
+
+use allocator_api2::{boxed::Box, vec::Vec};
+
+#[cfg(feature = "nightly")]
+use alloc::alloc::Global;
+
+#[cfg(not(feature = "nightly"))]
+use allocator_api2::alloc::Global;
+
+use crate::{shared_blink::SharedBlinkAlloc, source::ChunkSource};
+
+switch_alloc_default! {
+    /// Slice allocated from [`SharedBlinkAlloc`]
+    /// that keeps the arena alive.
+    ///
+    /// Unlike references returned by [`Blink`](crate::Blink),
+    /// [`OwnedSlice`] can escape the function that created the arena,
+    /// so results can be returned without copying them to the heap.
+    /// Elements are dropped when slice is dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use blink_alloc::{OwnedSlice, SharedBlinkAlloc};
+    ///
+    /// fn parse(input: &str) -> OwnedSlice<u32> {
+    ///     let blink = SharedBlinkAlloc::new();
+    ///     OwnedSlice::from_iter_in(input.split(',').map(|s| s.parse().unwrap()), blink)
+    /// }
+    ///
+    /// let numbers = parse("1,2,3");
+    /// assert_eq!(*numbers, [1, 2, 3]);
+    /// ```
+    pub struct OwnedSlice<T, A: ChunkSource = +Global> {
+        boxed: Box<[T], SharedBlinkAlloc<A>>,
+    }
+}
+
+impl<T, A> OwnedSlice<T, A>
+where
+    A: ChunkSource,
+{
+    /// Collects items from iterator into slice allocated from `blink`.
+    #[cfg(not(no_global_oom_handling))]
+    #[inline]
+    pub fn from_iter_in<I>(iter: I, blink: SharedBlinkAlloc<A>) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let iter = iter.into_iter();
+        let mut vec = Vec::with_capacity_in(iter.size_hint().0, blink);
+        vec.extend(iter);
+        OwnedSlice::from(vec)
+    }
+
+    /// Copies slice into memory allocated from `blink`.
+    #[cfg(not(no_global_oom_handling))]
+    #[inline]
+    pub fn copy_from_slice_in(slice: &[T], blink: SharedBlinkAlloc<A>) -> Self
+    where
+        T: Copy,
+    {
+        let mut vec = Vec::with_capacity_in(slice.len(), blink);
+        vec.extend_from_slice(slice);
+        OwnedSlice::from(vec)
+    }
+
+    /// Returns reference to the arena this slice is allocated from.
+    #[inline(always)]
+    pub fn allocator(&self) -> &SharedBlinkAlloc<A> {
+        Box::allocator(&self.boxed)
+    }
+
+    /// Returns boxed slice co-owning the arena.
+    #[inline(always)]
+    pub fn into_boxed_slice(self) -> Box<[T], SharedBlinkAlloc<A>> {
+        self.boxed
+    }
+}
+
+impl<T, A> From<Box<[T], SharedBlinkAlloc<A>>> for OwnedSlice<T, A>
+where
+    A: ChunkSource,
+{
+    #[inline(always)]
+    fn from(boxed: Box<[T], SharedBlinkAlloc<A>>) -> Self {
+        OwnedSlice { boxed }
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+impl<T, A> From<Vec<T, SharedBlinkAlloc<A>>> for OwnedSlice<T, A>
+where
+    A: ChunkSource,
+{
+    /// Shrinks vector in place and takes its memory.
+    #[inline(always)]
+    fn from(vec: Vec<T, SharedBlinkAlloc<A>>) -> Self {
+        OwnedSlice {
+            boxed: vec.into_boxed_slice(),
+        }
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+impl<T, A> Clone for OwnedSlice<T, A>
+where
+    T: Clone,
+    A: ChunkSource,
+{
+    /// Clones elements into the same arena.
+    #[inline]
+    fn clone(&self) -> Self {
+        OwnedSlice {
+            boxed: self.boxed.clone(),
+        }
+    }
+}
+
+impl<T, A> Deref for OwnedSlice<T, A>
+where
+    A: ChunkSource,
+{
+    type Target = [T];
+
+    #[inline(always)]
+    fn deref(&self) -> &[T] {
+        &self.boxed
+    }
+}
+
+impl<T, A> DerefMut for OwnedSlice<T, A>
+where
+    A: ChunkSource,
+{
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut [T] {
+        &mut self.boxed
+    }
+}
+
+impl<T, A> AsRef<[T]> for OwnedSlice<T, A>
+where
+    A: ChunkSource,
+{
+    #[inline(always)]
+    fn as_ref(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T, A> Borrow<[T]> for OwnedSlice<T, A>
+where
+    A: ChunkSource,
+{
+    #[inline(always)]
+    fn borrow(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T, A> fmt::Debug for OwnedSlice<T, A>
+where
+    T: fmt::Debug,
+    A: ChunkSource,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <[T] as fmt::Debug>::fmt(self, f)
+    }
+}
+
+switch_alloc_default! {
+    /// String allocated from [`SharedBlinkAlloc`]
+    /// that keeps the arena alive.
+    ///
+    /// See [`OwnedSlice`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use blink_alloc::{OwnedStr, SharedBlinkAlloc};
+    ///
+    /// fn greeting(name: &str) -> OwnedStr {
+    ///     let blink = SharedBlinkAlloc::new();
+    ///     let hello = OwnedStr::copy_in("hello, ", blink.clone());
+    ///     OwnedStr::concat_in(&[&hello, name], blink)
+    /// }
+    ///
+    /// assert_eq!(&*greeting("blink"), "hello, blink");
+    /// ```
+    pub struct OwnedStr<A: ChunkSource = +Global> {
+        bytes: OwnedSlice<u8, A>,
+    }
+}
+
+impl<A> OwnedStr<A>
+where
+    A: ChunkSource,
+{
+    /// Copies string into memory allocated from `blink`.
+    #[cfg(not(no_global_oom_handling))]
+    #[inline]
+    pub fn copy_in(string: &str, blink: SharedBlinkAlloc<A>) -> Self {
+        OwnedStr {
+            bytes: OwnedSlice::copy_from_slice_in(string.as_bytes(), blink),
+        }
+    }
+
+    /// Concatenates strings into memory allocated from `blink`.
+    #[cfg(not(no_global_oom_handling))]
+    #[inline]
+    pub fn concat_in(parts: &[&str], blink: SharedBlinkAlloc<A>) -> Self {
+        let len = parts.iter().map(|part| part.len()).sum();
+        let mut vec = Vec::with_capacity_in(len, blink);
+        for part in parts {
+            vec.extend_from_slice(part.as_bytes());
+        }
+        OwnedStr {
+            bytes: OwnedSlice::from(vec),
+        }
+    }
+
+    /// Returns string slice.
+    #[inline(always)]
+    pub fn as_str(&self) -> &str {
+        // Safety: bytes are copied from valid UTF-8 strings.
+        unsafe { core::str::from_utf8_unchecked(&self.bytes) }
+    }
+
+    /// Returns reference to the arena this string is allocated from.
+    #[inline(always)]
+    pub fn allocator(&self) -> &SharedBlinkAlloc<A> {
+        self.bytes.allocator()
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+impl<A> Clone for OwnedStr<A>
+where
+    A: ChunkSource,
+{
+    /// Clones string into the same arena.
+    #[inline]
+    fn clone(&self) -> Self {
+        OwnedStr {
+            bytes: self.bytes.clone(),
+        }
+    }
+}
+
+impl<A> Deref for OwnedStr<A>
+where
+    A: ChunkSource,
+{
+    type Target = str;
+
+    #[inline(always)]
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<A> AsRef<str> for OwnedStr<A>
+where
+    A: ChunkSource,
+{
+    #[inline(always)]
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl<A> Borrow<str> for OwnedStr<A>
+where
+    A: ChunkSource,
+{
+    #[inline(always)]
+    fn borrow(&self) -> &str {
+        self
+    }
+}
+
+impl<A> fmt::Debug for OwnedStr<A>
+where
+    A: ChunkSource,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <str as fmt::Debug>::fmt(self, f)
+    }
+}
+
+impl<A> fmt::Display for OwnedStr<A>
+where
+    A: ChunkSource,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <str as fmt::Display>::fmt(self, f)
+    }
+}
@@ -0,0 +1,361 @@
+//! This module provides chunk pools that recycle memory chunks
+//! between blink allocators.
+
+use core::{
+    alloc::Layout,
+    cell::UnsafeCell,
+    mem::{align_of, size_of},
+    ptr::{self, NonNull},
+};
+
+#[cfg(feature = "nightly")]
+use core::alloc::{AllocError, Allocator};
+
+#[cfg(not(feature = "nightly"))]
+use allocator_api2::alloc::{AllocError, Allocator};
+
+#[cfg(all(feature = "nightly", feature = "alloc"))]
+use alloc::alloc::Global;
+
+#[cfg(all(not(feature = "nightly"), feature = "alloc"))]
+use allocator_api2::alloc::Global;
+
+#[cfg(feature = "sync-core")]
+use crate::lock::Mutex;
+
+/// Header written into the free memory block.
+struct FreeBlock {
+    next: Option<NonNull<FreeBlock>>,
+    layout: Layout,
+}
+
+/// Intrusive list of free memory blocks.
+struct FreeList {
+    head: Option<NonNull<FreeBlock>>,
+    bytes: usize,
+}
+
+impl FreeList {
+    const fn new() -> Self {
+        FreeList {
+            head: None,
+            bytes: 0,
+        }
+    }
+
+    /// Takes block with exactly the same layout.
+    ///
+    /// Deallocating memory requires layout that fits the block
+    /// as allocated by underlying allocator.
+    /// Reusing blocks only for equal layouts guarantees this.
+    fn take(&mut self, layout: Layout) -> Option<NonNull<[u8]>> {
+        let mut link: *mut Option<NonNull<FreeBlock>> = &mut self.head;
+
+        // Safety: all blocks in the list are valid and owned by the list.
+        unsafe {
+            while let Some(block) = *link {
+                if block.as_ref().layout == layout {
+                    *link = block.as_ref().next;
+                    self.bytes -= layout.size();
+
+                    let slice =
+                        ptr::slice_from_raw_parts_mut(block.as_ptr().cast::<u8>(), layout.size());
+                    return Some(NonNull::new_unchecked(slice));
+                }
+                link = &mut (*block.as_ptr()).next;
+            }
+        }
+        None
+    }
+
+    /// Puts block into the list.
+    /// Returns `false` if block can't hold list header.
+    unsafe fn put(&mut self, ptr: NonNull<u8>, layout: Layout) -> bool {
+        if layout.size() < size_of::<FreeBlock>()
+            || ptr.as_ptr().addr() & (align_of::<FreeBlock>() - 1) != 0
+        {
+            return false;
+        }
+
+        let block = ptr.cast::<FreeBlock>();
+        unsafe {
+            ptr::write(
+                block.as_ptr(),
+                FreeBlock {
+                    next: self.head,
+                    layout,
+                },
+            );
+        }
+        self.head = Some(block);
+        self.bytes += layout.size();
+        true
+    }
+
+    /// Returns all blocks to the allocator.
+    unsafe fn clear(&mut self, allocator: impl Allocator) {
+        while let Some(block) = self.head {
+            // Safety: block is valid and owned by the list.
+            let FreeBlock { next, layout } = unsafe { ptr::read(block.as_ptr()) };
+            self.head = next;
+            unsafe { allocator.deallocate(block.cast(), layout) };
+        }
+        self.bytes = 0;
+    }
+}
+
+switch_alloc_default! {
+    /// Single-threaded pool of memory chunks.
+    ///
+    /// Implements [`Allocator`] on top of underlying allocator
+    /// and keeps deallocated memory blocks for reuse instead of freeing them.
+    /// Blocks are reused for allocations with the same layout.
+    /// Blink allocators with the same starting chunk size
+    /// request chunks of the same sizes, so their chunks are recycled well.
+    /// Blink allocators constructed over a reference to the pool return
+    /// their chunks to the pool on [`reset`](crate::BlinkAlloc::reset),
+    /// [`reset_final`](crate::BlinkAlloc::reset_final) and drop,
+    /// and take them from the pool when new chunk is needed.
+    ///
+    /// Cached blocks are freed when pool is dropped or
+    /// [`clear`](ChunkPool::clear) is called.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::{BlinkAlloc, ChunkPool};
+    ///
+    /// let pool = ChunkPool::new();
+    /// for _ in 0..10 {
+    ///     let blink = BlinkAlloc::new_in(&pool);
+    ///     blink.allocate(std::alloc::Layout::new::<[u8; 1000]>()).unwrap();
+    /// }
+    /// assert!(pool.cached_bytes() > 0);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    pub struct ChunkPool<A: Allocator = +Global> {
+        free: UnsafeCell<FreeList>,
+        allocator: A,
+    }
+}
+
+impl<A> Drop for ChunkPool<A>
+where
+    A: Allocator,
+{
+    #[inline]
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<A> Default for ChunkPool<A>
+where
+    A: Allocator + Default,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new_in(Default::default())
+    }
+}
+
+/// It is safe to send `ChunkPool` between threads.
+/// Blocks in free list are owned by the pool.
+unsafe impl<A> Send for ChunkPool<A> where A: Allocator + Send {}
+
+#[cfg(feature = "alloc")]
+impl ChunkPool<Global> {
+    /// Creates new pool that uses global allocator.
+    #[inline]
+    pub const fn new() -> Self {
+        ChunkPool::new_in(Global)
+    }
+}
+
+impl<A> ChunkPool<A>
+where
+    A: Allocator,
+{
+    /// Creates new pool that uses provided allocator.
+    #[inline]
+    pub const fn new_in(allocator: A) -> Self {
+        ChunkPool {
+            free: UnsafeCell::new(FreeList::new()),
+            allocator,
+        }
+    }
+
+    /// Returns reference to the underlying allocator.
+    #[inline(always)]
+    pub const fn inner(&self) -> &A {
+        &self.allocator
+    }
+
+    /// Returns number of bytes held by the pool for reuse.
+    #[inline]
+    pub fn cached_bytes(&self) -> usize {
+        // Safety: `ChunkPool` is not `Sync` and this method doesn't reenter.
+        unsafe { (*self.free.get()).bytes }
+    }
+
+    /// Frees all cached memory blocks.
+    #[inline]
+    pub fn clear(&mut self) {
+        unsafe { self.free.get_mut().clear(&self.allocator) }
+    }
+}
+
+unsafe impl<A> Allocator for ChunkPool<A>
+where
+    A: Allocator,
+{
+    #[inline]
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        // Safety: `ChunkPool` is not `Sync` and this method doesn't reenter.
+        if let Some(block) = unsafe { (*self.free.get()).take(layout) } {
+            return Ok(block);
+        }
+        self.allocator.allocate(layout)
+    }
+
+    #[inline]
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        // Safety: `ChunkPool` is not `Sync` and this method doesn't reenter.
+        if !unsafe { (*self.free.get()).put(ptr, layout) } {
+            unsafe { self.allocator.deallocate(ptr, layout) }
+        }
+    }
+}
+
+#[cfg(feature = "nightly")]
+impl_api2_allocator!(ChunkPool<A>);
+
+#[cfg(feature = "sync-core")]
+switch_alloc_default! {
+    /// Multi-threaded pool of memory chunks.
+    ///
+    /// Same as [`ChunkPool`] but can be shared between threads.
+    /// Internally uses a mutex, see `"sync-core"` feature for lock selection.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use blink_alloc::{BlinkAlloc, SyncChunkPool};
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// let pool = SyncChunkPool::new();
+    ///
+    /// std::thread::scope(|scope| {
+    ///     for _ in 0..4 {
+    ///         scope.spawn(|| {
+    ///             let blink = BlinkAlloc::new_in(&pool);
+    ///             blink.allocate(std::alloc::Layout::new::<[u8; 1000]>()).unwrap();
+    ///         });
+    ///     }
+    /// });
+    /// assert!(pool.cached_bytes() > 0);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    pub struct SyncChunkPool<A: Allocator = +Global> {
+        free: Mutex<FreeList>,
+        allocator: A,
+    }
+}
+
+#[cfg(feature = "sync-core")]
+impl<A> Drop for SyncChunkPool<A>
+where
+    A: Allocator,
+{
+    #[inline]
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+#[cfg(feature = "sync-core")]
+impl<A> Default for SyncChunkPool<A>
+where
+    A: Allocator + Default,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new_in(Default::default())
+    }
+}
+
+/// Blocks in free list are owned by the pool.
+/// Access to the list is synchronized.
+#[cfg(feature = "sync-core")]
+unsafe impl<A> Send for SyncChunkPool<A> where A: Allocator + Send {}
+
+#[cfg(feature = "sync-core")]
+unsafe impl<A> Sync for SyncChunkPool<A> where A: Allocator + Sync {}
+
+#[cfg(all(feature = "sync-core", feature = "alloc"))]
+impl SyncChunkPool<Global> {
+    /// Creates new pool that uses global allocator.
+    #[inline]
+    pub const fn new() -> Self {
+        SyncChunkPool::new_in(Global)
+    }
+}
+
+#[cfg(feature = "sync-core")]
+impl<A> SyncChunkPool<A>
+where
+    A: Allocator,
+{
+    /// Creates new pool that uses provided allocator.
+    #[inline]
+    pub const fn new_in(allocator: A) -> Self {
+        SyncChunkPool {
+            free: Mutex::new(FreeList::new()),
+            allocator,
+        }
+    }
+
+    /// Returns reference to the underlying allocator.
+    #[inline(always)]
+    pub const fn inner(&self) -> &A {
+        &self.allocator
+    }
+
+    /// Returns number of bytes held by the pool for reuse.
+    #[inline]
+    pub fn cached_bytes(&self) -> usize {
+        self.free.lock().bytes
+    }
+
+    /// Frees all cached memory blocks.
+    #[inline]
+    pub fn clear(&mut self) {
+        unsafe { self.free.get_mut().clear(&self.allocator) }
+    }
+}
+
+#[cfg(feature = "sync-core")]
+unsafe impl<A> Allocator for SyncChunkPool<A>
+where
+    A: Allocator,
+{
+    #[inline]
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        if let Some(block) = self.free.lock().take(layout) {
+            return Ok(block);
+        }
+        self.allocator.allocate(layout)
+    }
+
+    #[inline]
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        if !unsafe { self.free.lock().put(ptr, layout) } {
+            unsafe { self.allocator.deallocate(ptr, layout) }
+        }
+    }
+}
+
+#[cfg(all(feature = "nightly", feature = "sync-core"))]
+impl_api2_allocator!(SyncChunkPool<A>);
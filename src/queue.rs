@@ -0,0 +1,264 @@
+//! This module provides single-producer single-consumer queue
+//! with nodes allocated from multi-threaded blink allocator.
+
+use core::{
+    alloc::Layout,
+    marker::PhantomData,
+    mem::MaybeUninit,
+    ptr::{self, NonNull},
+    sync::atomic::{AtomicPtr, Ordering},
+};
+
+#[cfg(feature = "nightly")]
+use core::alloc::AllocError;
+
+#[cfg(not(feature = "nightly"))]
+use allocator_api2::alloc::AllocError;
+
+#[cfg(all(feature = "nightly", feature = "alloc"))]
+use alloc::alloc::Global;
+
+#[cfg(all(not(feature = "nightly"), feature = "alloc"))]
+use allocator_api2::alloc::Global;
+
+#[cfg(not(no_global_oom_handling))]
+use crate::oom::handle_alloc_error;
+
+use crate::{source::ChunkSource, sync::SyncBlinkAlloc};
+
+struct Node<T> {
+    next: AtomicPtr<Node<T>>,
+    value: MaybeUninit<T>,
+}
+
+switch_alloc_default! {
+    /// Unbounded single-producer single-consumer queue
+    /// with nodes allocated from [`SyncBlinkAlloc`].
+    ///
+    /// Designed for handing off per-frame data from one thread to another.
+    /// Nodes are never freed individually, their memory is recycled
+    /// when [`SyncBlinkAlloc`] is reset after the queue is dropped.
+    /// Values left in the queue are dropped with the queue.
+    ///
+    /// Use [`SpscQueue::split`] to get [`SpscProducer`] and [`SpscConsumer`] halves
+    /// that can be sent to different threads.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::{SpscQueue, SyncBlinkAlloc};
+    ///
+    /// let mut blink = SyncBlinkAlloc::new();
+    /// for frame in 0..3 {
+    ///     let mut queue = SpscQueue::new_in(&blink);
+    ///     let (mut producer, mut consumer) = queue.split();
+    ///
+    ///     std::thread::scope(|scope| {
+    ///         scope.spawn(move || {
+    ///             for i in 0..100 {
+    ///                 producer.push(frame * 100 + i);
+    ///             }
+    ///         });
+    ///         scope.spawn(move || {
+    ///             let mut received = 0;
+    ///             while received < 100 {
+    ///                 if let Some(value) = consumer.pop() {
+    ///                     assert_eq!(value, frame * 100 + received);
+    ///                     received += 1;
+    ///                 }
+    ///             }
+    ///         });
+    ///     });
+    ///
+    ///     drop(queue);
+    ///     blink.reset();
+    /// }
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    pub struct SpscQueue<'a, T, A: ChunkSource = +Global> {
+        /// Last consumed node. Its value is already taken.
+        head: NonNull<Node<T>>,
+
+        /// Last pushed node.
+        tail: NonNull<Node<T>>,
+
+        blink: &'a SyncBlinkAlloc<A>,
+        marker: PhantomData<T>,
+    }
+}
+
+/// Values are owned by the queue.
+unsafe impl<T, A> Send for SpscQueue<'_, T, A>
+where
+    T: Send,
+    A: ChunkSource + Sync,
+{
+}
+
+impl<T, A> Drop for SpscQueue<'_, T, A>
+where
+    A: ChunkSource,
+{
+    #[inline]
+    fn drop(&mut self) {
+        let (_, mut consumer) = self.split();
+        while consumer.pop().is_some() {}
+    }
+}
+
+impl<'a, T, A> SpscQueue<'a, T, A>
+where
+    A: ChunkSource,
+{
+    /// Creates new empty queue that allocates nodes from `blink`.
+    #[inline]
+    pub fn try_new_in(blink: &'a SyncBlinkAlloc<A>) -> Result<Self, AllocError> {
+        let stub = alloc_node(blink)?;
+        Ok(SpscQueue {
+            head: stub,
+            tail: stub,
+            blink,
+            marker: PhantomData,
+        })
+    }
+
+    /// Creates new empty queue that allocates nodes from `blink`.
+    ///
+    /// # Panics
+    ///
+    /// Calls [`handle_alloc_error`](alloc::alloc::handle_alloc_error)
+    /// if memory allocation fails.
+    #[cfg(not(no_global_oom_handling))]
+    #[inline]
+    pub fn new_in(blink: &'a SyncBlinkAlloc<A>) -> Self {
+        match Self::try_new_in(blink) {
+            Ok(queue) => queue,
+            Err(AllocError) => handle_alloc_error(Layout::new::<Node<T>>()),
+        }
+    }
+
+    /// Splits queue into producer and consumer halves.
+    #[inline]
+    pub fn split(&mut self) -> (SpscProducer<'_, 'a, T, A>, SpscConsumer<'_, T>) {
+        (
+            SpscProducer {
+                tail: &mut self.tail,
+                blink: self.blink,
+            },
+            SpscConsumer {
+                head: &mut self.head,
+            },
+        )
+    }
+}
+
+/// Producer half of the [`SpscQueue`].
+pub struct SpscProducer<'q, 'a, T, A: ChunkSource> {
+    tail: &'q mut NonNull<Node<T>>,
+    blink: &'a SyncBlinkAlloc<A>,
+}
+
+/// Producer only touches nodes that consumer doesn't access
+/// before observing them through synchronized `next` pointer.
+unsafe impl<T, A> Send for SpscProducer<'_, '_, T, A>
+where
+    T: Send,
+    A: ChunkSource + Sync,
+{
+}
+
+impl<T, A> SpscProducer<'_, '_, T, A>
+where
+    A: ChunkSource,
+{
+    /// Pushes value to the queue.
+    /// Returns value back if node allocation fails.
+    #[inline]
+    pub fn try_push(&mut self, value: T) -> Result<(), T> {
+        let Ok(node) = alloc_node::<T, A>(self.blink) else {
+            return Err(value);
+        };
+
+        // Safety:
+        // `node` is allocated for `Node<T>` and not yet published.
+        // Tail node is published but its `next` is written only by producer.
+        unsafe {
+            (*node.as_ptr()).value.write(value);
+            self.tail
+                .as_ref()
+                .next
+                .store(node.as_ptr(), Ordering::Release);
+        }
+        *self.tail = node;
+        Ok(())
+    }
+
+    /// Pushes value to the queue.
+    ///
+    /// # Panics
+    ///
+    /// Calls [`handle_alloc_error`](alloc::alloc::handle_alloc_error)
+    /// if memory allocation fails.
+    #[cfg(not(no_global_oom_handling))]
+    #[inline]
+    pub fn push(&mut self, value: T) {
+        if self.try_push(value).is_err() {
+            handle_alloc_error(Layout::new::<Node<T>>());
+        }
+    }
+}
+
+/// Consumer half of the [`SpscQueue`].
+pub struct SpscConsumer<'q, T> {
+    head: &'q mut NonNull<Node<T>>,
+}
+
+/// Consumer only touches nodes published by producer.
+unsafe impl<T> Send for SpscConsumer<'_, T> where T: Send {}
+
+impl<T> SpscConsumer<'_, T> {
+    /// Pops value from the queue.
+    /// Returns `None` if queue is empty.
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        // Safety: head node is always valid.
+        let next = unsafe { self.head.as_ref().next.load(Ordering::Acquire) };
+        let next = NonNull::new(next)?;
+
+        // Safety:
+        // `next` node is published by producer with initialized value.
+        // The value is taken once as `next` becomes new head.
+        let value = unsafe { ptr::read(ptr::addr_of!((*next.as_ptr()).value)).assume_init() };
+        *self.head = next;
+        Some(value)
+    }
+
+    /// Returns `true` if queue has no values available.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        // Safety: head node is always valid.
+        unsafe { self.head.as_ref().next.load(Ordering::Acquire).is_null() }
+    }
+}
+
+#[inline]
+fn alloc_node<T, A>(blink: &SyncBlinkAlloc<A>) -> Result<NonNull<Node<T>>, AllocError>
+where
+    A: ChunkSource,
+{
+    let node = blink.allocate(Layout::new::<Node<T>>())?.cast::<Node<T>>();
+
+    // Safety: memory is allocated for `Node<T>`.
+    unsafe {
+        ptr::write(
+            node.as_ptr(),
+            Node {
+                next: AtomicPtr::new(ptr::null_mut()),
+                value: MaybeUninit::uninit(),
+            },
+        );
+    }
+    Ok(node)
+}
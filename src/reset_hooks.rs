@@ -0,0 +1,77 @@
+//! This module provides callbacks that [`Blink`](crate::Blink)
+//! runs on every reset.
+
+use alloc::{boxed::Box, vec::Vec};
+
+/// Point of [`Blink`](crate::Blink) reset at which callback runs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ResetPhase {
+    /// Callback runs before emplaced values are dropped.
+    BeforeDrop,
+
+    /// Callback runs after emplaced values are dropped
+    /// and allocator is reset.
+    AfterDrop,
+}
+
+/// Identifier of a reset callback.
+///
+/// Returned by [`Blink::on_reset`](crate::Blink::on_reset)
+/// and used to remove the callback with
+/// [`Blink::remove_on_reset`](crate::Blink::remove_on_reset).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ResetHookId(u64);
+
+struct ResetHook {
+    id: ResetHookId,
+    phase: ResetPhase,
+    f: Box<dyn FnMut() + Send>,
+}
+
+/// Persistent callbacks run on every reset.
+pub(crate) struct ResetHooks {
+    hooks: Vec<ResetHook>,
+    next_id: u64,
+}
+
+impl ResetHooks {
+    #[inline(always)]
+    pub(crate) const fn new() -> Self {
+        ResetHooks {
+            hooks: Vec::new(),
+            next_id: 0,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn add(&mut self, phase: ResetPhase, f: Box<dyn FnMut() + Send>) -> ResetHookId {
+        let id = ResetHookId(self.next_id);
+        self.next_id += 1;
+        self.hooks.push(ResetHook { id, phase, f });
+        id
+    }
+
+    #[inline]
+    pub(crate) fn remove(&mut self, id: ResetHookId) -> bool {
+        match self.hooks.iter().position(|hook| hook.id == id) {
+            Some(idx) => {
+                self.hooks.remove(idx);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Runs callbacks of the phase in registration order.
+    #[inline(always)]
+    pub(crate) fn run(&mut self, phase: ResetPhase) {
+        if self.hooks.is_empty() {
+            return;
+        }
+        for hook in &mut self.hooks {
+            if hook.phase == phase {
+                (hook.f)();
+            }
+        }
+    }
+}
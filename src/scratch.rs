@@ -0,0 +1,66 @@
+//! This module provides thread-local pool of scratch [`Blink`] instances.
+
+use core::cell::RefCell;
+
+use alloc::vec::Vec;
+
+use crate::blink::Blink;
+
+std::thread_local! {
+    /// Pool of warmed up scratch arenas for this thread.
+    /// Nested [`with_scratch`] calls take separate instances.
+    static SCRATCH_POOL: RefCell<Vec<Blink>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Returns scratch arena to the pool when dropped,
+/// even if closure panics.
+struct ScratchGuard {
+    blink: Option<Blink>,
+}
+
+impl Drop for ScratchGuard {
+    #[inline]
+    fn drop(&mut self) {
+        if let Some(mut blink) = self.blink.take() {
+            blink.reset();
+
+            // Pool may be already destroyed if called during thread exit.
+            // Arena is simply dropped then.
+            let _ = SCRATCH_POOL.try_with(|pool| pool.borrow_mut().push(blink));
+        }
+    }
+}
+
+/// Runs closure with temporary scratch arena.
+///
+/// Arena is taken from thread-local pool of warmed up [`Blink`] instances,
+/// or created if pool is empty.
+/// After closure returns, arena is reset, dropping all values placed into it,
+/// and returned to the pool.
+/// Nested calls receive different arenas.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "std")] fn main() {
+/// use blink_alloc::with_scratch;
+///
+/// let total = with_scratch(|blink| {
+///     let words = blink.emplace().from_iter("a quick brown fox".split(' '));
+///     words.iter().map(|word| word.len()).sum::<usize>()
+/// });
+/// assert_eq!(total, 14);
+/// # }
+/// # #[cfg(not(feature = "std"))] fn main() {}
+/// ```
+#[inline]
+pub fn with_scratch<R>(f: impl FnOnce(&mut Blink) -> R) -> R {
+    let blink = SCRATCH_POOL
+        .try_with(|pool| pool.borrow_mut().pop())
+        .ok()
+        .flatten()
+        .unwrap_or_default();
+
+    let mut guard = ScratchGuard { blink: Some(blink) };
+    f(guard.blink.as_mut().unwrap())
+}
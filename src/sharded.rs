@@ -0,0 +1,482 @@
+//! This module provides multi-threaded blink allocator
+//! with one arena per shard.
+
+use core::{
+    alloc::Layout,
+    mem::ManuallyDrop,
+    ptr::NonNull,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use alloc::boxed::Box;
+
+#[cfg(not(feature = "nightly"))]
+use allocator_api2::alloc::{AllocError, Allocator};
+#[cfg(feature = "nightly")]
+use core::alloc::{AllocError, Allocator};
+
+#[cfg(feature = "nightly")]
+use alloc::alloc::Global;
+
+#[cfg(not(feature = "nightly"))]
+use allocator_api2::alloc::Global;
+
+use crate::{
+    api::BlinkAllocator,
+    arena::{pad_layout, pad_size, ArenaSync, CHUNK_START_SIZE},
+    debug_header,
+    hooks::{ArenaHooks, Events, Hooked},
+    source::ChunkSource,
+    stats::BlinkStats,
+};
+
+/// Number of shards used when parallelism can't be queried.
+const DEFAULT_SHARDS: usize = 8;
+
+/// Source of shard indices assigned to threads.
+static NEXT_SHARD: AtomicUsize = AtomicUsize::new(0);
+
+std::thread_local! {
+    /// Index assigned to this thread on first allocation.
+    /// Threads are assigned round-robin, so up to shard count
+    /// threads never share a shard.
+    static SHARD_INDEX: usize = NEXT_SHARD.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Arena padded to its own cache lines,
+/// so cursors of different shards never share one.
+#[repr(align(128))]
+struct Shard {
+    arena: ArenaSync,
+}
+
+switch_alloc_default! {
+    /// Multi-threaded blink allocator with one arena per shard.
+    ///
+    /// Works like [`SyncBlinkAlloc`](crate::SyncBlinkAlloc),
+    /// but each thread allocates from arena of its shard.
+    /// Threads are assigned to shards round-robin,
+    /// so concurrent allocations from different threads
+    /// bump different cursors on different cache lines
+    /// instead of contending on a single one.
+    ///
+    /// Each shard grows its own chunks, so total capacity after warm-up
+    /// is roughly the sum of per-shard peaks.
+    /// Prefer [`SyncBlinkAlloc`](crate::SyncBlinkAlloc) with
+    /// [`local`](crate::SyncBlinkAlloc::local) proxies when threads
+    /// can keep a proxy for many allocations.
+    ///
+    /// Memory allocated on one thread may be resized or deallocated on another,
+    /// that falls back to copying and no-op respectively.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #![cfg_attr(feature = "nightly", feature(allocator_api))]
+    /// # #[cfg(feature = "nightly")]
+    /// # use std::vec::Vec;
+    /// # #[cfg(not(feature = "nightly"))]
+    /// # use allocator_api2::vec::Vec;
+    /// use blink_alloc::ShardedBlinkAlloc;
+    ///
+    /// let mut blink = ShardedBlinkAlloc::new();
+    /// for _ in 0..3 {
+    ///     std::thread::scope(|scope| {
+    ///         for i in 0..8 {
+    ///             let blink = &blink;
+    ///             scope.spawn(move || {
+    ///                 let mut vec = Vec::new_in(blink);
+    ///                 vec.extend(0..i * 100);
+    ///             });
+    ///         }
+    ///     });
+    ///     blink.reset();
+    /// }
+    /// ```
+    pub struct ShardedBlinkAlloc<A: ChunkSource = +Global> {
+        shards: Box<[Shard]>,
+        allocator: A,
+        min_align: usize,
+        events: Events,
+    }
+}
+
+impl<A: ChunkSource> Drop for ShardedBlinkAlloc<A> {
+    fn drop(&mut self) {
+        self.reset_final();
+    }
+}
+
+impl<A> Default for ShardedBlinkAlloc<A>
+where
+    A: ChunkSource + Default,
+{
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new_in(Default::default())
+    }
+}
+
+impl ShardedBlinkAlloc<Global> {
+    /// Creates new sharded blink allocator that uses global allocator
+    /// to allocate memory chunks.
+    ///
+    /// Creates one shard per available CPU.
+    ///
+    /// See [`ShardedBlinkAlloc::new_in`] for using custom allocator.
+    #[inline(always)]
+    pub fn new() -> Self {
+        ShardedBlinkAlloc::new_in(Global)
+    }
+}
+
+impl<A> ShardedBlinkAlloc<A>
+where
+    A: ChunkSource,
+{
+    /// Creates new sharded blink allocator that uses provided allocator
+    /// to allocate memory chunks.
+    ///
+    /// Creates one shard per available CPU.
+    #[inline]
+    pub fn new_in(allocator: A) -> Self {
+        let shards = std::thread::available_parallelism().map_or(DEFAULT_SHARDS, |n| n.get());
+        ShardedBlinkAlloc::with_shards_in(shards, allocator)
+    }
+
+    /// Creates new sharded blink allocator with specified number of shards.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shards` is zero.
+    #[inline]
+    pub fn with_shards_in(shards: usize, allocator: A) -> Self {
+        ShardedBlinkAlloc::with_shards_and_chunk_size_in(shards, CHUNK_START_SIZE, allocator)
+    }
+
+    /// Creates new sharded blink allocator with specified number of shards
+    /// and initial chunk size of each shard.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shards` is zero.
+    pub fn with_shards_and_chunk_size_in(shards: usize, chunk_size: usize, allocator: A) -> Self {
+        assert!(shards > 0, "Sharded allocator requires at least one shard");
+        ShardedBlinkAlloc {
+            shards: (0..shards)
+                .map(|_| Shard {
+                    arena: ArenaSync::with_chunk_size(chunk_size),
+                })
+                .collect(),
+            allocator,
+            min_align: 1,
+            events: Events::new(),
+        }
+    }
+
+    /// Sets minimal alignment of all allocations.
+    ///
+    /// See [`SyncBlinkAlloc::with_min_align`](crate::SyncBlinkAlloc::with_min_align).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min_align` is not a power of two.
+    #[inline]
+    pub fn with_min_align(mut self, min_align: usize) -> Self {
+        assert!(
+            min_align.is_power_of_two(),
+            "Minimal alignment must be a power of two"
+        );
+        self.min_align = min_align;
+        self
+    }
+
+    /// Installs hooks called on arena events.
+    ///
+    /// See [`ArenaHooks`] for details.
+    #[inline]
+    pub fn with_hooks(mut self, hooks: &'static dyn ArenaHooks) -> Self {
+        self.events.hooks = Some(hooks);
+        self
+    }
+
+    /// Sets name of this allocator.
+    ///
+    /// See [`SyncBlinkAlloc::with_name`](crate::SyncBlinkAlloc::with_name).
+    #[inline]
+    pub fn with_name(mut self, name: &'static str) -> Self {
+        self.events.set_name(name);
+        self
+    }
+
+    /// Returns reference to the underlying allocator used by this blink allocator.
+    #[inline(always)]
+    pub fn inner(&self) -> &A {
+        &self.allocator
+    }
+
+    /// Returns number of shards.
+    #[inline(always)]
+    pub fn shards(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Returns arena of the calling thread's shard.
+    #[inline(always)]
+    fn arena(&self) -> &ArenaSync {
+        // Threads being torn down fall back to the first shard.
+        let index = SHARD_INDEX.try_with(|index| *index).unwrap_or(0);
+        &self.shards[index % self.shards.len()].arena
+    }
+
+    /// Allocates memory with specified layout from this allocator.
+    /// If needed it will allocate new chunk using underlying allocator.
+    /// If chunk allocation fails, it will return `Err`.
+    #[inline(always)]
+    pub fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        debug_header::allocate(layout, self.min_align, |layout| self.allocate_raw(layout))
+    }
+
+    #[inline(always)]
+    fn allocate_raw(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let layout = pad_layout(layout, self.min_align)?;
+        let arena = self.arena();
+
+        // Safety:
+        // Same instance is used for all allocations and resets.
+        if let Some(ptr) = unsafe { arena.alloc_fast(layout) } {
+            return Ok(ptr);
+        }
+        self.events.alloc_slow_path(layout);
+        self.events.retry_failed(
+            layout,
+            || self.failure_stats(),
+            || unsafe { arena.alloc_slow(layout, &Hooked::new(&self.allocator, self.events)) },
+        )
+    }
+
+    /// Returns statistics reported to hooks on allocation failure.
+    #[cold]
+    fn failure_stats(&self) -> BlinkStats {
+        BlinkStats {
+            allocated_bytes: self.allocated_bytes(),
+            total_capacity: self.total_capacity(),
+            ..BlinkStats::default()
+        }
+    }
+
+    /// Resizes memory allocation.
+    /// Potentially happens in-place.
+    ///
+    /// # Safety
+    ///
+    /// Same as for [`SyncBlinkAlloc::resize`](crate::SyncBlinkAlloc::resize).
+    #[inline(always)]
+    pub unsafe fn resize(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        unsafe {
+            debug_header::resize(
+                ptr,
+                old_layout,
+                new_layout,
+                self.min_align,
+                |layout| self.allocate_raw(layout),
+                |ptr, old_layout, new_layout| self.resize_raw(ptr, old_layout, new_layout),
+                |ptr, size| self.deallocate_raw(ptr, size),
+            )
+        }
+    }
+
+    #[inline(always)]
+    unsafe fn resize_raw(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let old_layout = pad_layout(old_layout, self.min_align)?;
+        let new_layout = pad_layout(new_layout, self.min_align)?;
+        let arena = self.arena();
+
+        // Safety:
+        // Same instance is used for all allocations and resets.
+        // `ptr` was allocated by this allocator, possibly from another shard.
+        // Arenas only grow allocations in place within their own chunks.
+        if let Some(ptr) = unsafe { arena.resize_fast(ptr, old_layout, new_layout) } {
+            return Ok(ptr);
+        }
+
+        self.events.alloc_slow_path(new_layout);
+
+        // Old allocation stays valid if resize fails.
+        self.events.retry_failed(
+            new_layout,
+            || self.failure_stats(),
+            || unsafe {
+                arena.resize_slow(
+                    ptr,
+                    old_layout,
+                    new_layout,
+                    &Hooked::new(&self.allocator, self.events),
+                )
+            },
+        )
+    }
+
+    /// Deallocates memory previously allocated from this allocator.
+    ///
+    /// This call may not actually free memory.
+    /// All memory is guaranteed to be freed on [`reset`](ShardedBlinkAlloc::reset) call.
+    ///
+    /// # Safety
+    ///
+    /// Same as for [`SyncBlinkAlloc::deallocate`](crate::SyncBlinkAlloc::deallocate).
+    #[inline(always)]
+    pub unsafe fn deallocate(&self, ptr: NonNull<u8>, size: usize) {
+        unsafe {
+            debug_header::deallocate(ptr, size, self.min_align, |ptr, size| {
+                self.deallocate_raw(ptr, size)
+            })
+        }
+    }
+
+    #[inline(always)]
+    unsafe fn deallocate_raw(&self, ptr: NonNull<u8>, size: usize) {
+        // Safety:
+        // `ptr` was allocated by this allocator.
+        // Deallocation of memory from another shard is no-op.
+        unsafe {
+            self.arena().dealloc(ptr, pad_size(size, self.min_align));
+        }
+    }
+
+    /// Resets this allocator, deallocating all chunks
+    /// except the last one of each shard.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.reset_shards(true);
+    }
+
+    /// Resets this allocator, deallocating all chunks.
+    #[inline]
+    pub fn reset_final(&mut self) {
+        self.reset_shards(false);
+    }
+
+    fn reset_shards(&mut self, keep_last: bool) {
+        self.events
+            .reset(|| ShardedBlinkAlloc::allocated_bytes(self));
+        let hooked = Hooked::new(&self.allocator, self.events);
+        for shard in self.shards.iter_mut() {
+            // Safety:
+            // Same instance is used for all allocations and resets.
+            unsafe { shard.arena.reset(keep_last, &hooked) }
+        }
+    }
+
+    /// Unwrap this allocator, returning the underlying allocator.
+    ///
+    /// All chunks are deallocated first, same as with [`reset_final`](ShardedBlinkAlloc::reset_final).
+    pub fn into_inner(self) -> A {
+        let mut me = ManuallyDrop::new(self);
+        me.reset_final();
+
+        // Safety: `me` is not used after this and is not dropped.
+        // Shards have no chunks after `reset_final`.
+        unsafe {
+            core::ptr::drop_in_place(&mut me.shards);
+            core::ptr::read(&me.allocator)
+        }
+    }
+
+    /// Returns the approximate number of bytes allocated from all shards.
+    ///
+    /// See [`SyncBlinkAlloc::allocated_bytes`](crate::SyncBlinkAlloc::allocated_bytes).
+    pub fn allocated_bytes(&self) -> usize {
+        self.shards
+            .iter()
+            .map(|shard| shard.arena.allocated_bytes())
+            .sum()
+    }
+
+    /// Returns the total capacity of chunks of all shards.
+    pub fn total_capacity(&self) -> usize {
+        self.shards
+            .iter()
+            .map(|shard| shard.arena.total_capacity())
+            .sum()
+    }
+
+    /// Checks if `ptr` points into memory allocated from any shard
+    /// since last reset.
+    pub fn contains(&self, ptr: NonNull<u8>) -> bool {
+        self.shards.iter().any(|shard| shard.arena.contains(ptr))
+    }
+}
+
+unsafe impl<A> Allocator for ShardedBlinkAlloc<A>
+where
+    A: ChunkSource,
+{
+    #[inline(always)]
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        ShardedBlinkAlloc::allocate(self, layout)
+    }
+
+    #[inline(always)]
+    unsafe fn shrink(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        ShardedBlinkAlloc::resize(self, ptr, old_layout, new_layout)
+    }
+
+    #[inline(always)]
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        ShardedBlinkAlloc::resize(self, ptr, old_layout, new_layout)
+    }
+
+    #[inline(always)]
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        ShardedBlinkAlloc::deallocate(self, ptr, layout.pad_to_align().size());
+    }
+}
+
+#[cfg(feature = "nightly")]
+impl_api2_allocator!(impl<A> ShardedBlinkAlloc<A> where A: crate::ChunkSource);
+
+unsafe impl<A> BlinkAllocator for ShardedBlinkAlloc<A>
+where
+    A: ChunkSource,
+{
+    #[inline(always)]
+    fn reset(&mut self) {
+        ShardedBlinkAlloc::reset(self)
+    }
+
+    #[inline(always)]
+    fn reset_final(&mut self) {
+        ShardedBlinkAlloc::reset_final(self)
+    }
+
+    #[inline(always)]
+    fn allocated_bytes(&self) -> Option<usize> {
+        Some(ShardedBlinkAlloc::allocated_bytes(self))
+    }
+
+    #[inline(always)]
+    fn total_capacity(&self) -> Option<usize> {
+        Some(ShardedBlinkAlloc::total_capacity(self))
+    }
+}
@@ -0,0 +1,173 @@
+//! This module provides allocator adaptor for shared handles to allocators.
+
+use core::{alloc::Layout, ops::Deref, ptr::NonNull};
+
+#[cfg(feature = "nightly")]
+use core::alloc::{AllocError, Allocator};
+
+#[cfg(not(feature = "nightly"))]
+use allocator_api2::alloc::{AllocError, Allocator};
+
+use crate::api::BlinkAllocator;
+
+/// Allocator adaptor for smart pointers to allocators,
+/// like `Rc<BlinkAlloc>`, `Arc<SyncBlinkAlloc>` or `Rc<Blink>`.
+///
+/// Collections using [`SharedAlloc`] own a handle to the allocator
+/// instead of borrowing it.
+/// So they can be stored in structures that outlive the scope
+/// where allocator was created.
+///
+/// Allocator can be reset once all handles but one are dropped,
+/// using methods like `Rc::get_mut`.
+/// Resetting through [`SharedAlloc`] itself is no-op,
+/// same as for shared references.
+///
+/// # Example
+///
+/// ```
+/// # #![cfg_attr(feature = "nightly", feature(allocator_api))]
+/// # #[cfg(feature = "alloc")] fn main() {
+/// # #[cfg(feature = "nightly")]
+/// # use std::vec::Vec;
+/// # #[cfg(not(feature = "nightly"))]
+/// # use allocator_api2::vec::Vec;
+/// use std::rc::Rc;
+/// use blink_alloc::{BlinkAlloc, SharedAlloc};
+///
+/// struct Frame {
+///     items: Vec<u32, SharedAlloc<Rc<BlinkAlloc>>>,
+/// }
+///
+/// fn build_frame(blink: &Rc<BlinkAlloc>) -> Frame {
+///     let mut items = Vec::new_in(SharedAlloc::new(blink.clone()));
+///     items.extend(0..100);
+///     Frame { items }
+/// }
+///
+/// let mut blink = Rc::new(BlinkAlloc::new());
+/// let frame = build_frame(&blink);
+/// assert_eq!(frame.items.len(), 100);
+///
+/// drop(frame);
+/// Rc::get_mut(&mut blink).unwrap().reset();
+/// # }
+/// # #[cfg(not(feature = "alloc"))] fn main() {}
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct SharedAlloc<P>(P);
+
+impl<P> SharedAlloc<P> {
+    /// Wraps shared handle to allocator.
+    #[inline(always)]
+    pub const fn new(handle: P) -> Self {
+        SharedAlloc(handle)
+    }
+
+    /// Returns reference to the wrapped handle.
+    #[inline(always)]
+    pub fn handle(&self) -> &P {
+        &self.0
+    }
+
+    /// Unwraps the handle.
+    #[inline(always)]
+    pub fn into_inner(self) -> P {
+        self.0
+    }
+}
+
+impl<P> From<P> for SharedAlloc<P> {
+    #[inline(always)]
+    fn from(handle: P) -> Self {
+        SharedAlloc(handle)
+    }
+}
+
+impl<P> Deref for SharedAlloc<P>
+where
+    P: Deref,
+{
+    type Target = P::Target;
+
+    #[inline(always)]
+    fn deref(&self) -> &P::Target {
+        &self.0
+    }
+}
+
+unsafe impl<P> Allocator for SharedAlloc<P>
+where
+    P: Deref,
+    P::Target: Allocator,
+{
+    #[inline(always)]
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        (**self).allocate(layout)
+    }
+
+    #[inline(always)]
+    fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        (**self).allocate_zeroed(layout)
+    }
+
+    #[inline(always)]
+    unsafe fn shrink(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        unsafe { (**self).shrink(ptr, old_layout, new_layout) }
+    }
+
+    #[inline(always)]
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        unsafe { (**self).grow(ptr, old_layout, new_layout) }
+    }
+
+    #[inline(always)]
+    unsafe fn grow_zeroed(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        unsafe { (**self).grow_zeroed(ptr, old_layout, new_layout) }
+    }
+
+    #[inline(always)]
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        unsafe { (**self).deallocate(ptr, layout) }
+    }
+}
+
+#[cfg(feature = "nightly")]
+impl_api2_allocator!(impl<P> SharedAlloc<P> where P: Deref, P::Target: core::alloc::Allocator);
+
+unsafe impl<P> BlinkAllocator for SharedAlloc<P>
+where
+    P: Deref,
+    P::Target: BlinkAllocator,
+{
+    /// Handle is shared and can't reset the allocator.
+    /// This is no-op.
+    #[inline(always)]
+    fn reset(&mut self) {}
+
+    #[inline(always)]
+    fn allocated_bytes(&self) -> Option<usize> {
+        (**self).allocated_bytes()
+    }
+
+    #[inline(always)]
+    fn total_capacity(&self) -> Option<usize> {
+        (**self).total_capacity()
+    }
+}
@@ -0,0 +1,246 @@
+//! This module provides blink allocator co-owned by multiple handles
+//! on a single thread.
+
+use core::{alloc::Layout, ops::Deref, ptr::NonNull};
+
+use alloc::rc::Rc;
+
+#[cfg(feature = "nightly")]
+use core::alloc::{AllocError, Allocator};
+
+#[cfg(not(feature = "nightly"))]
+use allocator_api2::alloc::{AllocError, Allocator};
+
+#[cfg(feature = "nightly")]
+use alloc::alloc::Global;
+
+#[cfg(not(feature = "nightly"))]
+use allocator_api2::alloc::Global;
+
+use crate::{api::BlinkAllocator, local::BlinkAlloc, source::ChunkSource};
+
+switch_alloc_default! {
+    /// Single-threaded blink allocator with shared ownership.
+    ///
+    /// Cloning [`SharedBlinkAlloc`] creates new owner of the same arena.
+    /// Any owner can allocate, so collections and structures
+    /// can keep their own handle instead of borrowing the allocator.
+    /// Uses `Rc` internally, which is cheaper than sharing [`SyncBlinkAlloc`].
+    ///
+    /// Arena can be reset only through the last owner,
+    /// see [`SharedBlinkAlloc::try_reset`].
+    ///
+    /// [`SyncBlinkAlloc`]: crate::SyncBlinkAlloc
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #![cfg_attr(feature = "nightly", feature(allocator_api))]
+    /// # #[cfg(feature = "nightly")]
+    /// # use std::vec::Vec;
+    /// # #[cfg(not(feature = "nightly"))]
+    /// # use allocator_api2::vec::Vec;
+    /// use blink_alloc::SharedBlinkAlloc;
+    ///
+    /// struct Widget {
+    ///     children: Vec<u32, SharedBlinkAlloc>,
+    /// }
+    ///
+    /// let mut frame = SharedBlinkAlloc::new();
+    /// let mut widget = Widget { children: Vec::new_in(frame.clone()) };
+    /// widget.children.extend(0..10);
+    ///
+    /// // Widget still owns the arena.
+    /// assert!(!frame.try_reset());
+    ///
+    /// drop(widget);
+    /// assert!(frame.try_reset());
+    /// ```
+    pub struct SharedBlinkAlloc<A: ChunkSource = +Global> {
+        inner: Rc<BlinkAlloc<A>>,
+    }
+}
+
+impl<A> Clone for SharedBlinkAlloc<A>
+where
+    A: ChunkSource,
+{
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        SharedBlinkAlloc {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<A> Default for SharedBlinkAlloc<A>
+where
+    A: ChunkSource + Default,
+{
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new_in(Default::default())
+    }
+}
+
+impl<A> From<BlinkAlloc<A>> for SharedBlinkAlloc<A>
+where
+    A: ChunkSource,
+{
+    #[inline(always)]
+    fn from(blink: BlinkAlloc<A>) -> Self {
+        SharedBlinkAlloc {
+            inner: Rc::new(blink),
+        }
+    }
+}
+
+impl SharedBlinkAlloc<Global> {
+    /// Creates new shared blink allocator that uses global allocator
+    /// to allocate memory chunks.
+    #[inline(always)]
+    pub fn new() -> Self {
+        SharedBlinkAlloc::from(BlinkAlloc::new())
+    }
+}
+
+impl<A> SharedBlinkAlloc<A>
+where
+    A: ChunkSource,
+{
+    /// Creates new shared blink allocator that uses provided allocator
+    /// to allocate memory chunks.
+    #[inline(always)]
+    pub fn new_in(allocator: A) -> Self {
+        SharedBlinkAlloc::from(BlinkAlloc::new_in(allocator))
+    }
+
+    /// Returns number of owners of the arena.
+    #[inline(always)]
+    pub fn owners(&self) -> usize {
+        Rc::strong_count(&self.inner)
+    }
+
+    /// Returns mutable reference to the allocator
+    /// if this is the only owner.
+    #[inline(always)]
+    pub fn get_mut(&mut self) -> Option<&mut BlinkAlloc<A>> {
+        Rc::get_mut(&mut self.inner)
+    }
+
+    /// Resets the allocator if this is the only owner.
+    /// Returns `false` and does nothing otherwise.
+    ///
+    /// See [`BlinkAlloc::reset`].
+    #[inline]
+    pub fn try_reset(&mut self) -> bool {
+        match self.get_mut() {
+            Some(blink) => {
+                blink.reset();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Resets the allocator releasing all its memory
+    /// if this is the only owner.
+    /// Returns `false` and does nothing otherwise.
+    ///
+    /// See [`BlinkAlloc::reset_final`].
+    #[inline]
+    pub fn try_reset_final(&mut self) -> bool {
+        match self.get_mut() {
+            Some(blink) => {
+                blink.reset_final();
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl<A> Deref for SharedBlinkAlloc<A>
+where
+    A: ChunkSource,
+{
+    type Target = BlinkAlloc<A>;
+
+    #[inline(always)]
+    fn deref(&self) -> &BlinkAlloc<A> {
+        &self.inner
+    }
+}
+
+unsafe impl<A> Allocator for SharedBlinkAlloc<A>
+where
+    A: ChunkSource,
+{
+    #[inline(always)]
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        BlinkAlloc::allocate(self, layout)
+    }
+
+    #[inline(always)]
+    fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        BlinkAlloc::allocate_zeroed(self, layout)
+    }
+
+    #[inline(always)]
+    unsafe fn shrink(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        BlinkAlloc::resize(self, ptr, old_layout, new_layout)
+    }
+
+    #[inline(always)]
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        BlinkAlloc::resize(self, ptr, old_layout, new_layout)
+    }
+
+    #[inline(always)]
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        BlinkAlloc::deallocate(self, ptr, layout.pad_to_align().size());
+    }
+}
+
+#[cfg(feature = "nightly")]
+impl_api2_allocator!(impl<A> SharedBlinkAlloc<A> where A: crate::ChunkSource);
+
+unsafe impl<A> BlinkAllocator for SharedBlinkAlloc<A>
+where
+    A: ChunkSource,
+{
+    /// Resets the allocator if this is the only owner.
+    /// Otherwise this is no-op.
+    #[inline(always)]
+    fn reset(&mut self) {
+        self.try_reset();
+    }
+
+    /// Resets the allocator releasing all its memory
+    /// if this is the only owner.
+    /// Otherwise this is no-op.
+    #[inline(always)]
+    fn reset_final(&mut self) {
+        self.try_reset_final();
+    }
+
+    #[inline(always)]
+    fn allocated_bytes(&self) -> Option<usize> {
+        Some(BlinkAlloc::allocated_bytes(self))
+    }
+
+    #[inline(always)]
+    fn total_capacity(&self) -> Option<usize> {
+        Some(BlinkAlloc::total_capacity(self))
+    }
+}
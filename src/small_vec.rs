@@ -0,0 +1,272 @@
+//! This module provides vector with inline storage
+//! that spills into blink allocator.
+
+use core::{
+    fmt,
+    ops::{Deref, DerefMut},
+};
+
+use allocator_api2::{alloc::Allocator, vec::Vec};
+use smallvec::SmallVec;
+
+/// Vector that keeps up to `N` elements inline
+/// and moves them into memory from allocator `A` when it overflows.
+///
+/// Intended to be used with blink allocators,
+/// so short vectors don't touch allocator at all
+/// and long ones are allocated from the arena instead of the heap.
+///
+/// Inline storage is [`SmallVec`] that never spills on its own.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "smallvec")] fn main() {
+/// use blink_alloc::{BlinkAlloc, BlinkSmallVec};
+///
+/// let mut blink = BlinkAlloc::new();
+///
+/// let mut vec = BlinkSmallVec::<u32, 4, _>::new_in(&blink);
+/// vec.extend(0..4);
+/// assert!(!vec.spilled());
+/// assert_eq!(blink.allocated_bytes(), 0);
+///
+/// vec.push(4);
+/// assert!(vec.spilled());
+/// assert_eq!(*vec, [0, 1, 2, 3, 4]);
+///
+/// drop(vec);
+/// blink.reset();
+/// # }
+/// # #[cfg(not(feature = "smallvec"))] fn main() {}
+/// ```
+pub struct BlinkSmallVec<T, const N: usize, A: Allocator> {
+    inline: SmallVec<[T; N]>,
+    heap: Vec<T, A>,
+    spilled: bool,
+}
+
+impl<T, const N: usize, A> BlinkSmallVec<T, N, A>
+where
+    A: Allocator,
+{
+    /// Creates new empty vector that spills into `alloc`.
+    #[inline(always)]
+    pub fn new_in(alloc: A) -> Self {
+        BlinkSmallVec {
+            inline: SmallVec::new(),
+            heap: Vec::new_in(alloc),
+            spilled: false,
+        }
+    }
+
+    /// Creates new empty vector with at least specified capacity.
+    /// Allocates from `alloc` right away if `capacity` exceeds `N`.
+    #[inline]
+    pub fn with_capacity_in(capacity: usize, alloc: A) -> Self {
+        if capacity <= N {
+            Self::new_in(alloc)
+        } else {
+            BlinkSmallVec {
+                inline: SmallVec::new(),
+                heap: Vec::with_capacity_in(capacity, alloc),
+                spilled: true,
+            }
+        }
+    }
+
+    /// Creates vector from elements of the `SmallVec`.
+    /// Elements are moved into `alloc` if they don't fit inline.
+    #[inline]
+    pub fn from_smallvec_in(vec: SmallVec<[T; N]>, alloc: A) -> Self {
+        if !vec.spilled() {
+            BlinkSmallVec {
+                inline: vec,
+                heap: Vec::new_in(alloc),
+                spilled: false,
+            }
+        } else {
+            let mut heap = Vec::with_capacity_in(vec.len(), alloc);
+            heap.extend(vec);
+            BlinkSmallVec {
+                inline: SmallVec::new(),
+                heap,
+                spilled: true,
+            }
+        }
+    }
+
+    /// Returns number of elements that fit inline.
+    #[inline(always)]
+    pub const fn inline_size(&self) -> usize {
+        N
+    }
+
+    /// Returns `true` if elements were moved into the allocator.
+    #[inline(always)]
+    pub fn spilled(&self) -> bool {
+        self.spilled
+    }
+
+    /// Returns reference to the allocator.
+    #[inline(always)]
+    pub fn allocator(&self) -> &A {
+        self.heap.allocator()
+    }
+
+    /// Returns number of elements in the vector.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        if self.spilled {
+            self.heap.len()
+        } else {
+            self.inline.len()
+        }
+    }
+
+    /// Returns `true` if the vector contains no elements.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns number of elements vector can hold
+    /// without allocating more memory.
+    #[inline(always)]
+    pub fn capacity(&self) -> usize {
+        if self.spilled {
+            self.heap.capacity()
+        } else {
+            N
+        }
+    }
+
+    /// Returns slice of all elements.
+    #[inline(always)]
+    pub fn as_slice(&self) -> &[T] {
+        if self.spilled {
+            &self.heap
+        } else {
+            &self.inline
+        }
+    }
+
+    /// Returns mutable slice of all elements.
+    #[inline(always)]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        if self.spilled {
+            &mut self.heap
+        } else {
+            &mut self.inline
+        }
+    }
+
+    /// Reserves capacity for at least `additional` more elements.
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        if self.spilled {
+            self.heap.reserve(additional);
+        } else if N - self.inline.len() < additional {
+            self.spill(additional);
+        }
+    }
+
+    /// Appends an element to the back of the vector.
+    #[inline]
+    pub fn push(&mut self, value: T) {
+        if !self.spilled {
+            if self.inline.len() < N {
+                self.inline.push(value);
+                return;
+            }
+            self.spill(1);
+        }
+        self.heap.push(value);
+    }
+
+    /// Removes the last element and returns it,
+    /// or `None` if vector is empty.
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        if self.spilled {
+            self.heap.pop()
+        } else {
+            self.inline.pop()
+        }
+    }
+
+    /// Shortens the vector to `len` elements, dropping the rest.
+    #[inline]
+    pub fn truncate(&mut self, len: usize) {
+        if self.spilled {
+            self.heap.truncate(len);
+        } else {
+            self.inline.truncate(len);
+        }
+    }
+
+    /// Removes all elements.
+    /// Memory from allocator is kept for reuse.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Moves inline elements into the allocator
+    /// with space for `additional` more elements.
+    #[cold]
+    fn spill(&mut self, additional: usize) {
+        let len = self.inline.len();
+        self.heap
+            .reserve(Ord::max(len.saturating_add(additional), N * 2));
+        self.heap.extend(self.inline.drain(..));
+        self.spilled = true;
+    }
+}
+
+impl<T, const N: usize, A> Deref for BlinkSmallVec<T, N, A>
+where
+    A: Allocator,
+{
+    type Target = [T];
+
+    #[inline(always)]
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, const N: usize, A> DerefMut for BlinkSmallVec<T, N, A>
+where
+    A: Allocator,
+{
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T, const N: usize, A> Extend<T> for BlinkSmallVec<T, N, A>
+where
+    A: Allocator,
+{
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<T, const N: usize, A> fmt::Debug for BlinkSmallVec<T, N, A>
+where
+    T: fmt::Debug,
+    A: Allocator,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <[T] as fmt::Debug>::fmt(self, f)
+    }
+}
@@ -0,0 +1,278 @@
+//! This module provides loader of arena snapshots
+//! dumped with [`FrozenBlink::as_bytes`](crate::FrozenBlink::as_bytes)
+//! and offsets to values in them.
+
+use core::{
+    convert::TryFrom,
+    fmt,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    mem::{align_of, size_of},
+};
+
+/// Chunk contents in the snapshot are padded to this alignment.
+/// Values aligned up to it keep their alignment in loaded snapshot.
+pub(crate) const SNAPSHOT_ALIGN: usize = 8;
+
+/// Returns number of padding bytes before contents of the chunk
+/// with used memory at `addr`.
+/// Keeps position of the contents modulo [`SNAPSHOT_ALIGN`].
+#[inline(always)]
+pub(crate) fn lead_padding(addr: usize) -> usize {
+    addr % SNAPSHOT_ALIGN
+}
+
+/// Returns number of bytes taken by contents of the chunk
+/// with `len` bytes of used memory at `addr`, including padding.
+#[inline(always)]
+pub(crate) fn padded_len(addr: usize, len: usize) -> usize {
+    let len = lead_padding(addr) + len;
+    (len + SNAPSHOT_ALIGN - 1) & !(SNAPSHOT_ALIGN - 1)
+}
+
+/// Offset of the value in arena snapshot.
+///
+/// Unlike references, offsets stay valid
+/// when snapshot is loaded at different address,
+/// so they can be persisted along with the snapshot.
+/// Obtained with [`FrozenBlink::offset_of`](crate::FrozenBlink::offset_of)
+/// before dumping the arena or [`BlinkSnapshot::offset_of`] after loading it.
+#[repr(transparent)]
+pub struct ArenaOffset<T> {
+    offset: usize,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> ArenaOffset<T> {
+    /// Creates offset from raw value.
+    #[inline(always)]
+    pub const fn new(offset: usize) -> Self {
+        ArenaOffset {
+            offset,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns raw offset in bytes from the start of snapshot data.
+    #[inline(always)]
+    pub const fn offset(self) -> usize {
+        self.offset
+    }
+}
+
+impl<T> Clone for ArenaOffset<T> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ArenaOffset<T> {}
+
+impl<T> PartialEq for ArenaOffset<T> {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.offset == other.offset
+    }
+}
+
+impl<T> Eq for ArenaOffset<T> {}
+
+impl<T> Hash for ArenaOffset<T> {
+    #[inline(always)]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.offset.hash(state)
+    }
+}
+
+impl<T> fmt::Debug for ArenaOffset<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ArenaOffset").field(&self.offset).finish()
+    }
+}
+
+/// Arena snapshot loaded from bytes
+/// dumped with [`FrozenBlink::as_bytes`](crate::FrozenBlink::as_bytes)
+/// or [`FrozenBlink::write_to`](crate::FrozenBlink::write_to).
+///
+/// Bytes are used in place, e.g. from memory-mapped file.
+/// Values are resolved with [`ArenaOffset`]s,
+/// and pointers stored inside the snapshot, that still
+/// point into original arena, are translated with [`BlinkSnapshot::resolve`].
+///
+/// Bytes must be aligned to 8 for values to keep their alignment.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "alloc")] fn main() {
+/// use blink_alloc::{BlinkAlloc, BlinkSnapshot};
+///
+/// struct Node {
+///     value: u64,
+///     next: *const Node,
+/// }
+///
+/// let blink = BlinkAlloc::new().with_zeroed_memory();
+/// let mut next = core::ptr::null();
+/// for value in 0..3 {
+///     // Padding after the byte is kept zeroed by the allocator.
+///     let tag = blink.allocate(std::alloc::Layout::new::<u8>()).unwrap();
+///     unsafe { tag.cast::<u8>().as_ptr().write(value as u8) };
+///
+///     let ptr = blink.allocate(std::alloc::Layout::new::<Node>()).unwrap();
+///     let node = ptr.cast::<Node>().as_ptr();
+///     unsafe { node.write(Node { value, next }) };
+///     next = node;
+/// }
+/// let frozen = blink.freeze();
+/// let head = frozen.offset_of(next).unwrap();
+///
+/// // Safety: all allocations are initialized and `Node` has no padding bytes.
+/// let bytes = unsafe { frozen.as_bytes() };
+/// drop(frozen);
+///
+/// // Copy into aligned buffer.
+/// let mut words = vec![0u64; bytes.len() / 8];
+/// let aligned = unsafe {
+///     core::ptr::copy_nonoverlapping(bytes.as_ptr(), words.as_mut_ptr().cast(), bytes.len());
+///     core::slice::from_raw_parts(words.as_ptr().cast::<u8>(), bytes.len())
+/// };
+///
+/// let snapshot = BlinkSnapshot::new(aligned).unwrap();
+/// let mut node = unsafe { snapshot.get(head) };
+/// let mut values = Vec::new();
+/// while let Some(n) = node {
+///     values.push(n.value);
+///     node = unsafe { snapshot.resolve(n.next) };
+/// }
+/// assert_eq!(values, [2, 1, 0]);
+/// # }
+/// # #[cfg(not(feature = "alloc"))] fn main() {}
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct BlinkSnapshot<'a> {
+    map: &'a [u8],
+    data: &'a [u8],
+}
+
+impl<'a> BlinkSnapshot<'a> {
+    /// Parses snapshot header.
+    /// Returns `None` if bytes are not a valid snapshot.
+    #[inline]
+    pub fn new(bytes: &'a [u8]) -> Option<Self> {
+        let count = read_u64(bytes, 0)?;
+        let map_len = count.checked_mul(16)?.checked_add(8)?;
+        if bytes.len() < map_len {
+            return None;
+        }
+        let snapshot = BlinkSnapshot {
+            map: &bytes[8..map_len],
+            data: &bytes[map_len..],
+        };
+
+        let mut data_len = 0usize;
+        for (addr, len) in snapshot.chunks() {
+            addr.checked_add(len)?;
+            len.checked_add(2 * SNAPSHOT_ALIGN)?;
+            data_len = data_len.checked_add(padded_len(addr, len))?;
+        }
+        if data_len != snapshot.data.len() {
+            return None;
+        }
+        Some(snapshot)
+    }
+
+    /// Returns contents of all chunks, including padding between them.
+    /// Offsets are relative to the start of this slice.
+    #[inline(always)]
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Returns number of chunks in the snapshot.
+    #[inline(always)]
+    pub fn chunk_count(&self) -> usize {
+        self.map.len() / 16
+    }
+
+    /// Returns reference to the value at `offset`.
+    /// Returns `None` if value doesn't fit into the snapshot data
+    /// or is not properly aligned.
+    ///
+    /// # Safety
+    ///
+    /// Bytes at `offset` must be a valid value of type `T`.
+    /// Pointers and references stored in the value
+    /// point into original arena and must not be dereferenced.
+    #[inline]
+    pub unsafe fn get<T>(&self, offset: ArenaOffset<T>) -> Option<&'a T> {
+        let offset = offset.offset();
+        if offset.checked_add(size_of::<T>())? > self.data.len() {
+            return None;
+        }
+
+        // Safety: offset is within the data.
+        let ptr = unsafe { self.data.as_ptr().add(offset) };
+        if (ptr as usize) & (align_of::<T>() - 1) != 0 {
+            return None;
+        }
+
+        // Safety: pointer is aligned and within the data.
+        // Caller guarantees that bytes are a valid value.
+        Some(unsafe { &*ptr.cast::<T>() })
+    }
+
+    /// Translates pointer into the original arena
+    /// to offset of the value in the snapshot.
+    /// Returns `None` if value is not inside the snapshot.
+    #[inline]
+    pub fn offset_of<T>(&self, ptr: *const T) -> Option<ArenaOffset<T>> {
+        let addr = ptr as usize;
+        let mut offset = 0;
+        for (start, len) in self.chunks() {
+            if start <= addr && addr.checked_add(size_of::<T>())? <= start + len {
+                let offset = offset + lead_padding(start) + (addr - start);
+                return Some(ArenaOffset::new(offset));
+            }
+            offset += padded_len(start, len);
+        }
+        None
+    }
+
+    /// Resolves pointer into the original arena
+    /// to reference to the value in the snapshot.
+    /// Returns `None` if value is not inside the snapshot, e.g. for null pointer.
+    ///
+    /// # Safety
+    ///
+    /// Same as for [`BlinkSnapshot::get`].
+    #[inline]
+    pub unsafe fn resolve<T>(&self, ptr: *const T) -> Option<&'a T> {
+        let offset = self.offset_of(ptr)?;
+        unsafe { self.get(offset) }
+    }
+
+    /// Returns address and length of used memory
+    /// of each chunk in the original arena.
+    #[inline]
+    fn chunks(&self) -> impl Iterator<Item = (usize, usize)> + 'a {
+        let map = self.map;
+        (0..map.len() / 16).map(move |idx| {
+            let addr = read_u64(map, idx * 16).unwrap_or(0);
+            let len = read_u64(map, idx * 16 + 8).unwrap_or(0);
+            (addr, len)
+        })
+    }
+}
+
+/// Reads little-endian `u64` at `offset`.
+/// Returns `None` if bytes are too short or value doesn't fit `usize`.
+#[inline(always)]
+fn read_u64(bytes: &[u8], offset: usize) -> Option<usize> {
+    let bytes = bytes.get(offset..offset.checked_add(8)?)?;
+    let mut word = [0; 8];
+    word.copy_from_slice(bytes);
+    usize::try_from(u64::from_le_bytes(word)).ok()
+}
@@ -0,0 +1,87 @@
+//! This module provides struct-of-arrays allocation support.
+
+use core::alloc::Layout;
+
+/// Tuple of mutable slices with equal length
+/// that can be allocated back-to-back in one allocation.
+///
+/// Implemented for tuples of up to 8 mutable slices.
+/// See [`Blink::soa_from_fn`](crate::Blink::soa_from_fn).
+///
+/// # Safety
+///
+/// This trait is not meant to be implemented outside of this crate.
+pub unsafe trait SoaSlices<'a>: Sized {
+    /// Tuple of slice elements.
+    type Elems;
+
+    #[doc(hidden)]
+    type Offsets: Copy;
+
+    /// Returns layout of all slices and offsets of each slice.
+    /// Returns `None` on size overflow.
+    #[doc(hidden)]
+    fn layout(len: usize) -> Option<(Layout, Self::Offsets)>;
+
+    /// Writes elements at index `idx` of each slice.
+    ///
+    /// # Safety
+    ///
+    /// `base` must be allocated with layout returned for `len > idx`.
+    #[doc(hidden)]
+    unsafe fn write(base: *mut u8, offsets: Self::Offsets, idx: usize, elems: Self::Elems);
+
+    /// Returns slices.
+    ///
+    /// # Safety
+    ///
+    /// `base` must be allocated with layout returned for `len`
+    /// and all elements must be written.
+    #[doc(hidden)]
+    unsafe fn slices(base: *mut u8, offsets: Self::Offsets, len: usize) -> Self;
+}
+
+macro_rules! impl_soa_slices {
+    (@usize $t:ident) => { usize };
+    ($($t:ident $o:ident $v:ident),+) => {
+        unsafe impl<'a, $($t),+> SoaSlices<'a> for ($(&'a mut [$t],)+) {
+            type Elems = ($($t,)+);
+            type Offsets = ($(impl_soa_slices!(@usize $t),)+);
+
+            #[inline(always)]
+            fn layout(len: usize) -> Option<(Layout, Self::Offsets)> {
+                let layout = Layout::new::<()>();
+                $(
+                    let (layout, $o) = layout.extend(Layout::array::<$t>(len).ok()?).ok()?;
+                )+
+                Some((layout, ($($o,)+)))
+            }
+
+            #[inline(always)]
+            unsafe fn write(base: *mut u8, offsets: Self::Offsets, idx: usize, elems: Self::Elems) {
+                let ($($o,)+) = offsets;
+                let ($($v,)+) = elems;
+                $(
+                    base.add($o).cast::<$t>().add(idx).write($v);
+                )+
+            }
+
+            #[inline(always)]
+            unsafe fn slices(base: *mut u8, offsets: Self::Offsets, len: usize) -> Self {
+                let ($($o,)+) = offsets;
+                ($(
+                    core::slice::from_raw_parts_mut(base.add($o).cast::<$t>(), len),
+                )+)
+            }
+        }
+    };
+}
+
+impl_soa_slices!(A oa va);
+impl_soa_slices!(A oa va, B ob vb);
+impl_soa_slices!(A oa va, B ob vb, C oc vc);
+impl_soa_slices!(A oa va, B ob vb, C oc vc, D od vd);
+impl_soa_slices!(A oa va, B ob vb, C oc vc, D od vd, E oe ve);
+impl_soa_slices!(A oa va, B ob vb, C oc vc, D od vd, E oe ve, F of vf);
+impl_soa_slices!(A oa va, B ob vb, C oc vc, D od vd, E oe ve, F of vf, G og vg);
+impl_soa_slices!(A oa va, B ob vb, C oc vc, D od vd, E oe ve, F of vf, G og vg, H oh vh);
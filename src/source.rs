@@ -0,0 +1,250 @@
+//! This module provides `ChunkSource` trait
+//! that blink allocators use to obtain memory chunks.
+
+use core::{
+    alloc::Layout,
+    marker::PhantomData,
+    mem::MaybeUninit,
+    ptr::{self, NonNull},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+#[cfg(feature = "nightly")]
+use core::alloc::{AllocError, Allocator};
+
+#[cfg(not(feature = "nightly"))]
+use allocator_api2::alloc::{AllocError, Allocator};
+
+/// Source of memory chunks for blink allocators.
+///
+/// Arena requests a chunk when allocation doesn't fit into current one
+/// and releases chunks on reset and drop.
+/// Implementing this trait is enough to plug custom memory backend,
+/// like `mmap`, chunk recycling or telemetry, into
+/// [`BlinkAlloc`](crate::BlinkAlloc) and [`SyncBlinkAlloc`](crate::SyncBlinkAlloc).
+///
+/// Every [`Allocator`] is a chunk source,
+/// including [`ChunkPool`](crate::ChunkPool) that recycles chunks.
+/// [`StaticBuffer`] provides single chunk from borrowed buffer.
+///
+/// Unlike [`Allocator`], references to chunk sources
+/// are not chunk sources automatically.
+/// Implement this trait for reference type as well
+/// to share one source between multiple allocators.
+///
+/// # Safety
+///
+/// Chunk returned from [`allocate_chunk`](ChunkSource::allocate_chunk)
+/// must be valid for reads and writes, aligned to `layout.align()`,
+/// at least `layout.size()` bytes long
+/// and not used by anything else until it is released.
+///
+/// # Example
+///
+/// ```
+/// # #![cfg_attr(feature = "nightly", feature(allocator_api))]
+/// # #[cfg(feature = "alloc")] fn main() {
+/// # use std::{alloc::Layout, ptr::NonNull};
+/// # #[cfg(feature = "nightly")]
+/// # use std::alloc::AllocError;
+/// # #[cfg(not(feature = "nightly"))]
+/// # use allocator_api2::alloc::AllocError;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use blink_alloc::{BlinkAlloc, ChunkSource};
+///
+/// /// Counts chunks in use.
+/// struct Counting(AtomicUsize);
+///
+/// unsafe impl ChunkSource for Counting {
+///     fn allocate_chunk(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+///         let ptr = NonNull::new(unsafe { std::alloc::alloc(layout) }).ok_or(AllocError)?;
+///         self.0.fetch_add(1, Ordering::Relaxed);
+///         Ok(NonNull::slice_from_raw_parts(ptr, layout.size()))
+///     }
+///
+///     unsafe fn release_chunk(&self, ptr: NonNull<u8>, layout: Layout) {
+///         self.0.fetch_sub(1, Ordering::Relaxed);
+///         unsafe { std::alloc::dealloc(ptr.as_ptr(), layout) }
+///     }
+/// }
+///
+/// let mut blink = BlinkAlloc::new_in(Counting(AtomicUsize::new(0)));
+/// blink.allocate(Layout::new::<[u8; 1000]>()).unwrap();
+/// assert_eq!(blink.inner().0.load(Ordering::Relaxed), 1);
+///
+/// blink.reset_final();
+/// assert_eq!(blink.inner().0.load(Ordering::Relaxed), 0);
+/// # }
+/// # #[cfg(not(feature = "alloc"))] fn main() {}
+/// ```
+pub unsafe trait ChunkSource {
+    /// Allocates memory chunk that fits `layout`.
+    /// Returned chunk may be larger than requested.
+    fn allocate_chunk(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError>;
+
+    /// Allocates zero-initialized memory chunk that fits `layout`.
+    #[inline]
+    fn allocate_chunk_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let chunk = self.allocate_chunk(layout)?;
+
+        // Safety: chunk is valid for writes of its length.
+        unsafe { ptr::write_bytes(chunk.as_ptr().cast::<u8>(), 0, chunk.len()) };
+        Ok(chunk)
+    }
+
+    /// Tries to extend memory chunk in place.
+    /// Returns new length of the chunk that is at least `new_layout.size()`.
+    ///
+    /// Thread-local [`BlinkAlloc`](crate::BlinkAlloc) calls this
+    /// when the current chunk is exhausted,
+    /// before falling back to a new chunk.
+    /// Growing keeps arena memory contiguous, which avoids wasting
+    /// the tail of the exhausted chunk.
+    ///
+    /// Chunk must not move, as arena allocations point into it.
+    /// This is why [`Allocator::grow`] is not used here.
+    /// Default implementation always fails.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be a chunk allocated from this source and not released yet.
+    /// `old_layout` must fit the chunk as for [`release_chunk`](ChunkSource::release_chunk).
+    /// `new_layout` must have the same alignment and a larger size.
+    ///
+    /// On success the chunk must be released with layout
+    /// that fits its new length.
+    #[inline]
+    unsafe fn grow_chunk_in_place(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<usize, AllocError> {
+        let _ = (ptr, old_layout, new_layout);
+        Err(AllocError)
+    }
+
+    /// Releases memory chunk.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be a chunk allocated from this source and not released yet.
+    /// `layout` must have the same alignment as requested
+    /// and size between requested size and length of returned chunk.
+    /// If chunk was grown, sizes are those of the last growth.
+    unsafe fn release_chunk(&self, ptr: NonNull<u8>, layout: Layout);
+}
+
+unsafe impl<A> ChunkSource for A
+where
+    A: Allocator + ?Sized,
+{
+    #[inline(always)]
+    fn allocate_chunk(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.allocate(layout)
+    }
+
+    #[inline(always)]
+    fn allocate_chunk_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.allocate_zeroed(layout)
+    }
+
+    #[inline(always)]
+    unsafe fn release_chunk(&self, ptr: NonNull<u8>, layout: Layout) {
+        unsafe { self.deallocate(ptr, layout) }
+    }
+}
+
+/// Chunk source that provides borrowed buffer as the only chunk.
+///
+/// Blink allocator over this source never touches the heap.
+/// Allocations fail once the buffer is exhausted.
+/// Buffer becomes available again when allocator releases it
+/// on [`reset_final`](crate::BlinkAlloc::reset_final) or drop.
+///
+/// # Example
+///
+/// ```
+/// use core::mem::MaybeUninit;
+/// use blink_alloc::{BlinkAlloc, StaticBuffer};
+///
+/// let mut buffer = [MaybeUninit::uninit(); 4096];
+/// let source = StaticBuffer::new(&mut buffer);
+///
+/// let blink = BlinkAlloc::new_in(&source);
+/// blink.allocate(core::alloc::Layout::new::<[u8; 1000]>()).unwrap();
+/// assert!(blink.allocate(core::alloc::Layout::new::<[u8; 4096]>()).is_err());
+/// ```
+pub struct StaticBuffer<'a> {
+    ptr: NonNull<u8>,
+    len: usize,
+    taken: AtomicBool,
+    marker: PhantomData<&'a mut [u8]>,
+}
+
+// Safety: `StaticBuffer` owns unique borrow of the buffer.
+// Access to the buffer is synchronized with `taken` flag.
+unsafe impl Send for StaticBuffer<'_> {}
+unsafe impl Sync for StaticBuffer<'_> {}
+
+impl<'a> StaticBuffer<'a> {
+    /// Creates chunk source from the buffer.
+    #[inline(always)]
+    pub fn new(buffer: &'a mut [MaybeUninit<u8>]) -> Self {
+        let len = buffer.len();
+        StaticBuffer {
+            ptr: NonNull::from(buffer).cast(),
+            len,
+            taken: AtomicBool::new(false),
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns size of the buffer.
+    #[inline(always)]
+    pub fn capacity(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if buffer is currently used as a chunk.
+    #[inline(always)]
+    pub fn is_taken(&self) -> bool {
+        self.taken.load(Ordering::Relaxed)
+    }
+}
+
+unsafe impl ChunkSource for StaticBuffer<'_> {
+    #[inline]
+    fn allocate_chunk(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let offset = self.ptr.as_ptr().align_offset(layout.align());
+        if offset > self.len || self.len - offset < layout.size() {
+            return Err(AllocError);
+        }
+
+        if self.taken.swap(true, Ordering::Acquire) {
+            return Err(AllocError);
+        }
+
+        // Safety: `offset` is within the buffer.
+        let ptr = unsafe { NonNull::new_unchecked(self.ptr.as_ptr().add(offset)) };
+        Ok(NonNull::slice_from_raw_parts(ptr, self.len - offset))
+    }
+
+    #[inline]
+    unsafe fn release_chunk(&self, ptr: NonNull<u8>, layout: Layout) {
+        let _ = (ptr, layout);
+        self.taken.store(false, Ordering::Release);
+    }
+}
+
+unsafe impl ChunkSource for &StaticBuffer<'_> {
+    #[inline(always)]
+    fn allocate_chunk(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        StaticBuffer::allocate_chunk(self, layout)
+    }
+
+    #[inline(always)]
+    unsafe fn release_chunk(&self, ptr: NonNull<u8>, layout: Layout) {
+        unsafe { StaticBuffer::release_chunk(self, ptr, layout) }
+    }
+}
@@ -0,0 +1,176 @@
+//! This module provides snapshot of blink allocator statistics.
+
+use core::fmt;
+
+/// Snapshot of blink allocator statistics.
+///
+/// Retrieved with [`BlinkAlloc::stats`](crate::BlinkAlloc::stats).
+/// Two snapshots can be compared with [`BlinkStats::diff`]
+/// to find how allocator usage changed between two points.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(all(feature = "alloc", not(feature = "debug-headers")))] fn main() {
+/// use blink_alloc::BlinkAlloc;
+/// let blink = BlinkAlloc::new();
+/// let start = blink.stats();
+///
+/// blink.allocate(std::alloc::Layout::new::<[u8; 100]>()).unwrap();
+/// blink.allocate(std::alloc::Layout::new::<[u8; 100]>()).unwrap();
+///
+/// let diff = blink.stats().diff(&start);
+/// assert_eq!(diff.allocations, 2);
+/// assert_eq!(diff.allocated_bytes, 200);
+/// assert_eq!(diff.chunks, 1);
+/// assert_eq!(diff.slow_path_hits, 1);
+///
+/// println!("{}", blink.stats());
+/// # }
+/// # #[cfg(not(all(feature = "alloc", not(feature = "debug-headers"))))] fn main() {}
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BlinkStats {
+    /// Bytes allocated since last reset.
+    /// See [`BlinkAlloc::allocated_bytes`](crate::BlinkAlloc::allocated_bytes).
+    pub allocated_bytes: usize,
+
+    /// Total capacity of all chunks.
+    /// See [`BlinkAlloc::total_capacity`](crate::BlinkAlloc::total_capacity).
+    pub total_capacity: usize,
+
+    /// Number of chunks held by the allocator.
+    pub chunks: usize,
+
+    /// Number of allocations made since allocator creation.
+    /// Not affected by resets.
+    pub allocations: usize,
+
+    /// Largest number of allocated bytes between resets
+    /// since allocator creation.
+    pub peak_allocated_bytes: usize,
+
+    /// Number of allocations and resizes served from current chunk
+    /// since allocator creation.
+    pub fast_path_hits: usize,
+
+    /// Number of allocations and resizes that didn't fit into current chunk
+    /// since allocator creation.
+    pub slow_path_hits: usize,
+}
+
+impl BlinkStats {
+    /// Returns change of statistics since `earlier` snapshot.
+    #[inline]
+    pub fn diff(&self, earlier: &BlinkStats) -> BlinkStatsDiff {
+        BlinkStatsDiff {
+            allocated_bytes: self.allocated_bytes.wrapping_sub(earlier.allocated_bytes) as isize,
+            total_capacity: self.total_capacity.wrapping_sub(earlier.total_capacity) as isize,
+            chunks: self.chunks.wrapping_sub(earlier.chunks) as isize,
+            allocations: self.allocations.wrapping_sub(earlier.allocations),
+            fast_path_hits: self.fast_path_hits.wrapping_sub(earlier.fast_path_hits),
+            slow_path_hits: self.slow_path_hits.wrapping_sub(earlier.slow_path_hits),
+        }
+    }
+}
+
+impl fmt::Display for BlinkStats {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "allocated: {} B, capacity: {} B, chunks: {}, peak: {} B, allocations: {}, slow path hits: {}",
+            self.allocated_bytes,
+            self.total_capacity,
+            self.chunks,
+            self.peak_allocated_bytes,
+            self.allocations,
+            self.slow_path_hits,
+        )
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for BlinkStats {
+    #[inline]
+    fn format(&self, f: defmt::Formatter<'_>) {
+        defmt::write!(
+            f,
+            "allocated: {=usize} B, capacity: {=usize} B, chunks: {=usize}, peak: {=usize} B, allocations: {=usize}, slow path hits: {=usize}",
+            self.allocated_bytes,
+            self.total_capacity,
+            self.chunks,
+            self.peak_allocated_bytes,
+            self.allocations,
+            self.slow_path_hits,
+        )
+    }
+}
+
+/// Change of blink allocator statistics between two snapshots.
+///
+/// Returned by [`BlinkStats::diff`].
+/// Byte and chunk counts are negative if they decreased,
+/// e.g. when allocator was reset between snapshots.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BlinkStatsDiff {
+    /// Growth of allocated bytes.
+    pub allocated_bytes: isize,
+
+    /// Growth of total capacity.
+    pub total_capacity: isize,
+
+    /// Growth of number of chunks.
+    pub chunks: isize,
+
+    /// Number of allocations made between snapshots.
+    pub allocations: usize,
+
+    /// Number of fast path hits between snapshots.
+    pub fast_path_hits: usize,
+
+    /// Number of slow path hits between snapshots.
+    pub slow_path_hits: usize,
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for BlinkStatsDiff {
+    #[inline]
+    fn format(&self, f: defmt::Formatter<'_>) {
+        defmt::write!(
+            f,
+            "allocated: {=isize} B, capacity: {=isize} B, chunks: {=isize}, allocations: {=usize}, slow path hits: {=usize}",
+            self.allocated_bytes,
+            self.total_capacity,
+            self.chunks,
+            self.allocations,
+            self.slow_path_hits,
+        )
+    }
+}
+
+/// Bytes allocated within a scope.
+///
+/// Returned by [`Blink::measure`](crate::Blink::measure).
+/// Allocators that don't track allocated bytes or capacity report zero.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ScopeBytes {
+    /// Bytes allocated within the scope.
+    /// See [`BlinkAllocator::allocated_bytes`](crate::BlinkAllocator::allocated_bytes).
+    pub allocated_bytes: usize,
+
+    /// Growth of total capacity within the scope.
+    /// Non-zero if the scope required new chunks.
+    pub capacity_growth: usize,
+}
+
+impl fmt::Display for ScopeBytes {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "allocated: {} B, capacity growth: {} B",
+            self.allocated_bytes, self.capacity_growth,
+        )
+    }
+}
@@ -18,9 +18,17 @@ use alloc::alloc::Global;
 #[cfg(all(not(feature = "nightly"), feature = "alloc"))]
 use allocator_api2::alloc::Global;
 
+#[cfg(feature = "debug-stats")]
+use crate::histogram::{SizeHistogram, SyncHistogram};
+
 use crate::{
     api::BlinkAllocator,
-    arena::{ArenaLocal, ArenaSync},
+    arena::{pad_layout, pad_size, ArenaLocal, ArenaSync, Utilization},
+    copy::copy_bulk,
+    debug_header,
+    hooks::{ArenaHooks, Events, Hooked},
+    source::ChunkSource,
+    stats::BlinkStats,
 };
 
 switch_alloc_default! {
@@ -52,8 +60,8 @@ switch_alloc_default! {
     /// It can be used from multiple threads concurrently to allocate memory.
     /// As mutable borrow is required to reset the allocator,
     /// it is not possible to do when shared.
-    /// Internally it uses [`RwLock`] and [`AtomicUsize`] for synchronized
-    /// interior mutability. [`RwLock`] is only write-locked when new chunk
+    /// Internally it uses `RwLock` and [`AtomicUsize`] for synchronized
+    /// interior mutability. `RwLock` is only write-locked when new chunk
     /// must be allocated. The arena allocation is performed using lock-free
     /// algorithm.
     ///
@@ -63,7 +71,6 @@ switch_alloc_default! {
     /// this allocator. [`LocalBlinkAlloc`] will allocate chunks from this
     /// allocator, but is single-threaded by itself.
     ///
-    /// [`RwLock`]: parking_lot::RwLock
     /// [`AtomicUsize`]: core::sync::atomic::AtomicUsize
     /// [`BlinkAlloc`]: crate::local::BlinkAlloc
     /// [`LocalBlinkAlloc`]: crate::sync::LocalBlinkAlloc
@@ -75,6 +82,7 @@ switch_alloc_default! {
     /// # use blink_alloc::SyncBlinkAlloc;
     /// # use std::ptr::NonNull;
     ///
+    /// # #[cfg(feature = "alloc")] fn main() {
     /// let mut blink = SyncBlinkAlloc::new();
     /// let layout = std::alloc::Layout::new::<[u32; 8]>();
     /// let ptr = blink.allocate(layout).unwrap();
@@ -85,6 +93,8 @@ switch_alloc_default! {
     /// }
     ///
     /// blink.reset();
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
     /// ```
     ///
     /// # Example that uses nightly's `allocator_api`
@@ -94,9 +104,9 @@ switch_alloc_default! {
     /// # use blink_alloc::SyncBlinkAlloc;
     /// # #[cfg(feature = "nightly")]
     /// # use std::vec::Vec;
-    /// # #[cfg(not(feature = "nightly"))]
+    /// # #[cfg(all(not(feature = "nightly"), feature = "alloc"))]
     /// # use allocator_api2::vec::Vec;
-    /// # fn main() {
+    /// # #[cfg(feature = "alloc")] fn main() {
     /// let mut blink = SyncBlinkAlloc::new();
     /// let mut vec = Vec::new_in(&blink);
     /// vec.push(1);
@@ -105,34 +115,45 @@ switch_alloc_default! {
     /// drop(vec);
     /// blink.reset();
     /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
     /// ```
-    pub struct SyncBlinkAlloc<A: Allocator = +Global> {
+    pub struct SyncBlinkAlloc<A: ChunkSource = +Global> {
         arena: ArenaSync,
         allocator: A,
+        min_align: usize,
         max_local_alloc: AtomicUsize,
+        events: Events,
+        #[cfg(feature = "debug-stats")]
+        histogram: SyncHistogram,
+        #[cfg(debug_assertions)]
+        live: Option<AtomicUsize>,
     }
 }
 
-impl<A: Allocator> Drop for SyncBlinkAlloc<A> {
+impl<A: ChunkSource> Drop for SyncBlinkAlloc<A> {
     fn drop(&mut self) {
         unsafe {
-            self.arena.reset(false, &self.allocator);
+            self.arena
+                .reset(false, &Hooked::new(&self.allocator, self.events));
         }
     }
 }
 
 #[test]
 fn check_sync() {
-    fn for_sync_alloc<A: Allocator + Sync>() {
+    fn for_sync_alloc<A: ChunkSource + Sync + 'static>() {
         fn is_sink<T: Sync>() {}
         is_sink::<SyncBlinkAlloc<A>>();
+        is_sink::<ScopedBlinkHandle<'static, A>>();
     }
+    #[cfg(feature = "alloc")]
     for_sync_alloc::<Global>();
+    for_sync_alloc::<crate::StaticBuffer<'static>>();
 }
 
 impl<A> Default for SyncBlinkAlloc<A>
 where
-    A: Allocator + Default,
+    A: ChunkSource + Default,
 {
     #[inline(always)]
     fn default() -> Self {
@@ -146,6 +167,23 @@ impl SyncBlinkAlloc<Global> {
     /// to allocate memory chunks.
     ///
     /// See [`SyncBlinkAlloc::new_in`] for using custom allocator.
+    ///
+    /// This function is `const`, so allocator can be placed into a `static`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #![cfg_attr(feature = "nightly", feature(allocator_api))]
+    /// # use blink_alloc::SyncBlinkAlloc;
+    /// static FRAME: SyncBlinkAlloc = SyncBlinkAlloc::new();
+    ///
+    /// let layout = std::alloc::Layout::new::<[u32; 8]>();
+    /// let ptr = FRAME.allocate(layout).unwrap();
+    /// assert!(FRAME.contains(ptr.cast()));
+    ///
+    /// // Safety: memory allocated from `FRAME` is not used after reset.
+    /// unsafe { FRAME.reset_unchecked() };
+    /// ```
     #[inline(always)]
     pub const fn new() -> Self {
         SyncBlinkAlloc::new_in(Global)
@@ -154,21 +192,143 @@ impl SyncBlinkAlloc<Global> {
 
 impl<A> SyncBlinkAlloc<A>
 where
-    A: Allocator,
+    A: ChunkSource,
 {
     /// Creates new blink allocator that uses provided allocator
     /// to allocate memory chunks.
+    /// Any [`ChunkSource`] can be used in place of allocator.
     ///
     /// See [`SyncBlinkAlloc::new`] for using global allocator.
     #[inline(always)]
     pub const fn new_in(allocator: A) -> Self {
         SyncBlinkAlloc {
+            min_align: 1,
             arena: ArenaSync::new(),
             allocator,
             max_local_alloc: AtomicUsize::new(0),
+            events: Events::new(),
+            #[cfg(feature = "debug-stats")]
+            histogram: SyncHistogram::new(),
+            #[cfg(debug_assertions)]
+            live: None,
         }
     }
 
+    /// Sets minimal alignment of all allocations.
+    ///
+    /// Alignment and size of every allocation are rounded up to `min_align`,
+    /// so no two allocations share a block of `min_align` bytes.
+    /// Set to cache line size, e.g. `64`, for data accessed concurrently
+    /// by multiple threads to avoid false sharing.
+    /// Local proxies created with [`SyncBlinkAlloc::local`] use the same alignment.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min_align` is not a power of two.
+    #[inline]
+    pub fn with_min_align(mut self, min_align: usize) -> Self {
+        assert!(
+            min_align.is_power_of_two(),
+            "Minimal alignment must be a power of two"
+        );
+        self.min_align = min_align;
+        self
+    }
+
+    /// Sets minimal alignment of chunks allocated from underlying allocator.
+    ///
+    /// See [`BlinkAlloc::with_chunk_align`](crate::BlinkAlloc::with_chunk_align).
+    /// Chunks of local proxies created with [`SyncBlinkAlloc::local`]
+    /// use the same alignment.
+    /// Chunks allocated before this call are deallocated,
+    /// same as with [`reset_final`](SyncBlinkAlloc::reset_final).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `align` is not a power of two.
+    #[inline]
+    pub fn with_chunk_align(mut self, align: usize) -> Self {
+        assert!(
+            align.is_power_of_two(),
+            "Chunk alignment must be a power of two"
+        );
+        self.reset_final();
+        self.arena.set_chunk_align(align);
+        self
+    }
+
+    /// Installs hooks called on arena events.
+    ///
+    /// See [`ArenaHooks`] for details.
+    #[inline]
+    pub fn with_hooks(mut self, hooks: &'static dyn ArenaHooks) -> Self {
+        self.events.hooks = Some(hooks);
+        self
+    }
+
+    /// Sets name of this allocator.
+    ///
+    /// Name is reported in tracing events under `"tracing"` feature.
+    /// Under `"tracy"` feature chunks of named allocator are reported
+    /// to Tracy profiler as memory pool with this name.
+    #[inline]
+    pub fn with_name(mut self, name: &'static str) -> Self {
+        self.events.set_name(name);
+        self
+    }
+
+    /// Enables tracking of live allocations in debug builds.
+    ///
+    /// Allocator counts allocations that are not deallocated yet
+    /// and panics if it is reset while any remain.
+    /// Allocations made by local proxies are not tracked.
+    ///
+    /// See [`BlinkAlloc::with_live_check`](crate::BlinkAlloc::with_live_check) for details.
+    #[inline]
+    #[cfg_attr(not(debug_assertions), allow(unused_mut))]
+    pub fn with_live_check(mut self) -> Self {
+        #[cfg(debug_assertions)]
+        if self.live.is_none() {
+            self.live = Some(AtomicUsize::new(0));
+        }
+        self
+    }
+
+    /// Returns number of allocations that are not deallocated yet
+    /// if tracking is enabled with [`with_live_check`](SyncBlinkAlloc::with_live_check).
+    ///
+    /// Always returns `None` when `debug_assertions` are disabled.
+    #[inline(always)]
+    pub fn live_allocations(&self) -> Option<usize> {
+        #[cfg(debug_assertions)]
+        {
+            self.live.as_ref().map(|live| live.load(Ordering::Relaxed))
+        }
+        #[cfg(not(debug_assertions))]
+        {
+            None
+        }
+    }
+
+    /// Panics if tracking is enabled and some allocations are still live.
+    #[inline(always)]
+    fn check_live(&self) {
+        #[cfg(debug_assertions)]
+        if let Some(live) = self.live_allocations() {
+            assert!(
+                live == 0,
+                "SyncBlinkAlloc is reset with {} live allocations",
+                live
+            );
+        }
+    }
+
+    /// Returns name of this allocator if set with [`with_name`](SyncBlinkAlloc::with_name).
+    #[inline(always)]
+    pub fn name(&self) -> Option<&'static str> {
+        self.events.name
+    }
+
     /// Returns reference to the underlying allocator used by this blink allocator.
     #[inline(always)]
     pub const fn inner(&self) -> &A {
@@ -183,9 +343,44 @@ where
     #[inline(always)]
     pub const fn with_chunk_size_in(chunk_size: usize, allocator: A) -> Self {
         SyncBlinkAlloc {
+            min_align: 1,
             arena: ArenaSync::with_chunk_size(chunk_size),
             allocator,
             max_local_alloc: AtomicUsize::new(0),
+            events: Events::new(),
+            #[cfg(feature = "debug-stats")]
+            histogram: SyncHistogram::new(),
+            #[cfg(debug_assertions)]
+            live: None,
+        }
+    }
+
+    /// Creates new blink allocator that uses provided allocator
+    /// to allocate memory chunks.
+    /// With this method you can specify initial chunk size
+    /// and maximum chunk size.
+    ///
+    /// Chunk size grows with each new chunk until it reaches `max_chunk_size`,
+    /// after that new chunks are allocated with `max_chunk_size`.
+    /// Allocations that don't fit into chunk of `max_chunk_size`
+    /// get chunks of required size.
+    /// Local proxies created with [`SyncBlinkAlloc::local`] use the same bound.
+    #[inline(always)]
+    pub const fn with_chunk_size_range_in(
+        min_chunk_size: usize,
+        max_chunk_size: usize,
+        allocator: A,
+    ) -> Self {
+        SyncBlinkAlloc {
+            min_align: 1,
+            arena: ArenaSync::with_chunk_size_range(min_chunk_size, max_chunk_size),
+            allocator,
+            max_local_alloc: AtomicUsize::new(0),
+            events: Events::new(),
+            #[cfg(feature = "debug-stats")]
+            histogram: SyncHistogram::new(),
+            #[cfg(debug_assertions)]
+            live: None,
         }
     }
 
@@ -209,7 +404,7 @@ where
     /// # use blink_alloc::SyncBlinkAlloc;
     /// # #[cfg(feature = "nightly")]
     /// # use std::vec::Vec;
-    /// # #[cfg(not(feature = "nightly"))]
+    /// # #[cfg(all(not(feature = "nightly"), feature = "alloc"))]
     /// # use allocator_api2::vec::Vec;
     /// # #[cfg(feature = "alloc")] fn main() {
     /// let mut blink = SyncBlinkAlloc::new();
@@ -231,23 +426,118 @@ where
     /// ```
     #[inline(always)]
     pub fn local(&self) -> LocalBlinkAlloc<'_, A> {
+        let mut arena = ArenaLocal::with_chunk_size_range(
+            self.max_local_alloc.load(Ordering::Relaxed),
+            self.arena.max_chunk_size(),
+        );
+        arena.set_chunk_align(self.arena.chunk_align());
+
         LocalBlinkAlloc {
-            arena: ArenaLocal::with_chunk_size(self.max_local_alloc.load(Ordering::Relaxed)),
+            arena,
             shared: self,
         }
     }
 
+    /// Returns a copyable handle to this allocator
+    /// bound to the lifetime of the borrow.
+    ///
+    /// See [`ScopedBlinkHandle`] for details.
+    #[inline(always)]
+    pub fn handle(&self) -> ScopedBlinkHandle<'_, A> {
+        ScopedBlinkHandle { shared: self }
+    }
+
     /// Allocates memory with specified layout from this allocator.
     /// If needed it will allocate new chunk using underlying allocator.
     /// If chunk allocation fails, it will return `Err`.
+    ///
+    /// Returned slice covers whole usable region of the allocation,
+    /// with size rounded up to the next alignment boundary.
     #[inline(always)]
     pub fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        #[cfg(feature = "debug-stats")]
+        self.histogram.record(layout.size());
+
+        let ptr =
+            debug_header::allocate(layout, self.min_align, |layout| self.allocate_raw(layout))?;
+        #[cfg(debug_assertions)]
+        if let Some(live) = &self.live {
+            live.fetch_add(1, Ordering::Relaxed);
+        }
+        Ok(ptr)
+    }
+
+    /// Allocates at least `layout.size()` bytes with specified alignment.
+    /// Returns the whole usable region, which may be larger than requested.
+    ///
+    /// Same as [`allocate`](SyncBlinkAlloc::allocate), provided for symmetry
+    /// with `Allocator::allocate` contract that allows returning more memory.
+    /// Any size in range `layout.size()..=slice.len()` may be used
+    /// to resize or deallocate the memory.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::SyncBlinkAlloc;
+    /// let blink = SyncBlinkAlloc::new();
+    /// let layout = std::alloc::Layout::from_size_align(10, 8).unwrap();
+    /// let ptr = blink.allocate_at_least(layout).unwrap();
+    /// assert!(ptr.len() >= 16);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline(always)]
+    pub fn allocate_at_least(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.allocate(layout)
+    }
+
+    /// Allocates memory for a copy of the slice and copies it there.
+    /// Returns pointer to the copy, valid until reset.
+    /// If chunk allocation fails, it will return `Err`.
+    ///
+    /// Low-level counterpart of [`SyncBlink::copy_slice`](crate::SyncBlink::copy_slice)
+    /// for containers built directly on the allocator.
+    #[inline(always)]
+    pub fn allocate_slice_copy<T>(&self, slice: &[T]) -> Result<NonNull<[T]>, AllocError>
+    where
+        T: Copy,
+    {
+        let ptr = self.allocate(Layout::for_value(slice))?.cast::<T>();
+        // Safety: memory is allocated for the copy of the slice
+        // and can't overlap with memory borrowed by the slice.
+        unsafe { copy_bulk(slice.as_ptr(), ptr.as_ptr(), slice.len()) };
+        Ok(NonNull::slice_from_raw_parts(ptr, slice.len()))
+    }
+
+    #[inline(always)]
+    fn allocate_raw(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let layout = pad_layout(layout, self.min_align)?;
+
         // Safety:
         // Same instance is used for all allocations and resets.
         if let Some(ptr) = unsafe { self.arena.alloc_fast(layout) } {
             return Ok(ptr);
         }
-        unsafe { self.arena.alloc_slow(layout, &self.allocator) }
+        self.events.alloc_slow_path(layout);
+        self.events.retry_failed(
+            layout,
+            || self.failure_stats(),
+            || unsafe {
+                self.arena
+                    .alloc_slow(layout, &Hooked::new(&self.allocator, self.events))
+            },
+        )
+    }
+
+    /// Returns statistics reported to hooks on allocation failure.
+    #[cold]
+    fn failure_stats(&self) -> BlinkStats {
+        BlinkStats {
+            allocated_bytes: self.allocated_bytes(),
+            total_capacity: self.total_capacity(),
+            ..BlinkStats::default()
+        }
     }
 
     /// Resizes memory allocation.
@@ -269,17 +559,54 @@ where
         old_layout: Layout,
         new_layout: Layout,
     ) -> Result<NonNull<[u8]>, AllocError> {
+        #[cfg(feature = "debug-stats")]
+        self.histogram.record(new_layout.size());
+
+        unsafe {
+            debug_header::resize(
+                ptr,
+                old_layout,
+                new_layout,
+                self.min_align,
+                |layout| self.allocate_raw(layout),
+                |ptr, old_layout, new_layout| self.resize_raw(ptr, old_layout, new_layout),
+                |ptr, size| self.deallocate_raw(ptr, size),
+            )
+        }
+    }
+
+    #[inline(always)]
+    unsafe fn resize_raw(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let old_layout = pad_layout(old_layout, self.min_align)?;
+        let new_layout = pad_layout(new_layout, self.min_align)?;
+
         if let Some(ptr) = unsafe { self.arena.resize_fast(ptr, old_layout, new_layout) } {
             return Ok(ptr);
         }
 
+        self.events.alloc_slow_path(new_layout);
+
         // Safety:
         // Same instance is used for all allocations and resets.
         // `ptr` was allocated by this allocator.
-        unsafe {
-            self.arena
-                .resize_slow(ptr, old_layout, new_layout, &self.allocator)
-        }
+        // Old allocation stays valid if resize fails.
+        self.events.retry_failed(
+            new_layout,
+            || self.failure_stats(),
+            || unsafe {
+                self.arena.resize_slow(
+                    ptr,
+                    old_layout,
+                    new_layout,
+                    &Hooked::new(&self.allocator, self.events),
+                )
+            },
+        )
     }
 
     /// Deallocates memory previously allocated from this allocator.
@@ -295,10 +622,23 @@ where
     /// and `slice` is the slice pointer returned by [`allocate`](SyncBlinkAlloc::allocate).
     #[inline(always)]
     pub unsafe fn deallocate(&self, ptr: NonNull<u8>, size: usize) {
+        #[cfg(debug_assertions)]
+        if let Some(live) = &self.live {
+            let _ = live.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1));
+        }
+        unsafe {
+            debug_header::deallocate(ptr, size, self.min_align, |ptr, size| {
+                self.deallocate_raw(ptr, size)
+            })
+        }
+    }
+
+    #[inline(always)]
+    unsafe fn deallocate_raw(&self, ptr: NonNull<u8>, size: usize) {
         // Safety:
         // `ptr` was allocated by this allocator.
         unsafe {
-            self.arena.dealloc(ptr, size);
+            self.arena.dealloc(ptr, pad_size(size, self.min_align));
         }
     }
 
@@ -308,23 +648,59 @@ where
     /// one chunk should be sufficient for all allocations between resets.
     #[inline(always)]
     pub fn reset(&mut self) {
+        self.check_live();
+        self.events.reset(|| self.arena.allocated_bytes());
         // Safety:
         // Same instance is used for all allocations and resets.
         unsafe {
-            self.arena.reset(true, &self.allocator);
+            self.arena
+                .reset(true, &Hooked::new(&self.allocator, self.events));
         }
     }
 
     /// Resets this allocator, deallocating all chunks.
     #[inline(always)]
     pub fn reset_final(&mut self) {
+        self.check_live();
+        self.events.reset(|| self.arena.allocated_bytes());
+        // Safety:
+        // Same instance is used for all allocations and resets.
+        unsafe {
+            self.arena
+                .reset(false, &Hooked::new(&self.allocator, self.events));
+        }
+    }
+
+    /// Resets this allocator, replacing all chunks with a single chunk
+    /// sized to fit all allocations made since previous reset.
+    /// Next cycle with similar usage runs entirely in that chunk.
+    #[inline(always)]
+    pub fn reset_compact(&mut self) {
+        self.check_live();
+        self.events.reset(|| self.arena.allocated_bytes());
         // Safety:
         // Same instance is used for all allocations and resets.
         unsafe {
-            self.arena.reset(false, &self.allocator);
+            self.arena
+                .reset_compact(&Hooked::new(&self.allocator, self.events));
         }
     }
 
+    /// Resets this allocator, keeping all chunks.
+    /// Chunks other than the last one are reused
+    /// before new chunks are allocated.
+    /// Useful for bursty workloads that need multiple chunks every cycle,
+    /// as they stop allocating from underlying allocator after first cycle.
+    ///
+    /// Following [`reset`](SyncBlinkAlloc::reset) deallocates kept chunks
+    /// except the last one.
+    #[inline(always)]
+    pub fn reset_keep_all(&mut self) {
+        self.check_live();
+        self.events.reset(|| self.arena.allocated_bytes());
+        self.arena.reset_keep_all();
+    }
+
     /// Resets this allocator, deallocating all chunks except the last one.
     /// Last chunk will be reused.
     /// With steady memory usage after few iterations
@@ -339,25 +715,78 @@ where
     /// that allocated memory won't be used after reset.
     #[inline(always)]
     pub unsafe fn reset_unchecked(&self) {
+        self.check_live();
+        self.events.reset(|| self.arena.allocated_bytes());
         // Safety:
         // Same instance is used for all allocations and resets.
         unsafe {
-            self.arena.reset_unchecked(true, &self.allocator);
+            self.arena
+                .reset_unchecked(true, &Hooked::new(&self.allocator, self.events));
         }
     }
 
-    /// Unwrap this allocator, returning the underlying allocator.
-    /// Leaks allocated chunks.
+    /// Allocates `value` in [`Box`](allocator_api2::boxed::Box)
+    /// that uses this allocator.
+    #[cfg(all(feature = "alloc", not(no_global_oom_handling)))]
+    #[inline(always)]
+    pub fn boxed<T>(&self, value: T) -> allocator_api2::boxed::Box<T, &Self> {
+        allocator_api2::boxed::Box::new_in(value, self)
+    }
+
+    /// Creates empty [`Vec`](allocator_api2::vec::Vec)
+    /// that uses this allocator.
     ///
-    /// To deallocate all chunks call [`reset_final`](BlinkAlloc::reset_final) first.
+    /// # Example
     ///
-    /// The second returned value will use global allocator, so
-    /// use with care if this method is used inside global allocator.
+    /// ```
+    /// use blink_alloc::SyncBlinkAlloc;
+    ///
+    /// let blink = SyncBlinkAlloc::new();
+    /// std::thread::scope(|scope| {
+    ///     for _ in 0..2 {
+    ///         scope.spawn(|| {
+    ///             let mut vec = blink.vec();
+    ///             vec.extend(0..10u32);
+    ///             assert_eq!(vec.iter().sum::<u32>(), 45);
+    ///         });
+    ///     }
+    /// });
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[inline(always)]
+    pub fn vec<T>(&self) -> allocator_api2::vec::Vec<T, &Self> {
+        allocator_api2::vec::Vec::new_in(self)
+    }
+
+    /// Creates [`Vec`](allocator_api2::vec::Vec) with space
+    /// for at least `capacity` elements that uses this allocator.
+    #[cfg(all(feature = "alloc", not(no_global_oom_handling)))]
+    #[inline(always)]
+    pub fn vec_with_capacity<T>(&self, capacity: usize) -> allocator_api2::vec::Vec<T, &Self> {
+        allocator_api2::vec::Vec::with_capacity_in(capacity, self)
+    }
+
+    /// Unwrap this allocator, returning the underlying allocator.
+    ///
+    /// All chunks are deallocated first, same as with [`reset_final`](SyncBlinkAlloc::reset_final).
     pub fn into_inner(self) -> A {
-        let me = ManuallyDrop::new(self);
+        let mut me = ManuallyDrop::new(self);
+        me.reset_final();
+
+        // Safety: `me` is not used after this and is not dropped.
+        // Other fields have no resources to release after `reset_final`.
         unsafe { core::ptr::read(&me.allocator) }
     }
 
+    /// Replaces underlying allocator, returning the previous one.
+    ///
+    /// All chunks are deallocated to the previous allocator first,
+    /// same as with [`reset_final`](SyncBlinkAlloc::reset_final).
+    pub fn set_allocator(&mut self, allocator: A) -> A {
+        self.reset_final();
+        core::mem::replace(&mut self.allocator, allocator)
+    }
+
     /// Update maximum local allocation size.
     /// Can be used by thread-local blink-allocators that use
     /// this shared blink-allocator.
@@ -378,6 +807,9 @@ where
     /// this allocator, so their chunk allocations (not individual items)
     /// will be reflected here. Use [`LocalBlinkAlloc::allocated_bytes`]
     /// to track items allocated through a proxy.
+    ///
+    /// Does not take the lock, so it can be polled from another thread
+    /// without contending with allocations.
     pub fn allocated_bytes(&self) -> usize {
         self.arena.allocated_bytes()
     }
@@ -393,14 +825,62 @@ where
     /// this allocator, which contributes to the capacity reported here.
     /// Use [`LocalBlinkAlloc::total_capacity`] to inspect a proxy's
     /// own capacity.
+    ///
+    /// Does not take the lock, so it can be polled from another thread
+    /// without contending with allocations.
     pub fn total_capacity(&self) -> usize {
         self.arena.total_capacity()
     }
+
+    /// Returns the number of bytes consumed by chunk headers
+    /// and tails of chunks left unused when allocation
+    /// switched to a newer chunk.
+    ///
+    /// Takes the lock to walk the chunk list.
+    pub fn overhead_bytes(&self) -> usize {
+        self.arena.overhead_bytes()
+    }
+
+    /// Checks if `ptr` points into memory allocated from this allocator
+    /// since last reset.
+    /// Memory of chunks owned by [`LocalBlinkAlloc`] proxies is included.
+    ///
+    /// See [`BlinkAlloc::contains`](crate::BlinkAlloc::contains).
+    pub fn contains(&self, ptr: NonNull<u8>) -> bool {
+        self.arena.contains(ptr)
+    }
+
+    /// Returns iterator over utilization of memory chunks,
+    /// from the current chunk to the oldest one.
+    ///
+    /// See [`BlinkAlloc::utilization`](crate::BlinkAlloc::utilization).
+    pub fn utilization(&mut self) -> Utilization<'_> {
+        self.arena.utilization().into()
+    }
+
+    /// Returns histogram of allocation sizes recorded since creation
+    /// or last call to [`reset_size_histogram`](SyncBlinkAlloc::reset_size_histogram).
+    ///
+    /// Allocations and resizes are recorded with requested size.
+    /// Allocations made by local proxies are not recorded,
+    /// chunks allocated for them are.
+    #[cfg(feature = "debug-stats")]
+    #[inline]
+    pub fn size_histogram(&self) -> SizeHistogram {
+        self.histogram.get()
+    }
+
+    /// Clears histogram of allocation sizes.
+    #[cfg(feature = "debug-stats")]
+    #[inline]
+    pub fn reset_size_histogram(&self) {
+        self.histogram.reset();
+    }
 }
 
 unsafe impl<A> Allocator for SyncBlinkAlloc<A>
 where
-    A: Allocator,
+    A: ChunkSource,
 {
     #[inline(always)]
     fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
@@ -429,18 +909,36 @@ where
 
     #[inline(always)]
     unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
-        SyncBlinkAlloc::deallocate(self, ptr, layout.size());
+        SyncBlinkAlloc::deallocate(self, ptr, layout.pad_to_align().size());
     }
 }
 
+#[cfg(feature = "nightly")]
+impl_api2_allocator!(impl<A> SyncBlinkAlloc<A> where A: crate::ChunkSource);
+
 unsafe impl<A> BlinkAllocator for SyncBlinkAlloc<A>
 where
-    A: Allocator,
+    A: ChunkSource,
 {
     #[inline(always)]
     fn reset(&mut self) {
         SyncBlinkAlloc::reset(self)
     }
+
+    #[inline(always)]
+    fn reset_final(&mut self) {
+        SyncBlinkAlloc::reset_final(self)
+    }
+
+    #[inline(always)]
+    fn allocated_bytes(&self) -> Option<usize> {
+        Some(SyncBlinkAlloc::allocated_bytes(self))
+    }
+
+    #[inline(always)]
+    fn total_capacity(&self) -> Option<usize> {
+        Some(SyncBlinkAlloc::total_capacity(self))
+    }
 }
 
 switch_alloc_default! {
@@ -450,7 +948,7 @@ switch_alloc_default! {
     /// it is possible to create proxy once to use for many allocations.
     ///
     /// See [`SyncBlinkAlloc::local`] for more details.
-    pub struct LocalBlinkAlloc<'a, A: Allocator = +Global> {
+    pub struct LocalBlinkAlloc<'a, A: ChunkSource = +Global> {
         arena: ArenaLocal,
         shared: &'a SyncBlinkAlloc<A>,
     }
@@ -458,7 +956,7 @@ switch_alloc_default! {
 
 impl<A> Drop for LocalBlinkAlloc<'_, A>
 where
-    A: Allocator,
+    A: ChunkSource,
 {
     fn drop(&mut self) {
         self.shared
@@ -469,13 +967,22 @@ where
 
 impl<A> LocalBlinkAlloc<'_, A>
 where
-    A: Allocator,
+    A: ChunkSource,
 {
     /// Allocates memory with specified layout from this allocator.
     /// If needed it will allocate new chunk using underlying allocator.
     /// If chunk allocation fails, it will return `Err`.
     #[inline(always)]
     pub fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        debug_header::allocate(layout, self.shared.min_align, |layout| {
+            self.allocate_raw(layout)
+        })
+    }
+
+    #[inline(always)]
+    fn allocate_raw(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let layout = pad_layout(layout, self.shared.min_align)?;
+
         // Safety:
         // Same instance is used for all allocations and resets.
         if let Some(ptr) = unsafe { self.arena.alloc_fast(layout) } {
@@ -503,6 +1010,29 @@ where
         old_layout: Layout,
         new_layout: Layout,
     ) -> Result<NonNull<[u8]>, AllocError> {
+        unsafe {
+            debug_header::resize(
+                ptr,
+                old_layout,
+                new_layout,
+                self.shared.min_align,
+                |layout| self.allocate_raw(layout),
+                |ptr, old_layout, new_layout| self.resize_raw(ptr, old_layout, new_layout),
+                |ptr, size| self.deallocate_raw(ptr, size),
+            )
+        }
+    }
+
+    #[inline(always)]
+    unsafe fn resize_raw(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let old_layout = pad_layout(old_layout, self.shared.min_align)?;
+        let new_layout = pad_layout(new_layout, self.shared.min_align)?;
+
         if let Some(ptr) = unsafe { self.arena.resize_fast(ptr, old_layout, new_layout) } {
             return Ok(ptr);
         }
@@ -529,10 +1059,20 @@ where
     /// and `slice` is the slice pointer returned by [`allocate`](LocalBlinkAlloc::allocate).
     #[inline(always)]
     pub unsafe fn deallocate(&self, ptr: NonNull<u8>, size: usize) {
+        unsafe {
+            debug_header::deallocate(ptr, size, self.shared.min_align, |ptr, size| {
+                self.deallocate_raw(ptr, size)
+            })
+        }
+    }
+
+    #[inline(always)]
+    unsafe fn deallocate_raw(&self, ptr: NonNull<u8>, size: usize) {
         // Safety:
         // `ptr` was allocated by this allocator.
         unsafe {
-            self.arena.dealloc(ptr, size);
+            self.arena
+                .dealloc(ptr, pad_size(size, self.shared.min_align));
         }
     }
 
@@ -584,11 +1124,33 @@ where
     pub fn total_capacity(&self) -> usize {
         self.arena.total_capacity()
     }
+
+    /// Returns the number of bytes consumed by chunk headers
+    /// and unreachable chunk tails of this thread-local proxy allocator.
+    pub fn overhead_bytes(&self) -> usize {
+        self.arena.overhead_bytes()
+    }
+
+    /// Checks if `ptr` points into memory allocated
+    /// through this thread-local proxy allocator since last reset.
+    ///
+    /// See [`BlinkAlloc::contains`](crate::BlinkAlloc::contains).
+    pub fn contains(&self, ptr: NonNull<u8>) -> bool {
+        self.arena.contains(ptr)
+    }
+
+    /// Returns iterator over utilization of memory chunks
+    /// owned by this thread-local proxy allocator.
+    ///
+    /// See [`BlinkAlloc::utilization`](crate::BlinkAlloc::utilization).
+    pub fn utilization(&self) -> Utilization<'_> {
+        self.arena.utilization().into()
+    }
 }
 
 unsafe impl<A> Allocator for LocalBlinkAlloc<'_, A>
 where
-    A: Allocator,
+    A: ChunkSource,
 {
     #[inline(always)]
     fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
@@ -617,16 +1179,317 @@ where
 
     #[inline(always)]
     unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
-        LocalBlinkAlloc::deallocate(self, ptr, layout.size())
+        LocalBlinkAlloc::deallocate(self, ptr, layout.pad_to_align().size())
     }
 }
 
+#[cfg(feature = "nightly")]
+impl_api2_allocator!(impl<A> LocalBlinkAlloc<'_, A> where A: crate::ChunkSource);
+
 unsafe impl<A> BlinkAllocator for LocalBlinkAlloc<'_, A>
 where
-    A: Allocator,
+    A: ChunkSource,
 {
     #[inline(always)]
     fn reset(&mut self) {
         LocalBlinkAlloc::reset(self)
     }
+
+    #[inline(always)]
+    fn allocated_bytes(&self) -> Option<usize> {
+        Some(LocalBlinkAlloc::allocated_bytes(self))
+    }
+
+    #[inline(always)]
+    fn total_capacity(&self) -> Option<usize> {
+        Some(LocalBlinkAlloc::total_capacity(self))
+    }
+}
+
+switch_alloc_default! {
+    /// Handle to [`SyncBlinkAlloc`] bound to a scope.
+    ///
+    /// Handle is [`Copy`], can be sent to other threads
+    /// and implements [`Allocator`] by allocating from shared [`SyncBlinkAlloc`].
+    /// Lifetime `'scope` ties handle to the borrow of the allocator,
+    /// so worker threads spawned with [`std::thread::scope`]
+    /// can use it without `'static` workarounds,
+    /// and it is impossible to reset the allocator while any handle is alive.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #![cfg_attr(feature = "nightly", feature(allocator_api))]
+    /// # use blink_alloc::SyncBlinkAlloc;
+    /// # #[cfg(feature = "nightly")]
+    /// # use std::vec::Vec;
+    /// # #[cfg(all(not(feature = "nightly"), feature = "alloc"))]
+    /// # use allocator_api2::vec::Vec;
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// let mut blink = SyncBlinkAlloc::new();
+    ///
+    /// std::thread::scope(|scope| {
+    ///     let handle = blink.handle();
+    ///     for i in 0..4 {
+    ///         scope.spawn(move || {
+    ///             let mut vec = Vec::new_in(handle);
+    ///             vec.extend(0..i * 10);
+    ///             vec.len()
+    ///         });
+    ///     }
+    /// });
+    ///
+    /// blink.reset();
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    pub struct ScopedBlinkHandle<'scope, A: ChunkSource = +Global> {
+        shared: &'scope SyncBlinkAlloc<A>,
+    }
+}
+
+impl<A> Clone for ScopedBlinkHandle<'_, A>
+where
+    A: ChunkSource,
+{
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<A> Copy for ScopedBlinkHandle<'_, A> where A: ChunkSource {}
+
+impl<'scope, A> ScopedBlinkHandle<'scope, A>
+where
+    A: ChunkSource,
+{
+    /// Creates new handle to the shared allocator.
+    #[inline(always)]
+    pub fn new(shared: &'scope SyncBlinkAlloc<A>) -> Self {
+        ScopedBlinkHandle { shared }
+    }
+
+    /// Returns reference to the shared allocator
+    /// with the lifetime of the scope.
+    #[inline(always)]
+    pub fn shared(&self) -> &'scope SyncBlinkAlloc<A> {
+        self.shared
+    }
+
+    /// Creates a new thread-local blink allocator proxy
+    /// that borrows from the shared allocator for the whole scope.
+    ///
+    /// See [`SyncBlinkAlloc::local`].
+    #[inline(always)]
+    pub fn local(&self) -> LocalBlinkAlloc<'scope, A> {
+        self.shared.local()
+    }
+}
+
+unsafe impl<A> Allocator for ScopedBlinkHandle<'_, A>
+where
+    A: ChunkSource,
+{
+    #[inline(always)]
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        SyncBlinkAlloc::allocate(self.shared, layout)
+    }
+
+    #[inline(always)]
+    unsafe fn shrink(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        SyncBlinkAlloc::resize(self.shared, ptr, old_layout, new_layout)
+    }
+
+    #[inline(always)]
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        SyncBlinkAlloc::resize(self.shared, ptr, old_layout, new_layout)
+    }
+
+    #[inline(always)]
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        SyncBlinkAlloc::deallocate(self.shared, ptr, layout.pad_to_align().size());
+    }
+}
+
+#[cfg(feature = "nightly")]
+impl_api2_allocator!(impl<A> ScopedBlinkHandle<'_, A> where A: crate::ChunkSource);
+
+unsafe impl<A> BlinkAllocator for ScopedBlinkHandle<'_, A>
+where
+    A: ChunkSource,
+{
+    /// Handle is shared and can't reset the allocator.
+    /// This is no-op.
+    #[inline(always)]
+    fn reset(&mut self) {}
+
+    #[inline(always)]
+    fn allocated_bytes(&self) -> Option<usize> {
+        Some(self.shared.allocated_bytes())
+    }
+
+    #[inline(always)]
+    fn total_capacity(&self) -> Option<usize> {
+        Some(self.shared.total_capacity())
+    }
+}
+
+switch_alloc_default! {
+    /// [`SyncBlinkAlloc`] paired with [`std::thread::scope`].
+    ///
+    /// [`ScopedSyncBlink::scope`] spawns a thread scope
+    /// and passes [`ScopedBlinkHandle`] to the closure
+    /// to be distributed to scoped threads.
+    /// Handles can't escape the scope, so when scope ends
+    /// allocator is reset automatically.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #![cfg_attr(feature = "nightly", feature(allocator_api))]
+    /// # use blink_alloc::ScopedSyncBlink;
+    /// # #[cfg(feature = "nightly")]
+    /// # use std::vec::Vec;
+    /// # #[cfg(all(not(feature = "nightly"), feature = "std"))]
+    /// # use allocator_api2::vec::Vec;
+    /// # #[cfg(feature = "std")] fn main() {
+    /// let mut blink = ScopedSyncBlink::new();
+    ///
+    /// for _ in 0..3 {
+    ///     let total = blink.scope(|scope, handle| {
+    ///         let workers: std::vec::Vec<_> = (0..4)
+    ///             .map(|i| {
+    ///                 scope.spawn(move || {
+    ///                     let mut vec = Vec::new_in(handle);
+    ///                     vec.extend(0..i * 10);
+    ///                     vec.len()
+    ///                 })
+    ///             })
+    ///             .collect();
+    ///
+    ///         workers.into_iter().map(|w| w.join().unwrap()).sum::<usize>()
+    ///     });
+    ///     assert_eq!(total, 60);
+    /// }
+    /// # }
+    /// # #[cfg(not(feature = "std"))] fn main() {}
+    /// ```
+    pub struct ScopedSyncBlink<A: ChunkSource = +Global> {
+        blink: SyncBlinkAlloc<A>,
+    }
+}
+
+impl<A> Default for ScopedSyncBlink<A>
+where
+    A: ChunkSource + Default,
+{
+    #[inline(always)]
+    fn default() -> Self {
+        ScopedSyncBlink::from(SyncBlinkAlloc::default())
+    }
+}
+
+impl<A> From<SyncBlinkAlloc<A>> for ScopedSyncBlink<A>
+where
+    A: ChunkSource,
+{
+    #[inline(always)]
+    fn from(blink: SyncBlinkAlloc<A>) -> Self {
+        ScopedSyncBlink { blink }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl ScopedSyncBlink<Global> {
+    /// Creates new scoped allocator that uses global allocator
+    /// to allocate memory chunks.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        ScopedSyncBlink {
+            blink: SyncBlinkAlloc::new(),
+        }
+    }
+}
+
+impl<A> ScopedSyncBlink<A>
+where
+    A: ChunkSource,
+{
+    /// Creates new scoped allocator that uses provided allocator
+    /// to allocate memory chunks.
+    #[inline(always)]
+    pub const fn new_in(allocator: A) -> Self {
+        ScopedSyncBlink {
+            blink: SyncBlinkAlloc::new_in(allocator),
+        }
+    }
+
+    /// Runs closure in [`std::thread::scope`] passing handle to the allocator.
+    /// All threads spawned in the scope are joined before this method returns.
+    /// Allocator is reset after the scope ends.
+    ///
+    /// Handle can't outlive the scope,
+    /// so no memory allocated from it may be in use at that point.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the threads spawned in the scope panicked,
+    /// see [`std::thread::scope`].
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn scope<'env, F, T>(&mut self, f: F) -> T
+    where
+        F: for<'scope> FnOnce(
+            &'scope std::thread::Scope<'scope, 'env>,
+            ScopedBlinkHandle<'scope, A>,
+        ) -> T,
+        A: 'env,
+    {
+        // Plain reborrow of `self.blink` doesn't work here.
+        // It would have to outlive `'env` that is chosen by the caller,
+        // and it would still be alive when allocator is reset below.
+        let shared: *const SyncBlinkAlloc<A> = &self.blink;
+
+        let result = std::thread::scope(|scope| {
+            // Safety: `self` is borrowed mutably for the duration of this call,
+            // so allocator outlives the scope.
+            // Handle and allocations made with it can't escape the scope
+            // as `T` can't reference `'scope`.
+            // All threads spawned in the scope are joined before `reset`.
+            let handle = ScopedBlinkHandle::new(unsafe { &*shared });
+            f(scope, handle)
+        });
+
+        self.blink.reset();
+        result
+    }
+
+    /// Returns reference to the underlying allocator.
+    #[inline(always)]
+    pub fn get(&self) -> &SyncBlinkAlloc<A> {
+        &self.blink
+    }
+
+    /// Returns mutable reference to the underlying allocator.
+    #[inline(always)]
+    pub fn get_mut(&mut self) -> &mut SyncBlinkAlloc<A> {
+        &mut self.blink
+    }
+
+    /// Unwraps underlying allocator.
+    #[inline(always)]
+    pub fn into_inner(self) -> SyncBlinkAlloc<A> {
+        self.blink
+    }
 }
@@ -0,0 +1,346 @@
+//! Provides `SyncBlink` allocator adaptor.
+
+use core::{
+    convert::{identity, Infallible},
+    mem::ManuallyDrop,
+};
+
+#[cfg(feature = "nightly")]
+use core::alloc::AllocError;
+
+#[cfg(not(feature = "nightly"))]
+use allocator_api2::alloc::AllocError;
+
+#[cfg(all(feature = "nightly", feature = "alloc"))]
+use alloc::alloc::Global;
+
+#[cfg(all(not(feature = "nightly"), feature = "alloc"))]
+use allocator_api2::alloc::Global;
+
+#[cfg(feature = "alloc")]
+use crate::sync::SyncBlinkAlloc;
+
+use crate::{
+    api::BlinkAllocator,
+    blink::{Emplace, RawBlink},
+    drop_list::{DropListRef, SyncDropList, DROP_ITEM_OVERHEAD},
+};
+
+#[cfg(not(no_global_oom_handling))]
+use crate::{oom::handle_alloc_error, ResultExt};
+
+switch_alloc_default! {
+    /// Multi-threaded counterpart of [`Blink`](crate::Blink).
+    ///
+    /// Provides the same user-friendly methods to emplace values
+    /// into allocated memory through shared reference,
+    /// but can be shared between threads.
+    /// Emplaced values are registered in concurrent drop list
+    /// and dropped on reset by the thread that resets.
+    /// Thus values that are dropped on reset must be [`Send`].
+    ///
+    /// Uses [`SyncBlinkAlloc`](crate::sync::SyncBlinkAlloc) by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use blink_alloc::SyncBlink;
+    ///
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// let mut blink = SyncBlink::new();
+    ///
+    /// std::thread::scope(|scope| {
+    ///     let blink = &blink;
+    ///     for i in 0..4 {
+    ///         scope.spawn(move || {
+    ///             let name = blink.copy_str(&format!("worker {i}"));
+    ///             let value = blink.put(vec![i; 4]);
+    ///             assert_eq!(value.len(), 4);
+    ///             name.make_ascii_uppercase();
+    ///         });
+    ///     }
+    /// });
+    ///
+    /// blink.reset();
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    pub struct SyncBlink<A = +SyncBlinkAlloc<Global>> {
+        drop_list: SyncDropList,
+        alloc: A,
+    }
+}
+
+impl<A> Drop for SyncBlink<A> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        self.drop_all();
+    }
+}
+
+impl<A> Default for SyncBlink<A>
+where
+    A: Default,
+{
+    fn default() -> Self {
+        SyncBlink::new_in(Default::default())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl SyncBlink<SyncBlinkAlloc<Global>> {
+    /// Creates new blink instance with `SyncBlinkAlloc` baked by `Global`
+    /// allocator.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        SyncBlink::new_in(SyncBlinkAlloc::new())
+    }
+}
+
+impl<A> SyncBlink<A> {
+    /// Creates new blink instance with provided allocator instance.
+    #[inline(always)]
+    pub const fn new_in(alloc: A) -> Self {
+        SyncBlink {
+            drop_list: SyncDropList::new(),
+            alloc,
+        }
+    }
+
+    /// Returns reference to allocator instance.
+    #[inline(always)]
+    pub fn allocator(&self) -> &A {
+        &self.alloc
+    }
+
+    /// Returns number of entries in the drop list.
+    ///
+    /// See [`Blink::drop_list_len`](crate::Blink::drop_list_len).
+    #[inline]
+    pub fn drop_list_len(&self) -> usize {
+        self.drop_list.count()
+    }
+
+    /// Returns number of bytes taken by drop list entries,
+    /// excluding alignment padding.
+    ///
+    /// See [`Blink::drop_list_bytes`](crate::Blink::drop_list_bytes).
+    #[inline]
+    pub fn drop_list_bytes(&self) -> usize {
+        self.drop_list.count() * DROP_ITEM_OVERHEAD
+    }
+
+    /// Drops all allocated values.
+    ///
+    /// Prefer to use `reset` method if associated allocator instance supports it.
+    #[inline(always)]
+    pub fn drop_all(&mut self) {
+        self.drop_list.reset();
+    }
+
+    #[inline(always)]
+    fn raw(&self) -> RawBlink<'_, A> {
+        RawBlink {
+            alloc: &self.alloc,
+            drop_list: DropListRef::Sync(&self.drop_list),
+        }
+    }
+}
+
+impl<A> SyncBlink<A>
+where
+    A: BlinkAllocator,
+{
+    /// Drops all allocated values.
+    /// And resets associated allocator instance.
+    #[inline(always)]
+    pub fn reset(&mut self) {
+        self.drop_list.reset();
+        self.alloc.reset();
+    }
+
+    /// Drops all allocated values.
+    /// And resets associated allocator instance,
+    /// releasing all its memory.
+    #[inline(always)]
+    pub fn reset_final(&mut self) {
+        self.drop_list.reset();
+        self.alloc.reset_final();
+    }
+
+    /// Returns the number of bytes allocated from associated allocator.
+    /// Returns `None` if allocator does not track it.
+    #[inline(always)]
+    pub fn allocated_bytes(&self) -> Option<usize> {
+        self.alloc.allocated_bytes()
+    }
+
+    /// Returns the total capacity of associated allocator.
+    /// Returns `None` if allocator does not track it.
+    #[inline(always)]
+    pub fn total_capacity(&self) -> Option<usize> {
+        self.alloc.total_capacity()
+    }
+
+    /// Puts value into this `SyncBlink` instance.
+    /// Returns reference to the value.
+    ///
+    /// Value will be dropped on reset,
+    /// possibly on another thread.
+    ///
+    /// See [`Blink::put`](crate::Blink::put).
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn put<T: Send + 'static>(&self, value: T) -> &mut T {
+        unsafe {
+            self.raw()._try_emplace(
+                value,
+                |slot, value| {
+                    slot.write(Ok::<_, ManuallyDrop<Infallible>>(value));
+                },
+                false,
+                identity,
+                |_, layout| handle_alloc_error(layout),
+            )
+        }
+        .safe_ok()
+    }
+
+    /// Puts value into this `SyncBlink` instance.
+    /// Returns reference to the value.
+    /// If allocation fails, returns `Err(value)`.
+    ///
+    /// See [`Blink::try_put`](crate::Blink::try_put).
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn try_put<T: Send + 'static>(&self, value: T) -> Result<&mut T, T> {
+        unsafe {
+            self.raw()._try_emplace(
+                value,
+                |slot, value| {
+                    slot.write(Ok::<_, ManuallyDrop<Infallible>>(value));
+                },
+                false,
+                |never| match never {},
+                |init, _| init,
+            )
+        }
+    }
+
+    /// Puts value into this `SyncBlink` instance.
+    /// Returns reference to the value.
+    ///
+    /// The value will not be dropped when `SyncBlink` is reset.
+    ///
+    /// See [`Blink::put_no_drop`](crate::Blink::put_no_drop).
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn put_no_drop<T>(&self, value: T) -> &mut T {
+        unsafe {
+            self.raw()._try_emplace(
+                value,
+                |slot, value| {
+                    slot.write(Ok::<_, ManuallyDrop<Infallible>>(value));
+                },
+                true,
+                identity,
+                |_, layout| handle_alloc_error(layout),
+            )
+        }
+        .safe_ok()
+    }
+
+    /// Copies the slice to the allocated memory
+    /// and returns reference to the new slice.
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn copy_slice<T>(&self, slice: &[T]) -> &mut [T]
+    where
+        T: Copy,
+    {
+        let result = unsafe { self.raw()._try_copy_slice(slice, handle_alloc_error) };
+        match result {
+            Ok(slice) => slice,
+            Err(never) => never,
+        }
+    }
+
+    /// Allocates memory for a copy of the slice.
+    /// Copies the slice to the allocated memory
+    /// and returns reference to the new slice.
+    /// If allocation fails, returns `Err(AllocError)`.
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn try_copy_slice<T>(&self, slice: &[T]) -> Result<&mut [T], AllocError>
+    where
+        T: Copy,
+    {
+        unsafe { self.raw()._try_copy_slice(slice, |_| AllocError) }
+    }
+
+    /// Copies the string to the allocated memory
+    /// and returns reference to the new string.
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn copy_str(&self, string: &str) -> &mut str {
+        let result = unsafe {
+            self.raw()
+                ._try_copy_slice(string.as_bytes(), handle_alloc_error)
+        };
+        match result {
+            Ok(slice) => unsafe { core::str::from_utf8_unchecked_mut(slice) },
+            Err(never) => never,
+        }
+    }
+
+    /// Allocates memory for a copy of the string.
+    /// Copies the string to the allocated memory
+    /// and returns reference to the new string.
+    /// If allocation fails, returns `Err(AllocError)`.
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn try_copy_str(&self, string: &str) -> Result<&mut str, AllocError> {
+        unsafe {
+            self.raw()
+                ._try_copy_slice(string.as_bytes(), |_| AllocError)
+        }
+        .map(|bytes| unsafe { core::str::from_utf8_unchecked_mut(bytes) })
+    }
+
+    /// Returns an `Emplace` adaptor that can emplace values into
+    /// the blink allocator.
+    ///
+    /// Values are dropped on reset, possibly on another thread.
+    ///
+    /// See [`Blink::emplace`](crate::Blink::emplace).
+    #[inline(always)]
+    pub fn emplace<T: Send + 'static>(&self) -> Emplace<'_, A, T> {
+        Emplace::new(self.raw(), false)
+    }
+
+    /// Returns an `Emplace` adaptor that can emplace values into
+    /// the blink allocator.
+    ///
+    /// This version causes emplaced value to be not-dropped on reset.
+    ///
+    /// See [`Blink::emplace_no_drop`](crate::Blink::emplace_no_drop).
+    #[inline(always)]
+    pub fn emplace_no_drop<T>(&self) -> Emplace<'_, A, T> {
+        Emplace::new(self.raw(), true)
+    }
+
+    /// Returns an `Emplace` adaptor that can emplace values into
+    /// the blink allocator.
+    ///
+    /// This version returns shared references to emplaced values.
+    ///
+    /// See [`Blink::emplace_shared`](crate::Blink::emplace_shared).
+    #[inline(always)]
+    pub fn emplace_shared<T>(&self) -> Emplace<'_, A, T, &T, &[T]> {
+        Emplace::new(self.raw(), true)
+    }
+}
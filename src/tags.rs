@@ -0,0 +1,318 @@
+//! This module provides per-tag counters of allocated bytes
+//! recorded under `"debug-stats"` feature
+//! and per-tag quotas enforced under `"tag-quotas"` feature.
+
+use core::cell::Cell;
+
+#[cfg(all(feature = "tag-quotas", feature = "nightly"))]
+use core::alloc::AllocError;
+
+#[cfg(all(feature = "tag-quotas", not(feature = "nightly")))]
+use allocator_api2::alloc::AllocError;
+
+/// Maximum number of distinct tags counted separately.
+/// Bytes allocated with other tags are counted as untagged.
+///
+/// Also maximum number of tags with quotas.
+pub const MAX_TAGS: usize = 32;
+
+/// Bytes allocated under each tag.
+///
+/// Allocations are tagged while [`TagGuard`] returned from
+/// `tag` method of blink allocators is alive.
+/// Retrieved with `tag_stats` method of blink allocators
+/// when `"debug-stats"` feature is enabled.
+///
+/// Allocations count requested size.
+/// Resizes that grow allocation count the difference.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "alloc")] fn main() {
+/// use blink_alloc::BlinkAlloc;
+/// let blink = BlinkAlloc::new();
+/// {
+///     let _physics = blink.tag("physics");
+///     blink.allocate(std::alloc::Layout::new::<[u8; 100]>()).unwrap();
+///     blink.allocate(std::alloc::Layout::new::<[u8; 20]>()).unwrap();
+/// }
+/// {
+///     let _audio = blink.tag("audio");
+///     blink.allocate(std::alloc::Layout::new::<[u8; 50]>()).unwrap();
+/// }
+/// blink.allocate(std::alloc::Layout::new::<[u8; 8]>()).unwrap();
+///
+/// let stats = blink.tag_stats();
+/// assert_eq!(stats.bytes("physics"), 120);
+/// assert_eq!(stats.bytes("audio"), 50);
+/// assert_eq!(stats.untagged_bytes(), 8);
+/// # }
+/// # #[cfg(not(feature = "alloc"))] fn main() {}
+/// ```
+#[cfg(feature = "debug-stats")]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TagStats {
+    tags: [Option<&'static str>; MAX_TAGS],
+    bytes: [usize; MAX_TAGS],
+    untagged: usize,
+}
+
+#[cfg(feature = "debug-stats")]
+impl TagStats {
+    /// Returns number of bytes allocated under the tag.
+    #[inline]
+    pub fn bytes(&self, tag: &str) -> usize {
+        self.iter()
+            .find(|&(t, _)| t == tag)
+            .map_or(0, |(_, bytes)| bytes)
+    }
+
+    /// Returns number of bytes allocated without a tag
+    /// or with tags that didn't fit into [`MAX_TAGS`] slots.
+    #[inline]
+    pub fn untagged_bytes(&self) -> usize {
+        self.untagged
+    }
+
+    /// Returns total number of recorded bytes.
+    #[inline]
+    pub fn total(&self) -> usize {
+        self.bytes.iter().sum::<usize>() + self.untagged
+    }
+
+    /// Returns iterator over tags in order of first allocation
+    /// and bytes allocated under them.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, usize)> + '_ {
+        self.tags
+            .iter()
+            .map_while(|tag| *tag)
+            .zip(self.bytes.iter().copied())
+    }
+}
+
+/// Guard that tags allocations made while it is alive.
+///
+/// Restores previous tag on drop, so tags can be nested.
+#[must_use = "Tag is removed when guard is dropped"]
+pub struct TagGuard<'a> {
+    tags: &'a LocalTags,
+    prev: Option<&'static str>,
+}
+
+impl Drop for TagGuard<'_> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        self.tags.set_current(self.prev);
+    }
+}
+
+/// Tag counters and quotas for single-threaded allocators.
+pub(crate) struct LocalTags {
+    current: Cell<Option<&'static str>>,
+
+    #[cfg(feature = "debug-stats")]
+    tags: [Cell<Option<&'static str>>; MAX_TAGS],
+    #[cfg(feature = "debug-stats")]
+    bytes: [Cell<usize>; MAX_TAGS],
+    #[cfg(feature = "debug-stats")]
+    untagged: Cell<usize>,
+
+    /// Index of the quota of current tag.
+    /// Looked up when tag changes, so allocations don't search for it.
+    #[cfg(feature = "tag-quotas")]
+    current_quota: Cell<Option<usize>>,
+
+    // Quotas are kept apart from counters,
+    // so they survive counter resets and don't compete for counter slots.
+    #[cfg(feature = "tag-quotas")]
+    quota_tags: [Cell<Option<&'static str>>; MAX_TAGS],
+    #[cfg(feature = "tag-quotas")]
+    quota_limits: [Cell<usize>; MAX_TAGS],
+    #[cfg(feature = "tag-quotas")]
+    quota_used: [Cell<usize>; MAX_TAGS],
+}
+
+impl LocalTags {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const NO_TAG: Cell<Option<&'static str>> = Cell::new(None);
+
+    #[allow(clippy::declare_interior_mutable_const)]
+    const ZERO: Cell<usize> = Cell::new(0);
+
+    #[inline(always)]
+    pub(crate) const fn new() -> Self {
+        LocalTags {
+            current: Cell::new(None),
+            #[cfg(feature = "debug-stats")]
+            tags: [Self::NO_TAG; MAX_TAGS],
+            #[cfg(feature = "debug-stats")]
+            bytes: [Self::ZERO; MAX_TAGS],
+            #[cfg(feature = "debug-stats")]
+            untagged: Cell::new(0),
+            #[cfg(feature = "tag-quotas")]
+            current_quota: Cell::new(None),
+            #[cfg(feature = "tag-quotas")]
+            quota_tags: [Self::NO_TAG; MAX_TAGS],
+            #[cfg(feature = "tag-quotas")]
+            quota_limits: [Self::ZERO; MAX_TAGS],
+            #[cfg(feature = "tag-quotas")]
+            quota_used: [Self::ZERO; MAX_TAGS],
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn tag(&self, tag: &'static str) -> TagGuard<'_> {
+        let prev = self.current.get();
+        self.set_current(Some(tag));
+        TagGuard { tags: self, prev }
+    }
+
+    #[inline(always)]
+    fn set_current(&self, tag: Option<&'static str>) {
+        self.current.set(tag);
+
+        #[cfg(feature = "tag-quotas")]
+        self.current_quota
+            .set(tag.and_then(|tag| self.quota_index(tag)));
+    }
+
+    /// Checks that `size` bytes fit into quota of current tag.
+    #[cfg(feature = "tag-quotas")]
+    #[inline(always)]
+    pub(crate) fn check_quota(&self, size: usize) -> Result<(), AllocError> {
+        if let Some(idx) = self.current_quota.get() {
+            match self.quota_used[idx].get().checked_add(size) {
+                Some(used) if used <= self.quota_limits[idx].get() => {}
+                _ => return Err(AllocError),
+            }
+        }
+        Ok(())
+    }
+
+    /// Records `size` bytes under current tag.
+    ///
+    /// Called after allocation succeeds,
+    /// so that failed allocations are not counted.
+    #[inline(always)]
+    pub(crate) fn record(&self, size: usize) {
+        #[cfg(feature = "tag-quotas")]
+        if let Some(idx) = self.current_quota.get() {
+            let used = &self.quota_used[idx];
+            used.set(used.get().saturating_add(size));
+        }
+
+        #[cfg(feature = "debug-stats")]
+        {
+            let bytes = match self.current.get() {
+                None => &self.untagged,
+                Some(tag) => self.slot(tag),
+            };
+            bytes.set(bytes.get().wrapping_add(size));
+        }
+    }
+
+    /// Returns counter of the tag, occupying free slot if needed.
+    #[cfg(feature = "debug-stats")]
+    #[inline]
+    fn slot(&self, tag: &'static str) -> &Cell<usize> {
+        for (slot, bytes) in self.tags.iter().zip(&self.bytes) {
+            match slot.get() {
+                Some(t) if t == tag => return bytes,
+                Some(_) => {}
+                None => {
+                    slot.set(Some(tag));
+                    return bytes;
+                }
+            }
+        }
+        &self.untagged
+    }
+
+    #[cfg(feature = "debug-stats")]
+    #[inline]
+    pub(crate) fn get(&self) -> TagStats {
+        TagStats {
+            tags: core::array::from_fn(|idx| self.tags[idx].get()),
+            bytes: core::array::from_fn(|idx| self.bytes[idx].get()),
+            untagged: self.untagged.get(),
+        }
+    }
+
+    /// Clears counters.
+    /// Current tag and quotas are kept, bytes counted against quotas are cleared.
+    #[inline]
+    pub(crate) fn reset(&self) {
+        #[cfg(feature = "debug-stats")]
+        {
+            for (slot, bytes) in self.tags.iter().zip(&self.bytes) {
+                slot.set(None);
+                bytes.set(0);
+            }
+            self.untagged.set(0);
+        }
+
+        #[cfg(feature = "tag-quotas")]
+        for used in &self.quota_used {
+            used.set(0);
+        }
+    }
+
+    /// Returns index of the quota for the tag.
+    #[cfg(feature = "tag-quotas")]
+    #[inline]
+    fn quota_index(&self, tag: &'static str) -> Option<usize> {
+        self.quota_tags
+            .iter()
+            .position(|slot| matches!(slot.get(), Some(t) if t == tag))
+    }
+
+    /// Limits bytes allocated under the tag.
+    /// Bytes already counted against previous quota of the tag are kept.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`MAX_TAGS`] other tags already have quotas.
+    #[cfg(feature = "tag-quotas")]
+    #[inline]
+    pub(crate) fn set_quota(&self, tag: &'static str, bytes: usize) {
+        let idx = match self.quota_index(tag) {
+            Some(idx) => idx,
+            None => {
+                let idx = self
+                    .quota_tags
+                    .iter()
+                    .position(|slot| slot.get().is_none())
+                    .expect("Too many tags with quotas");
+                self.quota_tags[idx].set(Some(tag));
+                self.quota_used[idx].set(0);
+                idx
+            }
+        };
+        self.quota_limits[idx].set(bytes);
+        self.set_current(self.current.get());
+    }
+
+    /// Removes quota of the tag, if any.
+    #[cfg(feature = "tag-quotas")]
+    #[inline]
+    pub(crate) fn remove_quota(&self, tag: &'static str) {
+        if let Some(idx) = self.quota_index(tag) {
+            self.quota_tags[idx].set(None);
+            self.set_current(self.current.get());
+        }
+    }
+
+    /// Returns bytes left in quota of the tag.
+    #[cfg(feature = "tag-quotas")]
+    #[inline]
+    pub(crate) fn quota_remaining(&self, tag: &'static str) -> Option<usize> {
+        let idx = self.quota_index(tag)?;
+        Some(
+            self.quota_limits[idx]
+                .get()
+                .saturating_sub(self.quota_used[idx].get()),
+        )
+    }
+}
@@ -0,0 +1,334 @@
+//! Allocators for testing code built on blink allocators.
+//!
+//! Wrap underlying allocator to inject allocation failures
+//! or count allocations, then pass it as chunk source
+//! to blink allocator under test.
+//!
+//! # Example
+//!
+//! ```
+//! # #[cfg(feature = "alloc")] fn main() {
+//! use blink_alloc::{testing::FailAfter, Blink, BlinkAlloc};
+//!
+//! let failing = FailAfter::new(1);
+//! let blink = Blink::new_in(BlinkAlloc::with_chunk_size_in(64, &failing));
+//!
+//! // First chunk is served.
+//! assert!(blink.try_copy_slice(&[0u8; 32]).is_ok());
+//!
+//! // Second one is not.
+//! assert!(blink.try_copy_slice(&[0u8; 1024]).is_err());
+//! assert_eq!(failing.failures(), 1);
+//! # }
+//! # #[cfg(not(feature = "alloc"))] fn main() {}
+//! ```
+
+use core::{
+    alloc::Layout,
+    ptr::NonNull,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+#[cfg(feature = "nightly")]
+use core::alloc::{AllocError, Allocator};
+
+#[cfg(not(feature = "nightly"))]
+use allocator_api2::alloc::{AllocError, Allocator};
+
+#[cfg(all(feature = "nightly", feature = "alloc"))]
+use alloc::alloc::Global;
+
+#[cfg(all(not(feature = "nightly"), feature = "alloc"))]
+use allocator_api2::alloc::Global;
+
+switch_alloc_default! {
+    /// Allocator that serves specified number of allocations
+    /// and fails all following ones.
+    ///
+    /// Deallocations are always forwarded to the underlying allocator.
+    pub struct FailAfter<A: Allocator = +Global> {
+        remaining: AtomicUsize,
+        failures: AtomicUsize,
+        allocator: A,
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl FailAfter<Global> {
+    /// Creates new allocator that serves `count` allocations
+    /// from global allocator.
+    #[inline]
+    pub const fn new(count: usize) -> Self {
+        FailAfter::new_in(count, Global)
+    }
+}
+
+impl<A> FailAfter<A>
+where
+    A: Allocator,
+{
+    /// Creates new allocator that serves `count` allocations
+    /// from provided allocator.
+    #[inline]
+    pub const fn new_in(count: usize, allocator: A) -> Self {
+        FailAfter {
+            remaining: AtomicUsize::new(count),
+            failures: AtomicUsize::new(0),
+            allocator,
+        }
+    }
+
+    /// Returns number of allocations left before failures start.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.remaining.load(Ordering::Relaxed)
+    }
+
+    /// Sets number of allocations left before failures start.
+    #[inline]
+    pub fn set_remaining(&self, count: usize) {
+        self.remaining.store(count, Ordering::Relaxed);
+    }
+
+    /// Returns number of failed allocations.
+    #[inline]
+    pub fn failures(&self) -> usize {
+        self.failures.load(Ordering::Relaxed)
+    }
+
+    /// Returns reference to the underlying allocator.
+    #[inline(always)]
+    pub const fn inner(&self) -> &A {
+        &self.allocator
+    }
+}
+
+unsafe impl<A> Allocator for FailAfter<A>
+where
+    A: Allocator,
+{
+    #[inline]
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let served = self
+            .remaining
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1));
+
+        if served.is_err() {
+            self.failures.fetch_add(1, Ordering::Relaxed);
+            return Err(AllocError);
+        }
+        self.allocator.allocate(layout)
+    }
+
+    #[inline]
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        unsafe { self.allocator.deallocate(ptr, layout) }
+    }
+}
+
+#[cfg(feature = "nightly")]
+impl_api2_allocator!(FailAfter<A>);
+
+switch_alloc_default! {
+    /// Allocator that fails allocations of specified size or larger.
+    ///
+    /// Useful to test handling of failures when chunks grow.
+    pub struct FailOnSize<A: Allocator = +Global> {
+        size: AtomicUsize,
+        failures: AtomicUsize,
+        allocator: A,
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl FailOnSize<Global> {
+    /// Creates new allocator that fails allocations of `size` bytes or larger
+    /// and serves others from global allocator.
+    #[inline]
+    pub const fn new(size: usize) -> Self {
+        FailOnSize::new_in(size, Global)
+    }
+}
+
+impl<A> FailOnSize<A>
+where
+    A: Allocator,
+{
+    /// Creates new allocator that fails allocations of `size` bytes or larger
+    /// and serves others from provided allocator.
+    #[inline]
+    pub const fn new_in(size: usize, allocator: A) -> Self {
+        FailOnSize {
+            size: AtomicUsize::new(size),
+            failures: AtomicUsize::new(0),
+            allocator,
+        }
+    }
+
+    /// Sets smallest size of failing allocations.
+    /// Use `usize::MAX` to serve all allocations.
+    #[inline]
+    pub fn set_size(&self, size: usize) {
+        self.size.store(size, Ordering::Relaxed);
+    }
+
+    /// Returns number of failed allocations.
+    #[inline]
+    pub fn failures(&self) -> usize {
+        self.failures.load(Ordering::Relaxed)
+    }
+
+    /// Returns reference to the underlying allocator.
+    #[inline(always)]
+    pub const fn inner(&self) -> &A {
+        &self.allocator
+    }
+}
+
+unsafe impl<A> Allocator for FailOnSize<A>
+where
+    A: Allocator,
+{
+    #[inline]
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        if layout.size() >= self.size.load(Ordering::Relaxed) {
+            self.failures.fetch_add(1, Ordering::Relaxed);
+            return Err(AllocError);
+        }
+        self.allocator.allocate(layout)
+    }
+
+    #[inline]
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        unsafe { self.allocator.deallocate(ptr, layout) }
+    }
+}
+
+#[cfg(feature = "nightly")]
+impl_api2_allocator!(FailOnSize<A>);
+
+switch_alloc_default! {
+    /// Allocator that counts allocations and bytes
+    /// served by the underlying allocator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::{testing::CountingAlloc, BlinkAlloc};
+    ///
+    /// let counting = CountingAlloc::new();
+    /// let mut blink = BlinkAlloc::new_in(&counting);
+    /// for _ in 0..10 {
+    ///     blink.allocate(std::alloc::Layout::new::<[u8; 1000]>()).unwrap();
+    /// }
+    /// assert!(counting.allocations() > 1);
+    ///
+    /// blink.reset_final();
+    /// assert_eq!(counting.live_allocations(), 0);
+    /// assert_eq!(counting.live_bytes(), 0);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    pub struct CountingAlloc<A: Allocator = +Global> {
+        allocations: AtomicUsize,
+        deallocations: AtomicUsize,
+        live_bytes: AtomicUsize,
+        peak_bytes: AtomicUsize,
+        allocator: A,
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl CountingAlloc<Global> {
+    /// Creates new allocator that counts allocations from global allocator.
+    #[inline]
+    pub const fn new() -> Self {
+        CountingAlloc::new_in(Global)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Default for CountingAlloc<Global> {
+    #[inline]
+    fn default() -> Self {
+        CountingAlloc::new()
+    }
+}
+
+impl<A> CountingAlloc<A>
+where
+    A: Allocator,
+{
+    /// Creates new allocator that counts allocations from provided allocator.
+    #[inline]
+    pub const fn new_in(allocator: A) -> Self {
+        CountingAlloc {
+            allocations: AtomicUsize::new(0),
+            deallocations: AtomicUsize::new(0),
+            live_bytes: AtomicUsize::new(0),
+            peak_bytes: AtomicUsize::new(0),
+            allocator,
+        }
+    }
+
+    /// Returns number of successful allocations.
+    #[inline]
+    pub fn allocations(&self) -> usize {
+        self.allocations.load(Ordering::Relaxed)
+    }
+
+    /// Returns number of deallocations.
+    #[inline]
+    pub fn deallocations(&self) -> usize {
+        self.deallocations.load(Ordering::Relaxed)
+    }
+
+    /// Returns number of allocations not deallocated yet.
+    #[inline]
+    pub fn live_allocations(&self) -> usize {
+        self.allocations() - self.deallocations()
+    }
+
+    /// Returns number of bytes allocated and not deallocated yet.
+    #[inline]
+    pub fn live_bytes(&self) -> usize {
+        self.live_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Returns maximum of [`live_bytes`](CountingAlloc::live_bytes) observed.
+    #[inline]
+    pub fn peak_bytes(&self) -> usize {
+        self.peak_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Returns reference to the underlying allocator.
+    #[inline(always)]
+    pub const fn inner(&self) -> &A {
+        &self.allocator
+    }
+}
+
+unsafe impl<A> Allocator for CountingAlloc<A>
+where
+    A: Allocator,
+{
+    #[inline]
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let ptr = self.allocator.allocate(layout)?;
+        self.allocations.fetch_add(1, Ordering::Relaxed);
+        let live = self.live_bytes.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+        self.peak_bytes.fetch_max(live, Ordering::Relaxed);
+        Ok(ptr)
+    }
+
+    #[inline]
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.deallocations.fetch_add(1, Ordering::Relaxed);
+        self.live_bytes.fetch_sub(layout.size(), Ordering::Relaxed);
+        unsafe { self.allocator.deallocate(ptr, layout) }
+    }
+}
+
+#[cfg(feature = "nightly")]
+impl_api2_allocator!(CountingAlloc<A>);
@@ -1,6 +1,11 @@
 #![cfg(feature = "alloc")]
 
-use core::{alloc::Layout, cell::Cell, mem::size_of, ptr::NonNull};
+use core::{
+    alloc::Layout,
+    cell::Cell,
+    mem::{align_of, size_of},
+    ptr::NonNull,
+};
 
 #[cfg(feature = "nightly")]
 use alloc::{
@@ -13,7 +18,11 @@ use allocator_api2::{
     vec::Vec,
 };
 
-use crate::{blink::Blink, local::BlinkAlloc};
+use crate::{
+    blink::Blink,
+    local::BlinkAlloc,
+    testing::{CountingAlloc, FailAfter},
+};
 
 #[test]
 fn test_local_alloc() {
@@ -30,35 +39,15 @@ fn test_local_alloc() {
     blink.reset();
 }
 
+#[cfg(not(feature = "debug-headers"))]
 #[test]
 fn test_bad_iter() {
-    struct OneTimeGlobal {
-        served: Cell<bool>,
-    }
-
-    unsafe impl Allocator for OneTimeGlobal {
-        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
-            if self.served.get() {
-                Err(AllocError)
-            } else {
-                self.served.set(true);
-                Global.allocate(layout)
-            }
-        }
-
-        unsafe fn deallocate(&self, ptr: core::ptr::NonNull<u8>, layout: Layout) {
-            Global.deallocate(ptr, layout)
-        }
-    }
-
     const ELEMENT_COUNT: usize = 2000;
     const ELEMENT_SIZE: usize = size_of::<u32>();
 
     let mut blink = Blink::new_in(BlinkAlloc::with_chunk_size_in(
         ELEMENT_SIZE * ELEMENT_COUNT,
-        OneTimeGlobal {
-            served: Cell::new(false),
-        },
+        FailAfter::new(1),
     ));
 
     blink
@@ -148,6 +137,7 @@ fn test_vec() {
     blink_alloc.reset();
 }
 
+#[cfg(not(feature = "debug-headers"))]
 #[test]
 fn test_tracking() {
     let item_size = 3;
@@ -168,14 +158,34 @@ fn test_tracking() {
     for _ in 0..item_count {
         blink.allocate(layout).unwrap();
     }
+    #[cfg(not(feature = "exact-stats"))]
     assert_eq!(
         blink.allocated_bytes(),
         data_bytes + initial_chunk_waste,
         "pre-warmup: over-counts by unused tail of exhausted first chunk"
     );
+    #[cfg(feature = "exact-stats")]
+    assert_eq!(blink.allocated_bytes(), data_bytes);
+    let overhead = blink.overhead_bytes();
+
+    let chunks: Vec<_> = blink.utilization().collect();
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(chunks[0].stranded, 0);
+    assert_eq!(chunks[1].stranded, initial_chunk_waste);
+    assert_eq!(chunks[1].used + chunks[1].stranded, chunks[1].capacity);
+    assert_eq!(
+        chunks.iter().map(|chunk| chunk.used).sum::<usize>(),
+        data_bytes
+    );
 
     blink.reset();
     assert_eq!(blink.allocated_bytes(), 0);
+    let header_size = blink.overhead_bytes();
+    assert_eq!(
+        overhead,
+        2 * header_size + initial_chunk_waste,
+        "two chunk headers and unused tail of the first chunk"
+    );
     let warmed_cap = blink.total_capacity();
     assert_eq!(warmed_cap, 96);
     assert!(
@@ -198,7 +208,7 @@ fn test_tracking() {
     );
 }
 
-#[cfg(feature = "sync")]
+#[cfg(all(feature = "sync-core", not(feature = "debug-headers")))]
 #[test]
 fn test_tracking_sync() {
     use crate::SyncBlinkAlloc;
@@ -220,11 +230,14 @@ fn test_tracking_sync() {
     for _ in 0..item_count {
         blink.allocate(layout).unwrap();
     }
+    #[cfg(not(feature = "exact-stats"))]
     assert_eq!(
         blink.allocated_bytes(),
         data_bytes + initial_chunk_waste,
         "pre-warmup: over-counts by unused tail of exhausted first chunk"
     );
+    #[cfg(feature = "exact-stats")]
+    assert_eq!(blink.allocated_bytes(), data_bytes);
 
     blink.reset();
     assert_eq!(blink.allocated_bytes(), 0);
@@ -246,7 +259,7 @@ fn test_tracking_sync() {
     );
 }
 
-#[cfg(feature = "sync")]
+#[cfg(all(feature = "sync-core", not(feature = "debug-headers")))]
 #[test]
 fn test_tracking_local_proxy() {
     use crate::SyncBlinkAlloc;
@@ -275,11 +288,14 @@ fn test_tracking_local_proxy() {
     for _ in 0..item_count {
         local.allocate(layout).unwrap();
     }
+    #[cfg(not(feature = "exact-stats"))]
     assert_eq!(
         local.allocated_bytes(),
         data_bytes + initial_chunk_waste,
         "local over-counts by chunk tail waste, same as BlinkAlloc"
     );
+    #[cfg(feature = "exact-stats")]
+    assert_eq!(local.allocated_bytes(), data_bytes);
 
     let local_chunk_1_cap = 32;
     let local_chunk_2_cap = 96;
@@ -319,3 +335,4299 @@ fn test_tracking_local_proxy() {
     assert_eq!(shared.allocated_bytes(), 0);
     assert_eq!(shared.total_capacity(), shared_cap);
 }
+
+#[cfg(feature = "std")]
+#[test]
+fn test_reset_guard() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let mut blink = BlinkAlloc::new();
+    {
+        let guard = blink.guard();
+        guard.allocate(Layout::new::<u64>()).unwrap();
+        assert!(guard.allocated_bytes() >= 8);
+    }
+    assert_eq!(blink.allocated_bytes(), 0);
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        let guard = blink.guard();
+        guard.allocate(Layout::new::<u64>()).unwrap();
+        panic!("unwinding through the guard");
+    }));
+    assert!(result.is_err());
+    assert_eq!(blink.allocated_bytes(), 0);
+
+    use alloc::sync::Arc;
+    use core::sync::atomic::{AtomicBool, Ordering};
+
+    struct Flag(Arc<AtomicBool>);
+
+    impl Drop for Flag {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::Relaxed);
+        }
+    }
+
+    let dropped = Arc::new(AtomicBool::new(false));
+    let mut blink = Blink::new();
+    {
+        let guard = blink.guard();
+        guard.put(Flag(dropped.clone()));
+    }
+    assert!(dropped.load(Ordering::Relaxed));
+    assert_eq!(blink.allocator().allocated_bytes(), 0);
+}
+
+#[test]
+fn test_mixed_alignment() {
+    let mut blink = BlinkAlloc::with_chunk_size(0);
+
+    let mut ranges = Vec::new();
+    for i in 0..200 {
+        let align = 1 << (i % 7);
+        let size = (i * 7) % 61 + 1;
+        let layout = Layout::from_size_align(size, align).unwrap();
+        let ptr = blink.allocate(layout).unwrap();
+        let addr = ptr.as_ptr().cast::<u8>() as usize;
+        assert_eq!(addr % align, 0);
+        assert!(ptr.len() >= size);
+        unsafe {
+            core::ptr::write_bytes(ptr.as_ptr().cast::<u8>(), i as u8, size);
+        }
+        ranges.push((addr, size, i as u8));
+    }
+
+    for &(addr, size, byte) in &ranges {
+        let slice = unsafe { core::slice::from_raw_parts(addr as *const u8, size) };
+        assert!(slice.iter().all(|&b| b == byte));
+    }
+
+    let mut vec = Vec::new_in(&blink);
+    for i in 0..1000u32 {
+        vec.push(i);
+    }
+    assert!(vec.iter().copied().eq(0..1000));
+    drop(vec);
+    drop(ranges);
+
+    blink.reset();
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_freeze() {
+    let blink = BlinkAlloc::new();
+
+    let ptr = blink.allocate(Layout::new::<[u64; 16]>()).unwrap();
+    let ptr = ptr.cast::<[u64; 16]>();
+    unsafe {
+        ptr.as_ptr().write([7; 16]);
+    }
+    let allocated = blink.allocated_bytes();
+
+    let frozen = blink.freeze();
+    assert_eq!(frozen.allocated_bytes(), allocated);
+
+    let data = unsafe { frozen.get(ptr) };
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| assert_eq!(data.iter().sum::<u64>(), 7 * 16));
+        }
+    });
+
+    let mut blink = frozen.thaw();
+    blink.reset();
+    assert_eq!(blink.allocated_bytes(), 0);
+}
+
+#[test]
+fn test_donate_capacity() {
+    let mut donor = BlinkAlloc::new();
+    for _ in 0..2 {
+        for _ in 0..100 {
+            donor.allocate(Layout::new::<[u64; 4]>()).unwrap();
+        }
+        donor.reset();
+    }
+    let capacity = donor.total_capacity();
+
+    let mut other = BlinkAlloc::new();
+    other.allocate(Layout::new::<u64>()).unwrap();
+    let other_capacity = other.total_capacity();
+    let other_allocated = other.allocated_bytes();
+
+    donor.donate_capacity(&mut other);
+    assert_eq!(donor.total_capacity(), 0);
+    assert_eq!(donor.allocated_bytes(), 0);
+    assert_eq!(other.total_capacity(), capacity + other_capacity);
+    #[cfg(not(feature = "exact-stats"))]
+    assert_eq!(other.allocated_bytes(), other_capacity);
+    assert!(other.allocated_bytes() >= other_allocated);
+
+    // Donated chunk serves allocations without growing.
+    for _ in 0..100 {
+        other.allocate(Layout::new::<[u64; 4]>()).unwrap();
+    }
+    #[cfg(not(feature = "debug-headers"))]
+    assert_eq!(other.total_capacity(), capacity + other_capacity);
+
+    other.reset();
+    #[cfg(not(feature = "debug-headers"))]
+    assert_eq!(other.total_capacity(), capacity);
+}
+
+#[test]
+fn test_chunk_pool() {
+    use crate::ChunkPool;
+
+    let mut pool = ChunkPool::new();
+    assert_eq!(pool.cached_bytes(), 0);
+
+    let mut cached = 0;
+    for i in 0..10 {
+        let mut blink = BlinkAlloc::new_in(&pool);
+        for _ in 0..100 {
+            blink.allocate(Layout::new::<[u64; 4]>()).unwrap();
+        }
+        blink.reset_final();
+
+        if i == 0 {
+            cached = pool.cached_bytes();
+            assert!(cached > 0);
+        } else {
+            assert_eq!(pool.cached_bytes(), cached, "chunks are recycled");
+        }
+    }
+
+    pool.clear();
+    assert_eq!(pool.cached_bytes(), 0);
+}
+
+#[test]
+fn test_static_buffer_source() {
+    use crate::StaticBuffer;
+    use core::mem::MaybeUninit;
+
+    let mut buffer = [MaybeUninit::new(0xAAu8); 4096];
+    let source = StaticBuffer::new(&mut buffer);
+
+    let mut blink = BlinkAlloc::new_in(&source);
+    let ptr = blink.allocate_zeroed(Layout::new::<[u8; 100]>()).unwrap();
+    assert!(unsafe { ptr.as_ref() }.iter().all(|&b| b == 0));
+    assert!(source.is_taken());
+
+    // Buffer is the only chunk.
+    assert!(blink.allocate(Layout::new::<[u8; 4096]>()).is_err());
+
+    blink.reset();
+    assert!(source.is_taken());
+    blink.allocate(Layout::new::<[u8; 1000]>()).unwrap();
+
+    blink.reset_final();
+    assert!(!source.is_taken());
+    drop(blink);
+
+    // Buffer is too small for the chunk.
+    let mut small = [MaybeUninit::uninit(); 16];
+    let blink = BlinkAlloc::new_in(StaticBuffer::new(&mut small));
+    assert!(blink.allocate(Layout::new::<u8>()).is_err());
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_par_from_indexed_iter() {
+    use alloc::string::ToString;
+    use rayon::prelude::*;
+
+    let mut blink = Blink::new();
+
+    let slice = blink
+        .emplace()
+        .par_from_indexed_iter((0..10000).into_par_iter().map(|x| x.to_string()));
+    assert_eq!(slice.len(), 10000);
+    assert!(slice
+        .iter()
+        .enumerate()
+        .all(|(idx, s)| *s == idx.to_string()));
+
+    let empty = blink
+        .emplace()
+        .par_from_indexed_iter((0..0).into_par_iter().map(|x: u32| x.to_string()));
+    assert!(empty.is_empty());
+
+    let units = blink
+        .emplace_no_drop()
+        .par_from_indexed_iter((0..100).into_par_iter().map(|_| ()));
+    assert_eq!(units.len(), 100);
+
+    blink.reset();
+}
+
+#[test]
+fn test_blink_allocator() {
+    let mut blink = Blink::new();
+    let x = blink.put(1u32);
+
+    let mut vec = Vec::new_in(&blink);
+    vec.extend([*x, 2, 3]);
+    let y = blink.copy_slice(&vec);
+
+    assert_eq!(y, [1, 2, 3]);
+    drop(vec);
+    blink.reset();
+}
+
+#[test]
+fn test_retention_policy() {
+    use crate::RetentionPolicy;
+
+    fn retained(policy: RetentionPolicy) -> (usize, usize, usize) {
+        let mut small = BlinkAlloc::new();
+        small.allocate(Layout::new::<[u8; 100]>()).unwrap();
+        let small_cap = small.total_capacity();
+
+        let mut blink = BlinkAlloc::new().with_retention(policy);
+        blink.allocate(Layout::new::<[u8; 10000]>()).unwrap();
+        let large_cap = blink.total_capacity();
+
+        // Donated chunk becomes the most recent one.
+        small.donate_capacity(&mut blink);
+        blink.allocate(Layout::new::<[u8; 10]>()).unwrap();
+
+        blink.reset();
+        (blink.total_capacity(), small_cap, large_cap)
+    }
+
+    let (cap, small, _) = retained(RetentionPolicy::KeepMostRecentlyUsed);
+    assert_eq!(cap, small);
+
+    let (cap, _, large) = retained(RetentionPolicy::KeepLargest);
+    assert_eq!(cap, large);
+
+    let (cap, small, _) = retained(RetentionPolicy::KeepBestFit(50));
+    assert_eq!(cap, small);
+
+    let (cap, _, large) = retained(RetentionPolicy::KeepBestFit(1000));
+    assert_eq!(cap, large);
+
+    let (cap, _, large) = retained(RetentionPolicy::KeepBestFit(usize::MAX));
+    assert_eq!(cap, large);
+}
+
+#[test]
+fn test_allocate_zeroed() {
+    let layout = Layout::new::<[u8; 64]>();
+    let mut blink = BlinkAlloc::new();
+
+    // Zeroed allocations from fresh chunk.
+    for _ in 0..4 {
+        let ptr = blink.allocate_zeroed(layout).unwrap();
+        assert!(unsafe { ptr.as_ref() }.iter().all(|&b| b == 0));
+    }
+
+    // Dirty some memory.
+    for _ in 0..4 {
+        let ptr = blink.allocate(layout).unwrap();
+        unsafe { core::ptr::write_bytes(ptr.as_ptr().cast::<u8>(), 0xFF, layout.size()) };
+    }
+
+    blink.reset();
+
+    // Reused memory must be zeroed again.
+    for _ in 0..16 {
+        let ptr = blink.allocate_zeroed(layout).unwrap();
+        assert!(unsafe { ptr.as_ref() }.iter().all(|&b| b == 0));
+        unsafe { core::ptr::write_bytes(ptr.as_ptr().cast::<u8>(), 0xFF, layout.size()) };
+    }
+
+    blink.reset();
+
+    for _ in 0..16 {
+        let ptr = blink.allocate_zeroed(layout).unwrap();
+        assert!(unsafe { ptr.as_ref() }.iter().all(|&b| b == 0));
+    }
+}
+
+#[cfg(not(feature = "debug-headers"))]
+#[test]
+fn test_resize_last_in_place() {
+    let mut blink = BlinkAlloc::new();
+
+    // Warm up so that everything fits into one chunk.
+    blink.allocate(Layout::new::<[u8; 4096]>()).unwrap();
+    blink.reset();
+
+    let remaining = blink.remaining_capacity();
+
+    let mut vec = Vec::<u32, _>::with_capacity_in(16, &blink);
+    vec.extend(0..16);
+    let ptr = vec.as_ptr();
+
+    // Growing from the last allocation doesn't copy when bumping upward.
+    vec.reserve_exact(256);
+    vec.extend(16..272);
+    if cfg!(not(feature = "bump-down")) {
+        assert_eq!(ptr, vec.as_ptr());
+    }
+    assert_eq!(
+        remaining - blink.remaining_capacity(),
+        272 * size_of::<u32>()
+    );
+
+    // Shrinking the last allocation returns memory to the chunk
+    // when bumping upward. Shrinks never move memory.
+    let ptr = vec.as_ptr();
+    vec.truncate(16);
+    vec.shrink_to_fit();
+    assert_eq!(ptr, vec.as_ptr());
+    if cfg!(not(feature = "bump-down")) {
+        assert_eq!(
+            remaining - blink.remaining_capacity(),
+            16 * size_of::<u32>()
+        );
+    }
+    assert!(vec.iter().copied().eq(0..16));
+
+    // Deallocating the last allocation returns memory to the chunk.
+    // Bumping downward, tail left by the shrink is not returned.
+    drop(vec);
+    if cfg!(not(feature = "bump-down")) {
+        assert_eq!(remaining, blink.remaining_capacity());
+    }
+}
+
+#[cfg(not(feature = "debug-headers"))]
+#[test]
+fn test_grow_in_older_chunk() {
+    let blink = BlinkAlloc::new();
+
+    let mut a = Vec::<u8, _>::with_capacity_in(16, &blink);
+    a.extend(0..16);
+    let ptr = a.as_ptr();
+
+    // Doesn't fit into the first chunk.
+    let mut b = Vec::<u8, _>::with_capacity_in(4096, &blink);
+    b.extend((0..4096).map(|i| i as u8));
+    let cap = blink.total_capacity();
+
+    // `a` is still the last allocation in the first chunk.
+    a.reserve_exact(16);
+    a.extend(16..32);
+    if cfg!(not(feature = "bump-down")) {
+        assert_eq!(ptr, a.as_ptr());
+    }
+    assert_eq!(cap, blink.total_capacity());
+    assert!(a.iter().copied().eq(0..32));
+    assert!(b.iter().copied().eq((0..4096).map(|i| i as u8)));
+}
+
+#[test]
+fn test_chunk_size_range() {
+    let blink = BlinkAlloc::with_chunk_size_range(256, 4096);
+
+    let mut total = 0;
+    for _ in 0..100 {
+        blink.allocate(Layout::new::<[u8; 1000]>()).unwrap();
+        let chunk = blink.total_capacity() - total;
+        total = blink.total_capacity();
+        assert!(chunk <= 4096);
+    }
+
+    blink.allocate(Layout::new::<[u8; 10000]>()).unwrap();
+    assert!(blink.total_capacity() - total >= 10000);
+}
+
+#[cfg(all(feature = "std", feature = "sync-core"))]
+#[test]
+fn test_spsc_queue() {
+    use crate::{SpscQueue, SyncBlinkAlloc};
+    use alloc::rc::Rc;
+    use std::sync::Arc;
+
+    let mut blink = SyncBlinkAlloc::new();
+    for _ in 0..3 {
+        let mut queue = SpscQueue::new_in(&blink);
+        let (mut producer, mut consumer) = queue.split();
+
+        std::thread::scope(|scope| {
+            scope.spawn(move || {
+                for i in 0..1000 {
+                    producer.push(Arc::new(i));
+                }
+            });
+            scope.spawn(move || {
+                let mut expected = 0;
+                while expected < 1000 {
+                    if let Some(value) = consumer.pop() {
+                        assert_eq!(*value, expected);
+                        expected += 1;
+                    }
+                }
+                assert!(consumer.is_empty());
+            });
+        });
+
+        drop(queue);
+        blink.reset();
+    }
+
+    // Values left in the queue are dropped.
+    let value = Rc::new(());
+    let mut queue = SpscQueue::new_in(&blink);
+    let (mut producer, _) = queue.split();
+    producer.push(value.clone());
+    producer.push(value.clone());
+    assert_eq!(Rc::strong_count(&value), 3);
+    drop(queue);
+    assert_eq!(Rc::strong_count(&value), 1);
+}
+
+#[test]
+fn test_retention_consolidate() {
+    use crate::RetentionPolicy;
+
+    let mut blink = BlinkAlloc::new().with_retention(RetentionPolicy::Consolidate);
+
+    let allocate = |blink: &BlinkAlloc| {
+        for _ in 0..100 {
+            blink.allocate(Layout::new::<[u8; 100]>()).unwrap();
+        }
+    };
+
+    allocate(&blink);
+    let used = blink.allocated_bytes();
+    blink.reset();
+
+    // Single chunk fits all allocations from previous cycle.
+    assert!(blink.total_capacity() >= used);
+    let cap = blink.total_capacity();
+    allocate(&blink);
+    assert_eq!(blink.total_capacity(), cap);
+    blink.reset();
+    assert_eq!(blink.total_capacity(), cap);
+
+    // Bounded by maximum chunk size.
+    let mut blink =
+        BlinkAlloc::with_chunk_size_range(256, 4096).with_retention(RetentionPolicy::Consolidate);
+    allocate(&blink);
+    blink.reset();
+    assert!(blink.total_capacity() <= 4096);
+}
+
+#[test]
+fn test_reset_compact() {
+    let mut blink = BlinkAlloc::new();
+
+    let allocate = |blink: &BlinkAlloc| {
+        for _ in 0..100 {
+            blink.allocate(Layout::new::<[u8; 100]>()).unwrap();
+        }
+    };
+
+    allocate(&blink);
+    let used = blink.allocated_bytes();
+    blink.reset_compact();
+
+    // Single chunk fits all allocations from previous cycle.
+    assert_eq!(blink.utilization().count(), 1);
+    assert!(blink.total_capacity() >= used);
+    let cap = blink.total_capacity();
+    allocate(&blink);
+    assert_eq!(blink.total_capacity(), cap);
+
+    // Default retention keeps the chunk.
+    blink.reset();
+    assert_eq!(blink.total_capacity(), cap);
+}
+
+#[cfg(feature = "sync-core")]
+#[test]
+fn test_reset_compact_sync() {
+    use crate::SyncBlinkAlloc;
+
+    let mut blink = SyncBlinkAlloc::new();
+
+    let allocate = |blink: &SyncBlinkAlloc| {
+        for _ in 0..100 {
+            blink.allocate(Layout::new::<[u8; 100]>()).unwrap();
+        }
+    };
+
+    allocate(&blink);
+    let used = blink.allocated_bytes();
+    blink.reset_compact();
+
+    assert!(blink.total_capacity() >= used);
+    let cap = blink.total_capacity();
+    allocate(&blink);
+    assert_eq!(blink.total_capacity(), cap);
+}
+
+#[test]
+fn test_arena_hooks() {
+    use crate::ArenaHooks;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    struct Counters {
+        chunks: AtomicUsize,
+        bytes: AtomicUsize,
+        resets: AtomicUsize,
+        slow: AtomicUsize,
+    }
+
+    impl ArenaHooks for Counters {
+        fn on_chunk_allocated(&self, size: usize) {
+            self.chunks.fetch_add(1, Ordering::Relaxed);
+            self.bytes.fetch_add(size, Ordering::Relaxed);
+        }
+
+        fn on_chunk_freed(&self, size: usize) {
+            self.chunks.fetch_sub(1, Ordering::Relaxed);
+            self.bytes.fetch_sub(size, Ordering::Relaxed);
+        }
+
+        fn on_reset(&self, _: usize) {
+            self.resets.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn on_alloc_slow_path(&self, _: Layout) {
+            self.slow.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    static COUNTERS: Counters = Counters {
+        chunks: AtomicUsize::new(0),
+        bytes: AtomicUsize::new(0),
+        resets: AtomicUsize::new(0),
+        slow: AtomicUsize::new(0),
+    };
+
+    let mut blink = BlinkAlloc::new().with_hooks(&COUNTERS);
+    for _ in 0..10 {
+        blink.allocate(Layout::new::<[u8; 1000]>()).unwrap();
+    }
+
+    let chunks = COUNTERS.chunks.load(Ordering::Relaxed);
+    assert!(chunks > 1);
+    assert_eq!(COUNTERS.slow.load(Ordering::Relaxed), chunks);
+    assert!(COUNTERS.bytes.load(Ordering::Relaxed) >= blink.total_capacity());
+
+    blink.reset();
+    assert_eq!(COUNTERS.chunks.load(Ordering::Relaxed), 1);
+    assert_eq!(COUNTERS.resets.load(Ordering::Relaxed), 1);
+
+    drop(blink);
+    assert_eq!(COUNTERS.chunks.load(Ordering::Relaxed), 0);
+    assert_eq!(COUNTERS.bytes.load(Ordering::Relaxed), 0);
+}
+
+#[test]
+fn test_named() {
+    let mut blink = BlinkAlloc::new().with_name("frame");
+    assert_eq!(blink.name(), Some("frame"));
+
+    blink.allocate(Layout::new::<[u8; 1000]>()).unwrap();
+    blink.reset();
+    blink.reset_final();
+}
+
+#[cfg(feature = "debug-stats")]
+#[test]
+fn test_size_histogram() {
+    let blink = BlinkAlloc::new();
+    for size in [0, 1, 2, 3, 4, 5, 100, 128, 129, 10000] {
+        blink
+            .allocate(Layout::from_size_align(size, 1).unwrap())
+            .unwrap();
+    }
+
+    let histogram = blink.size_histogram();
+    assert_eq!(histogram.total(), 10);
+    assert_eq!(histogram.count(1), 2);
+    assert_eq!(histogram.count(2), 1);
+    assert_eq!(histogram.count(4), 2);
+    assert_eq!(histogram.count(8), 1);
+    assert_eq!(histogram.count(128), 2);
+    assert_eq!(histogram.count(256), 1);
+    assert_eq!(histogram.max_size_class(), Some(16384));
+    assert_eq!(
+        histogram.iter().collect::<Vec<_>>(),
+        [
+            (1, 2),
+            (2, 1),
+            (4, 2),
+            (8, 1),
+            (128, 2),
+            (256, 1),
+            (16384, 1)
+        ]
+    );
+
+    blink.reset_size_histogram();
+    assert_eq!(blink.size_histogram().total(), 0);
+    assert_eq!(blink.size_histogram().max_size_class(), None);
+}
+
+#[cfg(feature = "debug-stats")]
+#[test]
+fn test_tag_stats() {
+    let blink = BlinkAlloc::new();
+    {
+        let _physics = blink.tag("physics");
+        blink.allocate(Layout::new::<[u8; 100]>()).unwrap();
+        {
+            let _audio = blink.tag("audio");
+            blink.allocate(Layout::new::<[u8; 10]>()).unwrap();
+        }
+        let ptr = blink.allocate(Layout::new::<[u8; 16]>()).unwrap();
+        unsafe {
+            blink
+                .resize(
+                    ptr.cast(),
+                    Layout::new::<[u8; 16]>(),
+                    Layout::new::<[u8; 64]>(),
+                )
+                .unwrap();
+        }
+    }
+    blink.allocate(Layout::new::<[u8; 7]>()).unwrap();
+
+    let stats = blink.tag_stats();
+    assert_eq!(stats.bytes("physics"), 164);
+    assert_eq!(stats.bytes("audio"), 10);
+    assert_eq!(stats.bytes("render"), 0);
+    assert_eq!(stats.untagged_bytes(), 7);
+    assert_eq!(stats.total(), 181);
+    assert_eq!(
+        stats.iter().collect::<Vec<_>>(),
+        [("physics", 164), ("audio", 10)]
+    );
+
+    // Tags beyond capacity are counted as untagged.
+    const NAMES: &str = "0123456789abcdefghijklmnopqrstuvwxyz";
+    for idx in 0..crate::MAX_TAGS {
+        let _tag = blink.tag(&NAMES[idx..idx + 1]);
+        blink.allocate(Layout::new::<u8>()).unwrap();
+    }
+    let stats = blink.tag_stats();
+    assert_eq!(stats.iter().count(), crate::MAX_TAGS);
+    assert_eq!(stats.untagged_bytes(), 9);
+
+    blink.reset_tag_stats();
+    assert_eq!(blink.tag_stats().total(), 0);
+    assert_eq!(blink.tag_stats().iter().count(), 0);
+}
+
+#[cfg(feature = "tag-quotas")]
+#[test]
+fn test_tag_quota() {
+    let blink = BlinkAlloc::new();
+    blink.set_tag_quota("ui", 100);
+    assert_eq!(blink.tag_quota_remaining("ui"), Some(100));
+    assert_eq!(blink.tag_quota_remaining("physics"), None);
+
+    {
+        let _ui = blink.tag("ui");
+        let ptr = blink.allocate(Layout::new::<[u8; 40]>()).unwrap();
+        assert!(blink.allocate(Layout::new::<[u8; 61]>()).is_err());
+
+        // Growing resize over the quota fails and keeps old allocation.
+        let grown = unsafe {
+            blink.resize(
+                ptr.cast(),
+                Layout::new::<[u8; 40]>(),
+                Layout::new::<[u8; 120]>(),
+            )
+        };
+        assert!(grown.is_err());
+
+        unsafe {
+            blink
+                .resize(
+                    ptr.cast(),
+                    Layout::new::<[u8; 40]>(),
+                    Layout::new::<[u8; 100]>(),
+                )
+                .unwrap();
+        }
+        assert_eq!(blink.tag_quota_remaining("ui"), Some(0));
+        assert!(blink.allocate(Layout::new::<u8>()).is_err());
+
+        // Nested tags without quota are not limited.
+        let _physics = blink.tag("physics");
+        blink.allocate(Layout::new::<[u8; 200]>()).unwrap();
+    }
+
+    // Untagged allocations are not limited.
+    blink.allocate(Layout::new::<[u8; 200]>()).unwrap();
+
+    // Failed allocations are not counted.
+    #[cfg(feature = "debug-stats")]
+    {
+        let stats = blink.tag_stats();
+        assert_eq!(stats.bytes("ui"), 100);
+        assert_eq!(stats.bytes("physics"), 200);
+        assert_eq!(stats.untagged_bytes(), 200);
+    }
+
+    // Raising quota keeps bytes counted against it.
+    blink.set_tag_quota("ui", 150);
+    assert_eq!(blink.tag_quota_remaining("ui"), Some(50));
+
+    // Reset clears counted bytes but keeps the quota.
+    blink.reset_tag_stats();
+    assert_eq!(blink.tag_quota_remaining("ui"), Some(150));
+    {
+        let _ui = blink.tag("ui");
+        blink.allocate(Layout::new::<[u8; 150]>()).unwrap();
+        assert!(blink.allocate(Layout::new::<u8>()).is_err());
+    }
+
+    blink.remove_tag_quota("ui");
+    assert_eq!(blink.tag_quota_remaining("ui"), None);
+    {
+        let _ui = blink.tag("ui");
+        blink.allocate(Layout::new::<u8>()).unwrap();
+    }
+}
+
+#[cfg(feature = "tag-quotas")]
+#[test]
+fn test_tag_quota_failed_allocation() {
+    let blink = BlinkAlloc::with_capacity_exact(256);
+    blink.set_tag_quota("ui", 1024);
+
+    let _ui = blink.tag("ui");
+    blink.allocate(Layout::new::<[u8; 64]>()).unwrap();
+
+    // Allocation that doesn't fit into the arena is not charged.
+    assert!(blink.allocate(Layout::new::<[u8; 512]>()).is_err());
+    assert_eq!(blink.tag_quota_remaining("ui"), Some(960));
+
+    // Quota set while the tag is active applies immediately.
+    blink.set_tag_quota("ui", 100);
+    assert!(blink.allocate(Layout::new::<[u8; 64]>()).is_err());
+    blink.allocate(Layout::new::<[u8; 36]>()).unwrap();
+    assert_eq!(blink.tag_quota_remaining("ui"), Some(0));
+
+    // Quota of the outer tag is restored with it.
+    {
+        let _physics = blink.tag("physics");
+        blink.allocate(Layout::new::<[u8; 8]>()).unwrap();
+    }
+    assert!(blink.allocate(Layout::new::<u8>()).is_err());
+}
+
+#[test]
+fn test_emplace_write_with() {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+    struct Big {
+        data: [u64; 1 << 16],
+    }
+
+    impl Drop for Big {
+        fn drop(&mut self) {
+            DROPPED.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let mut blink = Blink::new();
+
+    let big = blink
+        .emplace()
+        .write_with(|slot: &mut core::mem::MaybeUninit<Big>| {
+            let ptr = slot.as_mut_ptr();
+            unsafe {
+                let data = core::ptr::addr_of_mut!((*ptr).data).cast::<u64>();
+                for i in 0..1 << 16 {
+                    data.add(i).write(i as u64);
+                }
+                slot.assume_init_mut()
+            }
+        });
+    assert_eq!(big.data[1000], 1000);
+
+    let array = blink
+        .emplace_no_drop()
+        .write_with(|slot| slot.write([7u8; 100]));
+    assert_eq!(array[99], 7);
+
+    blink.reset();
+    assert_eq!(DROPPED.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn test_from_exact_size_iter() {
+    let mut blink = Blink::new();
+
+    let slice = blink.emplace().from_iter((0..3u32).map(|i| i * 2));
+    assert_eq!(slice, [0, 2, 4]);
+    #[cfg(not(feature = "debug-headers"))]
+    assert_eq!(blink.allocated_bytes(), Some(3 * size_of::<u32>()));
+    blink.reset();
+
+    let slice = blink
+        .emplace()
+        .from_iter((0..1000).map(alloc::boxed::Box::new));
+    assert_eq!(slice.len(), 1000);
+    assert_eq!(*slice[999], 999);
+    blink.reset();
+
+    let slice = blink.emplace().from_iter(core::iter::empty::<u32>());
+    assert!(slice.is_empty());
+    #[cfg(not(feature = "debug-headers"))]
+    assert_eq!(blink.allocated_bytes(), Some(0));
+}
+
+#[test]
+fn test_try_from_iter_all() {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+    struct Counted(#[allow(dead_code)] u32);
+
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            DROPPED.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    const ELEMENT_COUNT: usize = 20000;
+
+    let mut blink = Blink::new_in(BlinkAlloc::with_chunk_size_in(
+        size_of::<Counted>() * ELEMENT_COUNT / 4,
+        FailAfter::new(1),
+    ));
+
+    let result = blink
+        .emplace()
+        .try_from_iter_all((0..ELEMENT_COUNT as u32).map(Counted).filter(|_| true));
+    assert!(result.is_err());
+
+    // All values taken from iterator are dropped before return.
+    let taken = DROPPED.load(Ordering::Relaxed);
+    assert!(taken > 0);
+
+    // Memory is given back, except alignment padding when bumping down
+    // and headers of allocations moved on resize.
+    #[cfg(all(not(feature = "bump-down"), not(feature = "debug-headers")))]
+    assert_eq!(blink.allocated_bytes(), Some(0));
+
+    blink.reset();
+    assert_eq!(DROPPED.load(Ordering::Relaxed), taken);
+
+    let slice = blink
+        .emplace()
+        .try_from_iter_all((0..10).map(Counted))
+        .unwrap();
+    assert_eq!(slice.len(), 10);
+
+    blink.reset();
+    assert_eq!(DROPPED.load(Ordering::Relaxed), taken + 10);
+}
+
+#[test]
+fn test_reset_keep_all() {
+    let allocator = CountingAlloc::new();
+    let mut blink = BlinkAlloc::new_in(&allocator);
+
+    let layouts = [
+        Layout::new::<[u8; 100]>(),
+        Layout::new::<[u64; 300]>(),
+        Layout::new::<[u8; 5000]>(),
+    ];
+
+    for layout in layouts.iter().cycle().take(50) {
+        blink.allocate(*layout).unwrap();
+    }
+    let chunks = allocator.live_allocations();
+    assert!(chunks > 1);
+
+    for _ in 0..3 {
+        blink.reset_keep_all();
+        assert_eq!(blink.allocated_bytes(), 0);
+        assert_eq!(allocator.live_allocations(), chunks);
+
+        for layout in layouts.iter().cycle().take(50) {
+            blink.allocate(*layout).unwrap();
+        }
+        assert_eq!(allocator.live_allocations(), chunks);
+    }
+
+    blink.reset();
+    assert_eq!(allocator.live_allocations(), 1);
+
+    blink.reset_final();
+    assert_eq!(allocator.live_allocations(), 0);
+}
+
+#[test]
+fn test_blink_alloc_clone() {
+    let mut prototype = BlinkAlloc::with_chunk_size_range(100, 200)
+        .with_min_align(32)
+        .with_name("proto");
+    prototype.allocate(Layout::new::<[u8; 50]>()).unwrap();
+    let capacity = prototype.total_capacity();
+
+    let clone = prototype.clone();
+    assert_eq!(clone.name(), Some("proto"));
+    assert_eq!(clone.total_capacity(), 0);
+    assert_eq!(clone.stats().allocations, 0);
+
+    let a = clone.allocate(Layout::new::<u8>()).unwrap();
+    let b = clone.allocate(Layout::new::<u8>()).unwrap();
+    assert_eq!(a.as_ptr().cast::<u8>() as usize % 32, 0);
+    assert_eq!(b.as_ptr().cast::<u8>() as usize % 32, 0);
+    assert_eq!(clone.total_capacity(), capacity);
+
+    // Chunks don't grow past maximum size of the prototype.
+    for _ in 0..20 {
+        clone.allocate(Layout::new::<[u8; 100]>()).unwrap();
+    }
+    assert!(clone.utilization().all(|chunk| chunk.capacity < 400));
+
+    prototype.reset();
+}
+
+#[test]
+fn test_container_constructors() {
+    let mut alloc = BlinkAlloc::new();
+    let boxed = alloc.boxed(42u32);
+    assert_eq!(*boxed, 42);
+    assert!(alloc.contains(NonNull::from(&*boxed).cast()));
+
+    let mut vec = alloc.vec();
+    vec.extend(0..4u32);
+    assert_eq!(vec, [0, 1, 2, 3]);
+
+    let with_capacity = alloc.vec_with_capacity::<u64>(8);
+    assert!(with_capacity.capacity() >= 8);
+    assert!(alloc.contains(NonNull::from(&with_capacity[..]).cast()));
+
+    drop((boxed, vec, with_capacity));
+    alloc.reset();
+
+    let blink = Blink::new_in(&alloc);
+    let mut vec = blink.vec_with_capacity(2);
+    vec.push("foo");
+    vec.push("bar");
+    assert_eq!(vec, ["foo", "bar"]);
+    assert!(alloc.contains(NonNull::from(&vec[..]).cast()));
+}
+
+#[cfg(feature = "sync-core")]
+#[test]
+fn test_container_constructors_sync() {
+    use crate::SyncBlinkAlloc;
+
+    let alloc = SyncBlinkAlloc::with_chunk_size_in(64, Global);
+    let boxed = alloc.boxed(42u32);
+    let mut vec = alloc.vec_with_capacity(4);
+    vec.extend([1u8, 2, 3, 4]);
+    assert_eq!(*boxed, 42);
+    assert_eq!(vec, [1, 2, 3, 4]);
+    assert!(alloc.vec::<u8>().is_empty());
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn test_sharded() {
+    use crate::ShardedBlinkAlloc;
+
+    let mut alloc = ShardedBlinkAlloc::with_shards_in(4, Global);
+    assert_eq!(alloc.shards(), 4);
+
+    for _ in 0..3 {
+        std::thread::scope(|scope| {
+            for i in 0..8u32 {
+                let alloc = &alloc;
+                scope.spawn(move || {
+                    let mut vec = allocator_api2::vec::Vec::new_in(alloc);
+                    vec.extend(0..i * 100);
+                    assert!(vec.iter().copied().eq(0..i * 100));
+                    if i > 0 {
+                        assert!(alloc.contains(NonNull::from(&vec[..]).cast()));
+                    }
+                });
+            }
+        });
+        assert!(alloc.total_capacity() > 0);
+        alloc.reset();
+    }
+
+    // Memory allocated on one thread is grown on another.
+    let mut vec = allocator_api2::vec::Vec::new_in(&alloc);
+    vec.extend(0..100u32);
+    std::thread::scope(|scope| {
+        scope.spawn(|| vec.extend(100..1000));
+    });
+    assert!(vec.iter().copied().eq(0..1000));
+    drop(vec);
+
+    alloc.reset_final();
+    assert_eq!(alloc.total_capacity(), 0);
+    let _global: Global = alloc.into_inner();
+}
+
+#[test]
+fn test_testing_allocators() {
+    use crate::testing::FailOnSize;
+
+    let failing = FailAfter::new(2);
+    let blink = BlinkAlloc::with_chunk_size_in(64, &failing);
+    blink.allocate(Layout::new::<[u8; 32]>()).unwrap();
+    blink.allocate(Layout::new::<[u8; 128]>()).unwrap();
+    assert_eq!(failing.remaining(), 0);
+    assert!(blink.allocate(Layout::new::<[u8; 1024]>()).is_err());
+    assert_eq!(failing.failures(), 1);
+
+    failing.set_remaining(1);
+    blink.allocate(Layout::new::<[u8; 1024]>()).unwrap();
+
+    let limited = FailOnSize::new(4096);
+    let blink = BlinkAlloc::with_chunk_size_in(64, &limited);
+    blink.allocate(Layout::new::<[u8; 512]>()).unwrap();
+    assert!(blink.allocate(Layout::new::<[u8; 8192]>()).is_err());
+    assert_eq!(limited.failures(), 1);
+
+    limited.set_size(usize::MAX);
+    blink.allocate(Layout::new::<[u8; 8192]>()).unwrap();
+
+    let counting = CountingAlloc::new();
+    let mut blink = BlinkAlloc::with_chunk_size_in(64, &counting);
+    for _ in 0..10 {
+        blink.allocate(Layout::new::<[u8; 100]>()).unwrap();
+    }
+    let peak = counting.peak_bytes();
+    assert!(peak >= 1000);
+    assert_eq!(counting.live_bytes(), peak);
+
+    blink.reset_final();
+    assert_eq!(counting.live_bytes(), 0);
+    assert_eq!(counting.allocations(), counting.deallocations());
+    assert_eq!(counting.peak_bytes(), peak);
+}
+
+#[test]
+fn test_drop_list_stats() {
+    use alloc::string::String;
+
+    let mut blink = Blink::new();
+    assert_eq!(blink.drop_list_len(), 0);
+
+    blink.put(1u64);
+    blink.emplace_no_drop().value(String::new());
+    assert_eq!(blink.drop_list_len(), 0);
+    assert_eq!(blink.drop_list_bytes(), 0);
+
+    blink.put(String::from("foo"));
+    blink.emplace().from_iter((0..10).map(|_| String::new()));
+    assert_eq!(blink.drop_list_len(), 2);
+    assert_eq!(blink.drop_list_bytes(), 2 * 3 * size_of::<usize>());
+
+    blink.reset();
+    assert_eq!(blink.drop_list_len(), 0);
+
+    #[cfg(all(feature = "sync-core", feature = "std"))]
+    {
+        let mut blink = crate::SyncBlink::new();
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| blink.put(String::from("bar")));
+            }
+        });
+        assert_eq!(blink.drop_list_len(), 4);
+        assert_eq!(blink.drop_list_bytes(), 4 * 3 * size_of::<usize>());
+        blink.reset();
+        assert_eq!(blink.drop_list_len(), 0);
+    }
+}
+
+#[test]
+fn test_put_aligned() {
+    use alloc::sync::Arc;
+
+    let counter = Arc::new(());
+    let mut blink = Blink::new();
+    for shift in 0..13 {
+        let align = 1 << shift;
+        let byte = blink.put_aligned(shift as u8, align);
+        assert_eq!(*byte, shift as u8);
+        assert_eq!(byte as *const u8 as usize % align, 0);
+
+        let rc = blink.put_aligned(counter.clone(), align);
+        assert_eq!(rc as *const Arc<()> as usize % align, 0);
+
+        let wide = blink.put_aligned(u64::MAX, align);
+        assert_eq!(*wide, u64::MAX);
+        assert_eq!(wide as *const u64 as usize % align.max(8), 0);
+
+        let unit = blink.put_aligned((), align);
+        assert_eq!(unit as *const () as usize % align, 0);
+    }
+    assert_eq!(Arc::strong_count(&counter), 14);
+    assert_eq!(blink.drop_list_len(), 13);
+
+    blink.reset();
+    assert_eq!(Arc::strong_count(&counter), 1);
+
+    let blink = Blink::new_in(BlinkAlloc::with_chunk_size_in(0, FailAfter::new(0)));
+    let rc = blink.try_put_aligned(counter.clone(), 64).unwrap_err();
+    assert!(Arc::ptr_eq(&rc, &counter));
+}
+
+#[test]
+#[should_panic(expected = "power of two")]
+fn test_put_aligned_bad_align() {
+    Blink::new().put_aligned(0u32, 48);
+}
+
+#[test]
+fn test_blink_box_into_inner() {
+    use crate::BlinkBox;
+    use alloc::rc::Rc;
+
+    let counter = Rc::new(());
+    let mut blink = Blink::new();
+    let kept = BlinkBox::into_inner(blink.boxed(counter.clone()));
+    let dropped = blink.boxed(counter.clone());
+    assert_eq!(Rc::strong_count(&counter), 3);
+
+    drop(dropped);
+    blink.reset();
+    assert_eq!(Rc::strong_count(&counter), 2);
+
+    drop(kept);
+    assert_eq!(Rc::strong_count(&counter), 1);
+}
+
+#[test]
+fn test_default_and_fill_slice() {
+    use alloc::{string::String, sync::Arc};
+
+    let mut blink = Blink::new();
+    let strings: &mut [String] = blink.emplace_default_slice(4);
+    strings[3].push('!');
+    assert_eq!(strings, ["", "", "", "!"]);
+    let numbers: &mut [u64] = blink.emplace_default_slice(1000);
+    assert!(numbers.iter().all(|&n| n == 0));
+    assert!(blink.emplace_default_slice::<String>(0).is_empty());
+    assert_eq!(blink.drop_list_len(), 1);
+
+    let counter = Arc::new(());
+    let rcs = blink.fill_slice(counter.clone(), 5);
+    assert!(rcs.iter().all(|rc| Arc::ptr_eq(rc, &counter)));
+    assert_eq!(Arc::strong_count(&counter), 6);
+    assert!(blink.fill_slice(counter.clone(), 0).is_empty());
+    assert_eq!(Arc::strong_count(&counter), 6);
+
+    blink.reset();
+    assert_eq!(Arc::strong_count(&counter), 1);
+
+    let blink = Blink::new_in(BlinkAlloc::with_chunk_size_in(0, FailAfter::new(0)));
+    assert!(blink.try_emplace_default_slice::<String>(4).is_err());
+    let rc = blink.try_fill_slice(counter.clone(), 4).unwrap_err();
+    assert!(Arc::ptr_eq(&rc, &counter));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_fill_slice_panicking_clone() {
+    use alloc::sync::Arc;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    struct Bomb(Arc<()>);
+
+    impl Clone for Bomb {
+        fn clone(&self) -> Self {
+            if Arc::strong_count(&self.0) == 3 {
+                panic!("boom");
+            }
+            Bomb(self.0.clone())
+        }
+    }
+
+    let counter = Arc::new(());
+    let mut blink = Blink::new();
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        blink.fill_slice(Bomb(counter.clone()), 5);
+    }));
+    assert!(result.is_err());
+
+    // Clones made before the panic and the value itself are dropped.
+    assert_eq!(Arc::strong_count(&counter), 1);
+    assert_eq!(blink.drop_list_len(), 0);
+    blink.reset();
+}
+
+#[test]
+fn test_dyn_blink_allocator() {
+    use crate::BlinkAllocator;
+
+    fn fill(arena: &dyn BlinkAllocator) -> usize {
+        let mut vec = Vec::new_in(arena);
+        vec.extend(0..100u32);
+        arena.allocated_bytes().unwrap()
+    }
+
+    let mut alloc = BlinkAlloc::new();
+    assert!(fill(&alloc) >= 400);
+
+    let arena: &mut dyn BlinkAllocator = &mut alloc;
+    {
+        let blink = Blink::new_in(&mut *arena);
+        blink.put(1u32);
+    }
+    arena.reset();
+    assert_eq!(arena.allocated_bytes(), Some(0));
+
+    #[cfg(all(feature = "sync-core", feature = "std"))]
+    {
+        let alloc = crate::SyncBlinkAlloc::with_chunk_size_in(64, Global);
+        let arena: &(dyn BlinkAllocator + Sync) = &alloc;
+        std::thread::scope(|scope| {
+            scope.spawn(|| assert!(fill(arena) >= 400));
+        });
+    }
+}
+
+#[test]
+fn test_set_allocator() {
+    let first = CountingAlloc::new();
+    let second = CountingAlloc::new();
+
+    let mut blink = BlinkAlloc::with_chunk_size_in(64, &first);
+    blink.allocate(Layout::new::<[u8; 32]>()).unwrap();
+    assert_eq!(first.live_allocations(), 1);
+
+    let old = blink.set_allocator(&second);
+    assert!(core::ptr::eq(old, &first));
+    assert_eq!(first.live_allocations(), 0);
+
+    blink.allocate(Layout::new::<[u8; 32]>()).unwrap();
+    assert_eq!(first.allocations(), 1);
+    assert_eq!(second.live_allocations(), 1);
+
+    drop(blink);
+    assert_eq!(second.live_allocations(), 0);
+}
+
+#[cfg(feature = "sync-core")]
+#[test]
+fn test_set_allocator_sync() {
+    let first = CountingAlloc::new();
+    let second = CountingAlloc::new();
+
+    let mut blink = crate::SyncBlinkAlloc::with_chunk_size_in(64, &first);
+    blink.allocate(Layout::new::<[u8; 32]>()).unwrap();
+
+    blink.set_allocator(&second);
+    assert_eq!(first.live_allocations(), 0);
+
+    blink.allocate(Layout::new::<[u8; 32]>()).unwrap();
+    assert_eq!(second.live_allocations(), 1);
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn test_global_scope() {
+    use crate::global_scope;
+
+    global_scope(|blink| {
+        assert_eq!(blink.allocated_bytes(), 0);
+        blink.allocate(Layout::new::<u64>()).unwrap();
+
+        // Nested scope shares the allocator and doesn't reset it.
+        global_scope(|nested| {
+            assert!(core::ptr::eq(blink, nested));
+            nested.allocate(Layout::new::<u64>()).unwrap();
+        });
+        assert!(blink.allocated_bytes() >= 16);
+    });
+
+    global_scope(|blink| assert_eq!(blink.allocated_bytes(), 0));
+
+    let result = std::panic::catch_unwind(|| {
+        global_scope(|blink| {
+            blink.allocate(Layout::new::<u64>()).unwrap();
+            panic!("work item failed");
+        })
+    });
+    assert!(result.is_err());
+
+    global_scope(|blink| assert_eq!(blink.allocated_bytes(), 0));
+}
+
+#[test]
+fn test_blink_map() {
+    use crate::BlinkMap;
+
+    let mut blink = BlinkAlloc::with_chunk_size_in(64, Global);
+    {
+        let mut map = BlinkMap::new_in(&blink);
+        for key in [5u32, 1, 9, 3, 7] {
+            assert_eq!(map.insert(key, key * 10), None);
+        }
+        assert_eq!(map.insert(3, 33), Some(30));
+        assert_eq!(map.len(), 5);
+
+        let keys: Vec<u32> = map.keys().copied().collect();
+        assert_eq!(keys, [1, 3, 5, 7, 9]);
+        assert_eq!(map.get(&3), Some(&33));
+        assert_eq!(map.get(&4), None);
+        assert!(map.contains_key(&9));
+        assert!(blink.contains(NonNull::from(map.get(&1).unwrap()).cast()));
+
+        let range: Vec<u32> = map.range(3..7).map(|(k, _)| *k).collect();
+        assert_eq!(range, [3, 5]);
+        let range: Vec<u32> = map.range(4..=9).map(|(k, _)| *k).collect();
+        assert_eq!(range, [5, 7, 9]);
+        assert_eq!(map.range(..).len(), 5);
+        assert_eq!(map.range(6..6).len(), 0);
+
+        for (_, v) in map.range_mut(..5) {
+            *v += 1;
+        }
+        assert_eq!(map.get(&1), Some(&11));
+        assert_eq!(map.get(&5), Some(&50));
+
+        assert_eq!(map.remove(&5), Some(50));
+        assert_eq!(map.remove(&5), None);
+        assert_eq!(map.pop_first(), Some((1, 11)));
+        assert_eq!(map.pop_last(), Some((9, 90)));
+
+        map.extend([(2, 20), (8, 80)].iter().copied());
+        map.retain(|k, _| k % 2 == 1);
+        let entries: Vec<(u32, u32)> = map.into_iter().collect();
+        assert_eq!(entries, [(3, 34), (7, 70)]);
+    }
+    blink.reset();
+
+    // Borrowed lookups.
+    let mut map = BlinkMap::new_in(&blink);
+    map.insert(alloc::string::String::from("b"), 2);
+    map.insert(alloc::string::String::from("a"), 1);
+    assert_eq!(map.get("a"), Some(&1));
+    assert_eq!(alloc::format!("{:?}", map), r#"{"a": 1, "b": 2}"#);
+}
+
+#[test]
+fn test_allocate_slice_copy() {
+    let blink = BlinkAlloc::with_chunk_size_in(64, Global);
+    let source = [1u64, 2, 3, 4];
+    let copy = blink.allocate_slice_copy(&source).unwrap();
+    assert_eq!(copy.len(), 4);
+    assert!(blink.contains(copy.cast()));
+    assert_eq!(unsafe { copy.as_ref() }, source);
+
+    let empty = blink.allocate_slice_copy::<u64>(&[]).unwrap();
+    assert!(empty.is_empty());
+
+    let failing = FailAfter::new(0);
+    let blink = BlinkAlloc::new_in(&failing);
+    assert!(blink.allocate_slice_copy(&source).is_err());
+}
+
+#[cfg(feature = "sync-core")]
+#[test]
+fn test_allocate_slice_copy_sync() {
+    let blink = crate::SyncBlinkAlloc::with_chunk_size_in(64, Global);
+    let copy = blink.allocate_slice_copy(b"blink").unwrap();
+    assert_eq!(unsafe { copy.as_ref() }, b"blink");
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn test_live_check() {
+    let mut blink = BlinkAlloc::new().with_live_check();
+    assert_eq!(blink.live_allocations(), Some(0));
+
+    let mut vec = allocator_api2::vec::Vec::new_in(&blink);
+    vec.extend(0..100u32);
+    assert_eq!(blink.live_allocations(), Some(1));
+    drop(vec);
+    assert_eq!(blink.live_allocations(), Some(0));
+    blink.reset();
+
+    // Untracked allocator reports nothing.
+    assert_eq!(BlinkAlloc::new().live_allocations(), None);
+
+    // Clone keeps tracking enabled.
+    assert_eq!(blink.clone().live_allocations(), Some(0));
+}
+
+#[cfg(all(debug_assertions, feature = "std"))]
+#[test]
+fn test_live_check_panics_on_reset() {
+    let mut blink = BlinkAlloc::new().with_live_check();
+    core::mem::forget(allocator_api2::boxed::Box::new_in(1u32, &blink));
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| blink.reset()));
+    assert!(result.is_err());
+}
+
+#[cfg(all(debug_assertions, feature = "sync-core", feature = "std"))]
+#[test]
+fn test_live_check_sync() {
+    let mut blink = crate::SyncBlinkAlloc::new().with_live_check();
+    let vec = allocator_api2::vec::Vec::<u32, _>::with_capacity_in(10, &blink);
+    assert_eq!(blink.live_allocations(), Some(1));
+    drop(vec);
+    blink.reset();
+
+    core::mem::forget(allocator_api2::boxed::Box::new_in(1u32, &blink));
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| blink.reset()));
+    assert!(result.is_err());
+}
+
+#[cfg(all(
+    not(feature = "bump-down"),
+    not(feature = "header-end"),
+    not(feature = "debug-headers")
+))]
+#[test]
+fn test_grow_chunk_in_place() {
+    use crate::ChunkSource;
+
+    /// Hands out prefix of a single buffer and extends it on request.
+    struct Growable {
+        buffer: NonNull<u8>,
+        cap: usize,
+        chunks: Cell<usize>,
+        grown: Cell<usize>,
+    }
+
+    unsafe impl ChunkSource for Growable {
+        fn allocate_chunk(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            if self.chunks.get() > 0 || layout.size() > self.cap {
+                return Err(AllocError);
+            }
+            self.chunks.set(1);
+            Ok(NonNull::slice_from_raw_parts(self.buffer, layout.size()))
+        }
+
+        unsafe fn grow_chunk_in_place(
+            &self,
+            ptr: NonNull<u8>,
+            _old_layout: Layout,
+            new_layout: Layout,
+        ) -> Result<usize, AllocError> {
+            assert_eq!(ptr, self.buffer);
+            if new_layout.size() > self.cap {
+                return Err(AllocError);
+            }
+            self.grown.set(self.grown.get() + 1);
+            Ok(new_layout.size())
+        }
+
+        unsafe fn release_chunk(&self, ptr: NonNull<u8>, layout: Layout) {
+            assert_eq!(ptr, self.buffer);
+            assert!(layout.size() <= self.cap);
+            self.chunks.set(0);
+        }
+    }
+
+    let layout = Layout::from_size_align(1 << 16, 16).unwrap();
+    let buffer = Global.allocate(layout).unwrap().cast::<u8>();
+
+    let source = Growable {
+        buffer,
+        cap: layout.size(),
+        chunks: Cell::new(0),
+        grown: Cell::new(0),
+    };
+
+    let mut blink = BlinkAlloc::new_in(source);
+
+    let first = blink.allocate(Layout::new::<[u8; 100]>()).unwrap();
+    for _ in 0..20 {
+        blink.allocate(Layout::new::<[u8; 100]>()).unwrap();
+    }
+    assert!(blink.inner().grown.get() > 0);
+    assert_eq!(blink.inner().chunks.get(), 1);
+
+    // Memory stays contiguous.
+    let last = blink.allocate(Layout::new::<[u8; 100]>()).unwrap();
+    assert_eq!(
+        unsafe {
+            last.cast::<u8>()
+                .as_ptr()
+                .offset_from(first.cast::<u8>().as_ptr())
+        },
+        2100
+    );
+    assert_eq!(blink.allocated_bytes(), 2200);
+
+    // Last allocation is extended in place.
+    let grown = unsafe {
+        blink
+            .grow(
+                last.cast(),
+                Layout::new::<[u8; 100]>(),
+                Layout::new::<[u8; 10000]>(),
+            )
+            .unwrap()
+    };
+    assert_eq!(grown.cast::<u8>(), last.cast::<u8>());
+
+    // Source is exhausted, growth fails.
+    assert!(blink.allocate(Layout::new::<[u8; 1 << 16]>()).is_err());
+
+    blink.reset_final();
+    assert_eq!(blink.inner().chunks.get(), 0);
+    drop(blink);
+
+    unsafe { Global.deallocate(buffer, layout) };
+}
+
+#[test]
+fn test_chunk_align() {
+    use crate::ChunkSource;
+
+    /// Checks that chunks are requested and released with the same alignment.
+    struct Aligned {
+        align: usize,
+        chunks: Cell<usize>,
+    }
+
+    unsafe impl ChunkSource for Aligned {
+        fn allocate_chunk(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            assert_eq!(layout.align(), self.align);
+            assert_eq!(layout.size() % self.align, 0);
+            self.chunks.set(self.chunks.get() + 1);
+            Global.allocate(layout)
+        }
+
+        unsafe fn release_chunk(&self, ptr: NonNull<u8>, layout: Layout) {
+            assert_eq!(layout.align(), self.align);
+            assert_eq!(ptr.as_ptr() as usize % self.align, 0);
+            self.chunks.set(self.chunks.get() - 1);
+            unsafe { Global.deallocate(ptr, layout) }
+        }
+    }
+
+    let mut blink = BlinkAlloc::new_in(Aligned {
+        align: 4096,
+        chunks: Cell::new(0),
+    })
+    .with_chunk_align(4096);
+
+    for _ in 0..100 {
+        blink.allocate(Layout::new::<[u8; 100]>()).unwrap();
+    }
+    assert!(blink.inner().chunks.get() > 1);
+    blink.reset_compact();
+    assert_eq!(blink.inner().chunks.get(), 1);
+
+    // Page-aligned allocation needs no padding when usable memory
+    // starts or ends at the aligned chunk boundary.
+    blink.reset_final();
+    let page = Layout::from_size_align(4096, 4096).unwrap();
+    let ptr = blink.allocate(page).unwrap();
+    assert_eq!(ptr.as_ptr().cast::<u8>() as usize % 4096, 0);
+    if cfg!(feature = "header-end") != cfg!(feature = "bump-down")
+        && !cfg!(feature = "debug-headers")
+    {
+        assert_eq!(blink.allocated_bytes(), 4096);
+    }
+
+    blink.reset_final();
+    assert_eq!(blink.inner().chunks.get(), 0);
+}
+
+#[cfg(feature = "sync-core")]
+#[test]
+fn test_chunk_align_sync() {
+    use crate::SyncBlinkAlloc;
+
+    let mut blink = SyncBlinkAlloc::new().with_chunk_align(4096);
+    for _ in 0..100 {
+        blink.allocate(Layout::new::<[u8; 100]>()).unwrap();
+    }
+
+    let local = blink.local();
+    local.allocate(Layout::new::<[u8; 100]>()).unwrap();
+    drop(local);
+
+    // Single chunk is kept.
+    blink.reset();
+    let chunk_size = blink.total_capacity() + blink.overhead_bytes();
+    assert_eq!(chunk_size % 4096, 0);
+}
+
+#[test]
+fn test_measure() {
+    let mut blink = Blink::new_in(BlinkAlloc::with_chunk_size(4096));
+    blink.put(0u8);
+
+    let (value, bytes) = blink.measure(|blink| *blink.put([1u64; 8]));
+    assert_eq!(value, [1; 8]);
+    assert!(bytes.allocated_bytes >= 64);
+    assert!(bytes.allocated_bytes < 128);
+    assert_eq!(bytes.capacity_growth, 0);
+
+    let ((), bytes) = blink.measure(|_| {});
+    assert_eq!(bytes.allocated_bytes, 0);
+
+    let (_, bytes) = blink.measure(|blink| blink.copy_slice(&[0u8; 10000]));
+    assert!(bytes.allocated_bytes >= 10000);
+    assert!(bytes.capacity_growth >= 10000);
+
+    blink.reset();
+}
+
+#[test]
+fn test_put_any() {
+    use alloc::{string::String, sync::Arc};
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    struct Payload(Arc<AtomicUsize>);
+
+    impl Drop for Payload {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let mut blink = Blink::new();
+    {
+        let mut values = [
+            blink.put_any(String::from("event")),
+            blink.put_any(7u64),
+            blink.try_put_any([1u8; 3]).unwrap(),
+        ];
+
+        assert!(values[0].is::<String>());
+        assert!(!values[0].is::<u64>());
+        assert_eq!(values[1].type_name(), "u64");
+        assert_eq!(values[1].type_id(), core::any::TypeId::of::<u64>());
+        assert_eq!(values[2].downcast_ref::<[u8; 3]>(), Some(&[1; 3]));
+        assert_eq!(values[2].downcast_ref::<u64>(), None);
+
+        *values[1].downcast_mut::<u64>().unwrap() *= 6;
+
+        let [event, number, _] = values;
+        let event = event.downcast::<String>().unwrap();
+        event.push('s');
+        assert_eq!(event, "events");
+
+        let number = number.downcast::<String>().unwrap_err();
+        assert_eq!(number.downcast::<u64>().ok(), Some(&mut 42));
+    }
+    blink.reset();
+
+    // Payload is dropped on reset.
+    let dropped = Arc::new(AtomicUsize::new(0));
+    blink.put_any(Payload(dropped.clone()));
+    assert_eq!(dropped.load(Ordering::Relaxed), 0);
+    blink.reset();
+    assert_eq!(dropped.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn test_deep_copy() {
+    use alloc::string::String;
+
+    use crate::BlinkCopy;
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct Event<'a> {
+        id: u32,
+        name: &'a str,
+        tags: &'a [&'a str],
+        parent: Option<&'a Event<'a>>,
+    }
+
+    impl<'b> BlinkCopy<'b> for Event<'_> {
+        type Output = Event<'b>;
+
+        fn blink_copy<A: crate::BlinkAllocator>(&self, blink: &'b Blink<A>) -> Event<'b> {
+            Event {
+                id: self.id,
+                name: self.name.blink_copy(blink),
+                tags: self.tags.blink_copy(blink),
+                parent: self.parent.blink_copy(blink),
+            }
+        }
+    }
+
+    let persistent = Blink::new();
+    let mut frame = Blink::new();
+
+    let copied = {
+        let name = String::from("child");
+        let parent = Event {
+            id: 1,
+            name: frame.copy_str("parent"),
+            tags: &[],
+            parent: None,
+        };
+        let event = Event {
+            id: 2,
+            name: &name,
+            tags: frame.copy_slice(&[&*frame.copy_str("a"), &*frame.copy_str("b")]),
+            parent: Some(frame.put_no_drop(parent)),
+        };
+        persistent.deep_copy(&event)
+    };
+    frame.reset();
+
+    assert_eq!(copied.id, 2);
+    assert_eq!(copied.name, "child");
+    assert_eq!(copied.tags, ["a", "b"]);
+    let parent = copied.parent.unwrap();
+    assert_eq!((parent.id, parent.name, parent.parent), (1, "parent", None));
+    let contains = |ptr: *const u8| {
+        persistent
+            .allocator()
+            .contains(NonNull::new(ptr as *mut u8).unwrap())
+    };
+    assert!(contains(copied.name.as_ptr()));
+    assert!(contains(copied.tags[1].as_ptr()));
+
+    let tuple = persistent.deep_copy(&(1u8, [String::from("x")], Some("y")));
+    assert_eq!(tuple, (1, ["x"], Some("y")));
+}
+
+#[cfg(not(feature = "debug-headers"))]
+#[test]
+fn test_size_classes() {
+    let blink = BlinkAlloc::with_chunk_size(4096).with_size_classes();
+
+    let mut bytes = Vec::new();
+    for i in 0..200u8 {
+        let byte = blink.allocate(Layout::new::<u8>()).unwrap().cast::<u8>();
+        unsafe { byte.as_ptr().write(i) };
+        bytes.push(byte);
+
+        let word = blink.allocate(Layout::new::<u64>()).unwrap().cast::<u64>();
+        assert_eq!(word.as_ptr() as usize % align_of::<u64>(), 0);
+        unsafe { word.as_ptr().write(u64::MAX) };
+    }
+    for (i, byte) in bytes.iter().enumerate() {
+        assert_eq!(unsafe { byte.as_ptr().read() }, i as u8);
+    }
+
+    // Bytes are packed apart from words.
+    let dist = bytes[1].as_ptr() as usize - bytes[0].as_ptr() as usize;
+    assert_eq!(dist, 1);
+    assert!(blink.allocated_bytes() < 200 * 16);
+
+    unsafe {
+        // Last small allocation is reclaimed and grown in place.
+        let last = *bytes.last().unwrap();
+        blink.deallocate(last, 1);
+        let again = blink
+            .allocate(Layout::new::<[u8; 3]>())
+            .unwrap()
+            .cast::<u8>();
+        assert_eq!(again, last);
+
+        let grown = blink
+            .resize(again, Layout::new::<[u8; 3]>(), Layout::new::<[u8; 8]>())
+            .unwrap();
+        assert_eq!(grown.cast::<u8>(), again);
+
+        // Growing past small allocation limit moves it to the chunk.
+        again.as_ptr().write_bytes(7, 8);
+        let moved = blink
+            .resize(again, Layout::new::<[u8; 8]>(), Layout::new::<[u8; 100]>())
+            .unwrap()
+            .cast::<u8>();
+        assert_ne!(moved, again);
+        assert_eq!(core::slice::from_raw_parts(moved.as_ptr(), 8), [7; 8]);
+
+        // Higher alignment moves it as well.
+        let small = blink
+            .allocate(Layout::new::<[u8; 2]>())
+            .unwrap()
+            .cast::<u8>();
+        let aligned = blink
+            .resize(small, Layout::new::<[u8; 2]>(), Layout::new::<u64>())
+            .unwrap()
+            .cast::<u8>();
+        assert_eq!(aligned.as_ptr() as usize % align_of::<u64>(), 0);
+    }
+
+    let mut blink = blink;
+    blink.reset();
+    let byte = blink.allocate(Layout::new::<u8>()).unwrap();
+    let word = blink.allocate(Layout::new::<u64>()).unwrap();
+    assert_ne!(byte.cast::<u8>(), word.cast::<u8>());
+}
+
+#[test]
+fn test_allocate_at_least() {
+    let blink = BlinkAlloc::new();
+
+    // Size is not a multiple of alignment.
+    let layout = Layout::from_size_align(10, 8).unwrap();
+    let first = blink.allocate_at_least(layout).unwrap();
+    assert!(first.len() >= 16);
+
+    let byte = blink.allocate(Layout::new::<u8>()).unwrap();
+
+    // Padding belongs to the first allocation.
+    #[cfg(all(not(feature = "bump-down"), not(feature = "debug-headers")))]
+    assert_eq!(
+        byte.cast::<u8>().as_ptr() as usize - first.cast::<u8>().as_ptr() as usize,
+        16
+    );
+
+    // Deallocating with requested layout releases whole region.
+    unsafe {
+        Allocator::deallocate(&blink, byte.cast(), Layout::new::<u8>());
+        Allocator::deallocate(&blink, first.cast(), layout);
+    }
+    let again = blink.allocate(layout).unwrap();
+    assert_eq!(again.cast::<u8>(), first.cast::<u8>());
+}
+
+#[cfg(all(feature = "std", not(feature = "debug-headers")))]
+#[test]
+fn test_frozen_snapshot() {
+    let blink = BlinkAlloc::with_chunk_size(64).with_zeroed_memory();
+    for i in 0..50u64 {
+        let ptr = blink.allocate(Layout::new::<u64>()).unwrap();
+        unsafe { ptr.cast::<u64>().as_ptr().write(i) };
+    }
+    let frozen = blink.freeze();
+
+    // Safety: allocations are initialized and packed without padding.
+    let bytes = unsafe { frozen.as_bytes() };
+    let mut written = std::vec::Vec::new();
+    unsafe { frozen.write_to(&mut written).unwrap() };
+    assert_eq!(bytes, written);
+
+    let word = |idx: usize| {
+        u64::from_le_bytes(core::convert::TryInto::try_into(&bytes[idx * 8..][..8]).unwrap())
+    };
+
+    let count = word(0) as usize;
+    assert!(count > 1);
+
+    let mut offset = (1 + count * 2) * 8;
+    let mut values = std::vec::Vec::new();
+    for chunk in 0..count {
+        let len = word(2 + chunk * 2) as usize;
+        assert_eq!(len % 8, 0);
+        for idx in 0..len / 8 {
+            values.push(word(offset / 8 + idx));
+        }
+        offset += len;
+    }
+    assert_eq!(offset, bytes.len());
+
+    values.sort_unstable();
+    assert_eq!(values, (0..50).collect::<std::vec::Vec<_>>());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_snapshot_load() {
+    use crate::{ArenaOffset, BlinkSnapshot};
+
+    #[repr(C)]
+    struct Node {
+        value: u32,
+        tag: u32,
+        next: *const Node,
+    }
+
+    let blink = BlinkAlloc::with_chunk_size(64).with_zeroed_memory();
+    let mut next = core::ptr::null();
+    for value in 0..20 {
+        // Odd-sized allocations shift nodes in the chunks.
+        // Padding between them is kept zeroed by the allocator.
+        let odd = blink.allocate(Layout::new::<[u8; 3]>()).unwrap();
+        unsafe { odd.cast::<[u8; 3]>().as_ptr().write([0xFF; 3]) };
+        let ptr = blink.allocate(Layout::new::<Node>()).unwrap();
+        let node = ptr.cast::<Node>().as_ptr();
+        unsafe {
+            node.write(Node {
+                value,
+                tag: !value,
+                next,
+            })
+        };
+        next = node;
+    }
+    let frozen = blink.freeze();
+    let head = frozen.offset_of(next).unwrap();
+    assert!(frozen.offset_of(&0u32 as *const u32).is_none());
+
+    let bytes = unsafe { frozen.as_bytes() };
+    drop(frozen);
+
+    let mut words = alloc::vec![0u64; bytes.len() / 8];
+    let aligned = unsafe {
+        core::ptr::copy_nonoverlapping(bytes.as_ptr(), words.as_mut_ptr().cast(), bytes.len());
+        core::slice::from_raw_parts(words.as_ptr().cast::<u8>(), bytes.len())
+    };
+
+    let snapshot = BlinkSnapshot::new(aligned).unwrap();
+    assert!(snapshot.chunk_count() > 1);
+
+    let mut node = unsafe { snapshot.get(head) };
+    let mut expected = 20;
+    while let Some(n) = node {
+        expected -= 1;
+        assert_eq!(n.value, expected);
+        assert_eq!(n.tag, !expected);
+        node = unsafe { snapshot.resolve(n.next) };
+    }
+    assert_eq!(expected, 0);
+
+    let offset = snapshot.offset_of(unsafe { snapshot.get(head) }.unwrap().next);
+    assert!(offset.is_some());
+    assert!(unsafe { snapshot.get(ArenaOffset::<Node>::new(aligned.len())) }.is_none());
+
+    // Truncated snapshots are rejected.
+    assert!(BlinkSnapshot::new(&aligned[..aligned.len() - 8]).is_none());
+    assert!(BlinkSnapshot::new(&aligned[..4]).is_none());
+}
+
+#[cfg(not(feature = "debug-headers"))]
+#[test]
+fn test_zeroed_memory() {
+    let is_zeroed = |ptr: *const u8, len: usize| {
+        unsafe { core::slice::from_raw_parts(ptr, len) }
+            .iter()
+            .all(|&byte| byte == 0)
+    };
+
+    let mut blink = BlinkAlloc::with_chunk_size(256).with_zeroed_memory();
+    let layout = Layout::new::<[u8; 32]>();
+
+    // Shrunk tail is zeroed.
+    let ptr = blink.allocate(layout).unwrap().cast::<u8>();
+    unsafe { core::ptr::write_bytes(ptr.as_ptr(), 0xFF, 32) };
+    let shrunk = unsafe { blink.resize(ptr, layout, Layout::new::<[u8; 8]>()) }.unwrap();
+    assert_eq!(shrunk.cast::<u8>(), ptr);
+    assert!(is_zeroed(unsafe { ptr.as_ptr().add(8) }, 24));
+
+    // Deallocated memory is zeroed even if it can't be reused.
+    let first = blink.allocate(layout).unwrap().cast::<u8>();
+    unsafe { core::ptr::write_bytes(first.as_ptr(), 0xFF, 32) };
+    let last = blink.allocate(layout).unwrap().cast::<u8>();
+    unsafe { core::ptr::write_bytes(last.as_ptr(), 0xFF, 32) };
+    unsafe { blink.deallocate(first, 32) };
+    assert!(is_zeroed(first.as_ptr(), 32));
+
+    // Moved allocation leaves zeroed memory behind.
+    let grown = unsafe { blink.resize(last, layout, Layout::new::<[u8; 1024]>()) }.unwrap();
+    assert_ne!(grown.cast::<u8>(), last);
+    assert!(is_zeroed(last.as_ptr(), 32));
+
+    // Memory is zeroed on reset.
+    unsafe { core::ptr::write_bytes(grown.as_ptr().cast::<u8>(), 0xFF, 1024) };
+    blink.reset();
+    let ptr = blink.allocate(Layout::new::<[u8; 1024]>()).unwrap();
+    assert!(is_zeroed(ptr.as_ptr().cast::<u8>(), 1024));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+#[should_panic(expected = "with_zeroed_memory")]
+fn test_dump_requires_zeroed_memory() {
+    let blink = BlinkAlloc::new();
+    blink.allocate(Layout::new::<u64>()).unwrap();
+    let frozen = blink.freeze();
+    let _ = unsafe { frozen.as_bytes() };
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_blink_send() {
+    fn assert_send<T: Send>(_: &T) {}
+
+    let mut blink = Blink::new();
+    assert_send(&blink);
+
+    blink.put(std::string::String::from("frame"));
+
+    // Frame is built on one thread and processed and reset on another.
+    let blink = std::thread::spawn(move || {
+        blink.reset();
+        let sum: u32 = blink.emplace().from_iter(0..10u32).iter().sum();
+        assert_eq!(sum, 45);
+        blink
+    })
+    .join()
+    .unwrap();
+    drop(blink);
+}
+
+#[test]
+fn test_local_blink() {
+    use alloc::rc::Rc;
+
+    let rc = Rc::new(0u32);
+    let mut blink = crate::blink::LocalBlink::new();
+
+    let a = blink.put(Rc::clone(&rc));
+    let b = blink.try_put(Rc::clone(&rc)).unwrap();
+    assert_eq!(**a + **b, 0);
+    blink.put_pin(Rc::clone(&rc));
+    blink.emplace().from_iter((0..3).map(|_| Rc::clone(&rc)));
+    assert_eq!(Rc::strong_count(&rc), 7);
+
+    blink.reset();
+    assert_eq!(Rc::strong_count(&rc), 1);
+
+    blink.put(Rc::clone(&rc));
+    let blink = blink.into_inner();
+    assert_eq!(Rc::strong_count(&rc), 1);
+    drop(blink);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_put_vec() {
+    use alloc::sync::Arc;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    struct Tracked(Arc<AtomicUsize>, u32);
+
+    impl Drop for Tracked {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let dropped = Arc::new(AtomicUsize::new(0));
+    let mut blink = Blink::new();
+
+    let mut vec = alloc::vec::Vec::with_capacity(8);
+    for i in 0..5 {
+        vec.push(Tracked(dropped.clone(), i));
+    }
+    let slice = blink.emplace().from_vec(vec);
+    assert_eq!(slice.len(), 5);
+    assert!(slice.iter().map(|t| t.1).eq(0..5));
+
+    // Elements are moved, not dropped.
+    assert_eq!(dropped.load(Ordering::Relaxed), 0);
+    blink.reset();
+    assert_eq!(dropped.load(Ordering::Relaxed), 5);
+
+    let slice = blink
+        .emplace_no_drop()
+        .from_vec(alloc::vec![Tracked(dropped.clone(), 7)]);
+    assert_eq!(slice[0].1, 7);
+    blink.reset();
+    assert_eq!(dropped.load(Ordering::Relaxed), 5);
+
+    let words = blink.put_vec(alloc::vec![alloc::string::String::from("a")]);
+    words[0].push('b');
+    assert_eq!(words, ["ab"]);
+    assert!(blink.put_vec(alloc::vec::Vec::<u32>::new()).is_empty());
+    blink.reset();
+
+    // Vector is returned if allocation fails.
+    let blink = Blink::new_in(BlinkAlloc::with_chunk_size_in(64, FailAfter::new(0)));
+    let vec = alloc::vec![1u64; 4];
+    let vec = blink.try_put_vec(vec).unwrap_err();
+    assert_eq!(vec, [1, 1, 1, 1]);
+}
+
+#[test]
+fn test_copy_nested() {
+    let persistent = Blink::new();
+    let mut frame = Blink::new();
+
+    let strings: &[&str] =
+        frame.copy_slice(&[&*frame.copy_str("one"), "", &*frame.copy_str("three")]);
+    let strings = persistent.copy_str_slice(strings);
+
+    let rows: &[&[u16]] = frame.copy_slice(&[&*frame.copy_slice(&[1u16, 2]), &[], &[3]]);
+    let rows = persistent.copy_nested(rows);
+    frame.reset();
+
+    assert_eq!(strings, ["one", "", "three"]);
+    assert_eq!(rows, [&[1, 2][..], &[], &[3]]);
+
+    assert!(persistent.copy_str_slice(&[]).is_empty());
+    assert!(persistent.copy_nested::<u8>(&[]).is_empty());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_alloc_failed_hook() {
+    use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    use crate::{ArenaHooks, BlinkStats};
+
+    static SOURCE: FailAfter = FailAfter::new(1);
+
+    struct Refill {
+        refill: AtomicBool,
+        calls: AtomicUsize,
+        layout_size: AtomicUsize,
+        allocated_bytes: AtomicUsize,
+    }
+
+    impl ArenaHooks for Refill {
+        fn on_alloc_failed(&self, layout: Layout, stats: &BlinkStats) -> bool {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            self.layout_size.store(layout.size(), Ordering::Relaxed);
+            self.allocated_bytes
+                .store(stats.allocated_bytes, Ordering::Relaxed);
+
+            if self.refill.load(Ordering::Relaxed) {
+                SOURCE.set_remaining(1);
+                true
+            } else {
+                false
+            }
+        }
+    }
+
+    static REFILL: Refill = Refill {
+        refill: AtomicBool::new(true),
+        calls: AtomicUsize::new(0),
+        layout_size: AtomicUsize::new(0),
+        allocated_bytes: AtomicUsize::new(0),
+    };
+
+    let blink = BlinkAlloc::with_chunk_size_in(64, &SOURCE).with_hooks(&REFILL);
+    blink.allocate(Layout::new::<[u8; 32]>()).unwrap();
+    let allocated_bytes = blink.allocated_bytes();
+
+    // Retried once after hook made memory available.
+    blink.allocate(Layout::new::<[u8; 1024]>()).unwrap();
+    assert_eq!(REFILL.calls.load(Ordering::Relaxed), 1);
+    assert_eq!(
+        REFILL.allocated_bytes.load(Ordering::Relaxed),
+        allocated_bytes
+    );
+
+    // Debug headers are included in the failed layout.
+    let layout_size = REFILL.layout_size.load(Ordering::Relaxed);
+    assert!(layout_size >= 1024);
+    #[cfg(not(feature = "debug-headers"))]
+    assert_eq!(layout_size, 1024);
+    assert_eq!(SOURCE.failures(), 1);
+
+    // Failure is reported when hook gives up.
+    REFILL.refill.store(false, Ordering::Relaxed);
+    assert!(blink.allocate(Layout::new::<[u8; 1024]>()).is_err());
+    assert_eq!(REFILL.calls.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn test_capacity_exact() {
+    assert!(BlinkAlloc::try_with_capacity_exact_in(1024, FailAfter::new(0)).is_err());
+
+    let failing = FailAfter::new(1);
+    let mut blink = BlinkAlloc::try_with_capacity_exact_in(1024, &failing).unwrap();
+    let capacity = blink.total_capacity();
+    assert!(capacity >= 1024);
+
+    for _ in 0..3 {
+        let ptr = blink.allocate(Layout::new::<[u8; 512]>()).unwrap();
+        assert!(blink.allocate(Layout::new::<[u8; 1024]>()).is_err());
+        assert!(blink.allocate_zeroed(Layout::new::<[u8; 1024]>()).is_err());
+        assert!(unsafe {
+            blink.grow(
+                ptr.cast(),
+                Layout::new::<[u8; 512]>(),
+                Layout::new::<[u8; 2048]>(),
+            )
+        }
+        .is_err());
+
+        blink.allocate(Layout::new::<[u8; 256]>()).unwrap();
+        assert_eq!(blink.total_capacity(), capacity);
+        blink.reset();
+    }
+    assert_eq!(failing.failures(), 0);
+}
+
+#[test]
+fn test_owned_slice() {
+    use crate::{OwnedSlice, OwnedStr, SharedBlinkAlloc};
+    use alloc::{format, rc::Rc, string::ToString};
+
+    fn make(counter: &Rc<()>) -> (OwnedSlice<Rc<()>>, OwnedStr) {
+        let blink = SharedBlinkAlloc::new();
+        let slice = OwnedSlice::from_iter_in((0..3).map(|_| counter.clone()), blink.clone());
+        let string = OwnedStr::concat_in(&["foo", "-", "bar"], blink);
+        (slice, string)
+    }
+
+    let counter = Rc::new(());
+    let (slice, string) = make(&counter);
+    assert_eq!(slice.len(), 3);
+    assert_eq!(Rc::strong_count(&counter), 4);
+    assert_eq!(string.as_str(), "foo-bar");
+    assert_eq!(format!("{:?}", string), "\"foo-bar\"");
+    assert_eq!(string.to_string(), "foo-bar");
+
+    // Both handles co-own the arena.
+    let mut blink = slice.allocator().clone();
+    assert_eq!(blink.owners(), 3);
+    assert!(blink.contains(NonNull::from(&*string).cast()));
+    assert!(!blink.try_reset());
+
+    let copy = OwnedSlice::copy_from_slice_in(&[1u8, 2, 3], blink.clone());
+    let cloned = copy.clone();
+    assert_eq!(*cloned, [1, 2, 3]);
+    assert_ne!(cloned.as_ptr(), copy.as_ptr());
+    assert!(blink.contains(NonNull::from(&*cloned).cast()));
+
+    drop(slice);
+    assert_eq!(Rc::strong_count(&counter), 1);
+
+    drop((string, copy, cloned));
+    assert!(blink.try_reset());
+}
+
+#[test]
+fn test_leak() {
+    fn immortal() -> (&'static u32, &'static str) {
+        let blink = Blink::new().leak();
+        let value = blink.put(42);
+        let name = blink.copy_str("static");
+        (value, name)
+    }
+
+    let (value, name) = immortal();
+    assert_eq!((*value, name), (42, "static"));
+
+    let blink = BlinkAlloc::new().leak();
+    let ptr = blink.allocate(Layout::new::<u64>()).unwrap();
+    assert!(blink.contains(ptr.cast()));
+}
+
+#[test]
+fn test_into_inner_releases_chunks() {
+    let blink = BlinkAlloc::new_in(CountingAlloc::new());
+    for _ in 0..10 {
+        blink.allocate(Layout::new::<[u8; 1000]>()).unwrap();
+    }
+
+    let allocator = blink.into_inner();
+    assert_eq!(allocator.live_allocations(), 0);
+
+    #[cfg(feature = "sync-core")]
+    {
+        let blink = crate::SyncBlinkAlloc::new_in(allocator);
+        blink.allocate(Layout::new::<[u8; 1000]>()).unwrap();
+        blink.local().allocate(Layout::new::<u64>()).unwrap();
+
+        let allocator = blink.into_inner();
+        assert_eq!(allocator.live_allocations(), 0);
+    }
+}
+
+#[test]
+fn test_blink_reset_final() {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+    struct Counted(#[allow(dead_code)] u32);
+
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            DROPPED.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let mut blink = Blink::new();
+    blink.put(Counted(0));
+    blink.emplace().from_iter((0..10).map(Counted));
+    assert!(blink.total_capacity().unwrap() > 0);
+
+    blink.reset_final();
+    assert_eq!(DROPPED.load(Ordering::Relaxed), 11);
+    assert_eq!(blink.total_capacity(), Some(0));
+
+    assert_eq!(*blink.put(42), 42);
+    assert!(blink.total_capacity().unwrap() > 0);
+}
+
+#[test]
+fn test_from_iter_upper_hint() {
+    use crate::BlinkAllocator;
+    use alloc::string::{String, ToString};
+
+    /// Counts relocations of growing allocations.
+    struct GrowCounting {
+        blink: BlinkAlloc,
+        grows: Cell<usize>,
+    }
+
+    unsafe impl Allocator for GrowCounting {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            self.blink.allocate(layout)
+        }
+
+        unsafe fn grow(
+            &self,
+            ptr: NonNull<u8>,
+            old_layout: Layout,
+            new_layout: Layout,
+        ) -> Result<NonNull<[u8]>, AllocError> {
+            self.grows.set(self.grows.get() + 1);
+            Allocator::grow(&self.blink, ptr, old_layout, new_layout)
+        }
+
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            self.blink.deallocate(ptr, layout.size())
+        }
+    }
+
+    unsafe impl BlinkAllocator for GrowCounting {
+        fn reset(&mut self) {
+            self.blink.reset();
+        }
+    }
+
+    let mut blink = Blink::new_in(GrowCounting {
+        blink: BlinkAlloc::new(),
+        grows: Cell::new(0),
+    });
+
+    // Upper bound of `Filter` size hint is used to reserve memory.
+    let evens = blink
+        .emplace()
+        .from_iter((0..200u32).filter(|x| x % 2 == 0));
+    assert_eq!(evens.len(), 100);
+    assert_eq!(blink.allocator().grows.get(), 0);
+
+    let strings = blink
+        .emplace()
+        .from_iter((0..100).filter(|x| x % 3 == 0).map(|x| x.to_string()));
+    assert_eq!(strings.len(), 34);
+    assert_eq!(blink.allocator().grows.get(), 0);
+
+    // Loose upper bound is not trusted beyond the limit.
+    let few = blink
+        .emplace()
+        .from_iter((0..1_000_000u64).filter(|x| x % 100_000 == 0));
+    assert_eq!(few.len(), 10);
+    assert!(blink.allocator().blink.allocated_bytes() < 8 * 1_000_000);
+
+    let _: &mut [String] = blink.emplace().from_iter(core::iter::empty());
+    blink.reset();
+}
+
+#[test]
+fn test_blink_drop_order() {
+    use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+    static OUT_OF_ORDER: AtomicBool = AtomicBool::new(false);
+
+    /// Holds expected position in drop order.
+    struct Ordered(usize);
+
+    impl Drop for Ordered {
+        fn drop(&mut self) {
+            if NEXT.fetch_add(1, Ordering::Relaxed) != self.0 {
+                OUT_OF_ORDER.store(true, Ordering::Relaxed);
+            }
+        }
+    }
+
+    let mut blink = Blink::new();
+    blink.put(Ordered(5));
+    blink.emplace().from_iter((2..5).map(Ordered));
+    blink.put(Ordered(1));
+    blink.put(Ordered(0));
+
+    blink.reset();
+    assert_eq!(NEXT.load(Ordering::Relaxed), 6);
+    assert!(!OUT_OF_ORDER.load(Ordering::Relaxed));
+}
+
+#[test]
+fn test_put_pin() {
+    use core::{
+        marker::PhantomPinned,
+        pin::Pin,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+    /// Checks that it wasn't moved since it was pinned.
+    struct Node {
+        this: Cell<usize>,
+        _pin: PhantomPinned,
+    }
+
+    impl Node {
+        fn pin(self: Pin<&mut Self>) {
+            self.this.set(&*self as *const Node as usize);
+        }
+    }
+
+    impl Drop for Node {
+        fn drop(&mut self) {
+            assert_eq!(self.this.get(), self as *const Node as usize);
+            DROPPED.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let mut blink = Blink::new();
+    for _ in 0..10 {
+        let mut node = blink.put_pin(Node {
+            this: Cell::new(0),
+            _pin: PhantomPinned,
+        });
+        node.as_mut().pin();
+    }
+    let mut node = blink
+        .try_put_pin(Node {
+            this: Cell::new(0),
+            _pin: PhantomPinned,
+        })
+        .ok()
+        .unwrap();
+    node.as_mut().pin();
+
+    blink.reset();
+    assert_eq!(DROPPED.load(Ordering::Relaxed), 11);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_blink_panicking_drop() {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+    struct Counted;
+
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            DROPPED.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    struct Bomb;
+
+    impl Drop for Bomb {
+        fn drop(&mut self) {
+            panic!("bomb");
+        }
+    }
+
+    let mut blink = Blink::new();
+    blink.put(Counted);
+    blink.put(Bomb);
+    blink.put(Counted);
+
+    let result = catch_unwind(AssertUnwindSafe(|| blink.reset()));
+    assert!(result.is_err());
+    assert_eq!(DROPPED.load(Ordering::Relaxed), 1);
+
+    // Values after panicking one are dropped on next reset.
+    blink.reset();
+    assert_eq!(DROPPED.load(Ordering::Relaxed), 2);
+
+    // Values after panicking one are dropped when `Blink` is dropped.
+    blink.put(Counted);
+    blink.put(Bomb);
+    let result = catch_unwind(AssertUnwindSafe(move || drop(blink)));
+    assert!(result.is_err());
+    assert_eq!(DROPPED.load(Ordering::Relaxed), 3);
+}
+
+#[test]
+#[cfg(all(feature = "std", feature = "sync"))]
+fn test_sync_blink_panicking_drop() {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    use crate::SyncBlink;
+
+    static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+    struct Counted;
+
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            DROPPED.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    struct Bomb;
+
+    impl Drop for Bomb {
+        fn drop(&mut self) {
+            panic!("bomb");
+        }
+    }
+
+    let mut blink = SyncBlink::new();
+    blink.put(Counted);
+    blink.put(Bomb);
+    blink.put(Counted);
+
+    let result = catch_unwind(AssertUnwindSafe(|| blink.reset()));
+    assert!(result.is_err());
+    assert_eq!(DROPPED.load(Ordering::Relaxed), 1);
+
+    // Values after panicking one are dropped on next reset.
+    blink.reset();
+    assert_eq!(DROPPED.load(Ordering::Relaxed), 2);
+
+    // Values after panicking one are dropped when `SyncBlink` is dropped.
+    blink.put(Counted);
+    blink.put(Bomb);
+    let result = catch_unwind(AssertUnwindSafe(move || drop(blink)));
+    assert!(result.is_err());
+    assert_eq!(DROPPED.load(Ordering::Relaxed), 3);
+}
+
+#[test]
+fn test_blink_drop_list_steady_state() {
+    use alloc::string::String;
+
+    fn frame(blink: &mut Blink) {
+        for i in 0..1000 {
+            blink.put(String::new());
+            blink.emplace().from_iter((0..i % 4).map(|_| String::new()));
+        }
+        blink.reset();
+    }
+
+    let mut blink = Blink::new();
+    for _ in 0..8 {
+        frame(&mut blink);
+    }
+
+    // Drop list doesn't need memory beyond warmed up chunk.
+    let capacity = blink.total_capacity();
+    for _ in 0..4 {
+        frame(&mut blink);
+        assert_eq!(blink.total_capacity(), capacity);
+    }
+}
+
+#[test]
+fn test_blink_on_reset() {
+    use crate::ResetPhase;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    static DROPPED: AtomicUsize = AtomicUsize::new(0);
+    static SEEN_BEFORE: AtomicUsize = AtomicUsize::new(usize::MAX);
+    static SEEN_AFTER: AtomicUsize = AtomicUsize::new(usize::MAX);
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    struct Counted;
+
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            DROPPED.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let mut blink = Blink::new();
+    let before = blink.on_reset(ResetPhase::BeforeDrop, || {
+        SEEN_BEFORE.store(DROPPED.load(Ordering::Relaxed), Ordering::Relaxed);
+        CALLS.fetch_add(1, Ordering::Relaxed);
+    });
+    blink.on_reset(ResetPhase::AfterDrop, || {
+        SEEN_AFTER.store(DROPPED.load(Ordering::Relaxed), Ordering::Relaxed);
+    });
+
+    blink.put(Counted);
+    blink.reset();
+    assert_eq!(SEEN_BEFORE.load(Ordering::Relaxed), 0);
+    assert_eq!(SEEN_AFTER.load(Ordering::Relaxed), 1);
+
+    // Callbacks survive resets.
+    blink.put(Counted);
+    blink.reset_final();
+    assert_eq!(SEEN_BEFORE.load(Ordering::Relaxed), 1);
+    assert_eq!(SEEN_AFTER.load(Ordering::Relaxed), 2);
+    assert_eq!(CALLS.load(Ordering::Relaxed), 2);
+
+    assert!(blink.remove_on_reset(before));
+    assert!(!blink.remove_on_reset(before));
+    blink.reset();
+    assert_eq!(CALLS.load(Ordering::Relaxed), 2);
+
+    // Dropping `Blink` is not a reset.
+    blink.put(Counted);
+    drop(blink);
+    assert_eq!(SEEN_AFTER.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn test_soa_from_fn() {
+    let mut blink = Blink::new();
+
+    let (a, b, c): (&mut [u8], &mut [u64], &mut [u16]) =
+        blink.soa_from_fn(5, |i| (i as u8, i as u64 * 10, i as u16 * 100));
+    assert_eq!(a, [0, 1, 2, 3, 4]);
+    assert_eq!(b, [0, 10, 20, 30, 40]);
+    assert_eq!(c, [0, 100, 200, 300, 400]);
+    assert_eq!(b.as_ptr() as usize % align_of::<u64>(), 0);
+    assert_eq!(c.as_ptr() as usize % align_of::<u16>(), 0);
+
+    // Slices are laid out back-to-back in a single allocation.
+    assert!(b.as_ptr() as usize >= a.as_ptr() as usize + 5);
+    assert_eq!(c.as_ptr() as usize, b.as_ptr() as usize + 5 * 8);
+    // Allocation size is rounded up to `u64` alignment.
+    #[cfg(all(not(feature = "bump-down"), not(feature = "debug-headers")))]
+    assert!(blink.allocated_bytes().unwrap() <= 8 + 5 * 8 + 8 * 2);
+
+    let (empty, units): (&mut [u32], &mut [()]) = blink.soa_from_fn(0, |_| unreachable!());
+    assert!(empty.is_empty() && units.is_empty());
+
+    let (single,): (&mut [u32],) = blink.try_soa_from_fn(3, |i| (i as u32,)).unwrap();
+    assert_eq!(single, [0, 1, 2]);
+
+    let overflow: Result<(&mut [u64], &mut [u8]), _> =
+        blink.try_soa_from_fn(usize::MAX / 4, |_| unreachable!());
+    assert!(overflow.is_err());
+
+    blink.reset();
+}
+
+#[test]
+fn test_header_with_slice() {
+    #[repr(C)]
+    struct Header {
+        kind: u8,
+        len: u32,
+    }
+
+    let mut blink = Blink::new();
+
+    let (header, values) = blink.header_with_slice(Header { kind: 7, len: 3 }, [1u64, 2, 3]);
+    assert_eq!((header.kind, header.len), (7, 3));
+    assert_eq!(values, [1, 2, 3]);
+    assert_eq!(
+        values.as_ptr() as usize,
+        header as *mut Header as usize + size_of::<Header>().max(align_of::<u64>())
+    );
+
+    let (_, empty) = blink
+        .try_header_with_slice(0u8, core::iter::empty::<u16>())
+        .unwrap();
+    assert!(empty.is_empty());
+
+    /// Iterator that reports more items than it yields.
+    struct Short(u32);
+
+    impl Iterator for Short {
+        type Item = u32;
+
+        fn next(&mut self) -> Option<u32> {
+            self.0 = self.0.checked_sub(1)?;
+            Some(self.0)
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (10, Some(10))
+        }
+    }
+
+    impl ExactSizeIterator for Short {}
+
+    let (header, values) = blink.header_with_slice((), Short(2));
+    assert_eq!(*header, ());
+    assert_eq!(values, [1, 0]);
+
+    assert!(blink
+        .try_header_with_slice(0u8, (0..usize::MAX / 4).map(|_| 0u64))
+        .is_err());
+
+    blink.reset();
+}
+
+#[test]
+fn test_copy_utf8() {
+    let mut blink = Blink::new();
+
+    let string = blink.copy_utf8("привет".as_bytes()).unwrap();
+    assert_eq!(string, "привет");
+    let allocated = blink.allocated_bytes();
+
+    let err = blink.copy_utf8(b"ok\xF0\x28").unwrap_err();
+    assert_eq!(err.valid_up_to(), 2);
+    assert_eq!(blink.allocated_bytes(), allocated);
+
+    blink.reset();
+}
+
+#[test]
+fn test_copy_cstr() {
+    let mut blink = Blink::new();
+
+    let cstr = blink.copy_cstr("hello");
+    assert_eq!(cstr.to_bytes_with_nul(), b"hello\0");
+    #[cfg(not(feature = "debug-headers"))]
+    assert_eq!(blink.allocated_bytes(), Some(6));
+
+    let empty = blink.copy_cstr("");
+    assert_eq!(empty.to_bytes_with_nul(), b"\0");
+
+    let cstr = blink.copy_cstr_from_bytes_with_nul(b"world\0").unwrap();
+    assert_eq!(cstr.to_str(), Ok("world"));
+    assert!(blink.copy_cstr_from_bytes_with_nul(b"world").is_err());
+    assert!(blink.copy_cstr_from_bytes_with_nul(b"wo\0rld\0").is_err());
+
+    blink.reset();
+}
+
+#[test]
+#[should_panic(expected = "string contains NUL byte")]
+fn test_copy_cstr_interior_nul() {
+    let blink = Blink::new();
+    blink.copy_cstr("hel\0lo");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_copy_path() {
+    use std::{ffi::OsStr, path::Path};
+
+    let mut blink = Blink::new();
+
+    let source = Path::new("assets").join("textures").join("frame.png");
+    let path = blink.copy_path(&source);
+    assert_eq!(path, source);
+    assert!(!core::ptr::eq(path, &*source));
+    assert_eq!(path.extension(), Some(OsStr::new("png")));
+
+    let string = blink.copy_os_str(OsStr::new("name"));
+    assert_eq!(string, "name");
+
+    assert_eq!(blink.try_copy_path(&source).unwrap(), source);
+    assert_eq!(blink.try_copy_os_str(OsStr::new("")).unwrap(), "");
+
+    blink.reset();
+}
+
+#[test]
+fn test_blink_macros() {
+    use alloc::{string::String, vec};
+
+    let mut blink = BlinkAlloc::new();
+
+    let strings = crate::blink_vec![in &blink; String::from("a"), String::from("b")];
+    assert_eq!(strings, ["a", "b"]);
+    assert!(blink.contains(NonNull::from(&strings[0]).cast()));
+
+    let repeated = crate::blink_vec![in &blink; vec![1u8]; 3];
+    assert_eq!(repeated.len(), 3);
+    assert!(repeated.iter().all(|v| *v == [1]));
+
+    let mut empty: allocator_api2::vec::Vec<u32, _> = crate::blink_vec![in &blink;];
+    empty.push(1);
+
+    let boxed = crate::blink_box!(in &blink; [1u16; 4]);
+    assert_eq!(*boxed, [1; 4]);
+    assert!(blink.contains(NonNull::from(&*boxed).cast()));
+
+    drop((strings, repeated, empty, boxed));
+    blink.reset();
+
+    let blink = Blink::new();
+    let vec = crate::blink_vec![in &blink; 1, 2, 3];
+    assert_eq!(vec, [1, 2, 3]);
+}
+
+#[cfg(feature = "smallvec")]
+#[test]
+fn test_blink_small_vec() {
+    use crate::BlinkSmallVec;
+    use std::rc::Rc;
+
+    let mut blink = BlinkAlloc::new();
+    let counter = Rc::new(());
+
+    let mut vec = BlinkSmallVec::<Rc<()>, 3, _>::new_in(&blink);
+    assert_eq!(vec.capacity(), 3);
+    vec.extend((0..3).map(|_| counter.clone()));
+    assert!(!vec.spilled());
+    assert_eq!(blink.allocated_bytes(), 0);
+
+    vec.push(counter.clone());
+    assert!(vec.spilled());
+    assert_eq!(vec.len(), 4);
+    assert!(vec.capacity() >= 6);
+    assert_ne!(blink.allocated_bytes(), 0);
+    assert_eq!(Rc::strong_count(&counter), 5);
+
+    assert!(vec.pop().is_some());
+    vec.truncate(1);
+    assert_eq!(Rc::strong_count(&counter), 2);
+    vec.clear();
+    assert!(vec.is_empty());
+    assert!(vec.spilled());
+
+    vec.extend((0..10).map(|_| counter.clone()));
+    assert_eq!(Rc::strong_count(&counter), 11);
+    drop(vec);
+    assert_eq!(Rc::strong_count(&counter), 1);
+
+    let inline = smallvec::SmallVec::<[u32; 4]>::from_slice(&[1, 2]);
+    let vec = BlinkSmallVec::from_smallvec_in(inline, &blink);
+    assert!(!vec.spilled());
+    assert_eq!(*vec, [1, 2]);
+    drop(vec);
+
+    let spilled = smallvec::SmallVec::<[u32; 4]>::from_slice(&[1, 2, 3, 4, 5]);
+    let vec = BlinkSmallVec::from_smallvec_in(spilled, &blink);
+    assert!(vec.spilled());
+    assert_eq!(*vec, [1, 2, 3, 4, 5]);
+    drop(vec);
+
+    let vec = BlinkSmallVec::<u32, 4, _>::with_capacity_in(8, &blink);
+    assert!(vec.spilled());
+    assert!(vec.capacity() >= 8);
+
+    drop(vec);
+    blink.reset();
+}
+
+#[test]
+fn test_split_off() {
+    let mut blink = BlinkAlloc::new();
+
+    let mut sub = blink.split_off(4096).unwrap();
+    assert_eq!(sub.inner().capacity(), 4096);
+    let parent_bytes = blink.allocated_bytes();
+    assert!(parent_bytes >= 4096);
+
+    // Sub-arena never asks parent for more memory.
+    let mut count = 0;
+    while sub.allocate(Layout::new::<[u8; 256]>()).is_ok() {
+        count += 1;
+    }
+    assert!(count > 0 && count < 16);
+    assert_eq!(blink.allocated_bytes(), parent_bytes);
+
+    // Sub-arena resets independently.
+    sub.reset();
+    assert_eq!(sub.allocated_bytes(), 0);
+    sub.allocate(Layout::new::<[u8; 256]>()).unwrap();
+    assert!(blink.allocate(Layout::new::<u64>()).is_ok());
+
+    // Region can't be reclaimed until parent reset,
+    // since parent allocated after it.
+    drop(sub);
+    #[cfg(not(feature = "exact-stats"))]
+    assert!(blink.allocated_bytes() > parent_bytes);
+    blink.reset();
+
+    // Region is returned to parent on drop.
+    let sub = blink.split_off(1000).unwrap();
+    sub.allocate(Layout::new::<u32>()).unwrap();
+    drop(sub);
+    assert_eq!(blink.allocated_bytes(), 0);
+
+    // Empty region can't serve any allocation.
+    let empty = blink.split_off(0).unwrap();
+    assert!(empty.allocate(Layout::new::<u8>()).is_err());
+    drop(empty);
+
+    blink.reset();
+}
+
+#[cfg(feature = "debug-headers")]
+#[test]
+fn test_debug_headers() {
+    let mut blink = BlinkAlloc::new();
+
+    let layout = Layout::new::<[u32; 4]>();
+    let ptr = blink.allocate(layout).unwrap();
+    assert_eq!(ptr.len(), 16);
+
+    let ptr = unsafe {
+        blink
+            .resize(ptr.cast(), layout, Layout::new::<[u32; 8]>())
+            .unwrap()
+    };
+    unsafe { blink.deallocate(ptr.cast(), 32) };
+
+    blink.reset();
+}
+
+#[cfg(feature = "debug-headers")]
+#[test]
+fn test_debug_headers_shrink_in_place() {
+    let blink = BlinkAlloc::new();
+
+    let layout = Layout::new::<[u32; 8]>();
+    let ptr = blink.allocate(layout).unwrap().cast::<u8>();
+    let shrunk = unsafe { blink.resize(ptr, layout, Layout::new::<[u32; 2]>()) }.unwrap();
+    assert_eq!(shrunk.cast::<u8>(), ptr);
+    assert_eq!(shrunk.len(), 8);
+
+    // Header records the new size.
+    unsafe { blink.deallocate(ptr, 8) };
+}
+
+#[cfg(feature = "debug-headers")]
+#[test]
+#[should_panic(expected = "that was allocated with size 8")]
+fn test_debug_headers_shrink_updates_size() {
+    let blink = BlinkAlloc::new();
+
+    let layout = Layout::new::<[u32; 8]>();
+    let ptr = blink.allocate(layout).unwrap().cast::<u8>();
+    unsafe {
+        blink
+            .resize(ptr, layout, Layout::new::<[u32; 2]>())
+            .unwrap();
+        blink.deallocate(ptr, 32);
+    }
+}
+
+#[cfg(feature = "debug-headers")]
+#[test]
+fn test_debug_headers_padded_size() {
+    let blink = BlinkAlloc::new();
+
+    // Size is not a multiple of alignment.
+    let layout = Layout::from_size_align(10, 8).unwrap();
+    let ptr = Allocator::allocate(&blink, layout).unwrap();
+    assert_eq!(ptr.len(), 16);
+    unsafe { Allocator::deallocate(&blink, ptr.cast(), layout) };
+
+    // Any size that fits the allocation is accepted.
+    let ptr = blink.allocate(layout).unwrap();
+    unsafe { blink.deallocate(ptr.cast(), 12) };
+}
+
+#[cfg(feature = "debug-headers")]
+#[test]
+#[should_panic(expected = "that was allocated with size 16")]
+fn test_debug_headers_size_mismatch() {
+    let blink = BlinkAlloc::new();
+    let ptr = blink.allocate(Layout::new::<[u32; 4]>()).unwrap();
+    unsafe { blink.deallocate(ptr.cast(), 8) };
+}
+
+#[cfg(feature = "debug-headers")]
+#[test]
+#[should_panic(expected = "that was allocated with align 4")]
+fn test_debug_headers_align_mismatch() {
+    let blink = BlinkAlloc::new();
+    let ptr = blink.allocate(Layout::new::<[u32; 4]>()).unwrap();
+    let _ = unsafe {
+        blink.resize(
+            ptr.cast(),
+            Layout::from_size_align(16, 8).unwrap(),
+            Layout::new::<[u32; 8]>(),
+        )
+    };
+}
+
+#[cfg(feature = "debug-headers")]
+#[test]
+#[should_panic(expected = "already deallocated")]
+fn test_debug_headers_double_free() {
+    let blink = BlinkAlloc::new();
+    let ptr = blink.allocate(Layout::new::<[u32; 4]>()).unwrap();
+    blink.allocate(Layout::new::<u8>()).unwrap();
+    unsafe {
+        blink.deallocate(ptr.cast(), 16);
+        blink.deallocate(ptr.cast(), 16);
+    }
+}
+
+#[cfg(feature = "debug-headers")]
+#[test]
+#[should_panic(expected = "was not allocated by this allocator")]
+fn test_debug_headers_foreign_pointer() {
+    let blink = BlinkAlloc::new();
+    let ptr = blink.allocate_zeroed(Layout::new::<[u64; 8]>()).unwrap();
+    let inner = unsafe { NonNull::new_unchecked(ptr.cast::<u64>().as_ptr().add(4)) };
+    unsafe { blink.deallocate(inner.cast(), 8) };
+}
+
+#[test]
+fn test_try_api() {
+    struct FailingGlobal;
+
+    unsafe impl Allocator for FailingGlobal {
+        fn allocate(&self, _: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            Err(AllocError)
+        }
+
+        unsafe fn deallocate(&self, _: core::ptr::NonNull<u8>, _: Layout) {
+            unreachable!()
+        }
+    }
+
+    let blink = Blink::new_in(BlinkAlloc::new_in(FailingGlobal));
+
+    assert_eq!(blink.try_put(42).unwrap_err(), 42);
+    assert!(blink.try_uninit::<u32>().is_err());
+    assert!(blink.try_copy_slice(&[1, 2, 3]).is_err());
+    assert!(blink.try_copy_str("hello").is_err());
+    assert!(blink.try_copy_cstr("hello").is_err());
+    assert_eq!(blink.emplace().try_value(42).unwrap_err(), 42);
+    assert!(blink.emplace().try_with(|| 42).is_err());
+    assert!(blink.emplace().try_from_iter_all(0..10).is_err());
+
+    let s = alloc::borrow::ToOwned::to_owned("hello");
+    assert_eq!(blink.emplace_no_drop().try_value(&s).unwrap_err(), &s);
+    assert!(blink.emplace_no_drop().try_with(|| &s).is_err());
+    let (slice, _) = blink
+        .emplace_no_drop()
+        .try_from_iter((0..2).map(|_| &s).filter(|_| true))
+        .unwrap_err();
+    assert!(slice.is_empty());
+    assert!(blink.emplace_no_drop().try_from_iter_all(0..10).is_err());
+
+    let blink = Blink::new();
+    assert_eq!(*blink.try_put(42).unwrap(), 42);
+    assert_eq!(blink.try_copy_slice(&[1, 2, 3]).unwrap(), [1, 2, 3]);
+    assert_eq!(blink.try_copy_str("hello").unwrap(), "hello");
+}
+
+#[cfg(feature = "nightly")]
+#[test]
+fn test_api2_allocator_on_nightly() {
+    let mut blink = BlinkAlloc::new();
+
+    let mut core_vec = alloc::vec::Vec::new_in(&blink);
+    core_vec.extend(0..100);
+
+    let mut api2_vec = allocator_api2::vec::Vec::new_in(&blink);
+    api2_vec.extend(0..100);
+
+    assert!(core_vec.iter().eq(api2_vec.iter()));
+
+    drop(core_vec);
+    drop(api2_vec);
+    blink.reset();
+}
+
+#[test]
+fn test_shared_alloc() {
+    use crate::SharedAlloc;
+    use alloc::rc::Rc;
+
+    struct Owner {
+        values: Vec<u32, SharedAlloc<Rc<Blink>>>,
+    }
+
+    let mut blink = Rc::new(Blink::new());
+
+    let owner = {
+        let mut values = Vec::new_in(SharedAlloc::new(blink.clone()));
+        values.extend(0..100);
+        Owner { values }
+    };
+    assert_eq!(owner.values.iter().sum::<u32>(), 4950);
+    assert!(blink.allocated_bytes().unwrap() >= 400);
+
+    drop(owner);
+    Rc::get_mut(&mut blink).unwrap().reset();
+    assert_eq!(blink.allocated_bytes(), Some(0));
+}
+
+#[test]
+fn test_shared_blink_alloc() {
+    use crate::SharedBlinkAlloc;
+
+    let mut frame = SharedBlinkAlloc::new();
+    let mut a = Vec::new_in(frame.clone());
+    let mut b = Vec::new_in(frame.clone());
+    a.extend(0..100u32);
+    b.extend(0..100u64);
+    assert_eq!(frame.owners(), 3);
+    assert!(frame.allocated_bytes() >= 1200);
+
+    assert!(!frame.try_reset());
+    assert!(frame.get_mut().is_none());
+    drop(a);
+    assert!(!frame.try_reset_final());
+    drop(b);
+
+    assert!(frame.try_reset());
+    assert_eq!(frame.allocated_bytes(), 0);
+    assert!(frame.total_capacity() > 0);
+
+    // Shared owner resets as `BlinkAllocator` only when unique.
+    let mut blink = Blink::new_in(frame.clone());
+    blink.put(1u32);
+    blink.reset();
+    #[cfg(not(feature = "debug-headers"))]
+    assert_eq!(frame.allocated_bytes(), 4);
+
+    drop(blink);
+    crate::BlinkAllocator::reset_final(&mut frame);
+    assert_eq!(frame.total_capacity(), 0);
+}
+
+#[cfg(all(feature = "sync-core", feature = "std"))]
+#[test]
+fn test_shared_alloc_sync() {
+    use crate::{SharedAlloc, SyncBlinkAlloc};
+    use std::sync::Arc;
+
+    let mut blink = Arc::new(SyncBlinkAlloc::new());
+
+    let handles = (0..4)
+        .map(|i| {
+            let alloc = SharedAlloc::new(blink.clone());
+            std::thread::spawn(move || {
+                let mut values = Vec::new_in(alloc);
+                values.extend(i * 100..(i + 1) * 100);
+                values.iter().sum::<u32>()
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let sum: u32 = handles.into_iter().map(|h| h.join().unwrap()).sum();
+    assert_eq!(sum, (0..400).sum());
+
+    Arc::get_mut(&mut blink).unwrap().reset();
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_with_scratch() {
+    use crate::with_scratch;
+
+    let capacity = with_scratch(|blink| {
+        blink.emplace().from_iter(0..1000u32);
+
+        // Nested call receives another arena.
+        with_scratch(|nested| {
+            assert_eq!(nested.allocated_bytes(), Some(0));
+            nested.put(42);
+        });
+
+        blink.total_capacity().unwrap()
+    });
+    assert!(capacity >= 4000);
+
+    // Arena is reset and kept warm.
+    with_scratch(|blink| {
+        assert_eq!(blink.allocated_bytes(), Some(0));
+        assert!(blink.total_capacity().unwrap() > 0);
+    });
+}
+
+#[test]
+fn test_double_blink() {
+    use crate::DoubleBlink;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+    struct Counted(#[allow(dead_code)] u32);
+
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            DROPPED.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let mut frames = DoubleBlink::new();
+
+    let first = NonNull::from(frames.current().put(Counted(1)));
+    frames.swap();
+
+    // Frame N data is still alive during frame N+1.
+    assert_eq!(DROPPED.load(Ordering::Relaxed), 0);
+    assert!(frames.previous().allocated_bytes().unwrap() > 0);
+    assert_eq!(unsafe { first.as_ref() }.0, 1);
+    frames.current().put(Counted(2));
+
+    // And recycled at frame N+2.
+    frames.swap();
+    assert_eq!(DROPPED.load(Ordering::Relaxed), 1);
+    assert_eq!(frames.current().allocated_bytes(), Some(0));
+
+    frames.reset();
+    assert_eq!(DROPPED.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn test_ring_blink() {
+    use crate::RingBlink;
+
+    let mut frames = RingBlink::<3>::new();
+    assert!(frames.frame(3).is_none());
+
+    frames.current().put(1u32);
+    frames.advance();
+    frames.current().put(2u32);
+    frames.advance();
+
+    // Two previous frames are still alive.
+    assert!(frames.frame(1).unwrap().allocated_bytes().unwrap() > 0);
+    assert!(frames.frame(2).unwrap().allocated_bytes().unwrap() > 0);
+    assert_eq!(frames.frame(0).unwrap().allocated_bytes(), Some(0));
+
+    // Oldest frame is recycled.
+    frames.current().put(3u32);
+    frames.advance();
+    assert_eq!(frames.current().allocated_bytes(), Some(0));
+    assert!(frames.frame(2).unwrap().allocated_bytes().unwrap() > 0);
+
+    frames.reset();
+    for age in 0..3 {
+        assert_eq!(frames.frame(age).unwrap().allocated_bytes(), Some(0));
+    }
+}
+
+#[test]
+fn test_owned_blink() {
+    use crate::{BlinkRoot, OwnedBlink};
+
+    struct Node<'a> {
+        value: u32,
+        next: Option<&'a Node<'a>>,
+    }
+
+    struct List;
+
+    impl BlinkRoot for List {
+        type Root<'a> = Option<&'a Node<'a>>;
+    }
+
+    fn build(values: &[u32]) -> OwnedBlink<List> {
+        OwnedBlink::new(|blink| {
+            let mut head = None;
+            for &value in values.iter().rev() {
+                head = Some(&*blink.put_no_drop(Node { value, next: head }));
+            }
+            head
+        })
+    }
+
+    fn sum(mut node: Option<&Node<'_>>) -> u32 {
+        let mut sum = 0;
+        while let Some(n) = node {
+            sum += n.value;
+            node = n.next;
+        }
+        sum
+    }
+
+    let mut list = build(&[1, 2, 3]);
+    assert_eq!(list.with_root(|head| sum(*head)), 6);
+
+    list.with_root_mut(|blink, head| {
+        *head = Some(blink.put_no_drop(Node {
+            value: 10,
+            next: *head,
+        }));
+    });
+    assert_eq!(list.with_root(|head| sum(*head)), 16);
+
+    let mut blink = list.into_blink();
+    assert!(blink.allocated_bytes().unwrap() > 0);
+    blink.reset();
+}
+
+#[cfg(all(feature = "std", feature = "sync-core"))]
+#[test]
+fn test_scoped_sync_blink() {
+    use crate::ScopedSyncBlink;
+
+    let mut blink = ScopedSyncBlink::new();
+    let input = [1u32, 2, 3, 4];
+
+    let sum = blink.scope(|scope, handle| {
+        let input = &input;
+        let workers: std::vec::Vec<_> = (0..4)
+            .map(|i| {
+                scope.spawn(move || {
+                    let mut vec = Vec::new_in(handle);
+                    vec.extend(input.iter().map(|x| x * i));
+                    vec.iter().sum::<u32>()
+                })
+            })
+            .collect();
+        workers.into_iter().map(|w| w.join().unwrap()).sum::<u32>()
+    });
+
+    assert_eq!(sum, 60);
+    assert_eq!(blink.get().allocated_bytes(), 0);
+    assert!(blink.get().total_capacity() > 0);
+}
+
+#[cfg(all(feature = "sync-core", feature = "std"))]
+#[test]
+fn test_sync_blink() {
+    use crate::SyncBlink;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+    struct Counted(#[allow(dead_code)] u32);
+
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            DROPPED.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let mut blink = SyncBlink::new();
+
+    std::thread::scope(|scope| {
+        let blink = &blink;
+        for i in 0..4 {
+            scope.spawn(move || {
+                for j in 0..100 {
+                    let value = blink.put(Counted(i * 100 + j));
+                    assert_eq!(value.0, i * 100 + j);
+                }
+                let slice = blink.emplace().from_iter((0..10).map(Counted));
+                assert_eq!(slice.len(), 10);
+                assert_eq!(blink.copy_str("hello"), "hello");
+            });
+        }
+    });
+
+    assert_eq!(DROPPED.load(Ordering::Relaxed), 0);
+    blink.reset();
+    assert_eq!(DROPPED.load(Ordering::Relaxed), 440);
+    assert_eq!(blink.allocated_bytes(), Some(0));
+}
+
+#[test]
+fn test_min_align() {
+    let mut blink = BlinkAlloc::new().with_min_align(64);
+
+    let mut ptrs = Vec::new();
+    for size in 1..20 {
+        let ptr = blink
+            .allocate(Layout::from_size_align(size, 1).unwrap())
+            .unwrap();
+        assert_eq!(ptr.as_ptr().cast::<u8>() as usize % 64, 0);
+        ptrs.push(ptr.as_ptr().cast::<u8>() as usize);
+    }
+    for pair in ptrs.windows(2) {
+        assert!(pair[0].abs_diff(pair[1]) >= 64);
+    }
+
+    // Deallocating last allocation with original size frees padded block.
+    let before = blink.allocated_bytes();
+    let ptr = blink.allocate(Layout::new::<[u8; 3]>()).unwrap();
+    unsafe { blink.deallocate(ptr.cast(), 3) };
+    assert_eq!(blink.allocated_bytes(), before);
+
+    let mut vec = Vec::new_in(&blink);
+    vec.extend(0..100u8);
+    assert_eq!(vec.as_ptr() as usize % 64, 0);
+    drop(vec);
+
+    blink.reset();
+}
+
+#[cfg(feature = "sync-core")]
+#[test]
+fn test_min_align_sync() {
+    use crate::SyncBlinkAlloc;
+
+    let blink = SyncBlinkAlloc::new().with_min_align(128);
+    let ptr = blink.allocate(Layout::new::<u8>()).unwrap();
+    assert_eq!(ptr.as_ptr().cast::<u8>() as usize % 128, 0);
+
+    let local = blink.local();
+    for _ in 0..10 {
+        let ptr = local.allocate(Layout::new::<u16>()).unwrap();
+        assert_eq!(ptr.as_ptr().cast::<u8>() as usize % 128, 0);
+    }
+}
+
+#[cfg(all(feature = "sync-core", feature = "std"))]
+#[test]
+fn test_static_sync_blink_alloc() {
+    use crate::SyncBlinkAlloc;
+
+    static FRAME: SyncBlinkAlloc = SyncBlinkAlloc::new();
+
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                let local = FRAME.local();
+                for _ in 0..100 {
+                    local.allocate(Layout::new::<u64>()).unwrap();
+                }
+            });
+        }
+    });
+    assert!(FRAME.allocated_bytes() >= 400 * 8);
+
+    // Safety: memory allocated from `FRAME` is not used anymore.
+    unsafe { FRAME.reset_unchecked() };
+    assert_eq!(FRAME.allocated_bytes(), 0);
+}
+
+#[test]
+#[should_panic]
+fn test_min_align_not_power_of_two() {
+    let _ = BlinkAlloc::new().with_min_align(48);
+}
+
+#[test]
+fn test_chunk_header_placement() {
+    const PAGE: usize = 4096;
+
+    struct PageGlobal {
+        last: Cell<usize>,
+    }
+
+    unsafe impl Allocator for PageGlobal {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            let layout = layout.align_to(PAGE).map_err(|_| AllocError)?;
+            let ptr = Global.allocate(layout)?;
+            self.last.set(ptr.as_ptr().cast::<u8>() as usize);
+            Ok(ptr)
+        }
+
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            Global.deallocate(ptr, layout.align_to(PAGE).unwrap())
+        }
+    }
+
+    let allocator = PageGlobal { last: Cell::new(0) };
+    let mut blink = BlinkAlloc::with_chunk_size_in(0, &allocator);
+
+    let page = Layout::from_size_align(PAGE, PAGE).unwrap();
+    for _ in 0..10 {
+        let ptr = blink.allocate(page).unwrap();
+        let addr = ptr.as_ptr().cast::<u8>() as usize;
+        assert_eq!(addr % PAGE, 0);
+        unsafe { core::ptr::write_bytes(ptr.as_ptr().cast::<u8>(), 0xFF, PAGE) };
+    }
+    blink.reset();
+
+    // With header at the end first allocation takes the chunk's start.
+    let ptr = blink.allocate(Layout::new::<u64>()).unwrap();
+    if cfg!(all(
+        feature = "header-end",
+        not(feature = "bump-down"),
+        not(feature = "debug-headers")
+    )) {
+        assert_eq!(ptr.as_ptr().cast::<u8>() as usize, allocator.last.get());
+    }
+    blink.reset_final();
+}
+
+#[cfg(all(feature = "sync-core", feature = "std"))]
+#[test]
+fn test_stats_polling_sync() {
+    use core::sync::atomic::{AtomicBool, Ordering};
+
+    use crate::SyncBlinkAlloc;
+
+    let blink = SyncBlinkAlloc::with_chunk_size_in(64, Global);
+    let done = AtomicBool::new(false);
+
+    std::thread::scope(|scope| {
+        scope.spawn(|| {
+            while !done.load(Ordering::Relaxed) {
+                let allocated = blink.allocated_bytes();
+                let capacity = blink.total_capacity();
+                assert!(allocated <= 1 << 20 && capacity <= 1 << 20);
+            }
+        });
+
+        for _ in 0..100 {
+            for size in 1..100 {
+                blink
+                    .allocate(Layout::from_size_align(size, 8).unwrap())
+                    .unwrap();
+            }
+            // Safety: allocated memory is not used.
+            unsafe { blink.reset_unchecked() };
+        }
+        done.store(true, Ordering::Relaxed);
+    });
+
+    assert_eq!(blink.allocated_bytes(), 0);
+    assert!(blink.total_capacity() >= 99 * 8);
+}
+
+#[test]
+fn test_contains() {
+    let mut blink = BlinkAlloc::with_chunk_size(64);
+
+    let mut ptrs = Vec::new();
+    for size in 1..100 {
+        let ptr = blink
+            .allocate(Layout::from_size_align(size, 1).unwrap())
+            .unwrap();
+        ptrs.push(ptr.cast::<u8>());
+    }
+    for ptr in &ptrs {
+        assert!(blink.contains(*ptr));
+    }
+
+    let heap = alloc::boxed::Box::new(0u8);
+    assert!(!blink.contains(NonNull::from(&*heap)));
+
+    blink.reset_final();
+    assert!(!blink.contains(ptrs[0]));
+}
+
+#[test]
+fn test_lifo_dealloc() {
+    let mut blink = BlinkAlloc::new();
+
+    let byte = Layout::new::<u8>();
+    let word = Layout::new::<u64>();
+
+    // Warm up so that all allocations below are served from one chunk.
+    blink
+        .allocate(Layout::from_size_align(1024, 8).unwrap())
+        .unwrap();
+    blink.reset();
+
+    for depth in 1..=4 {
+        let before = blink.allocated_bytes();
+
+        // Every other allocation is preceded by alignment padding.
+        let mut ptrs = Vec::new();
+        for i in 0..depth * 2 {
+            let layout = if i % 2 == 0 { byte } else { word };
+            let ptr = blink.allocate(layout).unwrap();
+            ptrs.push((ptr.cast::<u8>(), layout.size()));
+        }
+        assert!(blink.allocated_bytes() > before);
+
+        for (ptr, size) in ptrs.into_iter().rev() {
+            unsafe { blink.deallocate(ptr, size) };
+        }
+        assert_eq!(blink.allocated_bytes(), before);
+    }
+
+    let mut outer = Vec::new_in(&blink);
+    outer.push(1u8);
+    let mut inner = Vec::new_in(&blink);
+    inner.push(1u64);
+    drop(inner);
+    drop(outer);
+    assert_eq!(blink.allocated_bytes(), 0);
+
+    blink.reset();
+}
+
+#[test]
+#[cfg(not(feature = "debug-headers"))]
+fn test_lifo_dealloc_window_overflow() {
+    let mut blink = BlinkAlloc::new();
+
+    let byte = Layout::new::<u8>();
+    let word = Layout::new::<u64>();
+
+    // Warm up so that all allocations below are served from one chunk.
+    blink
+        .allocate(Layout::from_size_align(1024, 8).unwrap())
+        .unwrap();
+    blink.reset();
+
+    // More padded allocations than LIFO window can track.
+    let mut ptrs = Vec::new();
+    for _ in 0..6 {
+        ptrs.push((blink.allocate(byte).unwrap().cast::<u8>(), byte.size()));
+        ptrs.push((blink.allocate(word).unwrap().cast::<u8>(), word.size()));
+    }
+    let last_word = ptrs[ptrs.len() - 1].0;
+
+    // Free padded words in reverse order, then bytes out of order.
+    for &(ptr, size) in ptrs.iter().rev().step_by(2) {
+        unsafe { blink.deallocate(ptr, size) };
+    }
+    for &(ptr, size) in ptrs.iter().step_by(2) {
+        unsafe { blink.deallocate(ptr, size) };
+    }
+
+    // Reach the start of the last padded word with unpadded allocations.
+    let mut prev = None;
+    let mut last = None;
+    for _ in 0..64 {
+        let ptr = blink.allocate(word).unwrap().cast::<u8>();
+        if ptr == last_word {
+            last = Some(ptr);
+            break;
+        }
+        prev = Some(ptr);
+    }
+    let (prev, last) = (prev.unwrap(), last.unwrap());
+
+    // Freeing it must not rewind cursor into `prev` that is still live.
+    unsafe { blink.deallocate(last, word.size()) };
+    let next = blink.allocate(byte).unwrap().cast::<u8>();
+    let prev_range = prev.as_ptr() as usize..prev.as_ptr() as usize + word.size();
+    assert!(!prev_range.contains(&(next.as_ptr() as usize)));
+
+    blink.reset();
+}
+
+#[test]
+fn test_stats_diff() {
+    let mut blink = BlinkAlloc::with_chunk_size(4096);
+
+    let start = blink.stats();
+    assert_eq!(start, Default::default());
+
+    for _ in 0..10 {
+        blink.allocate(Layout::new::<[u8; 100]>()).unwrap();
+    }
+    let frame = blink.stats();
+    let diff = frame.diff(&start);
+    assert_eq!(diff.allocations, 10);
+    #[cfg(not(feature = "debug-headers"))]
+    assert_eq!(diff.allocated_bytes, 1000);
+    assert!(diff.chunks >= 1);
+    assert!(diff.total_capacity >= 1000);
+
+    blink.reset();
+    let diff = blink.stats().diff(&frame);
+    assert_eq!(diff.allocations, 0);
+    assert_eq!(diff.allocated_bytes, -(frame.allocated_bytes as isize));
+    assert!(diff.chunks <= 0);
+}
+
+#[test]
+fn test_stats_peak_and_display() {
+    use alloc::{format, string::ToString};
+
+    let mut blink = BlinkAlloc::with_chunk_size(4096);
+
+    blink.allocate(Layout::new::<[u8; 1000]>()).unwrap();
+    let stats = blink.stats();
+    let peak = stats.peak_allocated_bytes;
+    assert!(peak >= 1000);
+    #[cfg(not(feature = "debug-headers"))]
+    assert_eq!(peak, 1000);
+    assert_eq!(stats.slow_path_hits, 1);
+
+    blink.reset();
+    blink.allocate(Layout::new::<[u8; 100]>()).unwrap();
+    let stats = blink.stats();
+    #[cfg(not(feature = "debug-headers"))]
+    assert_eq!(stats.allocated_bytes, 100);
+    assert_eq!(stats.peak_allocated_bytes, peak);
+    assert_eq!(stats.slow_path_hits, 1);
+
+    blink.allocate(Layout::new::<[u8; 8192]>()).unwrap();
+    let stats = blink.stats();
+    assert_eq!(stats.slow_path_hits, 2);
+    assert!(stats.peak_allocated_bytes >= 8292);
+
+    let line = stats.to_string();
+    assert!(line.starts_with(&format!("allocated: {} B, ", stats.allocated_bytes)));
+    assert!(line.ends_with("allocations: 3, slow path hits: 2"));
+}
+
+#[test]
+fn test_path_hits() {
+    let mut blink = BlinkAlloc::with_chunk_size(4096);
+
+    let ptr = blink.allocate(Layout::new::<[u8; 100]>()).unwrap();
+    blink.allocate(Layout::new::<[u8; 100]>()).unwrap();
+    assert_eq!(blink.fast_path_hits(), 1);
+    assert_eq!(blink.slow_path_hits(), 1);
+
+    // Resize that doesn't fit moves to a new chunk.
+    unsafe {
+        blink
+            .grow(
+                ptr.cast(),
+                Layout::new::<[u8; 100]>(),
+                Layout::new::<[u8; 8192]>(),
+            )
+            .unwrap();
+    }
+    assert_eq!(blink.fast_path_hits(), 1);
+    assert_eq!(blink.slow_path_hits(), 2);
+
+    let stats = blink.stats();
+    blink.reset();
+    assert_eq!(blink.fast_path_hits(), 0);
+    assert_eq!(blink.slow_path_hits(), 0);
+
+    blink.allocate(Layout::new::<[u8; 100]>()).unwrap();
+    assert_eq!(blink.fast_path_hits(), 1);
+    assert_eq!(blink.slow_path_hits(), 0);
+
+    // Statistics count since creation.
+    let diff = blink.stats().diff(&stats);
+    assert_eq!(blink.stats().fast_path_hits, 2);
+    assert_eq!(diff.fast_path_hits, 1);
+    assert_eq!(diff.slow_path_hits, 0);
+}
+
+#[test]
+#[cfg(all(feature = "exact-stats", not(feature = "debug-headers")))]
+fn test_exact_stats() {
+    let mut blink = BlinkAlloc::with_chunk_size(64);
+
+    blink.allocate(Layout::new::<u8>()).unwrap();
+    blink.allocate(Layout::new::<u64>()).unwrap();
+    assert_eq!(blink.allocated_bytes(), 9);
+
+    // Chunk tail skipped by the new chunk is not counted.
+    let big = blink.allocate(Layout::new::<[u8; 100]>()).unwrap();
+    assert_eq!(blink.allocated_bytes(), 109);
+
+    let big = unsafe {
+        blink.resize(
+            big.cast(),
+            Layout::new::<[u8; 100]>(),
+            Layout::new::<[u8; 200]>(),
+        )
+    }
+    .unwrap();
+    assert_eq!(blink.allocated_bytes(), 209);
+
+    unsafe { blink.deallocate(big.cast(), 200) };
+    assert_eq!(blink.allocated_bytes(), 9);
+
+    blink.reset();
+    assert_eq!(blink.allocated_bytes(), 0);
+}
+
+#[test]
+#[cfg(all(
+    feature = "exact-stats",
+    feature = "sync-core",
+    not(feature = "debug-headers")
+))]
+fn test_exact_stats_sync() {
+    use crate::SyncBlinkAlloc;
+
+    let mut blink = SyncBlinkAlloc::with_chunk_size_in(64, Global);
+
+    blink.allocate(Layout::new::<u8>()).unwrap();
+    let ptr = blink.allocate(Layout::new::<[u64; 20]>()).unwrap();
+    assert_eq!(blink.allocated_bytes(), 161);
+
+    unsafe { blink.deallocate(ptr.cast(), 160) };
+    assert_eq!(blink.allocated_bytes(), 1);
+
+    {
+        let local = blink.local();
+        local.allocate(Layout::new::<[u8; 10]>()).unwrap();
+        assert_eq!(local.allocated_bytes(), 10);
+    }
+
+    blink.reset();
+    assert_eq!(blink.allocated_bytes(), 0);
+}
+
+#[test]
+fn test_arena_write() {
+    use core::fmt::Write;
+
+    let mut blink = Blink::new();
+
+    let mut writer = blink.writer();
+    assert!(writer.is_empty());
+    for i in 0..1000 {
+        write!(writer, "{i},").unwrap();
+    }
+    let len = writer.len();
+    let numbers = writer.finish_str().unwrap();
+    assert_eq!(numbers.len(), len);
+    assert!(numbers.starts_with("0,1,2,"));
+    assert!(numbers.ends_with("998,999,"));
+
+    // Unused capacity is returned on finish.
+    let before = blink.allocated_bytes().unwrap();
+    let mut writer = blink.writer();
+    writer.write_str("0123456789").unwrap();
+    assert_eq!(writer.finish(), b"0123456789");
+    let after = blink.allocated_bytes().unwrap();
+    assert!(after >= before + 10);
+
+    // Debug headers move allocation on each resize.
+    // Bumping downward, shrinks don't return memory to the chunk.
+    #[cfg(all(not(feature = "debug-headers"), not(feature = "bump-down")))]
+    assert_eq!(after, before + 10);
+    assert!(blink.writer().finish().is_empty());
+
+    let mut writer = blink.writer();
+    writer.extend_from_slice(&[0xFF, 0xFE]);
+    assert_eq!(writer.as_bytes(), &[0xFF, 0xFE]);
+    assert!(writer.finish_str().is_err());
+
+    blink.reset();
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_arena_write_io() {
+    use std::io::Write;
+
+    let blink = Blink::new();
+    let mut writer = blink.writer();
+    writer.write_all(b"hello ").unwrap();
+    writer.write_all(&[b'x'; 300]).unwrap();
+    writer.flush().unwrap();
+    let bytes = writer.finish();
+    assert_eq!(&bytes[..6], b"hello ");
+    assert_eq!(bytes.len(), 306);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_read_all() {
+    struct Chunked<'a> {
+        data: &'a [u8],
+        interrupted: bool,
+    }
+
+    impl std::io::Read for Chunked<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.interrupted = !self.interrupted;
+            if self.interrupted {
+                return Err(std::io::ErrorKind::Interrupted.into());
+            }
+            let n = buf.len().min(self.data.len()).min(100);
+            buf[..n].copy_from_slice(&self.data[..n]);
+            self.data = &self.data[n..];
+            Ok(n)
+        }
+    }
+
+    let data: Vec<u8> = (0..10_000u32).map(|i| i as u8).collect();
+
+    let mut blink = Blink::new();
+    let before = blink.allocated_bytes().unwrap();
+    let mut reader = Chunked {
+        data: &data,
+        interrupted: false,
+    };
+    let bytes = blink.read_all(&mut reader).unwrap();
+    assert_eq!(&*bytes, &*data);
+    assert!(blink.allocated_bytes().unwrap() >= before + data.len());
+
+    let mut empty: &[u8] = &[];
+    assert!(blink.read_all(&mut empty).unwrap().is_empty());
+
+    blink.reset();
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_interner() {
+    use crate::BlinkInterner;
+
+    let mut interner = BlinkInterner::new();
+
+    let words = ["fn", "let", "mut", "fn", "", "let", ""];
+    let interned: Vec<&str> = words.iter().map(|w| interner.intern(w)).collect();
+    assert_eq!(interned, words);
+    assert_eq!(interner.len(), 4);
+
+    assert!(core::ptr::eq(interned[0], interned[3]));
+    assert!(core::ptr::eq(interned[1], interned[5]));
+    assert!(!core::ptr::eq(interned[0], words[0]));
+    assert!(core::ptr::eq(interner.get("mut").unwrap(), interned[2]));
+    assert_eq!(interner.get("struct"), None);
+    drop(interned);
+
+    interner.reset();
+    assert!(interner.is_empty());
+    assert_eq!(interner.get("fn"), None);
+    assert_eq!(interner.intern("fn"), "fn");
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_copy_cow() {
+    use alloc::borrow::Cow;
+
+    let mut blink = Blink::new();
+
+    let input = "borrowed";
+    let borrowed = blink.copy_cow(Cow::Borrowed(input));
+    assert!(core::ptr::eq(borrowed, input));
+
+    let owned = alloc::string::String::from("owned");
+    let copied = blink.try_copy_cow(Cow::Owned(owned)).unwrap();
+    assert_eq!(copied, "owned");
+    assert!(blink.allocator().contains(NonNull::from(copied).cast()));
+
+    let numbers = [1u32, 2, 3];
+    let borrowed = blink.copy_cow_slice(Cow::Borrowed(&numbers[..]));
+    assert!(core::ptr::eq(borrowed, &numbers[..]));
+
+    let copied = blink.copy_cow_slice::<u32>(Cow::Owned(alloc::vec![4, 5]));
+    assert_eq!(copied, [4, 5]);
+    assert!(blink.allocator().contains(NonNull::from(copied).cast()));
+
+    blink.reset();
+}
+
+#[test]
+fn test_blink_pool() {
+    use crate::BlinkPool;
+
+    struct Counted<'a>(&'a Cell<usize>, #[allow(dead_code)] [u64; 3]);
+
+    impl Drop for Counted<'_> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    let dropped = Cell::new(0);
+    let mut pool = BlinkPool::new();
+
+    let mut live = Vec::new();
+    for i in 0..100 {
+        live.push(pool.alloc(Counted(&dropped, [i; 3])));
+    }
+    assert_eq!(pool.len(), 100);
+    let capacity = pool.allocator().total_capacity();
+
+    // Churn: free and allocate many times without growing the arena.
+    for round in 0..1000u64 {
+        let value = live.swap_remove((round as usize * 7) % live.len());
+        unsafe { pool.free(value) };
+        live.push(pool.alloc(Counted(&dropped, [round; 3])));
+    }
+    assert_eq!(pool.len(), 100);
+    assert_eq!(dropped.get(), 1000);
+    assert_eq!(pool.allocator().total_capacity(), capacity);
+
+    for value in live.drain(..) {
+        assert_eq!(value.1[0], value.1[2]);
+        unsafe { pool.free(value) };
+    }
+    assert_eq!(dropped.get(), 1100);
+    assert!(pool.is_empty());
+    drop(live);
+
+    pool.reset();
+    let value = pool.try_alloc(Counted(&dropped, [7; 3])).ok().unwrap();
+    assert_eq!(value.1, [7; 3]);
+    unsafe { pool.free(value) };
+}
+
+#[test]
+fn test_typed_blink() {
+    use crate::TypedBlink;
+
+    struct Node<'a> {
+        value: usize,
+        dropped: &'a Cell<usize>,
+    }
+
+    impl Drop for Node<'_> {
+        fn drop(&mut self) {
+            self.dropped.set(self.dropped.get() + 1);
+        }
+    }
+
+    let dropped = Cell::new(0);
+    let mut nodes = TypedBlink::new();
+
+    for value in 0..100 {
+        let node = nodes.alloc(Node {
+            value,
+            dropped: &dropped,
+        });
+        assert_eq!(node.value, value);
+    }
+    assert_eq!(nodes.len(), 100);
+
+    for node in &mut nodes {
+        node.value *= 2;
+    }
+    let values: Vec<usize> = nodes.iter_mut().map(|node| node.value).collect();
+    assert_eq!(values, (0..100).map(|v| v * 2).collect::<Vec<_>>());
+    assert_eq!(dropped.get(), 0);
+
+    nodes.reset();
+    assert_eq!(dropped.get(), 100);
+    assert!(nodes.is_empty());
+    assert_eq!(nodes.iter_mut().count(), 0);
+
+    nodes.alloc(Node {
+        value: 1,
+        dropped: &dropped,
+    });
+    drop(nodes);
+    assert_eq!(dropped.get(), 101);
+
+    let mut units = TypedBlink::new();
+    for _ in 0..10 {
+        units.alloc(());
+    }
+    assert_eq!(units.iter_mut().count(), 10);
+}
+
+#[test]
+fn test_arc() {
+    use crate::ARc;
+
+    struct Counted<'a>(&'a Cell<usize>);
+
+    impl Drop for Counted<'_> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    let dropped = Cell::new(0);
+    let mut blink = Blink::new();
+
+    let a = blink.arc(Counted(&dropped));
+    let b = a.clone();
+    assert!(ARc::ptr_eq(&a, &b));
+    assert_eq!(ARc::strong_count(&a), 2);
+
+    drop(a);
+    assert_eq!(dropped.get(), 0);
+    assert_eq!(ARc::strong_count(&b), 1);
+    drop(b);
+    assert_eq!(dropped.get(), 1);
+
+    let c = blink.try_arc(42u32).unwrap();
+    assert_eq!(*c, 42);
+    drop(c);
+
+    blink.reset();
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "leaked")]
+fn test_arc_leak_detected() {
+    let mut blink = Blink::new();
+    core::mem::forget(blink.arc(1u32));
+    blink.reset();
+}
+
+#[test]
+fn test_blink_ref() {
+    use crate::BlinkRef;
+
+    let mut blink = Blink::new();
+    let value = blink.put(42u32);
+    let handle = unsafe { BlinkRef::new(&blink, value) };
+    let copy = handle.clone();
+    assert_eq!(*handle, 42);
+    assert_eq!(*copy, 42);
+
+    blink.reset();
+
+    // Handles created after reset are valid.
+    let value = blink.put(7u32);
+    let handle = unsafe { BlinkRef::new(&blink, value) };
+    assert_eq!(*handle, 7);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "after `Blink` was reset")]
+fn test_blink_ref_after_reset() {
+    use crate::BlinkRef;
+
+    let mut blink = Blink::new();
+    let value = blink.put(42u32);
+    let handle = unsafe { BlinkRef::new(&blink, value) };
+
+    blink.reset();
+    let _ = *handle;
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "after `Blink` was reset")]
+fn test_blink_ref_after_drop() {
+    use crate::BlinkRef;
+
+    let blink = Blink::new();
+    let value = blink.put(42u32);
+    let handle = unsafe { BlinkRef::new(&blink, value) };
+
+    drop(blink);
+    let _ = *handle;
+}
+
+#[test]
+fn test_box_future() {
+    use core::{
+        future::Future,
+        pin::Pin,
+        task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+    };
+
+    use crate::BlinkBox;
+
+    fn noop_raw() -> RawWaker {
+        fn clone(_: *const ()) -> RawWaker {
+            noop_raw()
+        }
+        fn noop(_: *const ()) {}
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+        RawWaker::new(core::ptr::null(), &VTABLE)
+    }
+
+    struct Yield(bool);
+
+    impl Future for Yield {
+        type Output = ();
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            if self.0 {
+                Poll::Ready(())
+            } else {
+                self.0 = true;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+
+    let waker = unsafe { Waker::from_raw(noop_raw()) };
+    let mut cx = Context::from_waker(&waker);
+
+    let dropped = Cell::new(0);
+    let mut blink = Blink::new();
+    {
+        let dropped = &dropped;
+        let guard = blink.boxed(scopeguard(move || dropped.set(dropped.get() + 1)));
+
+        let mut tasks: Vec<Pin<BlinkBox<dyn Future<Output = usize>>>> = Vec::new();
+        for i in 0..4 {
+            tasks.push(blink.box_future(async move {
+                Yield(false).await;
+                i
+            }));
+        }
+
+        let mut sum = 0;
+        while !tasks.is_empty() {
+            tasks.retain_mut(|task| match task.as_mut().poll(&mut cx) {
+                Poll::Ready(value) => {
+                    sum += value;
+                    false
+                }
+                Poll::Pending => true,
+            });
+        }
+        assert_eq!(sum, 6);
+
+        let task = blink.box_future_send(async { 1 });
+        let mut task = task;
+        assert_eq!(task.as_mut().poll(&mut cx), Poll::Ready(1));
+
+        assert_eq!(dropped.get(), 0);
+        drop(guard);
+        assert_eq!(dropped.get(), 1);
+    }
+
+    blink.reset();
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_box_future_leak_detected() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let mut blink = Blink::new();
+    core::mem::forget(blink.box_future(async {}));
+
+    // Memory of pinned future must not be reused without dropping it.
+    let result = catch_unwind(AssertUnwindSafe(|| blink.reset()));
+    assert!(result.is_err());
+
+    // Dropping `blink` would abort the process.
+    core::mem::forget(blink);
+}
+
+struct ScopeGuard<F: FnMut()>(F);
+
+impl<F: FnMut()> Drop for ScopeGuard<F> {
+    fn drop(&mut self) {
+        (self.0)()
+    }
+}
+
+fn scopeguard<F: FnMut()>(f: F) -> ScopeGuard<F> {
+    ScopeGuard(f)
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_emplace_array() {
+    struct Counted<'a>(&'a Cell<usize>, usize);
+
+    impl Drop for Counted<'_> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    let mut blink = Blink::new();
+
+    let big = blink.emplace().array::<4096, _>(|i| i as u32);
+    assert_eq!(big[4095], 4095);
+
+    let strings = blink
+        .emplace()
+        .from_iter_array::<3, _>(["a", "b", "c"].iter().map(|s| s.to_string()));
+    assert_eq!(strings.join(""), "abc");
+
+    let words = blink.emplace().try_array::<2, _>(|i| i + 1).ok().unwrap();
+    assert_eq!(*words, [1, 2]);
+
+    blink.reset();
+
+    let dropped = Cell::new(0);
+    {
+        let blink = Blink::new();
+        let dropped = &dropped;
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            blink.emplace_no_drop().array::<8, _>(|i| {
+                if i == 5 {
+                    panic!("boom");
+                }
+                Counted(dropped, i)
+            });
+        }));
+        assert!(result.is_err());
+        assert_eq!(dropped.get(), 5);
+
+        let array = blink
+            .emplace_no_drop()
+            .array::<4, _>(|i| Counted(dropped, i));
+        assert_eq!(array[3].1, 3);
+        unsafe { core::ptr::drop_in_place(array) };
+        assert_eq!(dropped.get(), 9);
+    }
+
+    let result = std::panic::catch_unwind(|| {
+        let blink = Blink::new();
+        blink.emplace().from_iter_array::<4, _>(0..3);
+    });
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_copy_large_slice() {
+    let mut blink = Blink::new();
+
+    // Unaligned start exercises head copy before streaming stores.
+    let data: Vec<u8> = (0..(3 << 20) + 37).map(|i| (i % 251) as u8).collect();
+    let copy = blink.copy_slice(&data[3..]);
+    assert_eq!(copy, &data[3..]);
+
+    let words: Vec<u64> = (0..1 << 18).collect();
+    let copy = blink.copy_slice(&words);
+    assert_eq!(copy, &words[..]);
+
+    blink.reset();
+}
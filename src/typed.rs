@@ -0,0 +1,312 @@
+//! This module provides typed arena that can iterate its values.
+
+use core::{
+    alloc::Layout,
+    cell::Cell,
+    marker::PhantomData,
+    mem::size_of,
+    ptr::{self, NonNull},
+};
+
+#[cfg(all(feature = "nightly", feature = "alloc"))]
+use alloc::alloc::Global;
+
+#[cfg(all(not(feature = "nightly"), feature = "alloc"))]
+use allocator_api2::alloc::Global;
+
+use crate::api::BlinkAllocator;
+
+#[cfg(feature = "alloc")]
+use crate::local::BlinkAlloc;
+
+#[cfg(not(no_global_oom_handling))]
+use crate::oom::handle_alloc_error;
+
+/// Number of values in the first chunk.
+const MIN_CHUNK_LEN: usize = 8;
+
+/// Chunk of values.
+/// Values are placed right after the header.
+#[repr(C)]
+struct Chunk<T> {
+    next: Option<NonNull<Chunk<T>>>,
+    len: usize,
+    cap: usize,
+    values: [T; 0],
+}
+
+impl<T> Chunk<T> {
+    /// Returns pointer to the first value slot of the chunk.
+    #[inline(always)]
+    unsafe fn values(chunk: NonNull<Self>) -> *mut T {
+        unsafe { ptr::addr_of_mut!((*chunk.as_ptr()).values).cast() }
+    }
+}
+
+switch_alloc_default! {
+    /// Typed arena that stores values of type `T`
+    /// in chunks allocated from blink allocator.
+    ///
+    /// Unlike [`Blink`](crate::Blink), it remembers all allocated values,
+    /// so they can be iterated in allocation order with [`TypedBlink::iter_mut`].
+    /// Values are dropped on reset or when [`TypedBlink`] is dropped.
+    /// Because of that values can't borrow from the same [`TypedBlink`].
+    ///
+    /// Uses [`BlinkAlloc`] by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// use blink_alloc::TypedBlink;
+    ///
+    /// struct Node { name: String, weight: u32 }
+    ///
+    /// let mut nodes = TypedBlink::new();
+    /// let a = nodes.alloc(Node { name: "a".to_owned(), weight: 1 });
+    /// let b = nodes.alloc(Node { name: "b".to_owned(), weight: 2 });
+    /// a.weight += b.weight;
+    ///
+    /// let total: u32 = nodes.iter_mut().map(|node| node.weight).sum();
+    /// assert_eq!(total, 5);
+    ///
+    /// // Values are dropped here.
+    /// nodes.reset();
+    /// assert!(nodes.is_empty());
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    pub struct TypedBlink<T, A = +BlinkAlloc<Global>> {
+        first: Cell<Option<NonNull<Chunk<T>>>>,
+        last: Cell<Option<NonNull<Chunk<T>>>>,
+        len: Cell<usize>,
+        alloc: A,
+        marker: PhantomData<T>,
+    }
+}
+
+// Safety: `TypedBlink` is not auto-send because of raw pointers.
+// All pointers point into memory owned by the allocator
+// that is moved together with the arena.
+unsafe impl<T, A> Send for TypedBlink<T, A>
+where
+    T: Send,
+    A: Send,
+{
+}
+
+impl<T, A> Drop for TypedBlink<T, A> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        self.drop_all();
+    }
+}
+
+impl<T, A> Default for TypedBlink<T, A>
+where
+    A: Default,
+{
+    #[inline(always)]
+    fn default() -> Self {
+        TypedBlink::new_in(A::default())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> TypedBlink<T, BlinkAlloc<Global>> {
+    /// Creates new typed arena with `BlinkAlloc` baked by `Global` allocator.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        TypedBlink::new_in(BlinkAlloc::new())
+    }
+}
+
+impl<T, A> TypedBlink<T, A> {
+    /// Creates new typed arena with provided allocator instance.
+    #[inline(always)]
+    pub const fn new_in(alloc: A) -> Self {
+        TypedBlink {
+            first: Cell::new(None),
+            last: Cell::new(None),
+            len: Cell::new(0),
+            alloc,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns reference to allocator instance.
+    #[inline(always)]
+    pub fn allocator(&self) -> &A {
+        &self.alloc
+    }
+
+    /// Returns number of values in the arena.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.len.get()
+    }
+
+    /// Returns `true` if arena has no values.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len.get() == 0
+    }
+
+    /// Returns iterator over all values in allocation order.
+    #[inline(always)]
+    pub fn iter_mut(&mut self) -> TypedBlinkIter<'_, T> {
+        TypedBlinkIter {
+            chunk: self.first.get(),
+            idx: 0,
+            marker: PhantomData,
+        }
+    }
+
+    /// Drops all values.
+    ///
+    /// Prefer to use `reset` method if associated allocator instance supports it.
+    #[inline]
+    pub fn drop_all(&mut self) {
+        let mut chunk = self.first.take();
+        self.last.set(None);
+        self.len.set(0);
+
+        while let Some(c) = chunk {
+            // Safety: chunk is valid and its first `len` values are initialized.
+            // Values are not accessed after this point.
+            unsafe {
+                let len = (*c.as_ptr()).len;
+                chunk = (*c.as_ptr()).next;
+                ptr::drop_in_place(ptr::slice_from_raw_parts_mut(Chunk::values(c), len));
+            }
+        }
+    }
+}
+
+impl<T, A> TypedBlink<T, A>
+where
+    A: BlinkAllocator,
+{
+    /// Moves value into the arena.
+    /// Returns reference to the value.
+    #[cfg(not(no_global_oom_handling))]
+    #[inline]
+    #[allow(clippy::mut_from_ref)]
+    pub fn alloc(&self, value: T) -> &mut T {
+        match self.try_alloc(value) {
+            Ok(value) => value,
+            Err(_) => handle_alloc_error(Layout::new::<T>()),
+        }
+    }
+
+    /// Moves value into the arena.
+    /// Returns reference to the value.
+    /// If allocation fails, returns `Err(value)`.
+    #[inline]
+    #[allow(clippy::mut_from_ref)]
+    pub fn try_alloc(&self, value: T) -> Result<&mut T, T> {
+        let chunk = match self.last.get() {
+            // Safety: last chunk is valid.
+            Some(chunk) if unsafe { (*chunk.as_ptr()).len < (*chunk.as_ptr()).cap } => chunk,
+            _ => match self.new_chunk() {
+                Some(chunk) => chunk,
+                None => return Err(value),
+            },
+        };
+
+        // Safety: chunk has free slot at `len`.
+        // `len` is increased after value is written,
+        // so slot is never exposed before initialization.
+        unsafe {
+            let len = (*chunk.as_ptr()).len;
+            let slot = Chunk::values(chunk).add(len);
+            slot.write(value);
+            (*chunk.as_ptr()).len = len + 1;
+            self.len.set(self.len.get() + 1);
+            Ok(&mut *slot)
+        }
+    }
+
+    /// Drops all values.
+    /// And resets associated allocator instance.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.drop_all();
+        self.alloc.reset();
+    }
+
+    #[cold]
+    fn new_chunk(&self) -> Option<NonNull<Chunk<T>>> {
+        let cap = match self.last.get() {
+            // Safety: last chunk is valid.
+            Some(last) => unsafe { (*last.as_ptr()).cap.checked_mul(2)? },
+            None if size_of::<T>() == 0 => usize::MAX,
+            None => MIN_CHUNK_LEN,
+        };
+
+        let (layout, _) = Layout::new::<Chunk<T>>()
+            .extend(Layout::array::<T>(cap).ok()?)
+            .ok()?;
+        let chunk = self.alloc.allocate(layout).ok()?.cast::<Chunk<T>>();
+
+        // Safety: chunk memory is allocated with proper layout.
+        unsafe {
+            chunk.as_ptr().write(Chunk {
+                next: None,
+                len: 0,
+                cap,
+                values: [],
+            });
+        }
+
+        match self.last.get() {
+            // Safety: last chunk is valid.
+            Some(last) => unsafe { (*last.as_ptr()).next = Some(chunk) },
+            None => self.first.set(Some(chunk)),
+        }
+        self.last.set(Some(chunk));
+        Some(chunk)
+    }
+}
+
+impl<'a, T, A> IntoIterator for &'a mut TypedBlink<T, A> {
+    type Item = &'a mut T;
+    type IntoIter = TypedBlinkIter<'a, T>;
+
+    #[inline(always)]
+    fn into_iter(self) -> TypedBlinkIter<'a, T> {
+        self.iter_mut()
+    }
+}
+
+/// Iterator over values of [`TypedBlink`] in allocation order.
+///
+/// Created with [`TypedBlink::iter_mut`].
+pub struct TypedBlinkIter<'a, T> {
+    chunk: Option<NonNull<Chunk<T>>>,
+    idx: usize,
+    marker: PhantomData<&'a mut T>,
+}
+
+impl<'a, T> Iterator for TypedBlinkIter<'a, T> {
+    type Item = &'a mut T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a mut T> {
+        loop {
+            let chunk = self.chunk?;
+
+            // Safety: chunk is valid while arena is borrowed mutably.
+            // Each value is yielded at most once.
+            unsafe {
+                if self.idx < (*chunk.as_ptr()).len {
+                    let value = Chunk::values(chunk).add(self.idx);
+                    self.idx += 1;
+                    return Some(&mut *value);
+                }
+                self.chunk = (*chunk.as_ptr()).next;
+                self.idx = 0;
+            }
+        }
+    }
+}
@@ -0,0 +1,292 @@
+//! Provides `ArenaWrite` writer that appends bytes
+//! into growing buffer allocated from blink allocator.
+
+use core::{alloc::Layout, fmt, ptr::NonNull, str::Utf8Error};
+
+#[cfg(feature = "nightly")]
+use core::alloc::{AllocError, Allocator};
+
+#[cfg(not(feature = "nightly"))]
+use allocator_api2::alloc::{AllocError, Allocator};
+
+#[cfg(not(no_global_oom_handling))]
+use crate::oom::handle_alloc_error;
+
+/// Minimal non-zero capacity of the buffer.
+const MIN_CAPACITY: usize = 64;
+
+/// Minimal number of bytes reserved for a read.
+#[cfg(feature = "std")]
+const READ_PROBE: usize = 32;
+
+/// Writer that appends bytes into a growing buffer
+/// allocated from blink allocator.
+///
+/// Implements [`core::fmt::Write`] and, with `"std"` feature,
+/// [`std::io::Write`], so values can be formatted or serialized
+/// directly into arena memory.
+/// Finished buffer lives as long as the allocator borrow.
+///
+/// Created with [`Blink::writer`](crate::Blink::writer).
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "std")] fn main() {
+/// use std::io::Write;
+/// use blink_alloc::Blink;
+///
+/// let blink = Blink::new();
+/// let mut writer = blink.writer();
+/// write!(writer, "Hello, {}!", "world").unwrap();
+/// let hello = writer.finish_str().unwrap();
+/// assert_eq!(hello, "Hello, world!");
+/// # }
+/// # #[cfg(not(feature = "std"))] fn main() {}
+/// ```
+pub struct ArenaWrite<'a, A: Allocator> {
+    alloc: &'a A,
+    ptr: NonNull<u8>,
+    len: usize,
+    cap: usize,
+}
+
+impl<A> Drop for ArenaWrite<'_, A>
+where
+    A: Allocator,
+{
+    #[inline]
+    fn drop(&mut self) {
+        if self.cap > 0 {
+            // Safety: buffer was allocated from `alloc` with this layout.
+            unsafe {
+                self.alloc
+                    .deallocate(self.ptr, Layout::from_size_align_unchecked(self.cap, 1));
+            }
+        }
+    }
+}
+
+impl<'a, A> ArenaWrite<'a, A>
+where
+    A: Allocator,
+{
+    #[inline(always)]
+    pub(crate) fn new(alloc: &'a A) -> Self {
+        ArenaWrite {
+            alloc,
+            ptr: NonNull::dangling(),
+            len: 0,
+            cap: 0,
+        }
+    }
+
+    /// Returns number of bytes written so far.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if nothing was written yet.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns bytes written so far.
+    #[inline(always)]
+    pub fn as_bytes(&self) -> &[u8] {
+        // Safety: `len` bytes are initialized.
+        unsafe { core::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+
+    /// Reserves capacity for at least `additional` more bytes.
+    /// If allocation fails, returns `Err(AllocError)`.
+    #[inline]
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), AllocError> {
+        if self.cap - self.len >= additional {
+            return Ok(());
+        }
+        self.grow(additional)
+    }
+
+    #[cold]
+    fn grow(&mut self, additional: usize) -> Result<(), AllocError> {
+        let required = self.len.checked_add(additional).ok_or(AllocError)?;
+        let cap = required.max(self.cap * 2).max(MIN_CAPACITY);
+        let new_layout = Layout::from_size_align(cap, 1).map_err(|_| AllocError)?;
+
+        let ptr = if self.cap == 0 {
+            self.alloc.allocate(new_layout)?
+        } else {
+            // Safety: buffer was allocated from `alloc` with this layout.
+            // Blink allocators grow the last allocation in place when possible.
+            unsafe {
+                let old_layout = Layout::from_size_align_unchecked(self.cap, 1);
+                self.alloc.grow(self.ptr, old_layout, new_layout)?
+            }
+        };
+
+        self.ptr = ptr.cast();
+        self.cap = ptr.len();
+        Ok(())
+    }
+
+    /// Appends bytes to the buffer.
+    /// If allocation fails, returns `Err(AllocError)`.
+    #[inline]
+    pub fn try_extend_from_slice(&mut self, bytes: &[u8]) -> Result<(), AllocError> {
+        self.try_reserve(bytes.len())?;
+
+        // Safety: capacity is reserved above.
+        // Buffer is not aliased by `bytes`.
+        unsafe {
+            core::ptr::copy_nonoverlapping(
+                bytes.as_ptr(),
+                self.ptr.as_ptr().add(self.len),
+                bytes.len(),
+            );
+        }
+        self.len += bytes.len();
+        Ok(())
+    }
+
+    /// Appends bytes to the buffer.
+    #[cfg(not(no_global_oom_handling))]
+    #[inline]
+    pub fn extend_from_slice(&mut self, bytes: &[u8]) {
+        if self.try_extend_from_slice(bytes).is_err() {
+            match Layout::array::<u8>(self.len.saturating_add(bytes.len())) {
+                Ok(layout) => handle_alloc_error(layout),
+                Err(_) => panic!("Size overflow"),
+            }
+        }
+    }
+
+    /// Reads all bytes until EOF from `reader` and appends them to the buffer.
+    /// Returns number of bytes read.
+    ///
+    /// Buffer grows in place when it is the last allocation,
+    /// so reading large streams does not copy data on each grow.
+    #[cfg(feature = "std")]
+    pub fn read_to_end<R>(&mut self, reader: &mut R) -> std::io::Result<usize>
+    where
+        R: std::io::Read + ?Sized,
+    {
+        let start = self.len;
+
+        // Bytes of the buffer known to be initialized.
+        let mut initialized = self.len;
+
+        loop {
+            if self.len == self.cap {
+                self.try_reserve(READ_PROBE)
+                    .map_err(|_| std::io::Error::from(std::io::ErrorKind::OutOfMemory))?;
+            }
+
+            // `Read` requires initialized buffer.
+            if initialized < self.cap {
+                // Safety: range is within allocated buffer.
+                unsafe {
+                    core::ptr::write_bytes(
+                        self.ptr.as_ptr().add(initialized),
+                        0,
+                        self.cap - initialized,
+                    );
+                }
+                initialized = self.cap;
+            }
+
+            // Safety: bytes in `len..cap` are initialized above.
+            let spare = unsafe {
+                core::slice::from_raw_parts_mut(
+                    self.ptr.as_ptr().add(self.len),
+                    self.cap - self.len,
+                )
+            };
+
+            match reader.read(spare) {
+                Ok(0) => return Ok(self.len - start),
+                Ok(n) => {
+                    assert!(
+                        n <= spare.len(),
+                        "Reader reported more bytes than buffer size"
+                    );
+                    self.len += n;
+                }
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Finishes writing and returns written bytes.
+    ///
+    /// Unused capacity is returned to the allocator
+    /// if buffer is the last allocation.
+    #[inline]
+    pub fn finish(self) -> &'a mut [u8] {
+        let me = core::mem::ManuallyDrop::new(self);
+
+        if me.cap == 0 {
+            return &mut [];
+        }
+
+        let mut ptr = me.ptr;
+        if me.len < me.cap {
+            // Safety: buffer was allocated from `alloc` with this layout.
+            // Shrink may move the data when bumping downward.
+            unsafe {
+                let old_layout = Layout::from_size_align_unchecked(me.cap, 1);
+                let new_layout = Layout::from_size_align_unchecked(me.len, 1);
+                if let Ok(shrunk) = me.alloc.shrink(ptr, old_layout, new_layout) {
+                    ptr = shrunk.cast();
+                }
+            }
+        }
+
+        // Safety: `len` bytes are initialized and not used by anything else.
+        unsafe { core::slice::from_raw_parts_mut(ptr.as_ptr(), me.len) }
+    }
+
+    /// Finishes writing and returns written bytes as string.
+    /// Returns `Err` if written bytes are not valid UTF-8.
+    #[inline]
+    pub fn finish_str(self) -> Result<&'a mut str, Utf8Error> {
+        core::str::from_utf8_mut(self.finish())
+    }
+}
+
+impl<A> fmt::Write for ArenaWrite<'_, A>
+where
+    A: Allocator,
+{
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.try_extend_from_slice(s.as_bytes())
+            .map_err(|_| fmt::Error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<A> std::io::Write for ArenaWrite<'_, A>
+where
+    A: Allocator,
+{
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.write_all(buf)?;
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        self.try_extend_from_slice(buf)
+            .map_err(|_| std::io::ErrorKind::OutOfMemory.into())
+    }
+
+    #[inline(always)]
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}